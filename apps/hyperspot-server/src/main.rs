@@ -5,7 +5,7 @@ use clap::{Parser, Subcommand};
 use mimalloc::MiMalloc;
 use modkit::bootstrap::{
     AppConfig, dump_effective_modules_config_json, dump_effective_modules_config_yaml,
-    host::init_logging_unified, list_module_names, run_migrate, run_server,
+    host::init_logging_unified, list_module_names, run_migrate, run_server, run_server_with_reload,
 };
 
 use std::path::PathBuf;
@@ -91,8 +91,25 @@ async fn main() -> Result<()> {
     #[cfg(not(feature = "otel"))]
     let otel_layer = None;
 
+    // OTLP log export shares the tracing service name and resource attributes
+    #[cfg(feature = "otel")]
+    let (logs_layer, logger_provider) = match modkit_tracing_config.as_ref() {
+        Some(tc) if tc.log_export_enabled() => {
+            let (layer, provider) = modkit::telemetry::init_log_export(tc)?;
+            (Some(layer), Some(provider))
+        }
+        _ => (None, None),
+    };
+    #[cfg(not(feature = "otel"))]
+    let logs_layer = None;
+
     // Initialize logging + otel in one Registry
-    init_logging_unified(&config.logging, &config.server.home_dir, otel_layer);
+    init_logging_unified(
+        &config.logging,
+        &config.server.home_dir,
+        otel_layer,
+        logs_layer,
+    );
 
     // One-time connectivity probe
     #[cfg(feature = "otel")]
@@ -139,12 +156,42 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Metrics pipeline (OTLP push and/or Prometheus scrape via the API gateway);
+    // installed before modules init so they can create their instruments
+    #[cfg(feature = "otel")]
+    let meter_provider = match config.metrics.as_ref() {
+        Some(mc) if mc.enabled => Some(modkit::telemetry::init_metrics(mc)?),
+        _ => None,
+    };
+
     // Dispatch subcommands (default: run)
-    match cli.command.as_ref().unwrap_or(&Commands::Run) {
-        Commands::Run => run_server(config).await,
+    let result = match cli.command.as_ref().unwrap_or(&Commands::Run) {
+        Commands::Run => match cli.config {
+            // Module sections are reloaded from the same file on SIGHUP or when it changes
+            Some(path) => run_server_with_reload(config, path).await,
+            None => run_server(config).await,
+        },
         Commands::Check => check_config(&config),
         Commands::Migrate => run_migrate(config).await,
+    };
+
+    // Flush pending metric exports
+    #[cfg(feature = "otel")]
+    if let Some(provider) = meter_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!(error = %e, "Metrics shutdown failed");
+    }
+
+    // Flush pending log records
+    #[cfg(feature = "otel")]
+    if let Some(provider) = logger_provider
+        && let Err(e) = provider.shutdown()
+    {
+        tracing::warn!(error = %e, "Log export shutdown failed");
     }
+
+    result
 }
 
 fn check_config(config: &AppConfig) -> Result<()> {
@@ -10,6 +10,7 @@ use authz_resolver as _;
 #[cfg(not(feature = "oop-example"))]
 use file_parser as _;
 use grpc_hub as _;
+use license_resolver as _;
 use module_orchestrator as _;
 use nodes_registry as _;
 #[cfg(not(feature = "oop-example"))]
@@ -30,6 +31,9 @@ use static_authn_plugin as _;
 #[cfg(feature = "static-authz")]
 use static_authz_plugin as _;
 
+#[cfg(feature = "static-license")]
+use static_license_plugin as _;
+
 // === Example Features ===
 
 #[cfg(feature = "users-info-example")]
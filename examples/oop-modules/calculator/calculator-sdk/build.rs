@@ -2,9 +2,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("cargo:rerun-if-changed=proto/oop/calculator/v1/accum.proto");
     println!("cargo:rerun-if-changed=proto");
 
+    let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+
     tonic_prost_build::configure()
         .build_client(true)
         .build_server(true)
+        .file_descriptor_set_path(out_dir.join("calculator_descriptor.bin"))
         .compile_protos(&["proto/oop/calculator/v1/accum.proto"], &["proto"])?;
 
     Ok(())
@@ -41,5 +41,8 @@ pub mod proto {
 pub use proto::calculator_service_server::{CalculatorService, CalculatorServiceServer};
 pub use proto::{AddRequest, AddResponse};
 
+/// Encoded `FileDescriptorSet` of CalculatorService, for server reflection
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("calculator_descriptor");
+
 /// Service name constant for CalculatorService (used for service discovery)
 pub const SERVICE_NAME: &str = "calculator.v1.CalculatorService";
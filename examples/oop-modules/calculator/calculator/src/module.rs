@@ -11,7 +11,7 @@ use async_trait::async_trait;
 use modkit::context::ModuleCtx;
 use modkit::contracts::{GrpcServiceCapability, RegisterGrpcServiceFn};
 
-use calculator_sdk::{CalculatorServiceServer, SERVICE_NAME};
+use calculator_sdk::{CalculatorServiceServer, FILE_DESCRIPTOR_SET, SERVICE_NAME};
 
 use crate::api::grpc::CalculatorServiceImpl;
 use crate::domain::Service;
@@ -67,6 +67,10 @@ impl GrpcServiceCapability for CalculatorModule {
             }),
         }])
     }
+
+    fn file_descriptor_sets(&self) -> Vec<&'static [u8]> {
+        vec![FILE_DESCRIPTOR_SET]
+    }
 }
 
 #[cfg(test)]
@@ -51,10 +51,7 @@ impl ClaimsPlugin for DemoClaimsPlugin {
             .map(|value| parse_timestamp(value, "iat"))
             .transpose()?;
 
-        let jwt_id = raw
-            .get("jti")
-            .and_then(|v| v.as_str())
-            .map(ToString::to_string);
+        let jwt_id = raw.get("jti").and_then(|v| v.as_str()).map(str::to_owned);
 
         let tenant_id_value = raw
             .get("tenant_id")
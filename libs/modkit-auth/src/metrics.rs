@@ -128,6 +128,84 @@ impl AuthMetrics for LoggingMetrics {
     }
 }
 
+/// OpenTelemetry metrics implementation
+///
+/// Records `auth.events` (counter, labelled with `auth.event` and the available labels)
+/// and `auth.validation.duration` (histogram, seconds) on the global meter provider, so
+/// they are exported with the rest of the process metrics once the host installs one
+/// (`modkit::telemetry::metrics::init_metrics`). Measurements are dropped otherwise.
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone)]
+pub struct OtelMetrics {
+    events: opentelemetry::metrics::Counter<u64>,
+    duration: opentelemetry::metrics::Histogram<f64>,
+}
+
+#[cfg(feature = "otel")]
+impl OtelMetrics {
+    /// Create the instruments on the global meter provider.
+    ///
+    /// Instruments created before the provider is installed are no-ops.
+    #[must_use]
+    pub fn new() -> Self {
+        let meter = opentelemetry::global::meter("modkit-auth");
+        Self {
+            events: meter
+                .u64_counter("auth.events")
+                .with_description("Token validations and JWKS refreshes by outcome")
+                .with_unit("{event}")
+                .build(),
+            duration: meter
+                .f64_histogram("auth.validation.duration")
+                .with_description("Duration of token validations")
+                .with_unit("s")
+                .build(),
+        }
+    }
+}
+
+#[cfg(feature = "otel")]
+impl Default for OtelMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "otel")]
+fn otel_attributes(labels: &AuthMetricLabels) -> Vec<opentelemetry::KeyValue> {
+    [
+        ("auth.provider", &labels.provider),
+        ("auth.issuer", &labels.issuer),
+        ("auth.kid", &labels.kid),
+        ("error.type", &labels.error_type),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| {
+        value
+            .as_ref()
+            .map(|v| opentelemetry::KeyValue::new(key, v.clone()))
+    })
+    .collect()
+}
+
+#[cfg(feature = "otel")]
+impl AuthMetrics for OtelMetrics {
+    fn record_event(&self, event: AuthEvent, labels: &AuthMetricLabels) {
+        let mut attributes = otel_attributes(labels);
+        attributes.push(opentelemetry::KeyValue::new(
+            "auth.event",
+            event.metric_name(),
+        ));
+        self.events.add(1, &attributes);
+    }
+
+    fn record_duration(&self, duration_ms: u64, labels: &AuthMetricLabels) {
+        #[allow(clippy::cast_precision_loss)]
+        let seconds = duration_ms as f64 / 1000.0;
+        self.duration.record(seconds, &otel_attributes(labels));
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -170,6 +248,41 @@ mod tests {
         metrics.record_duration(100, &labels);
     }
 
+    #[cfg(feature = "otel")]
+    #[test]
+    fn test_otel_metrics_export_events() {
+        use opentelemetry::metrics::MeterProvider as _;
+        use opentelemetry_sdk::metrics::data::{ResourceMetrics, ScopeMetrics};
+        use opentelemetry_sdk::metrics::{
+            InMemoryMetricExporter, PeriodicReader, SdkMeterProvider,
+        };
+
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        let meter = provider.meter("modkit-auth");
+        let metrics = OtelMetrics {
+            events: meter.u64_counter("auth.events").build(),
+            duration: meter.f64_histogram("auth.validation.duration").build(),
+        };
+        let labels = AuthMetricLabels::default().with_provider("keycloak");
+
+        metrics.record_event(AuthEvent::JwtInvalid, &labels);
+        metrics.record_duration(20, &labels);
+        provider.force_flush().unwrap();
+
+        let finished = exporter.get_finished_metrics().unwrap();
+        let names: Vec<_> = finished
+            .iter()
+            .flat_map(ResourceMetrics::scope_metrics)
+            .flat_map(ScopeMetrics::metrics)
+            .map(|m| m.name().to_owned())
+            .collect();
+        assert!(names.contains(&"auth.events".to_owned()));
+        assert!(names.contains(&"auth.validation.duration".to_owned()));
+    }
+
     #[test]
     fn test_logging_metrics() {
         let metrics = LoggingMetrics;
@@ -56,22 +56,14 @@ impl KeycloakClaimsPlugin {
 
         // 1. Check for top-level "roles" array (simplified format)
         if let Some(Value::Array(arr)) = raw.get("roles") {
-            roles.extend(
-                arr.iter()
-                    .filter_map(|v| v.as_str())
-                    .map(ToString::to_string),
-            );
+            roles.extend(arr.iter().filter_map(|v| v.as_str()).map(str::to_owned));
         }
 
         // 2. Extract from realm_access.roles
         if let Some(Value::Object(realm)) = raw.get("realm_access")
             && let Some(Value::Array(arr)) = realm.get("roles")
         {
-            roles.extend(
-                arr.iter()
-                    .filter_map(|v| v.as_str())
-                    .map(ToString::to_string),
-            );
+            roles.extend(arr.iter().filter_map(|v| v.as_str()).map(str::to_owned));
         }
 
         // 3. Extract from resource_access.<client>.roles
@@ -80,11 +72,7 @@ impl KeycloakClaimsPlugin {
             && let Some(Value::Object(client)) = resource_access.get(client_id)
             && let Some(Value::Array(arr)) = client.get("roles")
         {
-            roles.extend(
-                arr.iter()
-                    .filter_map(|v| v.as_str())
-                    .map(ToString::to_string),
-            );
+            roles.extend(arr.iter().filter_map(|v| v.as_str()).map(str::to_owned));
         }
 
         // Apply role prefix if configured
@@ -166,7 +154,7 @@ impl ClaimsPlugin for KeycloakClaimsPlugin {
         let jwt_id = raw
             .get(StandardClaim::JTI)
             .and_then(|v| v.as_str())
-            .map(ToString::to_string);
+            .map(str::to_owned);
 
         // 8. Extract tenant_id (required, must be UUID)
         let tenant_id = raw
@@ -40,7 +40,7 @@ fn test_keycloak_token_format() {
         Some(serde_json::Value::String(s)) => Some(vec![s.clone()]),
         Some(serde_json::Value::Array(arr)) => Some(
             arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string))
+                .filter_map(|x| x.as_str().map(str::to_owned))
                 .collect::<Vec<_>>(),
         ),
         _ => None,
@@ -54,17 +54,11 @@ fn test_keycloak_token_format() {
     // Test realm_access.roles extraction
     let mut roles: Vec<String> = Vec::new();
     if let Some(serde_json::Value::Array(arr)) = keycloak_token.get("roles") {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     } else if let Some(serde_json::Value::Object(realm)) = keycloak_token.get("realm_access")
         && let Some(serde_json::Value::Array(arr)) = realm.get("roles")
     {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     }
     assert_eq!(
         roles,
@@ -104,7 +98,7 @@ fn test_custom_token_format() {
         Some(serde_json::Value::String(s)) => Some(vec![s.clone()]),
         Some(serde_json::Value::Array(arr)) => Some(
             arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string))
+                .filter_map(|x| x.as_str().map(str::to_owned))
                 .collect::<Vec<_>>(),
         ),
         _ => None,
@@ -118,17 +112,11 @@ fn test_custom_token_format() {
     // Test top-level roles extraction
     let mut roles: Vec<String> = Vec::new();
     if let Some(serde_json::Value::Array(arr)) = custom_token.get("roles") {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     } else if let Some(serde_json::Value::Object(realm)) = custom_token.get("realm_access")
         && let Some(serde_json::Value::Array(arr)) = realm.get("roles")
     {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     }
     assert_eq!(
         roles,
@@ -151,7 +139,7 @@ fn test_minimal_token_format() {
         Some(serde_json::Value::String(s)) => Some(vec![s.clone()]),
         Some(serde_json::Value::Array(arr)) => Some(
             arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string))
+                .filter_map(|x| x.as_str().map(str::to_owned))
                 .collect::<Vec<_>>(),
         ),
         _ => None,
@@ -160,17 +148,11 @@ fn test_minimal_token_format() {
 
     let mut roles: Vec<String> = Vec::new();
     if let Some(serde_json::Value::Array(arr)) = minimal_token.get("roles") {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     } else if let Some(serde_json::Value::Object(realm)) = minimal_token.get("realm_access")
         && let Some(serde_json::Value::Array(arr)) = realm.get("roles")
     {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     }
     assert!(roles.is_empty(), "Missing roles should be empty array");
 
@@ -201,17 +183,11 @@ fn test_both_roles_locations() {
 
     let mut roles: Vec<String> = Vec::new();
     if let Some(serde_json::Value::Array(arr)) = token.get("roles") {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     } else if let Some(serde_json::Value::Object(realm)) = token.get("realm_access")
         && let Some(serde_json::Value::Array(arr)) = realm.get("roles")
     {
-        roles.extend(
-            arr.iter()
-                .filter_map(|x| x.as_str().map(ToString::to_string)),
-        );
+        roles.extend(arr.iter().filter_map(|x| x.as_str().map(str::to_owned)));
     }
     assert_eq!(
         roles,
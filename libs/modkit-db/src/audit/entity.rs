@@ -0,0 +1,31 @@
+//! `SeaORM` entity for the shared audit log table.
+//!
+//! This entity is crate-internal: callers go through the functions of
+//! [`audit`](super).
+
+use sea_orm::entity::prelude::*;
+use time::OffsetDateTime;
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "modkit_audit_log")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i64,
+    pub occurred_at: OffsetDateTime,
+    pub request_id: Option<String>,
+    pub subject_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    pub operation: String,
+    pub method: String,
+    pub path: String,
+    pub resource_ids: String,
+    pub status: i32,
+    pub outcome: String,
+    pub latency_ms: i64,
+    pub details: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
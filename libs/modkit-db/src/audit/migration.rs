@@ -0,0 +1,117 @@
+//! Migration that creates the `modkit_audit_log` table.
+//!
+//! Modules that write audit records append [`migration()`](super::migration) to the list
+//! returned from `DatabaseCapability::migrations()`. The table is created with
+//! `IF NOT EXISTS`, so several modules sharing one database can all include it.
+
+use sea_orm_migration::prelude::*;
+
+const INDEX_NAME: &str = "idx_modkit_audit_log_occurred_at";
+
+pub(super) struct CreateAuditLogTable;
+
+impl MigrationName for CreateAuditLogTable {
+    fn name(&self) -> &'static str {
+        "m00000000_000003_modkit_audit_log"
+    }
+}
+
+#[derive(Iden)]
+enum ModkitAuditLog {
+    Table,
+    Id,
+    OccurredAt,
+    RequestId,
+    SubjectId,
+    TenantId,
+    Operation,
+    Method,
+    Path,
+    ResourceIds,
+    Status,
+    Outcome,
+    LatencyMs,
+    Details,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateAuditLogTable {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut index = Index::create()
+            .name(INDEX_NAME)
+            .table(ModkitAuditLog::Table)
+            .col(ModkitAuditLog::OccurredAt)
+            .to_owned();
+        let mut table = Table::create()
+            .table(ModkitAuditLog::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(ModkitAuditLog::Id)
+                    .big_integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(ModkitAuditLog::OccurredAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitAuditLog::RequestId)
+                    .string_len(255)
+                    .null(),
+            )
+            .col(ColumnDef::new(ModkitAuditLog::SubjectId).uuid().null())
+            .col(ColumnDef::new(ModkitAuditLog::TenantId).uuid().null())
+            .col(
+                ColumnDef::new(ModkitAuditLog::Operation)
+                    .string_len(255)
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitAuditLog::Method)
+                    .string_len(16)
+                    .not_null(),
+            )
+            .col(ColumnDef::new(ModkitAuditLog::Path).text().not_null())
+            .col(
+                ColumnDef::new(ModkitAuditLog::ResourceIds)
+                    .text()
+                    .not_null(),
+            )
+            .col(ColumnDef::new(ModkitAuditLog::Status).integer().not_null())
+            .col(
+                ColumnDef::new(ModkitAuditLog::Outcome)
+                    .string_len(16)
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitAuditLog::LatencyMs)
+                    .big_integer()
+                    .not_null(),
+            )
+            .col(ColumnDef::new(ModkitAuditLog::Details).text().null())
+            .to_owned();
+
+        // MySQL has no `CREATE INDEX IF NOT EXISTS`; declare the index inline so that it
+        // is covered by `CREATE TABLE IF NOT EXISTS` instead.
+        if manager.get_database_backend() == sea_orm::DbBackend::MySql {
+            table.index(&mut index);
+            return manager.create_table(table).await;
+        }
+        manager.create_table(table).await?;
+        manager.create_index(index.if_not_exists().to_owned()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModkitAuditLog::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
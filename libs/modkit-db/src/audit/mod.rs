@@ -0,0 +1,134 @@
+//! Append-only audit log of security-relevant operations.
+//!
+//! Each [`AuditEntry`] records who invoked which operation on which resources and how it
+//! ended. Entries are written with [`append`], read back newest first with [`recent`] and
+//! removed once past their retention period with [`purge_before`]. Nothing updates an entry
+//! after it has been written.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use modkit_db::audit::{self, AuditEntry};
+//!
+//! // DatabaseCapability::migrations()
+//! migrations.push(modkit_db::audit::migration());
+//!
+//! let conn = db.conn()?;
+//! audit::append(&conn, &entry).await?;
+//! ```
+
+mod entity;
+mod migration;
+
+use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect};
+use sea_orm_migration::MigrationTrait;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::DbError;
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+/// Migration creating the `modkit_audit_log` table.
+///
+/// Append it to the module's migration list.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(migration::CreateAuditLogTable)
+}
+
+/// One audited invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub occurred_at: OffsetDateTime,
+    pub request_id: Option<String>,
+    pub subject_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    /// Operation id, or `METHOD path` for operations without one
+    pub operation: String,
+    pub method: String,
+    /// Route template, e.g. `/users/v1/users/{id}`
+    pub path: String,
+    /// JSON object of the resource ids taken from the path
+    pub resource_ids: String,
+    pub status: u16,
+    /// `success`, `denied` or `failure`
+    pub outcome: String,
+    pub latency_ms: u64,
+    /// Optional JSON document with further (already redacted) details
+    pub details: Option<String>,
+}
+
+impl From<entity::Model> for AuditEntry {
+    fn from(m: entity::Model) -> Self {
+        Self {
+            occurred_at: m.occurred_at,
+            request_id: m.request_id,
+            subject_id: m.subject_id,
+            tenant_id: m.tenant_id,
+            operation: m.operation,
+            method: m.method,
+            path: m.path,
+            resource_ids: m.resource_ids,
+            status: u16::try_from(m.status).unwrap_or_default(),
+            outcome: m.outcome,
+            latency_ms: u64::try_from(m.latency_ms).unwrap_or_default(),
+            details: m.details,
+        }
+    }
+}
+
+/// Write `entry` to the audit log.
+///
+/// # Errors
+/// Returns `DbError` if the insert fails.
+pub async fn append<C: DBRunner>(runner: &C, entry: &AuditEntry) -> Result<(), DbError> {
+    let am = entity::ActiveModel {
+        occurred_at: Set(entry.occurred_at),
+        request_id: Set(entry.request_id.clone()),
+        subject_id: Set(entry.subject_id),
+        tenant_id: Set(entry.tenant_id),
+        operation: Set(entry.operation.clone()),
+        method: Set(entry.method.clone()),
+        path: Set(entry.path.clone()),
+        resource_ids: Set(entry.resource_ids.clone()),
+        status: Set(i32::from(entry.status)),
+        outcome: Set(entry.outcome.clone()),
+        latency_ms: Set(i64::try_from(entry.latency_ms).unwrap_or(i64::MAX)),
+        details: Set(entry.details.clone()),
+        ..Default::default()
+    };
+    let insert = entity::Entity::insert(am);
+    match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => insert.exec_without_returning(db).await?,
+        SeaOrmRunner::Tx(tx) => insert.exec_without_returning(tx).await?,
+    };
+    Ok(())
+}
+
+/// The `limit` most recent entries, newest first.
+///
+/// # Errors
+/// Returns `DbError` if the query fails.
+pub async fn recent<C: DBRunner>(runner: &C, limit: u64) -> Result<Vec<AuditEntry>, DbError> {
+    let select = entity::Entity::find()
+        .order_by_desc(entity::Column::Id)
+        .limit(limit);
+    let rows = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => select.all(db).await?,
+        SeaOrmRunner::Tx(tx) => select.all(tx).await?,
+    };
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+/// Delete all entries that occurred before `cutoff`.
+///
+/// # Errors
+/// Returns `DbError` if the delete fails.
+pub async fn purge_before<C: DBRunner>(runner: &C, cutoff: OffsetDateTime) -> Result<u64, DbError> {
+    let delete = entity::Entity::delete_many().filter(entity::Column::OccurredAt.lt(cutoff));
+    let res = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => delete.exec(db).await?,
+        SeaOrmRunner::Tx(tx) => delete.exec(tx).await?,
+    };
+    Ok(res.rows_affected)
+}
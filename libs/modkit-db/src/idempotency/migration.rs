@@ -0,0 +1,90 @@
+//! Migration that creates the `modkit_idempotency` table.
+//!
+//! Modules that store idempotency records append [`migration()`](super::migration) to the
+//! list returned from `DatabaseCapability::migrations()`. The table is created with
+//! `IF NOT EXISTS`, so several modules sharing one database can all include it.
+
+use sea_orm_migration::prelude::*;
+
+const INDEX_NAME: &str = "idx_modkit_idempotency_expires_at";
+
+pub(super) struct CreateIdempotencyTable;
+
+impl MigrationName for CreateIdempotencyTable {
+    fn name(&self) -> &'static str {
+        "m00000000_000002_modkit_idempotency"
+    }
+}
+
+#[derive(Iden)]
+enum ModkitIdempotency {
+    Table,
+    Key,
+    Fingerprint,
+    Status,
+    Response,
+    CreatedAt,
+    ExpiresAt,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateIdempotencyTable {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut index = Index::create()
+            .name(INDEX_NAME)
+            .table(ModkitIdempotency::Table)
+            .col(ModkitIdempotency::ExpiresAt)
+            .to_owned();
+        let mut table = Table::create()
+            .table(ModkitIdempotency::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(ModkitIdempotency::Key)
+                    .string_len(255)
+                    .not_null()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(ModkitIdempotency::Fingerprint)
+                    .string_len(128)
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitIdempotency::Status)
+                    .string_len(16)
+                    .not_null(),
+            )
+            .col(ColumnDef::new(ModkitIdempotency::Response).text().null())
+            .col(
+                ColumnDef::new(ModkitIdempotency::CreatedAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitIdempotency::ExpiresAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .to_owned();
+
+        // MySQL has no `CREATE INDEX IF NOT EXISTS`; declare the index inline so that it
+        // is covered by `CREATE TABLE IF NOT EXISTS` instead.
+        if manager.get_database_backend() == sea_orm::DbBackend::MySql {
+            table.index(&mut index);
+            return manager.create_table(table).await;
+        }
+        manager.create_table(table).await?;
+        manager.create_index(index.if_not_exists().to_owned()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModkitIdempotency::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
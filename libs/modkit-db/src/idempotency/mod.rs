@@ -0,0 +1,215 @@
+//! Idempotency records for safely retried requests.
+//!
+//! A caller that wants to execute an operation at most once per client-supplied key
+//! first [`claim`]s the key together with a fingerprint of the request. The first claim
+//! wins and executes the operation, then stores the outcome with [`complete`] (or gives
+//! the key up with [`release`] so that a retry can execute it again). Later claims of
+//! the same key see the in-flight or completed record instead.
+//!
+//! An in-flight claim expires after the lease passed to [`claim`], so a key held by a
+//! crashed executor frees up quickly; a completed record expires after the TTL passed
+//! to [`complete`]. Expired records are replaced on the next claim of their key and
+//! removed in bulk by [`purge_expired`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use modkit_db::idempotency::{self, IdempotencyClaim};
+//!
+//! // DatabaseCapability::migrations()
+//! migrations.push(modkit_db::idempotency::migration());
+//!
+//! let conn = db.conn()?;
+//! match idempotency::claim(&conn, &key, &fingerprint, lease).await? {
+//!     IdempotencyClaim::Claimed => {
+//!         let response = execute().await;
+//!         idempotency::complete(&conn, &key, &response, ttl).await?;
+//!     }
+//!     IdempotencyClaim::InFlight { .. } => { /* conflict */ }
+//!     IdempotencyClaim::Completed { response, .. } => { /* replay */ }
+//! }
+//! ```
+
+mod entity;
+mod migration;
+
+use std::time::Duration;
+
+use sea_orm::sea_query::{Expr, OnConflict};
+use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm_migration::MigrationTrait;
+use time::OffsetDateTime;
+
+use crate::DbError;
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+const STATUS_IN_FLIGHT: &str = "in_flight";
+const STATUS_COMPLETED: &str = "completed";
+
+/// Claims are retried this many times when a competing record disappears in between.
+const CLAIM_ATTEMPTS: usize = 3;
+
+/// Migration creating the `modkit_idempotency` table.
+///
+/// Append it to the module's migration list.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(migration::CreateIdempotencyTable)
+}
+
+/// Result of [`claim`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// The key was free; the caller executes the operation.
+    Claimed,
+    /// Another execution holds the key and has not completed yet.
+    InFlight { fingerprint: String },
+    /// The operation completed; `response` is what was passed to [`complete`].
+    Completed {
+        fingerprint: String,
+        response: String,
+    },
+}
+
+/// Claim `key` for executing the request identified by `fingerprint`.
+///
+/// The claim is held for `lease`; pick it to cover the execution, e.g. the request
+/// timeout. An unfinished claim older than that is taken over by the next caller.
+///
+/// # Errors
+/// Returns `DbError` if a query fails or the lease is out of range.
+pub async fn claim<C: DBRunner>(
+    runner: &C,
+    key: &str,
+    fingerprint: &str,
+    lease: Duration,
+) -> Result<IdempotencyClaim, DbError> {
+    let lease = to_time_duration("lease", lease)?;
+
+    for _ in 0..CLAIM_ATTEMPTS {
+        let now = OffsetDateTime::now_utc();
+        let am = entity::ActiveModel {
+            key: Set(key.to_owned()),
+            fingerprint: Set(fingerprint.to_owned()),
+            status: Set(STATUS_IN_FLIGHT.to_owned()),
+            response: Set(None),
+            created_at: Set(now),
+            expires_at: Set(now + lease),
+        };
+        let insert = entity::Entity::insert(am).on_conflict(
+            OnConflict::column(entity::Column::Key)
+                .do_nothing()
+                .to_owned(),
+        );
+        let inserted = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => insert.exec_without_returning(db).await?,
+            SeaOrmRunner::Tx(tx) => insert.exec_without_returning(tx).await?,
+        };
+        if inserted > 0 {
+            return Ok(IdempotencyClaim::Claimed);
+        }
+
+        let find = entity::Entity::find_by_id(key.to_owned());
+        let existing = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => find.one(db).await?,
+            SeaOrmRunner::Tx(tx) => find.one(tx).await?,
+        };
+        match existing {
+            // Released or purged in between; try again
+            None => {}
+            Some(record) if record.expires_at <= now => {
+                delete(runner, key, Some(now), None).await?;
+            }
+            Some(record) => {
+                return Ok(match record.response {
+                    Some(response) if record.status == STATUS_COMPLETED => {
+                        IdempotencyClaim::Completed {
+                            fingerprint: record.fingerprint,
+                            response,
+                        }
+                    }
+                    _ => IdempotencyClaim::InFlight {
+                        fingerprint: record.fingerprint,
+                    },
+                });
+            }
+        }
+    }
+
+    Err(DbError::Other(anyhow::anyhow!(
+        "idempotency key '{key}' is contended"
+    )))
+}
+
+/// Store the outcome of a claimed execution; it is returned to claims of `key` for `ttl`.
+///
+/// Returns `false` if the claim no longer exists (released or expired and purged).
+///
+/// # Errors
+/// Returns `DbError` if the update fails or the TTL is out of range.
+pub async fn complete<C: DBRunner>(
+    runner: &C,
+    key: &str,
+    response: &str,
+    ttl: Duration,
+) -> Result<bool, DbError> {
+    let expires_at = OffsetDateTime::now_utc() + to_time_duration("ttl", ttl)?;
+    let update = entity::Entity::update_many()
+        .col_expr(entity::Column::Status, Expr::value(STATUS_COMPLETED))
+        .col_expr(entity::Column::Response, Expr::value(response))
+        .col_expr(entity::Column::ExpiresAt, Expr::value(expires_at))
+        .filter(entity::Column::Key.eq(key))
+        .filter(entity::Column::Status.eq(STATUS_IN_FLIGHT));
+    let res = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => update.exec(db).await?,
+        SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+    };
+    Ok(res.rows_affected > 0)
+}
+
+/// Give up an in-flight claim so that a retry executes the operation again.
+///
+/// # Errors
+/// Returns `DbError` if the delete fails.
+pub async fn release<C: DBRunner>(runner: &C, key: &str) -> Result<bool, DbError> {
+    Ok(delete(runner, key, None, Some(STATUS_IN_FLIGHT)).await? > 0)
+}
+
+/// Delete all expired records.
+///
+/// # Errors
+/// Returns `DbError` if the delete fails.
+pub async fn purge_expired<C: DBRunner>(runner: &C) -> Result<u64, DbError> {
+    let delete = entity::Entity::delete_many()
+        .filter(entity::Column::ExpiresAt.lte(OffsetDateTime::now_utc()));
+    let res = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => delete.exec(db).await?,
+        SeaOrmRunner::Tx(tx) => delete.exec(tx).await?,
+    };
+    Ok(res.rows_affected)
+}
+
+fn to_time_duration(name: &str, duration: Duration) -> Result<time::Duration, DbError> {
+    time::Duration::try_from(duration)
+        .map_err(|e| DbError::InvalidParameter(format!("idempotency {name}: {e}")))
+}
+
+async fn delete<C: DBRunner>(
+    runner: &C,
+    key: &str,
+    expired_at: Option<OffsetDateTime>,
+    status: Option<&str>,
+) -> Result<u64, DbError> {
+    let mut delete = entity::Entity::delete_many().filter(entity::Column::Key.eq(key));
+    if let Some(now) = expired_at {
+        delete = delete.filter(entity::Column::ExpiresAt.lte(now));
+    }
+    if let Some(status) = status {
+        delete = delete.filter(entity::Column::Status.eq(status));
+    }
+    let res = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => delete.exec(db).await?,
+        SeaOrmRunner::Tx(tx) => delete.exec(tx).await?,
+    };
+    Ok(res.rows_affected)
+}
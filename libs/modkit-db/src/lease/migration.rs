@@ -0,0 +1,64 @@
+//! Migration that creates the `modkit_leases` table.
+//!
+//! Modules that take leases append [`migration()`](super::migration) to the list
+//! returned from `DatabaseCapability::migrations()`. The table is created with
+//! `IF NOT EXISTS`, so several modules sharing one database can all include it.
+
+use sea_orm_migration::prelude::*;
+
+pub(super) struct CreateLeaseTable;
+
+impl MigrationName for CreateLeaseTable {
+    fn name(&self) -> &'static str {
+        "m00000000_000003_modkit_leases"
+    }
+}
+
+#[derive(Iden)]
+enum ModkitLeases {
+    Table,
+    Name,
+    Holder,
+    LockedUntil,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateLeaseTable {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ModkitLeases::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ModkitLeases::Name)
+                            .string_len(255)
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ModkitLeases::Holder)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ModkitLeases::LockedUntil)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModkitLeases::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,115 @@
+//! Time-bound leases for mutual exclusion across instances sharing a database.
+//!
+//! A lease is a row in the `modkit_leases` table naming its current holder and the
+//! time it is held until. [`acquire`] takes a free or expired lease, or extends one
+//! the caller already holds; [`release`] gives it up. A holder that crashes stops
+//! renewing, so its lease expires after the TTL and another instance takes over.
+//!
+//! Holders renew by calling [`acquire`] again well before the TTL runs out (e.g. every
+//! third of it). Expiry is computed from the callers' clocks, so the TTL must be large
+//! compared to the clock skew between instances.
+//!
+//! # Example
+//!
+//! ```ignore
+//! // DatabaseCapability::migrations()
+//! migrations.push(modkit_db::lease::migration());
+//!
+//! let holder = uuid::Uuid::new_v4().to_string();
+//! let conn = db.conn()?;
+//! if modkit_db::lease::acquire(&conn, "billing:rollup", &holder, ttl).await? {
+//!     // ... work, calling `acquire` again every `ttl / 3` ...
+//!     modkit_db::lease::release(&conn, "billing:rollup", &holder).await?;
+//! }
+//! ```
+
+mod entity;
+mod migration;
+
+use std::time::Duration;
+
+use sea_orm::sea_query::{Condition, Expr, OnConflict};
+use sea_orm::{ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm_migration::MigrationTrait;
+use time::OffsetDateTime;
+
+use crate::DbError;
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+/// Migration creating the `modkit_leases` table.
+///
+/// Append it to the module's migration list.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(migration::CreateLeaseTable)
+}
+
+/// Take or renew the lease `name` for `holder` until `ttl` from now.
+///
+/// `holder` identifies the caller and must be unique per instance (e.g. a random UUID).
+/// Returns `false` if another holder has a lease that has not expired yet.
+///
+/// # Errors
+/// Returns `DbError` if a query fails or the TTL is out of range.
+pub async fn acquire<C: DBRunner>(
+    runner: &C,
+    name: &str,
+    holder: &str,
+    ttl: Duration,
+) -> Result<bool, DbError> {
+    let ttl = time::Duration::try_from(ttl)
+        .map_err(|e| DbError::InvalidParameter(format!("lease ttl: {e}")))?;
+    let now = OffsetDateTime::now_utc();
+
+    let am = entity::ActiveModel {
+        name: Set(name.to_owned()),
+        holder: Set(holder.to_owned()),
+        locked_until: Set(now + ttl),
+    };
+    let insert = entity::Entity::insert(am).on_conflict(
+        OnConflict::column(entity::Column::Name)
+            .do_nothing()
+            .to_owned(),
+    );
+    let inserted = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => insert.exec_without_returning(db).await?,
+        SeaOrmRunner::Tx(tx) => insert.exec_without_returning(tx).await?,
+    };
+    if inserted > 0 {
+        return Ok(true);
+    }
+
+    // The row exists: take it over if it expired, or extend it if it is ours
+    let update = entity::Entity::update_many()
+        .col_expr(entity::Column::Holder, Expr::value(holder))
+        .col_expr(entity::Column::LockedUntil, Expr::value(now + ttl))
+        .filter(entity::Column::Name.eq(name))
+        .filter(
+            Condition::any()
+                .add(entity::Column::LockedUntil.lte(now))
+                .add(entity::Column::Holder.eq(holder)),
+        );
+    let res = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => update.exec(db).await?,
+        SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+    };
+    Ok(res.rows_affected > 0)
+}
+
+/// Give up the lease `name` if `holder` still holds it.
+///
+/// Returns `false` if the lease was not held by `holder` (expired and taken over, or
+/// never acquired).
+///
+/// # Errors
+/// Returns `DbError` if the delete fails.
+pub async fn release<C: DBRunner>(runner: &C, name: &str, holder: &str) -> Result<bool, DbError> {
+    let delete = entity::Entity::delete_many()
+        .filter(entity::Column::Name.eq(name))
+        .filter(entity::Column::Holder.eq(holder));
+    let res = match DBRunnerInternal::as_seaorm(runner) {
+        SeaOrmRunner::Conn(db) => delete.exec(db).await?,
+        SeaOrmRunner::Tx(tx) => delete.exec(tx).await?,
+    };
+    Ok(res.rows_affected > 0)
+}
@@ -74,17 +74,23 @@ pub use sea_orm_migration;
 
 // Core modules
 pub mod advisory_locks;
+pub mod audit;
 pub mod config;
+pub mod idempotency;
+pub mod lease;
 pub mod manager;
 pub mod migration_runner;
 pub mod odata;
 pub mod options;
+pub mod outbox;
 
 pub mod secure;
 
 mod db_provider;
 
 // Internal modules
+#[cfg(feature = "otel")]
+mod pool_metrics;
 mod pool_opts;
 #[cfg(feature = "sqlite")]
 mod sqlite;
@@ -444,6 +450,23 @@ impl DbHandle {
         &self.dsn
     }
 
+    /// Current connection pool usage.
+    #[cfg(feature = "otel")]
+    pub(crate) fn pool_stats(&self) -> pool_metrics::PoolStats {
+        match self.engine {
+            #[cfg(feature = "pg")]
+            DbEngine::Postgres => {
+                pool_metrics::PoolStats::of(self.sea.get_postgres_connection_pool())
+            }
+            #[cfg(feature = "mysql")]
+            DbEngine::MySql => pool_metrics::PoolStats::of(self.sea.get_mysql_connection_pool()),
+            #[cfg(feature = "sqlite")]
+            DbEngine::Sqlite => pool_metrics::PoolStats::of(self.sea.get_sqlite_connection_pool()),
+            #[allow(unreachable_patterns)]
+            _ => pool_metrics::PoolStats::default(),
+        }
+    }
+
     // NOTE: We intentionally do not expose raw `SQLx` pools from `DbHandle`.
     // Use `SecureConn` for all application-level DB access.
 
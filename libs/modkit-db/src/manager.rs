@@ -129,7 +129,10 @@ impl DbManager {
             "Built database handle for module"
         );
 
-        Ok(Some(Db::new(handle)))
+        let db = Db::new(handle);
+        #[cfg(feature = "otel")]
+        crate::pool_metrics::register(module, &db);
+        Ok(Some(db))
     }
 
     /// Merge global server configuration into module configuration.
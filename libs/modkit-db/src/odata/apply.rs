@@ -0,0 +1,202 @@
+//! `$apply` execution: `groupby`/`aggregate` compiled into one scoped `GROUP BY` query.
+//!
+//! The caller passes the same security-scoped `Select<E>` that is used for listings,
+//! so every aggregate only sees rows within the caller's `AccessScope`.
+//!
+//! Aggregates are type-checked against the `FieldMap`:
+//! - `sum`, `average`: numeric properties
+//! - `min`, `max`: any property except `Bool` and `Uuid`
+//! - `countdistinct`, `$count`: any property
+
+use modkit_odata::filter::FieldKind;
+use modkit_odata::{Aggregate, AggregateMethod, Error as ODataError, ODataQuery};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbBackend, EntityTrait, QueryFilter, QueryOrder, QueryResult,
+    QuerySelect, QueryTrait,
+    sea_query::{Alias, Asterisk, Expr, Func, SimpleExpr},
+};
+use serde_json::{Map, Value};
+
+use crate::odata::core::runner_backend;
+use crate::odata::{FieldMap, expr_to_condition_for};
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+/// One `$apply` result row: grouping properties and aggregate aliases.
+pub type AggregateRow = Map<String, Value>;
+
+/// Run the `$apply` of `q` over the (security-scoped) `select`.
+///
+/// The `$filter` of `q` is applied to the input rows together with the `filter(...)`
+/// steps of `$apply`; cursor, order and limit of `q` are ignored. Rows are ordered by
+/// the grouping properties. At most `max_groups` rows are returned.
+///
+/// # Errors
+/// Returns `ODataError::InvalidApply` if `q` has no `$apply`, references unknown
+/// properties, uses a method that does not fit the property type or yields more than
+/// `max_groups` rows; `ODataError::InvalidFilter` for invalid filters.
+pub async fn aggregate_with_odata<E, C>(
+    select: sea_orm::Select<E>,
+    conn: &C,
+    q: &ODataQuery,
+    fmap: &FieldMap<E>,
+    max_groups: u64,
+) -> Result<Vec<AggregateRow>, ODataError>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    C: DBRunner,
+{
+    let apply = q
+        .apply
+        .as_ref()
+        .ok_or_else(|| ODataError::InvalidApply("missing $apply".into()))?;
+    let backend = runner_backend(conn);
+
+    let mut s = select.select_only();
+    for ast in [q.filter.as_deref(), apply.filter.as_deref()]
+        .into_iter()
+        .flatten()
+    {
+        s = s.filter(
+            expr_to_condition_for::<E>(ast, fmap, backend)
+                .map_err(|e| ODataError::InvalidFilter(e.to_string()))?,
+        );
+    }
+
+    // (result name, decoded kind) in select order
+    let mut columns: Vec<(&str, FieldKind)> = Vec::new();
+    for name in &apply.group_by {
+        let field = fmap
+            .get(name)
+            .ok_or_else(|| ODataError::InvalidApply(format!("unknown property: {name}")))?;
+        s = s
+            .column_as(Expr::col(field.col), name.as_str())
+            .group_by(field.col)
+            .order_by_asc(field.col);
+        columns.push((name, field.kind));
+    }
+    for aggregate in &apply.aggregates {
+        let (expr, kind) = aggregate_expr(aggregate, fmap, backend)?;
+        s = s.column_as(expr, aggregate.alias.as_str());
+        columns.push((&aggregate.alias, kind));
+    }
+    s = s.limit(max_groups.saturating_add(1));
+
+    let stmt = s.into_query();
+    let rows = match DBRunnerInternal::as_seaorm(conn) {
+        SeaOrmRunner::Conn(db) => db.query_all(backend.build(&stmt)).await,
+        SeaOrmRunner::Tx(tx) => tx.query_all(backend.build(&stmt)).await,
+    }
+    .map_err(|e| ODataError::Db(e.to_string()))?;
+
+    if rows.len() as u64 > max_groups {
+        return Err(ODataError::InvalidApply(format!(
+            "result exceeds {max_groups} groups"
+        )));
+    }
+
+    rows.iter()
+        .map(|row| {
+            columns
+                .iter()
+                .map(|(name, kind)| Ok(((*name).to_owned(), read_value(row, name, *kind)?)))
+                .collect()
+        })
+        .collect::<Result<_, sea_orm::DbErr>>()
+        .map_err(|e| ODataError::Db(e.to_string()))
+}
+
+/// SQL expression and result kind of one aggregate.
+fn aggregate_expr<E: EntityTrait>(
+    aggregate: &Aggregate,
+    fmap: &FieldMap<E>,
+    backend: DbBackend,
+) -> Result<(SimpleExpr, FieldKind), ODataError>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let Some(name) = aggregate.field.as_deref() else {
+        return Ok((Func::count(Expr::col(Asterisk)).into(), FieldKind::I64));
+    };
+    let field = fmap
+        .get(name)
+        .ok_or_else(|| ODataError::InvalidApply(format!("unknown property: {name}")))?;
+    let col = Expr::col(field.col);
+    let numeric = matches!(
+        field.kind,
+        FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal
+    );
+
+    Ok(match aggregate.method {
+        AggregateMethod::Count => (Func::count(col).into(), FieldKind::I64),
+        AggregateMethod::CountDistinct => (Func::count_distinct(col).into(), FieldKind::I64),
+        // SUM(bigint) is numeric on Postgres and DECIMAL on MySQL.
+        AggregateMethod::Sum if field.kind == FieldKind::I64 => {
+            (integer(Func::sum(col).into(), backend), FieldKind::I64)
+        }
+        AggregateMethod::Sum if numeric => (Func::sum(col).into(), field.kind),
+        AggregateMethod::Average if field.kind == FieldKind::I64 => {
+            (double(Func::avg(col).into(), backend), FieldKind::F64)
+        }
+        AggregateMethod::Average if numeric => (Func::avg(col).into(), field.kind),
+        AggregateMethod::Min if !matches!(field.kind, FieldKind::Bool | FieldKind::Uuid) => {
+            (Func::min(col).into(), field.kind)
+        }
+        AggregateMethod::Max if !matches!(field.kind, FieldKind::Bool | FieldKind::Uuid) => {
+            (Func::max(col).into(), field.kind)
+        }
+        method => {
+            return Err(ODataError::InvalidApply(format!(
+                "{} is not supported for {name} ({:?})",
+                method.as_str(),
+                field.kind
+            )));
+        }
+    })
+}
+
+fn integer(expr: SimpleExpr, backend: DbBackend) -> SimpleExpr {
+    match backend {
+        DbBackend::Postgres => expr.cast_as(Alias::new("BIGINT")),
+        DbBackend::MySql => expr.cast_as(Alias::new("SIGNED")),
+        DbBackend::Sqlite => expr,
+    }
+}
+
+fn double(expr: SimpleExpr, backend: DbBackend) -> SimpleExpr {
+    match backend {
+        DbBackend::Postgres => expr.cast_as(Alias::new("DOUBLE PRECISION")),
+        DbBackend::MySql => expr.cast_as(Alias::new("DOUBLE")),
+        DbBackend::Sqlite => expr,
+    }
+}
+
+fn read_value(row: &QueryResult, name: &str, kind: FieldKind) -> Result<Value, sea_orm::DbErr> {
+    Ok(match kind {
+        FieldKind::String => row.try_get::<Option<String>>("", name)?.into(),
+        FieldKind::I64 => row.try_get::<Option<i64>>("", name)?.into(),
+        FieldKind::F64 => row.try_get::<Option<f64>>("", name)?.into(),
+        FieldKind::Bool => row.try_get::<Option<bool>>("", name)?.into(),
+        // Decimals are rendered as strings to keep their precision.
+        FieldKind::Decimal => row
+            .try_get::<Option<rust_decimal::Decimal>>("", name)?
+            .map(|v| v.to_string())
+            .into(),
+        FieldKind::Uuid => row
+            .try_get::<Option<uuid::Uuid>>("", name)?
+            .map(|v| v.to_string())
+            .into(),
+        FieldKind::DateTimeUtc => row
+            .try_get::<Option<chrono::DateTime<chrono::Utc>>>("", name)?
+            .map(|v| v.to_rfc3339())
+            .into(),
+        FieldKind::Date => row
+            .try_get::<Option<chrono::NaiveDate>>("", name)?
+            .map(|v| v.to_string())
+            .into(),
+        FieldKind::Time => row
+            .try_get::<Option<chrono::NaiveTime>>("", name)?
+            .map(|v| v.to_string())
+            .into(),
+    })
+}
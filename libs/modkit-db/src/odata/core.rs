@@ -8,14 +8,16 @@ use chrono::{NaiveDate, NaiveTime, Utc};
 use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, ODataQuery, SortDir, ast as core};
 use rust_decimal::Decimal;
 use sea_orm::{
-    ColumnTrait, Condition, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
-    sea_query::{Expr, Order},
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, QueryTrait,
+    sea_query::{Alias, Asterisk, Expr, Func, Order, Query, SimpleExpr},
 };
 use thiserror::Error;
 
 use modkit_odata::filter::FieldKind;
 
 use crate::odata::LimitCfg;
+use crate::odata::functions::{FnCtx, Operand, comparable, kind_name, operand};
 use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
 
 /// Type alias for cursor extraction function to reduce type complexity
@@ -127,7 +129,7 @@ fn bigdecimal_to_decimal(bd: &BigDecimal) -> ODataBuildResult<Decimal> {
         .map_err(|_| ODataBuildError::Other("invalid decimal"))
 }
 
-fn coerce(kind: FieldKind, v: &core::Value) -> ODataBuildResult<sea_orm::Value> {
+pub(super) fn coerce(kind: FieldKind, v: &core::Value) -> ODataBuildResult<sea_orm::Value> {
     use core::Value as V;
     Ok(match (kind, v) {
         (FieldKind::String, V::String(s)) => sea_orm::Value::String(Some(Box::new(s.clone()))),
@@ -251,19 +253,6 @@ fn like_ends(s: &str) -> String {
     format!("%{}", like_escape(s))
 }
 
-/* ---------- small guards ---------- */
-
-#[inline]
-fn ensure_string_field<E: EntityTrait>(f: &Field<E>, _field_name: &str) -> ODataBuildResult<()> {
-    if f.kind != FieldKind::String {
-        return Err(ODataBuildError::TypeMismatch {
-            expected: FieldKind::String,
-            got: "non-string field",
-        });
-    }
-    Ok(())
-}
-
 /* ---------- cursor value encoding/decoding ---------- */
 
 /// Parse a cursor value from string based on field kind
@@ -456,12 +445,72 @@ pub fn build_cursor_for_model<E: EntityTrait>(
 
 /// Convert an `OData` filter expression AST to a `SeaORM` Condition.
 ///
+/// Backend-specific functions (date parts such as `year()`) are rejected here;
+/// use [`expr_to_condition_for`] when the database backend is known.
+///
 /// # Errors
 /// Returns `ODataBuildError` if the expression contains unknown fields or unsupported operations.
 pub fn expr_to_condition<E: EntityTrait>(
     expr: &core::Expr,
     fmap: &FieldMap<E>,
 ) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    compile_condition(
+        expr,
+        &FnCtx {
+            fmap,
+            backend: None,
+        },
+    )
+}
+
+/// Convert an `OData` filter expression AST to a `SeaORM` Condition for `backend`.
+///
+/// # Errors
+/// Returns `ODataBuildError` if the expression contains unknown fields or unsupported operations.
+pub fn expr_to_condition_for<E: EntityTrait>(
+    expr: &core::Expr,
+    fmap: &FieldMap<E>,
+    backend: DbBackend,
+) -> ODataBuildResult<Condition>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    compile_condition(
+        expr,
+        &FnCtx {
+            fmap,
+            backend: Some(backend),
+        },
+    )
+}
+
+/// Database backend behind a runner, for [`expr_to_condition_for`].
+pub(super) fn runner_backend(runner: &(impl DBRunnerInternal + ?Sized)) -> DbBackend {
+    match runner.as_seaorm() {
+        SeaOrmRunner::Conn(db) => db.get_database_backend(),
+        SeaOrmRunner::Tx(tx) => tx.get_database_backend(),
+    }
+}
+
+/// `a op b` is the same as `b flip(op) a`.
+fn flip(op: core::CompareOperator) -> core::CompareOperator {
+    use core::CompareOperator as Op;
+    match op {
+        Op::Gt => Op::Lt,
+        Op::Ge => Op::Le,
+        Op::Lt => Op::Gt,
+        Op::Le => Op::Ge,
+        Op::Eq | Op::Ne => op,
+    }
+}
+
+fn compile_condition<E: EntityTrait>(
+    expr: &core::Expr,
+    ctx: &FnCtx<'_, E>,
+) -> ODataBuildResult<Condition>
 where
     E::Column: ColumnTrait + Copy,
 {
@@ -470,102 +519,107 @@ where
 
     Ok(match expr {
         X::And(a, b) => {
-            let left = expr_to_condition::<E>(a, fmap)?;
-            let right = expr_to_condition::<E>(b, fmap)?;
+            let left = compile_condition::<E>(a, ctx)?;
+            let right = compile_condition::<E>(b, ctx)?;
             Condition::all().add(left).add(right) // AND
         }
         X::Or(a, b) => {
-            let left = expr_to_condition::<E>(a, fmap)?;
-            let right = expr_to_condition::<E>(b, fmap)?;
+            let left = compile_condition::<E>(a, ctx)?;
+            let right = compile_condition::<E>(b, ctx)?;
             Condition::any().add(left).add(right) // OR
         }
         X::Not(x) => {
-            let inner = expr_to_condition::<E>(x, fmap)?;
+            let inner = compile_condition::<E>(x, ctx)?;
             Condition::all().add(inner).not()
         }
 
-        // Identifier op Value
+        // Operand op Operand, where an operand is a field, a literal or a function call
         X::Compare(lhs, op, rhs) => {
-            let (name, rhs_val) = match (&**lhs, &**rhs) {
-                (X::Identifier(name), X::Value(val)) => (name, val),
-                (X::Identifier(_), X::Identifier(_)) => {
-                    return Err(ODataBuildError::Other(
-                        "field-to-field comparison is not supported",
-                    ));
+            if let (X::Identifier(_), X::Identifier(_)) = (&**lhs, &**rhs) {
+                return Err(ODataBuildError::Other(
+                    "field-to-field comparison is not supported",
+                ));
+            }
+            // Keep the typed side on the left so literals can be coerced to its kind.
+            let (left, kind, op, right) = match (operand(lhs, ctx)?, operand(rhs, ctx)?) {
+                (Operand::Typed(e, k), right) => (e, k, *op, right),
+                (Operand::Literal(v), Operand::Typed(e, k)) => {
+                    (e, k, flip(*op), Operand::Literal(v))
+                }
+                (Operand::Literal(_), Operand::Literal(_)) => {
+                    return Err(ODataBuildError::Other("unsupported comparison form"));
                 }
-                _ => return Err(ODataBuildError::Other("unsupported comparison form")),
             };
-            let field = fmap
-                .get(name)
-                .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-            let col = field.col;
+            let left = Expr::expr(left);
 
             // null handling
-            if matches!(rhs_val, core::Value::Null) {
+            if matches!(right, Operand::Literal(core::Value::Null)) {
                 return Ok(match op {
-                    Op::Eq => Condition::all().add(Expr::col(col).is_null()),
-                    Op::Ne => Condition::all().add(Expr::col(col).is_not_null()),
-                    _ => return Err(ODataBuildError::UnsupportedOp(*op)),
+                    Op::Eq => Condition::all().add(left.is_null()),
+                    Op::Ne => Condition::all().add(left.is_not_null()),
+                    _ => return Err(ODataBuildError::UnsupportedOp(op)),
                 });
             }
 
-            let value = coerce(field.kind, rhs_val)?;
+            let value = match right {
+                Operand::Literal(v) => SimpleExpr::Value(coerce(kind, v)?),
+                Operand::Typed(e, k) if comparable(kind, k) => e,
+                Operand::Typed(_, k) => {
+                    return Err(ODataBuildError::TypeMismatch {
+                        expected: kind,
+                        got: kind_name(k),
+                    });
+                }
+            };
             let expr = match op {
-                Op::Eq => Expr::col(col).eq(value),
-                Op::Ne => Expr::col(col).ne(value),
-                Op::Gt => Expr::col(col).gt(value),
-                Op::Ge => Expr::col(col).gte(value),
-                Op::Lt => Expr::col(col).lt(value),
-                Op::Le => Expr::col(col).lte(value),
+                Op::Eq => left.eq(value),
+                Op::Ne => left.ne(value),
+                Op::Gt => left.gt(value),
+                Op::Ge => left.gte(value),
+                Op::Lt => left.lt(value),
+                Op::Le => left.lte(value),
             };
             Condition::all().add(expr)
         }
 
-        // Identifier IN (value, value, ...)
+        // Operand IN (value, value, ...)
         X::In(l, list) => {
-            let X::Identifier(name) = &**l else {
-                return Err(ODataBuildError::Other("left side of IN must be a field"));
+            let Operand::Typed(left, kind) = operand(l, ctx)? else {
+                return Err(ODataBuildError::Other(
+                    "left side of IN must be a field or function",
+                ));
             };
-            let f = fmap
-                .get(name)
-                .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-            let col = f.col;
-            let vals = coerce_many(f.kind, list)?;
+            let vals = coerce_many(kind, list)?;
             if vals.is_empty() {
                 // IN () → always false
                 Condition::all().add(Expr::value(1).eq(0))
             } else {
-                Condition::all().add(Expr::col(col).is_in(vals))
+                Condition::all().add(Expr::expr(left).is_in(vals))
             }
         }
 
-        // Supported functions: contains/startswith/endswith
+        // Boolean functions: contains/startswith/endswith
         X::Function(fname, args) => {
-            let n = fname.to_ascii_lowercase();
-            match (n.as_str(), args.as_slice()) {
-                ("contains", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_contains(s)))
-                }
-                ("startswith", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_starts(s)))
-                }
-                ("endswith", [X::Identifier(name), X::Value(core::Value::String(s))]) => {
-                    let f = fmap
-                        .get(name)
-                        .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
-                    ensure_string_field(f, name)?;
-                    Condition::all().add(Expr::col(f.col).like(like_ends(s)))
-                }
+            let pattern: fn(&str) -> String = match fname.to_ascii_lowercase().as_str() {
+                "contains" => like_contains,
+                "startswith" => like_starts,
+                "endswith" => like_ends,
                 _ => return Err(ODataBuildError::UnsupportedFn(fname.clone())),
-            }
+            };
+            let [target, X::Value(core::Value::String(s))] = args.as_slice() else {
+                return Err(ODataBuildError::UnsupportedFn(fname.clone()));
+            };
+            let target = match operand(target, ctx)? {
+                Operand::Typed(e, FieldKind::String) => e,
+                Operand::Typed(..) => {
+                    return Err(ODataBuildError::TypeMismatch {
+                        expected: FieldKind::String,
+                        got: "non-string field",
+                    });
+                }
+                Operand::Literal(_) => return Err(ODataBuildError::UnsupportedFn(fname.clone())),
+            };
+            Condition::all().add(Expr::expr(target).like(pattern(s)))
         }
 
         // Leaf forms are not valid WHERE by themselves
@@ -832,7 +886,7 @@ where
     // Apply filter
     if let Some(ast) = q.filter.as_deref() {
         s = s.filter(
-            expr_to_condition::<E>(ast, fmap)
+            expr_to_condition_for::<E>(ast, fmap, runner_backend(conn))
                 .map_err(|e| ODataError::InvalidFilter(e.to_string()))?,
         );
     }
@@ -932,10 +986,78 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count: None,
+            total_count_capped: false,
         },
     })
 }
 
+/// Count the rows matching the `OData` filter of `q` (`$count=true`).
+///
+/// Cursor, order and limit of `q` are ignored, so every page of a listing reports the
+/// same total. With `cap`, at most `cap` rows are counted and the result is
+/// `(cap, true)` when more rows match.
+///
+/// Pass the same (security-scoped) `select` that is used for the page itself.
+///
+/// # Errors
+/// Returns `ODataError` if the filter is invalid or the count query fails.
+pub async fn count_with_odata<E, C>(
+    select: sea_orm::Select<E>,
+    conn: &C,
+    q: &ODataQuery,
+    fmap: &FieldMap<E>,
+    cap: Option<u64>,
+) -> Result<(u64, bool), ODataError>
+where
+    E: EntityTrait,
+    E::Column: ColumnTrait + Copy,
+    C: DBRunner,
+{
+    let mut s = select;
+    if let Some(ast) = q.filter.as_deref() {
+        s = s.filter(
+            expr_to_condition_for::<E>(ast, fmap, runner_backend(conn))
+                .map_err(|e| ODataError::InvalidFilter(e.to_string()))?,
+        );
+    }
+
+    // SELECT COUNT(*) FROM (SELECT 1 FROM ... WHERE ... [LIMIT cap + 1]) AS sub_query
+    let mut inner = s.select_only().expr(Expr::val(1));
+    if let Some(cap) = cap {
+        inner = inner.limit(cap.saturating_add(1));
+    }
+    let stmt = Query::select()
+        .expr_as(Func::count(Expr::col(Asterisk)), Alias::new("num_items"))
+        .from_subquery(inner.into_query(), Alias::new("sub_query"))
+        .to_owned();
+
+    let row = match DBRunnerInternal::as_seaorm(conn) {
+        SeaOrmRunner::Conn(db) => {
+            let stmt = db.get_database_backend().build(&stmt);
+            db.query_one(stmt).await
+        }
+        SeaOrmRunner::Tx(tx) => {
+            let stmt = tx.get_database_backend().build(&stmt);
+            tx.query_one(stmt).await
+        }
+    }
+    .map_err(|e| ODataError::Db(e.to_string()))?;
+
+    let total = match row {
+        Some(row) => row
+            .try_get::<i64>("", "num_items")
+            .map_err(|e| ODataError::Db(e.to_string()))?,
+        None => 0,
+    };
+    let total = u64::try_from(total).unwrap_or(0);
+
+    Ok(match cap {
+        Some(cap) if total > cap => (cap, true),
+        _ => (total, false),
+    })
+}
+
 fn build_cursor<E: EntityTrait>(
     rows: &[E::Model],
     effective_order: &ODataOrderBy,
@@ -0,0 +1,518 @@
+//! `$expand` execution through the Secure ORM.
+//!
+//! A [`RelationMap`] declares, next to a [`FieldMap`], which navigation properties of an
+//! entity can be expanded. Each expanded entity set is loaded with one
+//! `SecureSelect::find_with_related_scoped` query per level (not one query per item);
+//! every relation is registered with the `AccessScope` of its related entity.
+//! `max_per_parent` is applied in SQL with `ROW_NUMBER()`, so parents with many related
+//! rows do not load them all.
+//!
+//! ```ignore
+//! fn user_relations(scopes: &Scopes) -> RelationMap<user::Entity> {
+//!     RelationMap::new().insert_nested(
+//!         "addresses",
+//!         &scopes.addresses,
+//!         address_field_map(),
+//!         AddressDto::from,
+//!         RelationMap::new().insert("city", &scopes.cities, city_field_map(), CityDto::from),
+//!     )
+//! }
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use modkit_odata::{Error as ODataError, ExpandItem};
+use modkit_security::AccessScope;
+use sea_orm::sea_query::{
+    Alias, Asterisk, Expr, Order, OverStatement, Query, SelectStatement, WindowStatement,
+};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbBackend, EntityTrait, FromQueryResult, IdenStatic,
+    Iterable, ModelTrait, PrimaryKeyToColumn, QueryTrait, Related,
+};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::odata::core::runner_backend;
+use crate::odata::{FieldMap, expr_to_condition_for};
+use crate::secure::{DBRunner, DBRunnerInternal, ScopableEntity, SeaOrmRunner, SecureEntityExt};
+
+/// Primary key values of a model, used to attach related items to their parent.
+type Key = Vec<sea_orm::Value>;
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Expanded navigation properties of one item, keyed by property name.
+pub type ExpandedProps = Map<String, Value>;
+
+/// Maximum number of parent keys bound into a single `IN (...)` list.
+const KEY_CHUNK: usize = 500;
+
+/// Column prefixes of the parent and related entity in `find_with_related` queries.
+const PARENT_PREFIX: &str = "A_";
+const CHILD_PREFIX: &str = "B_";
+
+/// Alias of the per-parent row number used to apply `max_per_parent`.
+const ROW_NUMBER: &str = "expand_row_number";
+
+/// Depth and fan-out limits for `$expand`.
+#[derive(Clone, Copy, Debug)]
+pub struct ExpandLimits {
+    /// Maximum nesting depth (`addresses($expand=city)` has depth 2).
+    pub max_depth: usize,
+    /// Maximum number of related items returned per parent and property.
+    pub max_per_parent: usize,
+}
+
+impl Default for ExpandLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_per_parent: 100,
+        }
+    }
+}
+
+/// An item together with its expanded navigation properties.
+///
+/// Serializes as the item's own fields followed by one array per expanded property.
+#[derive(Clone, Debug, Serialize)]
+pub struct Expanded<T> {
+    #[serde(flatten)]
+    pub item: T,
+    #[serde(flatten)]
+    pub expanded: ExpandedProps,
+}
+
+#[derive(Clone, Copy)]
+struct ExpandCtx<'a> {
+    runner: &'a dyn DBRunnerInternal,
+    /// Scope of the parent entity of the current level.
+    scope: &'a AccessScope,
+    limits: ExpandLimits,
+}
+
+/// Type-erased loader for one navigation property of `E`.
+trait ExpandRelation<E: EntityTrait>: Send + Sync {
+    fn validate(
+        &self,
+        item: &ExpandItem,
+        depth: usize,
+        backend: DbBackend,
+        limits: ExpandLimits,
+    ) -> Result<(), ODataError>;
+
+    fn load<'a>(
+        &'a self,
+        ctx: ExpandCtx<'a>,
+        parents: &'a [Key],
+        item: &'a ExpandItem,
+    ) -> BoxFuture<'a, Result<HashMap<Key, Vec<Value>>, ODataError>>;
+}
+
+/// Declarative map of expandable navigation properties of `E`.
+#[must_use]
+pub struct RelationMap<E: EntityTrait> {
+    map: HashMap<String, Arc<dyn ExpandRelation<E>>>,
+}
+
+impl<E: EntityTrait> Clone for RelationMap<E> {
+    fn clone(&self) -> Self {
+        Self {
+            map: self.map.clone(),
+        }
+    }
+}
+
+impl<E: EntityTrait> Default for RelationMap<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: EntityTrait> RelationMap<E> {
+    pub fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+
+    /// Register navigation property `name` to the related entity `R`, readable within `scope`.
+    ///
+    /// `fmap` resolves the nested `$filter` and lists the properties allowed in the nested
+    /// `$select`; `map` turns each related model into its DTO.
+    pub fn insert<R, D>(
+        self,
+        name: impl Into<String>,
+        scope: &AccessScope,
+        fmap: FieldMap<R>,
+        map: fn(R::Model) -> D,
+    ) -> Self
+    where
+        E: ScopableEntity + Related<R>,
+        E::Column: ColumnTrait + Copy,
+        R: ScopableEntity,
+        R::Column: ColumnTrait + Copy,
+        D: Serialize + 'static,
+    {
+        self.insert_nested(name, scope, fmap, map, RelationMap::new())
+    }
+
+    /// Like [`RelationMap::insert`], with the navigation properties of `R` for nested `$expand`.
+    pub fn insert_nested<R, D>(
+        mut self,
+        name: impl Into<String>,
+        scope: &AccessScope,
+        fmap: FieldMap<R>,
+        map: fn(R::Model) -> D,
+        nested: RelationMap<R>,
+    ) -> Self
+    where
+        E: ScopableEntity + Related<R>,
+        E::Column: ColumnTrait + Copy,
+        R: ScopableEntity,
+        R::Column: ColumnTrait + Copy,
+        D: Serialize + 'static,
+    {
+        self.map.insert(
+            name.into().to_lowercase(),
+            Arc::new(Relation::<E, R, D> {
+                scope: scope.clone(),
+                fmap,
+                map,
+                nested,
+                _parent: PhantomData,
+            }),
+        );
+        self
+    }
+
+    #[must_use]
+    pub fn contains(&self, name: &str) -> bool {
+        self.map.contains_key(&name.to_lowercase())
+    }
+
+    /// Names of the expandable navigation properties (e.g. for `OpenAPI` docs).
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.map.keys().map(String::as_str)
+    }
+
+    fn relation(&self, name: &str) -> Result<&Arc<dyn ExpandRelation<E>>, ODataError> {
+        self.map.get(name).ok_or_else(|| {
+            ODataError::InvalidExpand(format!("unknown navigation property: {name}"))
+        })
+    }
+
+    /// Check that every requested property exists, the nesting stays within `limits`,
+    /// nested `$select`s name known properties and nested `$filter`s compile for `backend`.
+    ///
+    /// # Errors
+    /// Returns `ODataError::InvalidExpand` on unknown properties (navigation or selected)
+    /// or excessive depth, and `ODataError::InvalidFilter` for an invalid nested `$filter`.
+    pub fn validate(
+        &self,
+        items: &[ExpandItem],
+        backend: DbBackend,
+        limits: ExpandLimits,
+    ) -> Result<(), ODataError> {
+        self.validate_at(items, 1, backend, limits)
+    }
+
+    fn validate_at(
+        &self,
+        items: &[ExpandItem],
+        depth: usize,
+        backend: DbBackend,
+        limits: ExpandLimits,
+    ) -> Result<(), ODataError> {
+        if !items.is_empty() && depth > limits.max_depth {
+            return Err(ODataError::InvalidExpand(format!(
+                "nesting deeper than {} levels",
+                limits.max_depth
+            )));
+        }
+        for item in items {
+            self.relation(&item.name)?
+                .validate(item, depth, backend, limits)?;
+        }
+        Ok(())
+    }
+
+    async fn load(
+        &self,
+        ctx: ExpandCtx<'_>,
+        parents: &[Key],
+        items: &[ExpandItem],
+    ) -> Result<HashMap<Key, ExpandedProps>, ODataError> {
+        let mut out: HashMap<Key, ExpandedProps> = HashMap::new();
+        if parents.is_empty() {
+            return Ok(out);
+        }
+
+        let mut seen = HashSet::new();
+        let parents: Vec<Key> = parents
+            .iter()
+            .filter(|k| seen.insert(*k))
+            .cloned()
+            .collect();
+
+        for item in items {
+            let relation = self.relation(&item.name)?;
+            for (key, values) in relation.load(ctx, &parents, item).await? {
+                out.entry(key)
+                    .or_default()
+                    .insert(item.name.clone(), Value::Array(values));
+            }
+        }
+
+        // Parents without related rows still get an empty array per property.
+        for key in parents {
+            let props = out.entry(key).or_default();
+            for item in items {
+                props
+                    .entry(item.name.clone())
+                    .or_insert_with(|| Value::Array(Vec::new()));
+            }
+        }
+        Ok(out)
+    }
+}
+
+struct Relation<E: EntityTrait, R: EntityTrait, D> {
+    scope: AccessScope,
+    fmap: FieldMap<R>,
+    map: fn(R::Model) -> D,
+    nested: RelationMap<R>,
+    _parent: PhantomData<fn() -> E>,
+}
+
+impl<E, R, D> ExpandRelation<E> for Relation<E, R, D>
+where
+    E: ScopableEntity + Related<R>,
+    E::Column: ColumnTrait + Copy,
+    R: ScopableEntity,
+    R::Column: ColumnTrait + Copy,
+    D: Serialize + 'static,
+{
+    fn validate(
+        &self,
+        item: &ExpandItem,
+        depth: usize,
+        backend: DbBackend,
+        limits: ExpandLimits,
+    ) -> Result<(), ODataError> {
+        if let Some(unknown) = item
+            .select
+            .iter()
+            .flatten()
+            .find(|f| self.fmap.get(f).is_none())
+        {
+            return Err(ODataError::InvalidExpand(format!(
+                "unknown property in $select of {}: {unknown}",
+                item.name
+            )));
+        }
+        if let Some(expr) = item.filter.as_deref() {
+            expr_to_condition_for::<R>(expr, &self.fmap, backend)
+                .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
+        }
+        self.nested
+            .validate_at(&item.expand, depth + 1, backend, limits)
+    }
+
+    fn load<'a>(
+        &'a self,
+        ctx: ExpandCtx<'a>,
+        parents: &'a [Key],
+        item: &'a ExpandItem,
+    ) -> BoxFuture<'a, Result<HashMap<Key, Vec<Value>>, ODataError>> {
+        Box::pin(async move {
+            let child_filter = item
+                .filter
+                .as_deref()
+                .map(|expr| {
+                    expr_to_condition_for::<R>(expr, &self.fmap, runner_backend(ctx.runner))
+                })
+                .transpose()
+                .map_err(|e| ODataError::InvalidFilter(e.to_string()))?;
+
+            let backend = runner_backend(ctx.runner);
+            let mut groups: Vec<(Key, Vec<R::Model>)> = Vec::new();
+            for chunk in parents.chunks(KEY_CHUNK) {
+                let stmt = self.chunk_query(ctx, chunk, child_filter.clone());
+                let rows = match ctx.runner.as_seaorm() {
+                    SeaOrmRunner::Conn(db) => db.query_all(backend.build(&stmt)).await,
+                    SeaOrmRunner::Tx(tx) => tx.query_all(backend.build(&stmt)).await,
+                }
+                .map_err(|e| ODataError::Db(e.to_string()))?;
+
+                // Rows arrive ordered by parent, so children of a parent are adjacent.
+                for row in rows {
+                    let parent = E::Model::from_query_result(&row, PARENT_PREFIX)
+                        .map_err(|e| ODataError::Db(e.to_string()))?;
+                    let Some(child) = R::Model::from_query_result_optional(&row, CHILD_PREFIX)
+                        .map_err(|e| ODataError::Db(e.to_string()))?
+                    else {
+                        continue;
+                    };
+                    let key = model_key::<E>(&parent);
+                    match groups.last_mut() {
+                        Some((last, children)) if *last == key => children.push(child),
+                        _ => groups.push((key, vec![child])),
+                    }
+                }
+            }
+
+            let nested = if item.expand.is_empty() {
+                HashMap::new()
+            } else {
+                let child_keys: Vec<Key> = groups
+                    .iter()
+                    .flat_map(|(_, children)| children.iter().map(model_key::<R>))
+                    .collect();
+                let ctx = ExpandCtx {
+                    scope: &self.scope,
+                    ..ctx
+                };
+                self.nested.load(ctx, &child_keys, &item.expand).await?
+            };
+
+            let mut out = HashMap::with_capacity(groups.len());
+            for (parent_key, children) in groups {
+                let mut values = Vec::with_capacity(children.len());
+                for child in children {
+                    let child_key = model_key::<R>(&child);
+                    let mut value = serde_json::to_value((self.map)(child))
+                        .map_err(|e| ODataError::Db(e.to_string()))?;
+                    if let Value::Object(obj) = &mut value {
+                        if let Some(fields) = item.select.as_deref() {
+                            obj.retain(|k, _| fields.iter().any(|f| f.eq_ignore_ascii_case(k)));
+                        }
+                        if let Some(props) = nested.get(&child_key) {
+                            obj.extend(props.clone());
+                        }
+                    }
+                    values.push(value);
+                }
+                out.insert(parent_key, values);
+            }
+            Ok(out)
+        })
+    }
+}
+
+impl<E, R, D> Relation<E, R, D>
+where
+    E: ScopableEntity + Related<R>,
+    E::Column: ColumnTrait + Copy,
+    R: ScopableEntity,
+    R::Column: ColumnTrait + Copy,
+{
+    /// Related rows of the `parents`, at most `max_per_parent` per parent, ordered by
+    /// parent and then by the primary key of `R`.
+    fn chunk_query(
+        &self,
+        ctx: ExpandCtx<'_>,
+        parents: &[Key],
+        child_filter: Option<Condition>,
+    ) -> SelectStatement {
+        let mut select = E::find()
+            .secure()
+            .scope_with(ctx.scope)
+            .filter(keys_condition::<E>(parents))
+            .find_with_related_scoped(R::default(), &self.scope);
+        if let Some(cond) = child_filter {
+            select = select.filter(cond);
+        }
+        let mut inner = select.into_inner().into_query();
+        inner.clear_order_by();
+
+        let mut window = WindowStatement::new();
+        for pk in E::PrimaryKey::iter() {
+            window.partition_by((E::default(), pk.into_column()));
+        }
+        for pk in R::PrimaryKey::iter() {
+            window.order_by((R::default(), pk.into_column()), Order::Asc);
+        }
+        inner.expr_window_as(Expr::cust("ROW_NUMBER()"), window, Alias::new(ROW_NUMBER));
+
+        let mut outer = Query::select();
+        outer
+            .column(Asterisk)
+            .from_subquery(inner, Alias::new("expand_rows"))
+            .and_where(Expr::col(Alias::new(ROW_NUMBER)).lte(ctx.limits.max_per_parent as u64));
+        for pk in E::PrimaryKey::iter() {
+            let col = format!("{PARENT_PREFIX}{}", pk.into_column().as_str());
+            outer.order_by(Alias::new(col), Order::Asc);
+        }
+        outer.order_by(Alias::new(ROW_NUMBER), Order::Asc);
+        outer
+    }
+}
+
+fn model_key<E: EntityTrait>(model: &E::Model) -> Key {
+    E::PrimaryKey::iter()
+        .map(|pk| model.get(pk.into_column()))
+        .collect()
+}
+
+fn keys_condition<E: EntityTrait>(keys: &[Key]) -> Condition {
+    let cols: Vec<E::Column> = E::PrimaryKey::iter()
+        .map(PrimaryKeyToColumn::into_column)
+        .collect();
+    if let [col] = cols.as_slice() {
+        return Condition::all().add(col.is_in(keys.iter().filter_map(|k| k.first().cloned())));
+    }
+    keys.iter().fold(Condition::any(), |any, key| {
+        any.add(
+            cols.iter()
+                .zip(key)
+                .fold(Condition::all(), |all, (col, v)| all.add(col.eq(v.clone()))),
+        )
+    })
+}
+
+/// Load the `$expand` items for already-fetched (and already scoped) `models`.
+///
+/// Returns one [`ExpandedProps`] per model, in the same order. `scope` is the scope the
+/// `models` were read with; every expanded entity set is loaded via
+/// `SecureSelect::find_with_related_scoped` under the scope of its relation.
+///
+/// # Errors
+/// Returns `ODataError::InvalidExpand` for unknown properties or excessive depth,
+/// `ODataError::InvalidFilter` for an invalid nested `$filter`, and `ODataError::Db`
+/// if a query fails.
+pub async fn expand_with_odata<E, C>(
+    conn: &C,
+    scope: &AccessScope,
+    relations: &RelationMap<E>,
+    items: &[ExpandItem],
+    models: &[E::Model],
+    limits: ExpandLimits,
+) -> Result<Vec<ExpandedProps>, ODataError>
+where
+    E: EntityTrait,
+    C: DBRunner,
+{
+    relations.validate(items, runner_backend(conn), limits)?;
+    if items.is_empty() {
+        return Ok(vec![ExpandedProps::new(); models.len()]);
+    }
+
+    let keys: Vec<Key> = models.iter().map(model_key::<E>).collect();
+    let ctx = ExpandCtx {
+        runner: conn,
+        scope,
+        limits,
+    };
+    let loaded = relations.load(ctx, &keys, items).await?;
+
+    Ok(keys
+        .iter()
+        .map(|k| loaded.get(k).cloned().unwrap_or_default())
+        .collect())
+}
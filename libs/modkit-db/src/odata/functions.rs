@@ -0,0 +1,278 @@
+//! Scalar `OData` functions and arithmetic inside `$filter`.
+//!
+//! Every operand compiles to a `SimpleExpr` together with its [`FieldKind`], so
+//! `tolower(name) eq 'bob'`, `year(created_at) ge 2024` or `score add 5 gt 10`
+//! are type-checked like plain field comparisons.
+//!
+//! Supported canonical functions:
+//! - string: `tolower`, `toupper`, `trim`, `length`
+//! - date/time: `year`, `month`, `day`, `hour`, `minute`, `second`, `now()`
+//! - arithmetic operators: `add`, `sub`, `mul`, `div`, `mod` (e.g. `price mul 2`), which
+//!   the filter parser turns into `Function("mul", [price, 2])` nodes
+//!
+//! Date parts have no portable SQL, so they need the database backend
+//! (see [`expr_to_condition_for`](super::expr_to_condition_for)). `mod` is limited to
+//! integer and decimal operands, as Postgres has no modulo for floating point.
+
+use chrono::Utc;
+use modkit_odata::ast as core;
+use modkit_odata::filter::FieldKind;
+use sea_orm::{
+    ColumnTrait, DbBackend, EntityTrait,
+    sea_query::{Alias, BinOper, Expr, Func, SimpleExpr},
+};
+
+use super::core::{FieldMap, ODataBuildError, ODataBuildResult, coerce};
+
+/// Compilation context of a single filter expression.
+pub(super) struct FnCtx<'a, E: EntityTrait> {
+    pub fmap: &'a FieldMap<E>,
+    /// `None` when the target database is unknown; date parts are rejected then.
+    pub backend: Option<DbBackend>,
+}
+
+/// A compiled operand of a comparison or function call.
+pub(super) enum Operand<'a> {
+    /// Field or function result with its type.
+    Typed(SimpleExpr, FieldKind),
+    /// Literal, typed by the other side of the expression.
+    Literal(&'a core::Value),
+}
+
+pub(super) fn kind_name(kind: FieldKind) -> &'static str {
+    match kind {
+        FieldKind::String => "string",
+        FieldKind::I64 => "i64",
+        FieldKind::F64 => "f64",
+        FieldKind::Decimal => "decimal",
+        FieldKind::Bool => "bool",
+        FieldKind::Uuid => "uuid",
+        FieldKind::DateTimeUtc => "datetime",
+        FieldKind::Date => "date",
+        FieldKind::Time => "time",
+    }
+}
+
+fn is_numeric(kind: FieldKind) -> bool {
+    matches!(kind, FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal)
+}
+
+/// Whether values of the two kinds can be compared with each other.
+pub(super) fn comparable(a: FieldKind, b: FieldKind) -> bool {
+    a == b || (is_numeric(a) && is_numeric(b))
+}
+
+/// Compile a field, literal or scalar function call.
+pub(super) fn operand<'a, E: EntityTrait>(
+    expr: &'a core::Expr,
+    ctx: &FnCtx<'_, E>,
+) -> ODataBuildResult<Operand<'a>>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    use core::Expr as X;
+
+    match expr {
+        X::Identifier(name) => {
+            let f = ctx
+                .fmap
+                .get(name)
+                .ok_or_else(|| ODataBuildError::UnknownField(name.clone()))?;
+            // Qualified, as nested `$expand` filters run on a join with the parent table.
+            Ok(Operand::Typed(f.col.into_expr().into(), f.kind))
+        }
+        X::Value(v) => Ok(Operand::Literal(v)),
+        X::Function(name, args) => {
+            let (expr, kind) = scalar_fn(name, args, ctx)?;
+            Ok(Operand::Typed(expr, kind))
+        }
+        _ => Err(ODataBuildError::Other(
+            "boolean expression is not allowed as an operand",
+        )),
+    }
+}
+
+/// Compile an operand that must be of `kind`; literals are bound as `kind`.
+pub(super) fn operand_of_kind<E: EntityTrait>(
+    expr: &core::Expr,
+    kind: FieldKind,
+    ctx: &FnCtx<'_, E>,
+) -> ODataBuildResult<SimpleExpr>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    match operand(expr, ctx)? {
+        Operand::Typed(e, k) if k == kind => Ok(e),
+        Operand::Typed(_, k) => Err(ODataBuildError::TypeMismatch {
+            expected: kind,
+            got: kind_name(k),
+        }),
+        Operand::Literal(v) => Ok(SimpleExpr::Value(coerce(kind, v)?)),
+    }
+}
+
+fn scalar_fn<E: EntityTrait>(
+    name: &str,
+    args: &[core::Expr],
+    ctx: &FnCtx<'_, E>,
+) -> ODataBuildResult<(SimpleExpr, FieldKind)>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let n = name.to_ascii_lowercase();
+    match (n.as_str(), args) {
+        ("tolower", [a]) => Ok((
+            Func::lower(operand_of_kind(a, FieldKind::String, ctx)?).into(),
+            FieldKind::String,
+        )),
+        ("toupper", [a]) => Ok((
+            Func::upper(operand_of_kind(a, FieldKind::String, ctx)?).into(),
+            FieldKind::String,
+        )),
+        ("trim", [a]) => Ok((
+            Func::cust(Alias::new("TRIM"))
+                .arg(operand_of_kind(a, FieldKind::String, ctx)?)
+                .into(),
+            FieldKind::String,
+        )),
+        // CHAR_LENGTH (LENGTH on SQLite) counts characters, not bytes.
+        ("length", [a]) => Ok((
+            Func::char_length(operand_of_kind(a, FieldKind::String, ctx)?).into(),
+            FieldKind::I64,
+        )),
+        ("year" | "month" | "day", [a]) => date_part(&n, a, FieldKind::Date, ctx),
+        ("hour" | "minute" | "second", [a]) => date_part(&n, a, FieldKind::Time, ctx),
+        ("now", []) => Ok((Expr::val(Utc::now()).into(), FieldKind::DateTimeUtc)),
+        ("add" | "sub" | "mul" | "div" | "mod", [a, b]) => arithmetic(&n, a, b, ctx),
+        _ => Err(ODataBuildError::UnsupportedFn(name.to_owned())),
+    }
+}
+
+/// `part` of a `DateTimeUtc` or `kind` (`Date` or `Time`) operand.
+fn date_part<E: EntityTrait>(
+    part: &str,
+    arg: &core::Expr,
+    kind: FieldKind,
+    ctx: &FnCtx<'_, E>,
+) -> ODataBuildResult<(SimpleExpr, FieldKind)>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let Some(backend) = ctx.backend else {
+        return Err(ODataBuildError::Other(
+            "date/time functions require a known database backend",
+        ));
+    };
+
+    let arg = match operand(arg, ctx)? {
+        Operand::Typed(e, k) if k == kind || k == FieldKind::DateTimeUtc => e,
+        Operand::Typed(_, k) => {
+            return Err(ODataBuildError::TypeMismatch {
+                expected: kind,
+                got: kind_name(k),
+            });
+        }
+        Operand::Literal(_) => {
+            return Err(ODataBuildError::Other(
+                "date/time functions require a field or function operand",
+            ));
+        }
+    };
+
+    let expr: SimpleExpr = match backend {
+        DbBackend::Postgres => {
+            let value = Func::cust(Alias::new("DATE_PART")).arg(part).arg(arg);
+            // DATE_PART yields double precision with fractional seconds.
+            let value: SimpleExpr = if part == "second" {
+                Func::cust(Alias::new("FLOOR")).arg(value).into()
+            } else {
+                value.into()
+            };
+            value.cast_as(Alias::new("INTEGER"))
+        }
+        // YEAR(x), MONTH(x), ... return integers.
+        DbBackend::MySql => Func::cust(Alias::new(part.to_ascii_uppercase()))
+            .arg(arg)
+            .into(),
+        DbBackend::Sqlite => {
+            let format = match part {
+                "year" => "%Y",
+                "month" => "%m",
+                "day" => "%d",
+                "hour" => "%H",
+                "minute" => "%M",
+                _ => "%S",
+            };
+            SimpleExpr::from(Func::cust(Alias::new("strftime")).arg(format).arg(arg))
+                .cast_as(Alias::new("INTEGER"))
+        }
+    };
+
+    Ok((expr, FieldKind::I64))
+}
+
+fn arithmetic<E: EntityTrait>(
+    op: &str,
+    a: &core::Expr,
+    b: &core::Expr,
+    ctx: &FnCtx<'_, E>,
+) -> ODataBuildResult<(SimpleExpr, FieldKind)>
+where
+    E::Column: ColumnTrait + Copy,
+{
+    let (a, b) = (operand(a, ctx)?, operand(b, ctx)?);
+
+    // I64 op I64 stays integral; F64 wins over Decimal.
+    let kind = match (&a, &b) {
+        (Operand::Typed(_, ka), Operand::Typed(_, kb)) => match (*ka, *kb) {
+            (FieldKind::I64, FieldKind::I64) => FieldKind::I64,
+            (FieldKind::F64, k) | (k, FieldKind::F64) if is_numeric(k) => FieldKind::F64,
+            (ka, kb) if is_numeric(ka) && is_numeric(kb) => FieldKind::Decimal,
+            _ => {
+                return Err(ODataBuildError::Other(
+                    "arithmetic operands must be numeric",
+                ));
+            }
+        },
+        (Operand::Typed(_, k), Operand::Literal(_))
+        | (Operand::Literal(_), Operand::Typed(_, k))
+            if is_numeric(*k) =>
+        {
+            *k
+        }
+        (Operand::Literal(_), Operand::Literal(_)) => {
+            return Err(ODataBuildError::Other(
+                "arithmetic requires a field or function operand",
+            ));
+        }
+        _ => {
+            return Err(ODataBuildError::Other(
+                "arithmetic operands must be numeric",
+            ));
+        }
+    };
+
+    let to_expr = |o: Operand<'_>| match o {
+        Operand::Typed(e, _) => Ok(e),
+        Operand::Literal(v) => coerce(kind, v).map(SimpleExpr::Value),
+    };
+    if op == "mod" && kind == FieldKind::F64 {
+        return Err(ODataBuildError::Other(
+            "mod is not supported for floating-point operands",
+        ));
+    }
+    let (a, b) = (to_expr(a)?, to_expr(b)?);
+
+    let expr = match op {
+        "add" => Expr::expr(a).add(b),
+        "sub" => Expr::expr(a).sub(b),
+        "mul" => Expr::expr(a).mul(b),
+        // `/` is floating-point division on MySQL, even for integers.
+        "div" if kind == FieldKind::I64 && ctx.backend == Some(DbBackend::MySql) => {
+            Expr::expr(a).binary(BinOper::Custom("DIV"), b)
+        }
+        "div" => Expr::expr(a).div(b),
+        _ => Expr::expr(a).modulo(b),
+    };
+    Ok((expr, kind))
+}
@@ -17,20 +17,35 @@
 //!
 //! - `core`: Core `OData` to `SeaORM` translation (filters, cursors, ordering) - legacy `FieldMap` based
 //! - `sea_orm_filter`: Type-safe mapping from `FilterNode<F>` to `SeaORM` conditions
+//! - `functions`: Scalar `$filter` functions (`tolower`, `year`, `add`, ...) used by `core`
 //! - `pager`: Fluent builder for secure + `OData` pagination
+//! - `expand`: `$expand` of related entity sets via `RelationMap`
+//! - `apply`: `$apply` grouping and aggregation as scoped `GROUP BY` queries
 
 // Core OData functionality (legacy FieldMap-based)
 mod core;
 
+// Scalar functions and arithmetic in $filter
+mod functions;
+
 // SeaORM-specific filter mapping
 pub mod sea_orm_filter;
 
 // Fluent pagination builder
 pub mod pager;
 
+// $expand execution through the secure ORM
+pub mod expand;
+
+// $apply (groupby/aggregate) execution
+pub mod apply;
+
 // Re-export all public items from core (legacy API)
 pub use core::*;
 
+pub use apply::{AggregateRow, aggregate_with_odata};
+pub use expand::{ExpandLimits, Expanded, ExpandedProps, RelationMap, expand_with_odata};
+
 // Re-export SeaORM filter mapping and pagination
 pub use sea_orm_filter::{
     FieldToColumn, LimitCfg, ODataFieldMapping, encode_cursor_value, filter_node_to_condition,
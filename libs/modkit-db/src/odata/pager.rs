@@ -3,6 +3,9 @@
 //! This module provides `OPager`, a small ergonomic builder that:
 //! - Applies security scope via `Entity::find().secure().scope_with(&scope)`
 //! - Applies `OData` filter + cursor + order + limit via `paginate_with_odata`
+//! - Answers `$count=true` with a scoped count query via `count_with_odata`
+//! - Loads `$expand` navigation properties declared in a `RelationMap` via `fetch_expanded`
+//! - Runs `$apply` grouping/aggregation via `fetch_aggregate`
 //! - Keeps all existing types without introducing facades or macros
 //!
 //! # Quick Start
@@ -101,10 +104,16 @@
 //!
 //! - Uses cursor-based pagination for efficient large dataset traversal
 //! - Fetches limit+1 rows to detect "has more" without separate COUNT query
+//! - Runs a COUNT query only when the client asks for `$count=true`; use
+//!   [`OPager::count_cap`] to bound its cost on large tables
 //! - Applies filters at the database level (not in application memory)
 //! - Supports indexed columns via field mappings for optimal query performance
 
-use crate::odata::{FieldMap, LimitCfg, paginate_with_odata};
+use crate::odata::core::runner_backend;
+use crate::odata::{
+    AggregateRow, ExpandLimits, Expanded, FieldMap, LimitCfg, RelationMap, aggregate_with_odata,
+    count_with_odata, expand_with_odata, paginate_with_odata,
+};
 use crate::secure::{DBRunner, ScopableEntity, SecureEntityExt};
 use modkit_odata::{Error as ODataError, ODataQuery, Page, SortDir};
 use modkit_security::AccessScope;
@@ -136,6 +145,8 @@ use sea_orm::{ColumnTrait, EntityTrait};
 ///
 /// - Tiebreaker: `("id", SortDir::Desc)` - ensures stable pagination
 /// - Limits: `{ default: 25, max: 1000 }` - reasonable defaults for most APIs
+/// - Count cap: none - `$count=true` counts every matching row
+/// - Expand limits: depth 3, at most 100 related items per parent and property
 #[must_use]
 pub struct OPager<'a, E, C>
 where
@@ -148,6 +159,8 @@ where
     fmap: &'a FieldMap<E>,
     tiebreaker: (&'a str, SortDir),
     limits: LimitCfg,
+    count_cap: Option<u64>,
+    expand_limits: ExpandLimits,
 }
 
 impl<'a, E, C> OPager<'a, E, C>
@@ -184,6 +197,8 @@ where
                 default: 25,
                 max: 1000,
             },
+            count_cap: None,
+            expand_limits: ExpandLimits::default(),
         }
     }
 
@@ -228,6 +243,39 @@ where
         self
     }
 
+    /// Cap the number of rows counted for `$count=true` (default: no cap).
+    ///
+    /// Counting stops after `cap` rows; the page then reports `total_count = cap`
+    /// with `total_count_capped = true`, so clients can render "1000+".
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// pager.count_cap(10_000)
+    /// ```
+    pub fn count_cap(mut self, cap: u64) -> Self {
+        self.count_cap = Some(cap);
+        self
+    }
+
+    /// Override the `$expand` limits (defaults: depth 3, 100 items per parent).
+    ///
+    /// Requests nested deeper than `max_depth` are rejected; related items beyond
+    /// `max_per_parent` are dropped.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// pager.expand_limits(2, 20)
+    /// ```
+    pub fn expand_limits(mut self, max_depth: usize, max_per_parent: usize) -> Self {
+        self.expand_limits = ExpandLimits {
+            max_depth,
+            max_per_parent,
+        };
+        self
+    }
+
     /// Execute paging and map models to domain DTOs.
     ///
     /// This is the terminal operation that:
@@ -236,7 +284,8 @@ where
     /// 3. Applies cursor-based pagination
     /// 4. Fetches limit+1 rows (to detect "has more")
     /// 5. Maps entity models to domain DTOs
-    /// 6. Returns a `Page<D>` with items and pagination metadata
+    /// 6. Runs a scoped count query with the same filter if `$count=true`
+    /// 7. Returns a `Page<D>` with items and pagination metadata
     ///
     /// # Type Parameters
     ///
@@ -255,6 +304,7 @@ where
     /// - `OData` filter is invalid
     /// - Database query fails
     /// - Cursor is malformed or inconsistent
+    /// - `q` has an `$expand`, which needs [`OPager::fetch_expanded`] (`ODataError::InvalidExpand`)
     ///
     /// # Example
     ///
@@ -268,6 +318,19 @@ where
     ///     .await?;
     /// ```
     pub async fn fetch<D, F>(self, q: &ODataQuery, map: F) -> Result<Page<D>, ODataError>
+    where
+        E: ScopableEntity,
+        F: Fn(E::Model) -> D + Copy,
+    {
+        if !q.expand.is_empty() {
+            return Err(ODataError::InvalidExpand(
+                "$expand requires OPager::fetch_expanded".to_owned(),
+            ));
+        }
+        self.fetch_page(q, map).await
+    }
+
+    async fn fetch_page<D, F>(self, q: &ODataQuery, map: F) -> Result<Page<D>, ODataError>
     where
         E: ScopableEntity,
         F: Fn(E::Model) -> D + Copy,
@@ -275,8 +338,14 @@ where
         // Apply security scope first - this enforces tenant isolation
         let select = E::find().secure().scope_with(self.scope).inner;
 
+        let total = if q.count {
+            Some(count_with_odata(select.clone(), self.conn, q, self.fmap, self.count_cap).await?)
+        } else {
+            None
+        };
+
         // Now apply OData filters, cursor, order, and limits
-        paginate_with_odata::<E, D, _, _>(
+        let mut page = paginate_with_odata::<E, D, _, _>(
             select,
             self.conn,
             q,
@@ -285,6 +354,92 @@ where
             self.limits,
             map,
         )
-        .await
+        .await?;
+
+        if let Some((count, capped)) = total {
+            page.page_info.total_count = Some(count);
+            page.page_info.total_count_capped = capped;
+        }
+        Ok(page)
+    }
+    /// Run the `$apply` of `q` (`groupby`/`aggregate`) instead of listing items.
+    ///
+    /// The aggregation runs over the same scoped select as [`OPager::fetch`]; the
+    /// `$filter` of `q` narrows its input rows. At most `limits.max` groups are returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ODataError::InvalidApply` if `q` has no `$apply`, the `$apply` does not
+    /// fit the field map or yields more groups than allowed; `ODataError::Db` on
+    /// query failures.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// // GET /orders?$apply=groupby((status),aggregate(amount with sum as total))
+    /// let rows: Vec<AggregateRow> = pager.fetch_aggregate(&odata_query).await?;
+    /// ```
+    pub async fn fetch_aggregate(self, q: &ODataQuery) -> Result<Vec<AggregateRow>, ODataError>
+    where
+        E: ScopableEntity,
+    {
+        let select = E::find().secure().scope_with(self.scope).inner;
+        aggregate_with_odata(select, self.conn, q, self.fmap, self.limits.max).await
+    }
+
+    /// Like [`OPager::fetch`], and additionally loads the `$expand` items of `q`.
+    ///
+    /// Navigation properties are resolved through `relations`; each expanded entity
+    /// set is queried once per level via `SecureSelect::find_with_related_scoped`, so it
+    /// is filtered by the access scope registered with its relation.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`OPager::fetch`], plus `ODataError::InvalidExpand` for unknown
+    /// navigation properties or nesting beyond the expand limits.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let page: Page<Expanded<UserDto>> = pager
+    ///     .fetch_expanded(&odata_query, &user_relations(&address_scope), UserDto::from)
+    ///     .await?;
+    /// ```
+    pub async fn fetch_expanded<D, F>(
+        self,
+        q: &ODataQuery,
+        relations: &RelationMap<E>,
+        map: F,
+    ) -> Result<Page<Expanded<D>>, ODataError>
+    where
+        E: ScopableEntity,
+        F: Fn(E::Model) -> D + Copy,
+    {
+        let (scope, conn, expand_limits) = (self.scope, self.conn, self.expand_limits);
+
+        // Reject unknown or too deep expansions before touching the database
+        relations.validate(&q.expand, runner_backend(conn), expand_limits)?;
+
+        let page = self.fetch_page(q, |m| m).await?;
+        let expanded = expand_with_odata(
+            conn,
+            scope,
+            relations,
+            &q.expand,
+            &page.items,
+            expand_limits,
+        )
+        .await?;
+
+        let items = page
+            .items
+            .into_iter()
+            .zip(expanded)
+            .map(|(model, expanded)| Expanded {
+                item: map(model),
+                expanded,
+            })
+            .collect();
+        Ok(Page::new(items, page.page_info))
     }
 }
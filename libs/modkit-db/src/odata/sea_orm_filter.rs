@@ -587,6 +587,8 @@ where
             next_cursor,
             prev_cursor,
             limit,
+            total_count: None,
+            total_count_capped: false,
         },
     })
 }
@@ -700,7 +702,7 @@ where
         k: cursor_keys,
         o: primary_dir,
         s: order.to_signed_tokens(),
-        f: filter_hash.map(ToString::to_string),
+        f: filter_hash.map(str::to_owned),
         d: direction.to_owned(),
     })
 }
@@ -0,0 +1,114 @@
+//! Migration that creates the `modkit_outbox` table.
+//!
+//! Modules that use the outbox append [`migration()`](super::migration) to the list
+//! returned from `DatabaseCapability::migrations()`. The table is created with
+//! `IF NOT EXISTS`, so several modules sharing one database can all include it.
+
+use sea_orm_migration::prelude::*;
+
+const INDEX_NAME: &str = "idx_modkit_outbox_ns_status_id";
+
+pub(super) struct CreateOutboxTable;
+
+impl MigrationName for CreateOutboxTable {
+    fn name(&self) -> &'static str {
+        "m00000000_000001_modkit_outbox"
+    }
+}
+
+#[derive(Iden)]
+enum ModkitOutbox {
+    Table,
+    Id,
+    Namespace,
+    Topic,
+    Payload,
+    Status,
+    Attempts,
+    LastError,
+    CreatedAt,
+    NextAttemptAt,
+    ProcessedAt,
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for CreateOutboxTable {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut index = Index::create()
+            .name(INDEX_NAME)
+            .table(ModkitOutbox::Table)
+            .col(ModkitOutbox::Namespace)
+            .col(ModkitOutbox::Status)
+            .col(ModkitOutbox::Id)
+            .to_owned();
+        let mut table = Table::create()
+            .table(ModkitOutbox::Table)
+            .if_not_exists()
+            .col(
+                ColumnDef::new(ModkitOutbox::Id)
+                    .big_integer()
+                    .not_null()
+                    .auto_increment()
+                    .primary_key(),
+            )
+            .col(
+                ColumnDef::new(ModkitOutbox::Namespace)
+                    .string_len(255)
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitOutbox::Topic)
+                    .string_len(255)
+                    .not_null(),
+            )
+            .col(ColumnDef::new(ModkitOutbox::Payload).text().not_null())
+            .col(
+                ColumnDef::new(ModkitOutbox::Status)
+                    .string_len(16)
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitOutbox::Attempts)
+                    .integer()
+                    .not_null()
+                    .default(0),
+            )
+            .col(ColumnDef::new(ModkitOutbox::LastError).text().null())
+            .col(
+                ColumnDef::new(ModkitOutbox::CreatedAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitOutbox::NextAttemptAt)
+                    .timestamp_with_time_zone()
+                    .not_null(),
+            )
+            .col(
+                ColumnDef::new(ModkitOutbox::ProcessedAt)
+                    .timestamp_with_time_zone()
+                    .null(),
+            )
+            .to_owned();
+
+        // MySQL has no `CREATE INDEX IF NOT EXISTS`; declare the index inline so that it
+        // is covered by `CREATE TABLE IF NOT EXISTS` instead.
+        if manager.get_database_backend() == sea_orm::DbBackend::MySql {
+            table.index(&mut index);
+            return manager.create_table(table).await;
+        }
+        manager.create_table(table).await?;
+        manager.create_index(index.if_not_exists().to_owned()).await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(
+                Table::drop()
+                    .table(ModkitOutbox::Table)
+                    .if_exists()
+                    .to_owned(),
+            )
+            .await
+    }
+}
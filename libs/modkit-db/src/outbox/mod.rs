@@ -0,0 +1,251 @@
+//! Transactional outbox for reliable domain events.
+//!
+//! Writing a row and publishing an event about it are two separate operations; if the
+//! process crashes in between, the event is lost (or published for a row that was rolled
+//! back). The outbox closes that gap: services append the event to the `modkit_outbox`
+//! table **inside the same transaction** as their domain writes, and a background
+//! [`OutboxRelay`] later delivers committed entries to an [`OutboxSink`].
+//!
+//! # Guarantees
+//!
+//! - **Atomicity**: an entry exists iff the surrounding transaction committed.
+//! - **Ordering**: pending entries of a namespace are delivered in id order, and a
+//!   failing entry blocks the ones behind it until it is delivered or dead-lettered.
+//!   Ids are assigned at insert, not at commit, and a poll only sees committed entries:
+//!   an entry whose transaction commits late is delivered after entries with higher ids
+//!   that were already relayed. Neither commit order nor a gap-free id order is
+//!   guaranteed across concurrent writers.
+//! - **At-least-once**: a crash after delivery but before the status update causes a
+//!   redelivery, so sinks must be idempotent.
+//! - **Single relay**: the active relay holds a `modkit_db::lease` per namespace and
+//!   renews it before delivering each entry; other relays stand by. If the active relay
+//!   crashes, another one takes over once the lease expires
+//!   ([`OutboxRelayConfig::lease_ttl`]). A single delivery that outlasts the TTL can
+//!   overlap with the successor's, so keep `lease_ttl` well above the sink's timeout.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use modkit_db::outbox::{Outbox, OutboxRelay, OutboxRelayConfig};
+//!
+//! // DatabaseCapability::migrations()
+//! let mut migrations = Migrator::migrations();
+//! migrations.push(modkit_db::outbox::migration());
+//! migrations.push(modkit_db::lease::migration());
+//!
+//! // In a service, inside the domain transaction
+//! let outbox = Outbox::new("users-info");
+//! db.in_transaction(move |tx| Box::pin(async move {
+//!     repo.create(tx, &scope, user).await?;
+//!     outbox.enqueue(tx, "users.created", &UserCreated { id }).await?;
+//!     Ok(())
+//! })).await;
+//!
+//! // In Module::init, run the relay under a lifecycle
+//! let relay = OutboxRelay::new(db, Outbox::new("users-info"), sink, OutboxRelayConfig::default());
+//! ```
+
+mod entity;
+mod migration;
+mod relay;
+
+pub use relay::{OutboxRelay, OutboxRelayConfig, OutboxSink, RelayStats, SinkError};
+
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, sea_query::Expr,
+};
+use sea_orm_migration::MigrationTrait;
+use serde::{Serialize, de::DeserializeOwned};
+use time::OffsetDateTime;
+
+use crate::DbError;
+use crate::secure::{DBRunner, DBRunnerInternal, SeaOrmRunner};
+
+/// Migration creating the `modkit_outbox` table.
+///
+/// Append it to the module's migration list, together with `modkit_db::lease::migration()`
+/// for the relay.
+#[must_use]
+pub fn migration() -> Box<dyn MigrationTrait> {
+    Box::new(migration::CreateOutboxTable)
+}
+
+/// Delivery status of an outbox entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    /// Waiting for (re)delivery.
+    Pending,
+    /// Delivered to the sink; kept until the retention period expires.
+    Delivered,
+    /// Gave up after a permanent error or exhausted retries.
+    Dead,
+}
+
+impl OutboxStatus {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Delivered => "delivered",
+            OutboxStatus::Dead => "dead",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "delivered" => OutboxStatus::Delivered,
+            "dead" => OutboxStatus::Dead,
+            _ => OutboxStatus::Pending,
+        }
+    }
+}
+
+/// An outbox entry as seen by sinks and dead-letter inspection.
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    pub id: i64,
+    pub namespace: String,
+    pub topic: String,
+    /// JSON-encoded payload, as passed to [`Outbox::enqueue`].
+    pub payload: String,
+    pub status: OutboxStatus,
+    /// Number of failed delivery attempts so far.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    pub created_at: OffsetDateTime,
+}
+
+impl OutboxMessage {
+    /// Decode the payload into a typed event.
+    ///
+    /// # Errors
+    /// Returns an error if the payload does not match `T`.
+    pub fn payload_as<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.payload)
+    }
+}
+
+impl From<entity::Model> for OutboxMessage {
+    fn from(m: entity::Model) -> Self {
+        Self {
+            id: m.id,
+            namespace: m.namespace,
+            topic: m.topic,
+            payload: m.payload,
+            status: OutboxStatus::parse(&m.status),
+            attempts: u32::try_from(m.attempts).unwrap_or(0),
+            last_error: m.last_error,
+            created_at: m.created_at,
+        }
+    }
+}
+
+/// Append-side handle of the outbox for one namespace (usually the module name).
+#[derive(Debug, Clone)]
+pub struct Outbox {
+    namespace: String,
+}
+
+impl Outbox {
+    #[must_use]
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+        }
+    }
+
+    #[must_use]
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// Append an event to the outbox.
+    ///
+    /// Pass the transaction runner (`DbTx` / `SecureTx`) so that the entry commits or
+    /// rolls back together with the domain writes.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the payload cannot be serialized or the insert fails.
+    pub async fn enqueue<C, T>(&self, runner: &C, topic: &str, payload: &T) -> Result<i64, DbError>
+    where
+        C: DBRunner,
+        T: Serialize + Sync,
+    {
+        let payload = serde_json::to_string(payload)
+            .map_err(|e| DbError::InvalidParameter(format!("outbox payload: {e}")))?;
+        let now = OffsetDateTime::now_utc();
+        let am = entity::ActiveModel {
+            namespace: Set(self.namespace.clone()),
+            topic: Set(topic.to_owned()),
+            payload: Set(payload),
+            status: Set(OutboxStatus::Pending.as_str().to_owned()),
+            attempts: Set(0),
+            last_error: Set(None),
+            created_at: Set(now),
+            next_attempt_at: Set(now),
+            processed_at: Set(None),
+            ..Default::default()
+        };
+        let model = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => am.insert(db).await?,
+            SeaOrmRunner::Tx(tx) => am.insert(tx).await?,
+        };
+        Ok(model.id)
+    }
+
+    /// List dead-lettered entries, oldest first.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the query fails.
+    pub async fn dead_letters<C: DBRunner>(
+        &self,
+        runner: &C,
+        limit: u64,
+    ) -> Result<Vec<OutboxMessage>, DbError> {
+        let query = entity::Entity::find()
+            .filter(entity::Column::Namespace.eq(self.namespace.as_str()))
+            .filter(entity::Column::Status.eq(OutboxStatus::Dead.as_str()))
+            .order_by_asc(entity::Column::Id)
+            .limit(limit);
+        let rows = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => query.all(db).await?,
+            SeaOrmRunner::Tx(tx) => query.all(tx).await?,
+        };
+        Ok(rows.into_iter().map(OutboxMessage::from).collect())
+    }
+
+    /// Move a dead-lettered entry back to pending with a fresh retry budget.
+    ///
+    /// The entry keeps its original position, so it is delivered before any newer
+    /// pending entries of the namespace.
+    ///
+    /// Returns `false` if no dead entry with this id exists in the namespace.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the update fails.
+    pub async fn requeue_dead_letter<C: DBRunner>(
+        &self,
+        runner: &C,
+        id: i64,
+    ) -> Result<bool, DbError> {
+        let update = entity::Entity::update_many()
+            .col_expr(
+                entity::Column::Status,
+                Expr::value(OutboxStatus::Pending.as_str()),
+            )
+            .col_expr(entity::Column::Attempts, Expr::value(0))
+            .col_expr(
+                entity::Column::NextAttemptAt,
+                Expr::value(OffsetDateTime::now_utc()),
+            )
+            .filter(entity::Column::Id.eq(id))
+            .filter(entity::Column::Namespace.eq(self.namespace.as_str()))
+            .filter(entity::Column::Status.eq(OutboxStatus::Dead.as_str()));
+        let res = match DBRunnerInternal::as_seaorm(runner) {
+            SeaOrmRunner::Conn(db) => update.exec(db).await?,
+            SeaOrmRunner::Tx(tx) => update.exec(tx).await?,
+        };
+        Ok(res.rows_affected > 0)
+    }
+}
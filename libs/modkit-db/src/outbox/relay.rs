@@ -0,0 +1,423 @@
+//! Background relay delivering committed outbox entries to a sink.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder, QuerySelect, sea_query::Expr};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use time::OffsetDateTime;
+use tokio_util::sync::CancellationToken;
+
+use super::{Outbox, OutboxMessage, OutboxStatus, entity};
+use crate::{DBProvider, DbConn, DbError, lease};
+
+/// Destination for outbox entries (message broker, webhook, in-process bus, ...).
+///
+/// Delivery is at-least-once: the same entry may be handed to the sink again after a
+/// crash, so implementations must be idempotent (e.g. dedupe by [`OutboxMessage::id`]).
+#[async_trait]
+pub trait OutboxSink: Send + Sync + 'static {
+    /// Deliver one entry.
+    ///
+    /// # Errors
+    /// Return [`SinkError::Retryable`] for transient failures and
+    /// [`SinkError::Permanent`] to dead-letter the entry immediately.
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), SinkError>;
+}
+
+/// Failure reported by an [`OutboxSink`].
+#[derive(Debug, Error)]
+pub enum SinkError {
+    /// Transient failure; the entry is retried with backoff.
+    #[error("retryable delivery failure: {0}")]
+    Retryable(String),
+    /// The entry can never be delivered; it is dead-lettered.
+    #[error("permanent delivery failure: {0}")]
+    Permanent(String),
+}
+
+/// Relay tuning knobs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct OutboxRelayConfig {
+    /// Delay between polls when the outbox is drained or blocked on a retry.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub poll_interval: Duration,
+    /// Maximum entries fetched per poll.
+    pub batch_size: u64,
+    /// Failed attempts after which an entry is dead-lettered.
+    pub max_attempts: u32,
+    /// Backoff after the first failed attempt; doubled on each further failure.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub initial_backoff: Duration,
+    /// Upper bound for the retry backoff.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_backoff: Duration,
+    /// How long delivered entries are kept (`None` = forever).
+    #[serde(with = "modkit_utils::humantime_serde::option")]
+    pub retention: Option<Duration>,
+    /// How often delivered entries older than `retention` are purged.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub cleanup_interval: Duration,
+    /// How long the active relay's lease outlives its last renewal. The lease is renewed
+    /// before each entry once a third of it has passed, so it must exceed the time the
+    /// sink needs to deliver one entry.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub lease_ttl: Duration,
+}
+
+impl Default for OutboxRelayConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 100,
+            max_attempts: 10,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_mins(1),
+            retention: Some(Duration::from_hours(24)),
+            cleanup_interval: Duration::from_mins(5),
+            lease_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+impl OutboxRelayConfig {
+    /// Backoff before the next attempt after `attempts` failures (`attempts >= 1`).
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Outcome of a single [`OutboxRelay::relay_once`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelayStats {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+    /// `true` if the batch was full and fully processed, i.e. more entries may be waiting.
+    pub more_pending: bool,
+}
+
+/// Background task delivering one outbox namespace to a sink, in id order.
+///
+/// `run` honours a `CancellationToken`, so the relay plugs into `WithLifecycle` directly
+/// (`modkit` implements `Runnable` for it).
+pub struct OutboxRelay {
+    db: DBProvider<DbError>,
+    outbox: Outbox,
+    sink: Arc<dyn OutboxSink>,
+    config: OutboxRelayConfig,
+}
+
+impl OutboxRelay {
+    #[must_use]
+    pub fn new(
+        db: DBProvider<DbError>,
+        outbox: Outbox,
+        sink: Arc<dyn OutboxSink>,
+        config: OutboxRelayConfig,
+    ) -> Self {
+        Self {
+            db,
+            outbox,
+            sink,
+            config,
+        }
+    }
+
+    /// Run until `cancel` fires.
+    ///
+    /// The relay waits for the namespace lease (standby mode), then keeps delivering and
+    /// periodically purging delivered entries while it holds it. If a renewal fails, the
+    /// relay stops delivering and goes back to standby.
+    ///
+    /// # Errors
+    /// Never fails; database errors are logged and retried on the next poll.
+    pub async fn run(self: Arc<Self>, cancel: CancellationToken) -> anyhow::Result<()> {
+        let holder = uuid::Uuid::new_v4().to_string();
+        while !cancel.is_cancelled() {
+            match self.acquire_lease(&holder).await {
+                Ok(true) => {
+                    tracing::info!(namespace = %self.outbox.namespace(), "outbox relay active");
+                    self.pump(&cancel, &holder).await;
+                }
+                Ok(false) => {
+                    tracing::debug!(namespace = %self.outbox.namespace(), "outbox relay standing by");
+                }
+                Err(e) => {
+                    tracing::warn!(namespace = %self.outbox.namespace(), error = %e, "outbox relay lease failed");
+                }
+            }
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+
+        if let Err(e) = self.release_lease(&holder).await {
+            tracing::warn!(namespace = %self.outbox.namespace(), error = %e, "outbox relay lease release failed");
+        }
+        tracing::info!(namespace = %self.outbox.namespace(), "outbox relay stopped");
+        Ok(())
+    }
+
+    fn lease_name(&self) -> String {
+        format!("{}:outbox-relay", self.outbox.namespace())
+    }
+
+    async fn acquire_lease(&self, holder: &str) -> Result<bool, DbError> {
+        let conn = self.db.conn()?;
+        lease::acquire(&conn, &self.lease_name(), holder, self.config.lease_ttl).await
+    }
+
+    async fn release_lease(&self, holder: &str) -> Result<bool, DbError> {
+        let conn = self.db.conn()?;
+        lease::release(&conn, &self.lease_name(), holder).await
+    }
+
+    /// Renew the lease if a third of its TTL has passed; `false` if it was lost.
+    async fn keep_lease(&self, lease: &mut HeldLease<'_>) -> bool {
+        if lease.renewed_at.elapsed() < self.config.lease_ttl / 3 {
+            return true;
+        }
+        match self.acquire_lease(lease.holder).await {
+            Ok(true) => {
+                lease.renewed_at = Instant::now();
+                true
+            }
+            Ok(false) => {
+                tracing::warn!(namespace = %self.outbox.namespace(), "outbox relay lease was taken over");
+                false
+            }
+            Err(e) => {
+                tracing::warn!(namespace = %self.outbox.namespace(), error = %e, "outbox relay lease renewal failed");
+                false
+            }
+        }
+    }
+
+    /// Deliver while holding the lease; returns on cancellation or when the lease is lost.
+    async fn pump(&self, cancel: &CancellationToken, holder: &str) {
+        let mut last_cleanup = Instant::now();
+        let mut lease = HeldLease {
+            holder,
+            renewed_at: Instant::now(),
+        };
+        while !cancel.is_cancelled() {
+            if !self.keep_lease(&mut lease).await {
+                return;
+            }
+
+            let more_pending = match self.relay_batch(Some(&mut lease)).await {
+                Ok(None) => return,
+                Ok(Some(stats)) => {
+                    if stats.dead_lettered > 0 {
+                        tracing::warn!(
+                            namespace = %self.outbox.namespace(),
+                            count = stats.dead_lettered,
+                            "outbox entries dead-lettered"
+                        );
+                    }
+                    stats.more_pending
+                }
+                Err(e) => {
+                    tracing::warn!(namespace = %self.outbox.namespace(), error = %e, "outbox relay pass failed");
+                    false
+                }
+            };
+
+            if last_cleanup.elapsed() >= self.config.cleanup_interval {
+                if let Err(e) = self.cleanup().await {
+                    tracing::warn!(namespace = %self.outbox.namespace(), error = %e, "outbox cleanup failed");
+                }
+                last_cleanup = Instant::now();
+            }
+
+            if more_pending {
+                continue;
+            }
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                () = tokio::time::sleep(self.config.poll_interval) => {}
+            }
+        }
+    }
+
+    /// Deliver one batch of pending entries, in id order.
+    ///
+    /// Does not check the lease; [`run`](Self::run) delivers only while holding it.
+    /// Stops at the first entry that is waiting for a retry, so later entries are never
+    /// delivered ahead of it.
+    ///
+    /// # Errors
+    /// Returns `DbError` if reading the batch or recording a delivery outcome fails.
+    pub async fn relay_once(&self) -> Result<RelayStats, DbError> {
+        Ok(self.relay_batch(None).await?.unwrap_or_default())
+    }
+
+    /// Deliver one batch, renewing `lease` (if any) before each entry.
+    ///
+    /// Returns `None` if the lease was lost part-way; the rest of the batch is left to
+    /// the next holder.
+    async fn relay_batch(
+        &self,
+        mut lease: Option<&mut HeldLease<'_>>,
+    ) -> Result<Option<RelayStats>, DbError> {
+        let conn = self.db.conn()?;
+        let batch = entity::Entity::find()
+            .filter(entity::Column::Namespace.eq(self.outbox.namespace()))
+            .filter(entity::Column::Status.eq(OutboxStatus::Pending.as_str()))
+            .order_by_asc(entity::Column::Id)
+            .limit(self.config.batch_size)
+            .all(conn.conn)
+            .await?;
+
+        let mut stats = RelayStats::default();
+        let fetched = batch.len();
+        let mut processed = 0usize;
+
+        for row in batch {
+            if row.next_attempt_at > OffsetDateTime::now_utc() {
+                break;
+            }
+            if let Some(lease) = lease.as_deref_mut()
+                && !self.keep_lease(lease).await
+            {
+                return Ok(None);
+            }
+            let msg = OutboxMessage::from(row);
+            match self.sink.deliver(&msg).await {
+                Ok(()) => {
+                    mark(&conn, msg.id, OutboxStatus::Delivered, msg.attempts, None).await?;
+                    stats.delivered += 1;
+                }
+                Err(SinkError::Permanent(reason)) => {
+                    mark(
+                        &conn,
+                        msg.id,
+                        OutboxStatus::Dead,
+                        msg.attempts + 1,
+                        Some(reason),
+                    )
+                    .await?;
+                    stats.dead_lettered += 1;
+                }
+                Err(SinkError::Retryable(reason)) => {
+                    let attempts = msg.attempts + 1;
+                    if attempts >= self.config.max_attempts {
+                        mark(&conn, msg.id, OutboxStatus::Dead, attempts, Some(reason)).await?;
+                        stats.dead_lettered += 1;
+                    } else {
+                        self.schedule_retry(&conn, msg.id, attempts, reason).await?;
+                        stats.retried += 1;
+                        break;
+                    }
+                }
+            }
+            processed += 1;
+        }
+
+        stats.more_pending = fetched > 0
+            && processed == fetched
+            && u64::try_from(fetched).unwrap_or(u64::MAX) >= self.config.batch_size;
+        Ok(Some(stats))
+    }
+
+    /// Purge delivered entries older than the configured retention.
+    ///
+    /// Dead-lettered entries are never purged automatically.
+    ///
+    /// # Errors
+    /// Returns `DbError` if the delete fails.
+    pub async fn cleanup(&self) -> Result<u64, DbError> {
+        let Some(retention) = self.config.retention else {
+            return Ok(0);
+        };
+        let cutoff = OffsetDateTime::now_utc() - retention;
+        let conn = self.db.conn()?;
+        let res = entity::Entity::delete_many()
+            .filter(entity::Column::Namespace.eq(self.outbox.namespace()))
+            .filter(entity::Column::Status.eq(OutboxStatus::Delivered.as_str()))
+            .filter(entity::Column::ProcessedAt.lt(cutoff))
+            .exec(conn.conn)
+            .await?;
+        Ok(res.rows_affected)
+    }
+
+    async fn schedule_retry(
+        &self,
+        conn: &DbConn<'_>,
+        id: i64,
+        attempts: u32,
+        error: String,
+    ) -> Result<(), DbError> {
+        let next = OffsetDateTime::now_utc() + self.config.backoff(attempts);
+        entity::Entity::update_many()
+            .col_expr(
+                entity::Column::Attempts,
+                Expr::value(i32::try_from(attempts).unwrap_or(i32::MAX)),
+            )
+            .col_expr(entity::Column::LastError, Expr::value(Some(error)))
+            .col_expr(entity::Column::NextAttemptAt, Expr::value(next))
+            .filter(entity::Column::Id.eq(id))
+            .exec(conn.conn)
+            .await?;
+        Ok(())
+    }
+}
+
+/// The relay's namespace lease and when it was last renewed.
+struct HeldLease<'a> {
+    holder: &'a str,
+    renewed_at: Instant,
+}
+
+/// Record a terminal outcome (delivered or dead) for an entry.
+async fn mark(
+    conn: &DbConn<'_>,
+    id: i64,
+    status: OutboxStatus,
+    attempts: u32,
+    error: Option<String>,
+) -> Result<(), DbError> {
+    entity::Entity::update_many()
+        .col_expr(entity::Column::Status, Expr::value(status.as_str()))
+        .col_expr(
+            entity::Column::Attempts,
+            Expr::value(i32::try_from(attempts).unwrap_or(i32::MAX)),
+        )
+        .col_expr(entity::Column::LastError, Expr::value(error))
+        .col_expr(
+            entity::Column::ProcessedAt,
+            Expr::value(Some(OffsetDateTime::now_utc())),
+        )
+        .filter(entity::Column::Id.eq(id))
+        .exec(conn.conn)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let cfg = OutboxRelayConfig {
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(500),
+            ..Default::default()
+        };
+        assert_eq!(cfg.backoff(1), Duration::from_millis(100));
+        assert_eq!(cfg.backoff(2), Duration::from_millis(200));
+        assert_eq!(cfg.backoff(3), Duration::from_millis(400));
+        assert_eq!(cfg.backoff(4), Duration::from_millis(500));
+        assert_eq!(cfg.backoff(40), Duration::from_millis(500));
+    }
+}
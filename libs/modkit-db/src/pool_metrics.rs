@@ -0,0 +1,100 @@
+//! Connection pool metrics (feature `otel`).
+//!
+//! Pools built by [`DbManager`](crate::DbManager) are reported through observable instruments
+//! on the global meter provider, labelled `db.client.connection.pool.name` = module name:
+//! - `db.client.connection.count` with `db.client.connection.state` = `idle` | `used`
+//! - `db.client.connection.max`
+//!
+//! Pools are tracked weakly: a pool is no longer observed (or kept open) once every `Db`
+//! clone is dropped.
+
+use std::sync::{Mutex, OnceLock, Weak};
+
+use opentelemetry::KeyValue;
+use opentelemetry::metrics::ObservableUpDownCounter;
+
+use crate::{Db, DbHandle};
+
+/// Point-in-time usage of a connection pool.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: u32,
+    pub max: u32,
+}
+
+impl PoolStats {
+    #[cfg(any(feature = "pg", feature = "mysql", feature = "sqlite"))]
+    pub fn of<DB: sqlx::Database>(pool: &sqlx::Pool<DB>) -> Self {
+        Self {
+            size: pool.size(),
+            idle: u32::try_from(pool.num_idle()).unwrap_or(u32::MAX),
+            max: pool.options().get_max_connections(),
+        }
+    }
+}
+
+type Instruments = (ObservableUpDownCounter<i64>, ObservableUpDownCounter<i64>);
+
+static POOLS: Mutex<Vec<(String, Weak<DbHandle>)>> = Mutex::new(Vec::new());
+static INSTRUMENTS: OnceLock<Instruments> = OnceLock::new();
+
+/// Report the pool of `db` under the given module name.
+pub fn register(module: &str, db: &Db) {
+    INSTRUMENTS.get_or_init(build_instruments);
+    if let Ok(mut pools) = POOLS.lock() {
+        pools.retain(|(_, handle)| handle.strong_count() > 0);
+        pools.push((module.to_owned(), db.downgrade_handle()));
+    }
+}
+
+fn live_pools() -> Vec<(String, PoolStats)> {
+    POOLS.lock().map_or_else(
+        |_| Vec::new(),
+        |pools| {
+            pools
+                .iter()
+                .filter_map(|(module, handle)| {
+                    Some((module.clone(), handle.upgrade()?.pool_stats()))
+                })
+                .collect()
+        },
+    )
+}
+
+fn build_instruments() -> Instruments {
+    let meter = opentelemetry::global::meter("modkit-db");
+    let count = meter
+        .i64_observable_up_down_counter("db.client.connection.count")
+        .with_description("Connections in the pool by state")
+        .with_unit("{connection}")
+        .with_callback(|observer| {
+            for (module, stats) in live_pools() {
+                let used = stats.size.saturating_sub(stats.idle);
+                for (state, value) in [("idle", stats.idle), ("used", used)] {
+                    observer.observe(
+                        i64::from(value),
+                        &[
+                            KeyValue::new("db.client.connection.pool.name", module.clone()),
+                            KeyValue::new("db.client.connection.state", state),
+                        ],
+                    );
+                }
+            }
+        })
+        .build();
+    let max = meter
+        .i64_observable_up_down_counter("db.client.connection.max")
+        .with_description("Maximum number of connections allowed in the pool")
+        .with_unit("{connection}")
+        .with_callback(|observer| {
+            for (module, stats) in live_pools() {
+                observer.observe(
+                    i64::from(stats.max),
+                    &[KeyValue::new("db.client.connection.pool.name", module)],
+                );
+            }
+        })
+        .build();
+    (count, max)
+}
@@ -92,7 +92,8 @@ where
     }
     let mut and_cond = Condition::all();
     for filter in constraint.filters() {
-        let col = E::resolve_property(filter.property())?;
+        // Qualify with the table so the condition stays unambiguous in joins
+        let col = (E::default(), E::resolve_property(filter.property())?);
         match filter {
             ScopeFilter::Eq(eq) => {
                 let expr = scope_value_to_sea_expr(eq.value());
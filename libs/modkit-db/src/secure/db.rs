@@ -128,6 +128,19 @@ impl Db {
         }
     }
 
+    /// **INTERNAL**: Weak reference to the handle, for observers that must not keep the
+    /// pool alive (pool metrics).
+    #[cfg(feature = "otel")]
+    pub(crate) fn downgrade_handle(&self) -> std::sync::Weak<DbHandle> {
+        Arc::downgrade(&self.handle)
+    }
+
+    /// DSN of the underlying connection; equal DSNs address the same database.
+    #[must_use]
+    pub fn dsn(&self) -> &str {
+        self.handle.dsn()
+    }
+
     /// **INTERNAL**: Get a privileged `SeaORM` connection clone.
     ///
     /// This must not be exposed to module code. It exists for infrastructure
@@ -520,6 +520,25 @@ where
     ///     .await?;
     /// ```
     pub fn find_with_related<R>(self, r: R) -> SecureSelectTwoMany<E, R, Scoped>
+    where
+        R: ScopableEntity + EntityTrait,
+        R::Column: ColumnTrait + Copy,
+        E: Related<R>,
+    {
+        let scope = Arc::clone(&self.state.scope);
+        self.find_with_related_scoped(r, &scope)
+    }
+
+    /// Like [`SecureSelect::find_with_related`], but scopes the related entity `R` with
+    /// its own `related_scope` instead of the scope of `E`.
+    ///
+    /// Use this when access to `R` is decided separately, e.g. when the scope of `E`
+    /// carries resource or owner constraints that do not apply to `R`.
+    pub fn find_with_related_scoped<R>(
+        self,
+        r: R,
+        related_scope: &AccessScope,
+    ) -> SecureSelectTwoMany<E, R, Scoped>
     where
         R: ScopableEntity + EntityTrait,
         R::Column: ColumnTrait + Copy,
@@ -528,7 +547,7 @@ where
         let select_two_many = self.inner.find_with_related(r);
 
         // Auto-apply scope to the related entity R (no-op if R has no tenant_col)
-        let select_two_many = if let Some(cond) = apply_related_scope::<R>(&self.state.scope) {
+        let select_two_many = if let Some(cond) = apply_related_scope::<R>(related_scope) {
             QueryFilter::filter(select_two_many, cond)
         } else {
             select_two_many
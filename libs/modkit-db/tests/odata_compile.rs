@@ -4,21 +4,24 @@
 mod tests {
     use bigdecimal::BigDecimal;
     use sea_orm::entity::prelude::*;
+    use sea_orm::{DbBackend, QueryFilter, QueryTrait};
     use std::str::FromStr;
 
-    use modkit_db::odata::{FieldMap, expr_to_condition};
+    use modkit_db::odata::{FieldMap, ODataBuildError, expr_to_condition, expr_to_condition_for};
     use modkit_odata::ast::{CompareOperator, Expr, Value};
     use modkit_odata::filter::FieldKind;
 
     // Simple test entity for compilation tests
-    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
     #[sea_orm(table_name = "test_users")]
     pub struct Model {
         #[sea_orm(primary_key)]
         pub id: uuid::Uuid,
         pub name: String,
         pub score: i64,
+        pub weight: f64,
         pub email: String,
+        pub created_at: chrono::DateTime<chrono::Utc>,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -31,7 +34,35 @@ mod tests {
             .insert("id", Column::Id, FieldKind::Uuid)
             .insert("name", Column::Name, FieldKind::String)
             .insert("score", Column::Score, FieldKind::I64)
+            .insert("weight", Column::Weight, FieldKind::F64)
             .insert("email", Column::Email, FieldKind::String)
+            .insert("created_at", Column::CreatedAt, FieldKind::DateTimeUtc)
+    }
+
+    fn ident(name: &str) -> Expr {
+        Expr::Identifier(name.to_owned())
+    }
+
+    fn num(n: &str) -> Expr {
+        Expr::Value(Value::Number(BigDecimal::from_str(n).unwrap()))
+    }
+
+    fn text(s: &str) -> Expr {
+        Expr::Value(Value::String(s.to_owned()))
+    }
+
+    fn call(name: &str, args: Vec<Expr>) -> Expr {
+        Expr::Function(name.to_owned(), args)
+    }
+
+    fn cmp(lhs: Expr, op: CompareOperator, rhs: Expr) -> Expr {
+        Expr::Compare(Box::new(lhs), op, Box::new(rhs))
+    }
+
+    fn where_sql(ast: &Expr, backend: DbBackend) -> String {
+        let cond = expr_to_condition_for::<Entity>(ast, &setup_field_map(), backend).unwrap();
+        let sql = Entity::find().filter(cond).build(backend).to_string();
+        sql.split_once(" WHERE ").unwrap().1.to_owned()
     }
 
     #[test]
@@ -125,4 +156,201 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("unknown field"));
     }
+
+    #[test]
+    fn test_string_functions() {
+        let ast = cmp(
+            call("tolower", vec![ident("name")]),
+            CompareOperator::Eq,
+            text("bob"),
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Postgres),
+            r#"LOWER("test_users"."name") = 'bob'"#
+        );
+
+        // Literal on the left is flipped; length is counted in characters.
+        let ast = cmp(
+            num("3"),
+            CompareOperator::Lt,
+            call("length", vec![call("trim", vec![ident("email")])]),
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Sqlite),
+            r#"LENGTH(TRIM("test_users"."email")) > 3"#
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::MySql),
+            "CHAR_LENGTH(TRIM(`test_users`.`email`)) > 3"
+        );
+
+        let ast = call(
+            "startswith",
+            vec![call("toupper", vec![ident("name")]), text("AL")],
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Postgres),
+            r#"UPPER("test_users"."name") LIKE 'AL%'"#
+        );
+    }
+
+    #[test]
+    fn test_date_part_functions_per_backend() {
+        let ast = cmp(
+            call("year", vec![ident("created_at")]),
+            CompareOperator::Ge,
+            num("2024"),
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Postgres),
+            r#"CAST(DATE_PART('year', "test_users"."created_at") AS INTEGER) >= 2024"#
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::MySql),
+            "YEAR(`test_users`.`created_at`) >= 2024"
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Sqlite),
+            r#"CAST(strftime('%Y', "test_users"."created_at") AS INTEGER) >= 2024"#
+        );
+
+        // Placeholders are numbered by the backend's query builder.
+        for (backend, expected) in [
+            (
+                DbBackend::Postgres,
+                r#"CAST(DATE_PART($1, "test_users"."created_at") AS INTEGER) >= $2"#,
+            ),
+            (
+                DbBackend::Sqlite,
+                r#"CAST(strftime(?, "test_users"."created_at") AS INTEGER) >= ?"#,
+            ),
+        ] {
+            let cond = expr_to_condition_for::<Entity>(&ast, &setup_field_map(), backend).unwrap();
+            let stmt = Entity::find().filter(cond).build(backend);
+            assert!(stmt.sql.ends_with(expected), "{}", stmt.sql);
+            assert_eq!(stmt.values.unwrap().0.len(), 2);
+        }
+
+        // Date parts need the backend.
+        let err = expr_to_condition::<Entity>(&ast, &setup_field_map()).unwrap_err();
+        assert!(err.to_string().contains("backend"));
+
+        // Date parts only apply to date/time fields.
+        let ast = cmp(
+            call("hour", vec![ident("name")]),
+            CompareOperator::Eq,
+            num("1"),
+        );
+        let err = expr_to_condition_for::<Entity>(&ast, &setup_field_map(), DbBackend::Sqlite)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ODataBuildError::TypeMismatch {
+                    expected: FieldKind::Time,
+                    got: "string"
+                }
+            ),
+            "{err}"
+        );
+        let ast = cmp(
+            call("year", vec![ident("score")]),
+            CompareOperator::Eq,
+            num("1"),
+        );
+        let err = expr_to_condition_for::<Entity>(&ast, &setup_field_map(), DbBackend::Sqlite)
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ODataBuildError::TypeMismatch {
+                    expected: FieldKind::Date,
+                    got: "i64"
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_now_compares_with_datetime_fields() {
+        let ast = cmp(
+            ident("created_at"),
+            CompareOperator::Lt,
+            call("now", vec![]),
+        );
+        let cond = expr_to_condition::<Entity>(&ast, &setup_field_map()).unwrap();
+        assert!(!cond.is_empty());
+
+        let ast = cmp(ident("score"), CompareOperator::Lt, call("now", vec![]));
+        let err = expr_to_condition::<Entity>(&ast, &setup_field_map()).unwrap_err();
+        assert!(err.to_string().contains("type mismatch"));
+    }
+
+    #[test]
+    fn test_arithmetic_functions() {
+        let ast = cmp(
+            call(
+                "add",
+                vec![call("mul", vec![ident("score"), num("2")]), num("1")],
+            ),
+            CompareOperator::Gt,
+            num("10"),
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Postgres),
+            r#"("test_users"."score" * 2) + 1 > 10"#
+        );
+
+        let ast = cmp(
+            call("div", vec![ident("score"), num("3")]),
+            CompareOperator::Eq,
+            num("2"),
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::MySql),
+            "(`test_users`.`score` DIV 3) = 2"
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Sqlite),
+            r#""test_users"."score" / 3 = 2"#
+        );
+
+        // Arithmetic is numeric only.
+        let ast = cmp(
+            call("sub", vec![ident("name"), num("1")]),
+            CompareOperator::Eq,
+            num("0"),
+        );
+        assert!(expr_to_condition::<Entity>(&ast, &setup_field_map()).is_err());
+
+        // Postgres has no modulo for floating point.
+        let ast = cmp(
+            call("mod", vec![ident("weight"), num("2")]),
+            CompareOperator::Eq,
+            num("0"),
+        );
+        let err = expr_to_condition::<Entity>(&ast, &setup_field_map()).unwrap_err();
+        assert!(err.to_string().contains("mod"), "{err}");
+        let ast = cmp(
+            call("mod", vec![ident("score"), num("2")]),
+            CompareOperator::Eq,
+            num("0"),
+        );
+        assert_eq!(
+            where_sql(&ast, DbBackend::Postgres),
+            r#""test_users"."score" % 2 = 0"#
+        );
+    }
+
+    #[test]
+    fn test_unknown_function_error() {
+        let ast = cmp(
+            call("soundex", vec![ident("name")]),
+            CompareOperator::Eq,
+            text("B"),
+        );
+        let err = expr_to_condition::<Entity>(&ast, &setup_field_map()).unwrap_err();
+        assert!(err.to_string().contains("soundex"));
+    }
 }
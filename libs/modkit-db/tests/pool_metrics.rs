@@ -0,0 +1,109 @@
+#![cfg(all(feature = "sqlite", feature = "otel"))]
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Connection pool metrics are reported per module for pools built by `DbManager`.
+
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use figment::Figment;
+use figment::providers::Serialized;
+use modkit_db::DbManager;
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::data::{
+    AggregatedMetrics, MetricData, ResourceMetrics, ScopeMetrics,
+};
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use opentelemetry_sdk::metrics::{
+    InstrumentKind, ManualReader, Pipeline, SdkMeterProvider, Temporality,
+};
+use tempfile::TempDir;
+
+/// Shares one `ManualReader` between the provider and the test.
+#[derive(Clone, Debug)]
+struct SharedReader(Arc<ManualReader>);
+
+impl MetricReader for SharedReader {
+    fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+        self.0.register_pipeline(pipeline);
+    }
+
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.0.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.0.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.0.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self, kind: InstrumentKind) -> Temporality {
+        self.0.temporality(kind)
+    }
+}
+
+/// `(pool, state, value)` of every `db.client.connection.count` data point.
+fn connection_counts(reader: &SharedReader) -> Vec<(String, String, i64)> {
+    let mut metrics = ResourceMetrics::default();
+    reader.collect(&mut metrics).unwrap();
+    let mut out = Vec::new();
+    for metric in metrics.scope_metrics().flat_map(ScopeMetrics::metrics) {
+        if metric.name() != "db.client.connection.count" {
+            continue;
+        }
+        let AggregatedMetrics::I64(MetricData::Sum(sum)) = metric.data() else {
+            panic!("unexpected data for {}", metric.name());
+        };
+        for point in sum.data_points() {
+            let attr = |key: &str| {
+                point
+                    .attributes()
+                    .find(|kv| kv.key.as_str() == key)
+                    .map(|kv| kv.value.as_str().into_owned())
+                    .unwrap_or_default()
+            };
+            out.push((
+                attr("db.client.connection.pool.name"),
+                attr("db.client.connection.state"),
+                point.value(),
+            ));
+        }
+    }
+    out.sort();
+    out
+}
+
+#[tokio::test]
+async fn pool_usage_reported_per_module() {
+    let reader = SharedReader(Arc::new(ManualReader::builder().build()));
+    let provider = SdkMeterProvider::builder()
+        .with_reader(reader.clone())
+        .build();
+    opentelemetry::global::set_meter_provider(provider);
+
+    let temp_dir = TempDir::new().unwrap();
+    let figment = Figment::new().merge(Serialized::defaults(serde_json::json!({
+        "modules": {
+            "orders": {
+                "database": {
+                    "engine": "sqlite",
+                    "file": "orders.db",
+                    "pool": { "max_conns": 4, "min_conns": 1 }
+                }
+            }
+        }
+    })));
+    let manager = DbManager::from_figment(figment, temp_dir.path().to_path_buf()).unwrap();
+
+    let db = manager.get("orders").await.unwrap().unwrap();
+    assert_eq!(db.db_engine(), "sqlite");
+
+    let counts = connection_counts(&reader);
+    assert_eq!(counts.len(), 2);
+    assert!(counts.iter().all(|(pool, _, _)| pool == "orders"));
+    assert!(counts.iter().any(|(_, state, _)| state == "idle"));
+    assert!(counts.iter().any(|(_, state, _)| state == "used"));
+}
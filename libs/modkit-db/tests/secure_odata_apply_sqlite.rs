@@ -0,0 +1,291 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![cfg(feature = "sqlite")]
+
+//! `SQLite` integration tests for `$apply` (groupby/aggregate) through the Secure ORM.
+
+use anyhow::anyhow;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::odata::FieldMap;
+use modkit_db::odata::pager::OPager;
+use modkit_db::secure::{Db, DbConn, ScopableEntity, secure_insert};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_odata::ast::{CompareOperator, Expr, Value};
+use modkit_odata::filter::FieldKind;
+use modkit_odata::{Aggregate, AggregateMethod, Apply, Error as ODataError, ODataQuery};
+use modkit_security::{AccessScope, pep_properties};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use serde_json::json;
+use uuid::Uuid;
+
+mod ent {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, DeriveEntityModel)]
+    #[sea_orm(table_name = "apply_orders")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub status: String,
+        pub amount: i64,
+        pub weight: f64,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+impl ScopableEntity for ent::Entity {
+    fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+        Some(ent::Column::TenantId)
+    }
+    fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn type_col() -> Option<<Self as EntityTrait>::Column> {
+        None
+    }
+    fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+        match property {
+            p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+            _ => None,
+        }
+    }
+}
+
+struct CreateApplyOrders;
+
+impl mig::MigrationName for CreateApplyOrders {
+    fn name(&self) -> &'static str {
+        "m001_create_apply_orders"
+    }
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateApplyOrders {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("apply_orders"))
+                    .if_not_exists()
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("id"))
+                            .big_integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("tenant_id"))
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("status"))
+                            .string()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("amount"))
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("weight"))
+                            .double()
+                            .not_null(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .drop_table(
+                mig::Table::drop()
+                    .table(mig::Alias::new("apply_orders"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+struct TestDb {
+    db: Db,
+    scope: AccessScope,
+}
+
+impl TestDb {
+    async fn new() -> Self {
+        let db = connect_db("sqlite::memory:", ConnectOpts::default())
+            .await
+            .expect("db connect");
+
+        run_migrations_for_testing(&db, vec![Box::new(CreateApplyOrders)])
+            .await
+            .map_err(|e| anyhow!(e.to_string()))
+            .expect("migrate");
+
+        let tenant_id = Uuid::new_v4();
+        let scope = AccessScope::for_tenants(vec![tenant_id]);
+        let conn = db.conn().expect("conn");
+        seed(
+            &conn,
+            tenant_id,
+            &[
+                ("open", 10, 1.5),
+                ("open", 30, 2.5),
+                ("paid", 20, 4.0),
+                ("void", 5, 1.0),
+            ],
+        )
+        .await;
+
+        // Rows of another tenant must never show up in aggregates.
+        let other = Uuid::new_v4();
+        seed(&conn, other, &[("open", 1000, 9.0), ("lost", 1, 1.0)]).await;
+
+        Self { db, scope }
+    }
+
+    fn conn(&self) -> DbConn<'_> {
+        self.db.conn().expect("conn")
+    }
+}
+
+async fn seed(conn: &DbConn<'_>, tenant_id: Uuid, rows: &[(&str, i64, f64)]) {
+    let scope = AccessScope::for_tenants(vec![tenant_id]);
+    for (status, amount, weight) in rows {
+        let am = ent::ActiveModel {
+            tenant_id: Set(tenant_id),
+            status: Set((*status).to_owned()),
+            amount: Set(*amount),
+            weight: Set(*weight),
+            ..Default::default()
+        };
+        secure_insert::<ent::Entity>(am, &scope, conn)
+            .await
+            .expect("insert");
+    }
+}
+
+fn fmap() -> FieldMap<ent::Entity> {
+    FieldMap::new()
+        .insert("id", ent::Column::Id, FieldKind::I64)
+        .insert("status", ent::Column::Status, FieldKind::String)
+        .insert("amount", ent::Column::Amount, FieldKind::I64)
+        .insert("weight", ent::Column::Weight, FieldKind::F64)
+}
+
+#[tokio::test]
+async fn groupby_aggregates_within_scope() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap = fmap();
+
+    let apply = Apply::new()
+        .with_group_by(vec!["status".to_owned()])
+        .with_aggregate(Aggregate::new("amount", AggregateMethod::Sum, "total"))
+        .with_aggregate(Aggregate::new("weight", AggregateMethod::Max, "heaviest"))
+        .with_aggregate(Aggregate::count("n"));
+    let q = ODataQuery::new().with_apply(apply);
+
+    let rows = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch_aggregate(&q)
+        .await
+        .expect("aggregate");
+
+    assert_eq!(
+        serde_json::to_value(rows).unwrap(),
+        json!([
+            { "status": "open", "total": 40, "heaviest": 2.5, "n": 2 },
+            { "status": "paid", "total": 20, "heaviest": 4.0, "n": 1 },
+            { "status": "void", "total": 5, "heaviest": 1.0, "n": 1 },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn aggregate_without_groupby_applies_filters() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap = fmap();
+
+    let not_void = Expr::Compare(
+        Box::new(Expr::Identifier("status".to_owned())),
+        CompareOperator::Ne,
+        Box::new(Expr::Value(Value::String("void".to_owned()))),
+    );
+    let apply = Apply::new()
+        .with_filter(not_void)
+        .with_aggregate(Aggregate::new("amount", AggregateMethod::Average, "avg"))
+        .with_aggregate(Aggregate::new(
+            "status",
+            AggregateMethod::CountDistinct,
+            "statuses",
+        ));
+    let q = ODataQuery::new()
+        .with_filter(Expr::Compare(
+            Box::new(Expr::Identifier("amount".to_owned())),
+            CompareOperator::Ge,
+            Box::new(Expr::Value(Value::Number(20.into()))),
+        ))
+        .with_apply(apply);
+
+    let rows = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch_aggregate(&q)
+        .await
+        .expect("aggregate");
+
+    assert_eq!(
+        serde_json::to_value(rows).unwrap(),
+        json!([{ "avg": 25.0, "statuses": 2 }])
+    );
+}
+
+#[tokio::test]
+async fn apply_rejects_invalid_aggregations() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap = fmap();
+    let pager = || OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap);
+
+    let sum_of_text = ODataQuery::new().with_apply(Apply::new().with_aggregate(Aggregate::new(
+        "status",
+        AggregateMethod::Sum,
+        "s",
+    )));
+    let err = pager().fetch_aggregate(&sum_of_text).await.unwrap_err();
+    assert!(matches!(err, ODataError::InvalidApply(_)), "{err:?}");
+
+    let unknown =
+        ODataQuery::new().with_apply(Apply::new().with_group_by(vec!["tenant_id".to_owned()]));
+    let err = pager().fetch_aggregate(&unknown).await.unwrap_err();
+    assert!(matches!(err, ODataError::InvalidApply(_)), "{err:?}");
+
+    let err = pager()
+        .fetch_aggregate(&ODataQuery::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ODataError::InvalidApply(_)), "{err:?}");
+
+    // Three groups exceed a limit of two.
+    let by_status =
+        ODataQuery::new().with_apply(Apply::new().with_group_by(vec!["status".to_owned()]));
+    let err = pager()
+        .limits(2, 2)
+        .fetch_aggregate(&by_status)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ODataError::InvalidApply(_)), "{err:?}");
+}
@@ -0,0 +1,578 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+#![cfg(feature = "sqlite")]
+
+//! `SQLite` integration tests for `$expand` through the Secure ORM.
+//!
+//! Security contract:
+//! - Do not use any raw SeaORM/SQLx executors from test code.
+//! - Execute queries only through `SecureConn` / `SecureTx` + secure wrappers.
+
+use anyhow::anyhow;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::odata::pager::OPager;
+use modkit_db::odata::{FieldMap, RelationMap};
+use modkit_db::secure::{Db, DbConn, ScopableEntity, secure_insert};
+use modkit_db::{ConnectOpts, connect_db};
+use modkit_odata::ast::{CompareOperator, Expr, Value as OValue};
+use modkit_odata::filter::FieldKind;
+use modkit_odata::{Error as ODataError, ExpandItem, ODataQuery, SortDir};
+use modkit_security::{AccessScope, pep_properties};
+use sea_orm::Set;
+use sea_orm::entity::prelude::*;
+use sea_orm_migration::prelude as mig;
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+mod user {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "expand_users")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(has_many = "super::address::Entity")]
+        Addresses,
+    }
+
+    impl Related<super::address::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::Addresses.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod address {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "expand_addresses")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub user_id: i64,
+        pub city_id: i64,
+        pub street: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {
+        #[sea_orm(
+            belongs_to = "super::user::Entity",
+            from = "Column::UserId",
+            to = "super::user::Column::Id"
+        )]
+        User,
+        #[sea_orm(
+            belongs_to = "super::city::Entity",
+            from = "Column::CityId",
+            to = "super::city::Column::Id"
+        )]
+        City,
+    }
+
+    impl Related<super::user::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::User.def()
+        }
+    }
+
+    impl Related<super::city::Entity> for Entity {
+        fn to() -> RelationDef {
+            Relation::City.def()
+        }
+    }
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+mod city {
+    use sea_orm::entity::prelude::*;
+    use uuid::Uuid;
+
+    #[derive(Debug, Clone, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "expand_cities")]
+    pub struct Model {
+        #[sea_orm(primary_key)]
+        pub id: i64,
+        pub tenant_id: Uuid,
+        pub name: String,
+    }
+
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+macro_rules! impl_tenant_scoped {
+    ($ent:ident) => {
+        impl ScopableEntity for $ent::Entity {
+            fn tenant_col() -> Option<<Self as EntityTrait>::Column> {
+                Some($ent::Column::TenantId)
+            }
+            fn resource_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn owner_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn type_col() -> Option<<Self as EntityTrait>::Column> {
+                None
+            }
+            fn resolve_property(property: &str) -> Option<<Self as EntityTrait>::Column> {
+                match property {
+                    p if p == pep_properties::OWNER_TENANT_ID => Self::tenant_col(),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_tenant_scoped!(user);
+impl_tenant_scoped!(address);
+impl_tenant_scoped!(city);
+
+struct CreateExpandTables;
+
+impl mig::MigrationName for CreateExpandTables {
+    fn name(&self) -> &'static str {
+        "m001_create_expand_tables"
+    }
+}
+
+fn id_col() -> mig::ColumnDef {
+    mig::ColumnDef::new(mig::Alias::new("id"))
+        .big_integer()
+        .not_null()
+        .auto_increment()
+        .primary_key()
+        .to_owned()
+}
+
+fn col(name: &str) -> mig::ColumnDef {
+    mig::ColumnDef::new(mig::Alias::new(name))
+        .not_null()
+        .to_owned()
+}
+
+#[async_trait::async_trait]
+impl mig::MigrationTrait for CreateExpandTables {
+    async fn up(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("expand_users"))
+                    .col(id_col())
+                    .col(col("tenant_id").uuid())
+                    .col(col("name").string())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("expand_cities"))
+                    .col(id_col())
+                    .col(col("tenant_id").uuid())
+                    .col(col("name").string())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_table(
+                mig::Table::create()
+                    .table(mig::Alias::new("expand_addresses"))
+                    .col(id_col())
+                    .col(col("tenant_id").uuid())
+                    .col(col("user_id").big_integer())
+                    .col(col("city_id").big_integer())
+                    .col(col("street").string())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &mig::SchemaManager) -> Result<(), mig::DbErr> {
+        for table in ["expand_addresses", "expand_cities", "expand_users"] {
+            manager
+                .drop_table(mig::Table::drop().table(mig::Alias::new(table)).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct UserDto {
+    id: i64,
+    name: String,
+}
+
+#[derive(Debug, Serialize)]
+struct AddressDto {
+    id: i64,
+    street: String,
+    city_id: i64,
+}
+
+#[derive(Debug, Serialize)]
+struct CityDto {
+    id: i64,
+    name: String,
+}
+
+fn user_dto(m: user::Model) -> UserDto {
+    UserDto {
+        id: m.id,
+        name: m.name,
+    }
+}
+
+fn address_dto(m: address::Model) -> AddressDto {
+    AddressDto {
+        id: m.id,
+        street: m.street,
+        city_id: m.city_id,
+    }
+}
+
+fn city_dto(m: city::Model) -> CityDto {
+    CityDto {
+        id: m.id,
+        name: m.name,
+    }
+}
+
+fn user_fmap() -> FieldMap<user::Entity> {
+    FieldMap::new()
+        .insert_with_extractor("id", user::Column::Id, FieldKind::I64, |m: &user::Model| {
+            m.id.to_string()
+        })
+        .insert("name", user::Column::Name, FieldKind::String)
+}
+
+fn user_relations(addresses: &AccessScope) -> RelationMap<user::Entity> {
+    RelationMap::new().insert_nested(
+        "addresses",
+        addresses,
+        FieldMap::new()
+            .insert("id", address::Column::Id, FieldKind::I64)
+            .insert("street", address::Column::Street, FieldKind::String),
+        address_dto,
+        RelationMap::new().insert(
+            "city",
+            addresses,
+            FieldMap::<city::Entity>::new().insert("name", city::Column::Name, FieldKind::String),
+            city_dto,
+        ),
+    )
+}
+
+struct TestDb {
+    db: Db,
+    tenant_id: Uuid,
+    scope: AccessScope,
+}
+
+impl TestDb {
+    async fn new() -> Self {
+        let db = connect_db("sqlite::memory:", ConnectOpts::default())
+            .await
+            .expect("db connect");
+
+        run_migrations_for_testing(&db, vec![Box::new(CreateExpandTables)])
+            .await
+            .map_err(|e| anyhow!(e.to_string()))
+            .expect("migrate");
+
+        let tenant_id = Uuid::new_v4();
+        let scope = AccessScope::for_tenants(vec![tenant_id]);
+
+        Self {
+            db,
+            tenant_id,
+            scope,
+        }
+    }
+
+    fn conn(&self) -> DbConn<'_> {
+        self.db.conn().expect("conn")
+    }
+}
+
+/// Seeds two cities, two users of the test tenant with addresses, and one address
+/// of another tenant that points at the first user. Returns the other tenant.
+async fn seed(test_db: &TestDb) -> Uuid {
+    let conn = test_db.conn();
+
+    for name in ["Berlin", "Paris"] {
+        let am = city::ActiveModel {
+            tenant_id: Set(test_db.tenant_id),
+            name: Set(name.to_owned()),
+            ..Default::default()
+        };
+        secure_insert::<city::Entity>(am, &test_db.scope, &conn)
+            .await
+            .expect("insert city");
+    }
+
+    for name in ["alice", "bob"] {
+        let am = user::ActiveModel {
+            tenant_id: Set(test_db.tenant_id),
+            name: Set(name.to_owned()),
+            ..Default::default()
+        };
+        secure_insert::<user::Entity>(am, &test_db.scope, &conn)
+            .await
+            .expect("insert user");
+    }
+
+    let other_tenant = Uuid::new_v4();
+    let addresses = [
+        (test_db.tenant_id, 1, 1, "Main St"),
+        (test_db.tenant_id, 1, 2, "Rue Haute"),
+        (test_db.tenant_id, 2, 2, "Rue Basse"),
+        (other_tenant, 1, 1, "Foreign St"),
+    ];
+    for (tenant_id, user_id, city_id, street) in addresses {
+        let am = address::ActiveModel {
+            tenant_id: Set(tenant_id),
+            user_id: Set(user_id),
+            city_id: Set(city_id),
+            street: Set(street.to_owned()),
+            ..Default::default()
+        };
+        secure_insert::<address::Entity>(am, &AccessScope::for_tenants(vec![tenant_id]), &conn)
+            .await
+            .expect("insert address");
+    }
+    other_tenant
+}
+
+fn base_query(expand: Vec<ExpandItem>) -> ODataQuery {
+    ODataQuery::new().with_limit(10).with_expand(expand)
+}
+
+#[tokio::test]
+async fn expand_loads_nested_relations_within_scope() {
+    let test_db = TestDb::new().await;
+    seed(&test_db).await;
+    let conn = test_db.conn();
+    let fmap = user_fmap();
+
+    let q = base_query(vec![
+        ExpandItem::new("addresses")
+            .with_select(vec!["street".to_owned()])
+            .with_expand(vec![ExpandItem::new("city")]),
+    ]);
+
+    let page = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .tiebreaker("id", SortDir::Asc)
+        .fetch_expanded(&q, &user_relations(&test_db.scope), user_dto)
+        .await
+        .expect("fetch");
+
+    let items = serde_json::to_value(&page.items).unwrap();
+    assert_eq!(
+        items,
+        json!([
+            {
+                "id": 1,
+                "name": "alice",
+                "addresses": [
+                    { "street": "Main St", "city": [{ "id": 1, "name": "Berlin" }] },
+                    { "street": "Rue Haute", "city": [{ "id": 2, "name": "Paris" }] },
+                ],
+            },
+            {
+                "id": 2,
+                "name": "bob",
+                "addresses": [
+                    { "street": "Rue Basse", "city": [{ "id": 2, "name": "Paris" }] },
+                ],
+            },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn expand_applies_nested_filter_and_fan_out_limit() {
+    let test_db = TestDb::new().await;
+    seed(&test_db).await;
+    let conn = test_db.conn();
+    let fmap = user_fmap();
+    let relations = user_relations(&test_db.scope);
+
+    let q = base_query(vec![ExpandItem::new("addresses").with_filter(
+        Expr::Compare(
+            Box::new(Expr::Identifier("street".to_owned())),
+            CompareOperator::Ne,
+            Box::new(Expr::Value(OValue::String("Main St".to_owned()))),
+        ),
+    )]);
+    let page = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .tiebreaker("id", SortDir::Asc)
+        .fetch_expanded(&q, &relations, user_dto)
+        .await
+        .expect("fetch");
+    let streets: Vec<usize> = page
+        .items
+        .iter()
+        .map(|u| u.expanded["addresses"].as_array().unwrap().len())
+        .collect();
+    assert_eq!(streets, vec![1, 1]);
+
+    let q = base_query(vec![ExpandItem::new("addresses")]);
+    let page = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .tiebreaker("id", SortDir::Asc)
+        .expand_limits(1, 1)
+        .fetch_expanded(&q, &relations, user_dto)
+        .await
+        .expect("fetch");
+    let alice = &page.items[0];
+    assert_eq!(alice.item.name, "alice");
+    assert_eq!(alice.expanded["addresses"][0]["street"], "Main St");
+    assert_eq!(alice.expanded["addresses"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn expand_rejects_unknown_and_too_deep_properties() {
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    let fmap = user_fmap();
+    let relations = user_relations(&test_db.scope);
+
+    let q = base_query(vec![ExpandItem::new("pets")]);
+    let err = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch_expanded(&q, &relations, user_dto)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ODataError::InvalidExpand(_)), "{err:?}");
+
+    let q = base_query(vec![
+        ExpandItem::new("addresses").with_expand(vec![ExpandItem::new("city")]),
+    ]);
+    let err = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .expand_limits(1, 10)
+        .fetch_expanded(&q, &relations, user_dto)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ODataError::InvalidExpand(_)), "{err:?}");
+
+    let q = base_query(vec![
+        ExpandItem::new("addresses").with_select(vec!["password".to_owned()]),
+    ]);
+    let err = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch_expanded(&q, &relations, user_dto)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, ODataError::InvalidExpand(msg) if msg.contains("password")),
+        "{err:?}"
+    );
+
+    // `fetch` cannot load related entities, so it refuses `$expand` instead of ignoring it.
+    let q = base_query(vec![ExpandItem::new("addresses")]);
+    let err = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, user_dto)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, ODataError::InvalidExpand(msg) if msg.contains("fetch_expanded")),
+        "{err:?}"
+    );
+}
+
+#[tokio::test]
+async fn expand_filter_on_columns_shared_with_parent() {
+    let test_db = TestDb::new().await;
+    seed(&test_db).await;
+    let conn = test_db.conn();
+    let fmap = user_fmap();
+
+    // `id` exists on users and addresses, `name` on users and cities.
+    let q = base_query(vec![
+        ExpandItem::new("addresses")
+            .with_filter(Expr::Compare(
+                Box::new(Expr::Identifier("id".to_owned())),
+                CompareOperator::Ne,
+                Box::new(Expr::Value(OValue::Number(1.into()))),
+            ))
+            .with_expand(vec![ExpandItem::new("city").with_filter(Expr::Compare(
+                Box::new(Expr::Identifier("name".to_owned())),
+                CompareOperator::Eq,
+                Box::new(Expr::Value(OValue::String("Paris".to_owned()))),
+            ))]),
+    ]);
+    let page = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .tiebreaker("id", SortDir::Asc)
+        .fetch_expanded(&q, &user_relations(&test_db.scope), user_dto)
+        .await
+        .expect("fetch");
+
+    let items = serde_json::to_value(&page.items).unwrap();
+    assert_eq!(
+        items,
+        json!([
+            {
+                "id": 1,
+                "name": "alice",
+                "addresses": [
+                    { "id": 2, "street": "Rue Haute", "city_id": 2, "city": [{ "id": 2, "name": "Paris" }] },
+                ],
+            },
+            {
+                "id": 2,
+                "name": "bob",
+                "addresses": [
+                    { "id": 3, "street": "Rue Basse", "city_id": 2, "city": [{ "id": 2, "name": "Paris" }] },
+                ],
+            },
+        ])
+    );
+}
+
+#[tokio::test]
+async fn expand_scopes_related_entity_with_its_own_scope() {
+    let test_db = TestDb::new().await;
+    let other_tenant = seed(&test_db).await;
+    let conn = test_db.conn();
+    let fmap = user_fmap();
+
+    // Users are read within the test tenant, their addresses within the other tenant.
+    let q = base_query(vec![ExpandItem::new("addresses")]);
+    let relations = user_relations(&AccessScope::for_tenants(vec![other_tenant]));
+    let page = OPager::<user::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .tiebreaker("id", SortDir::Asc)
+        .fetch_expanded(&q, &relations, user_dto)
+        .await
+        .expect("fetch");
+
+    let streets: Vec<Vec<&str>> = page
+        .items
+        .iter()
+        .map(|u| {
+            u.expanded["addresses"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|a| a["street"].as_str().unwrap())
+                .collect()
+        })
+        .collect();
+    assert_eq!(streets, vec![vec!["Foreign St"], vec![]]);
+}
@@ -33,6 +33,7 @@ mod ent {
         pub tenant_id: Uuid,
         pub name: String,
         pub score: i64,
+        pub created_at: chrono::DateTime<chrono::Utc>,
     }
 
     #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -100,6 +101,11 @@ impl mig::MigrationTrait for CreateSecureOdataTest {
                             .big_integer()
                             .not_null(),
                     )
+                    .col(
+                        mig::ColumnDef::new(mig::Alias::new("created_at"))
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
                     .to_owned(),
             )
             .await
@@ -150,13 +156,19 @@ impl TestDb {
 }
 
 async fn seed<R: modkit_db::secure::DBRunner>(runner: &R, tenant_id: Uuid, scope: &AccessScope) {
-    let rows = [("alice", 10), ("bob", 20), ("charlie", 30), ("dave", 40)];
+    let rows = [
+        ("alice", 10, "2023-05-01T08:00:00Z"),
+        ("bob", 20, "2024-01-15T12:30:00Z"),
+        ("charlie", 30, "2024-06-30T23:59:59Z"),
+        ("dave", 40, "2025-02-01T00:00:00Z"),
+    ];
 
-    for (name, score) in rows {
+    for (name, score, created_at) in rows {
         let am = ent::ActiveModel {
             tenant_id: Set(tenant_id),
             name: Set(name.to_owned()),
             score: Set(score),
+            created_at: Set(created_at.parse().expect("timestamp")),
             ..Default::default()
         };
         secure_insert::<ent::Entity>(am, scope, runner)
@@ -190,3 +202,152 @@ async fn paginate_odata_works_with_secure_conn() {
 
     assert_eq!(page.items.len(), 2, "page size");
 }
+
+#[tokio::test]
+async fn opager_counts_scoped_filtered_rows() {
+    use modkit_odata::ast::{CompareOperator, Expr, Value};
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    // Rows of another tenant must not be counted.
+    let other_tenant = Uuid::new_v4();
+    seed(
+        &conn,
+        other_tenant,
+        &AccessScope::for_tenants(vec![other_tenant]),
+    )
+    .await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("score", ent::Column::Score, FieldKind::I64);
+
+    let q = ODataQuery::new()
+        .with_filter(Expr::Compare(
+            Box::new(Expr::Identifier("score".to_owned())),
+            CompareOperator::Ge,
+            Box::new(Expr::Value(Value::Number(20.into()))),
+        ))
+        .with_limit(1)
+        .with_count(true);
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q, |m| m.score)
+        .await
+        .expect("fetch");
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.page_info.total_count, Some(3));
+    assert!(!page.page_info.total_count_capped);
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .count_cap(2)
+        .fetch(&q, |m| m.score)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, Some(2));
+    assert!(page.page_info.total_count_capped);
+
+    let page = OPager::<ent::Entity, _>::new(&test_db.scope, &conn, &fmap)
+        .fetch(&q.clone().with_count(false), |m| m.score)
+        .await
+        .expect("fetch");
+    assert_eq!(page.page_info.total_count, None);
+}
+
+#[tokio::test]
+async fn opager_filters_with_scalar_functions() {
+    use modkit_odata::ast::{CompareOperator, Expr, Value};
+
+    fn call(name: &str, args: Vec<Expr>) -> Expr {
+        Expr::Function(name.to_owned(), args)
+    }
+    fn ident(name: &str) -> Expr {
+        Expr::Identifier(name.to_owned())
+    }
+    fn num(n: i64) -> Expr {
+        Expr::Value(Value::Number(n.into()))
+    }
+    fn cmp(lhs: Expr, op: CompareOperator, rhs: Expr) -> Expr {
+        Expr::Compare(Box::new(lhs), op, Box::new(rhs))
+    }
+
+    let test_db = TestDb::new().await;
+    let conn = test_db.conn();
+    seed(&conn, test_db.tenant_id, &test_db.scope).await;
+
+    let fmap: FieldMap<ent::Entity> = FieldMap::new()
+        .insert_with_extractor("id", ent::Column::Id, FieldKind::I64, |m: &ent::Model| {
+            m.id.to_string()
+        })
+        .insert("name", ent::Column::Name, FieldKind::String)
+        .insert("score", ent::Column::Score, FieldKind::I64)
+        .insert("created_at", ent::Column::CreatedAt, FieldKind::DateTimeUtc);
+
+    let names = |filter: Expr| {
+        let q = ODataQuery::new()
+            .with_filter(filter)
+            .with_order(modkit_odata::ODataOrderBy(vec![modkit_odata::OrderKey {
+                field: "id".to_owned(),
+                dir: modkit_odata::SortDir::Asc,
+            }]));
+        let conn = &conn;
+        let fmap = &fmap;
+        let scope = &test_db.scope;
+        async move {
+            OPager::<ent::Entity, _>::new(scope, conn, fmap)
+                .fetch(&q, |m| m.name)
+                .await
+                .expect("fetch")
+                .items
+        }
+    };
+
+    let filter = cmp(
+        call("toupper", vec![ident("name")]),
+        CompareOperator::Eq,
+        Expr::Value(Value::String("BOB".to_owned())),
+    );
+    assert_eq!(names(filter).await, ["bob"]);
+
+    let filter = cmp(
+        call("length", vec![ident("name")]),
+        CompareOperator::Gt,
+        num(4),
+    );
+    assert_eq!(names(filter).await, ["alice", "charlie"]);
+
+    let filter = cmp(
+        call(
+            "mod",
+            vec![call("div", vec![ident("score"), num(10)]), num(2)],
+        ),
+        CompareOperator::Eq,
+        num(0),
+    );
+    assert_eq!(names(filter).await, ["bob", "dave"]);
+
+    let filter = Expr::And(
+        Box::new(cmp(
+            call("year", vec![ident("created_at")]),
+            CompareOperator::Eq,
+            num(2024),
+        )),
+        Box::new(cmp(
+            call("hour", vec![ident("created_at")]),
+            CompareOperator::Ge,
+            num(12),
+        )),
+    );
+    assert_eq!(names(filter).await, ["bob", "charlie"]);
+
+    let filter = cmp(
+        ident("created_at"),
+        CompareOperator::Lt,
+        call("now", vec![]),
+    );
+    assert_eq!(names(filter).await.len(), 4);
+}
@@ -0,0 +1,83 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Audit log tests.
+
+use modkit_db::audit::{self, AuditEntry};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, Db, connect_db};
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+async fn setup(name: &str) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(
+        &format!("sqlite:file:{name}?mode=memory&cache=shared"),
+        opts,
+    )
+    .await
+    .expect("connect");
+    run_migrations_for_testing(&db, vec![audit::migration()])
+        .await
+        .expect("migrate");
+    db
+}
+
+fn entry(operation: &str, occurred_at: OffsetDateTime) -> AuditEntry {
+    AuditEntry {
+        // Stored with microsecond precision
+        occurred_at: occurred_at.replace_nanosecond(0).unwrap(),
+        request_id: Some("req-1".to_owned()),
+        subject_id: Some(Uuid::new_v4()),
+        tenant_id: Some(Uuid::new_v4()),
+        operation: operation.to_owned(),
+        method: "DELETE".to_owned(),
+        path: "/users/v1/users/{id}".to_owned(),
+        resource_ids: r#"{"id":"42"}"#.to_owned(),
+        status: 204,
+        outcome: "success".to_owned(),
+        latency_ms: 12,
+        details: None,
+    }
+}
+
+#[tokio::test]
+async fn appended_entries_read_back_newest_first() {
+    let db = setup("memdb_audit_append").await;
+    let conn = db.conn().unwrap();
+    let now = OffsetDateTime::now_utc();
+
+    let first = entry("users.delete", now);
+    let second = AuditEntry {
+        details: Some(r#"{"name":"x"}"#.to_owned()),
+        ..entry("users.update", now)
+    };
+    audit::append(&conn, &first).await.unwrap();
+    audit::append(&conn, &second).await.unwrap();
+
+    assert_eq!(audit::recent(&conn, 10).await.unwrap(), vec![second, first]);
+    assert_eq!(audit::recent(&conn, 1).await.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn purge_removes_only_older_entries() {
+    let db = setup("memdb_audit_purge").await;
+    let conn = db.conn().unwrap();
+    let now = OffsetDateTime::now_utc();
+
+    let live = entry("live", now);
+    audit::append(&conn, &entry("old", now - Duration::days(100)))
+        .await
+        .unwrap();
+    audit::append(&conn, &live).await.unwrap();
+
+    assert_eq!(
+        audit::purge_before(&conn, now - Duration::days(90))
+            .await
+            .unwrap(),
+        1
+    );
+    assert_eq!(audit::recent(&conn, 10).await.unwrap(), vec![live]);
+}
@@ -0,0 +1,134 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Idempotency record tests.
+
+use std::time::Duration;
+
+use modkit_db::idempotency::{self, IdempotencyClaim};
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, Db, connect_db};
+
+const LEASE: Duration = Duration::from_secs(30);
+const TTL: Duration = Duration::from_mins(1);
+
+async fn setup(name: &str) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(
+        &format!("sqlite:file:{name}?mode=memory&cache=shared"),
+        opts,
+    )
+    .await
+    .expect("connect");
+    run_migrations_for_testing(&db, vec![idempotency::migration()])
+        .await
+        .expect("migrate");
+    db
+}
+
+#[tokio::test]
+async fn claim_complete_and_replay() {
+    let db = setup("memdb_idempotency_claim").await;
+    let conn = db.conn().unwrap();
+
+    assert_eq!(
+        idempotency::claim(&conn, "k", "fp", LEASE).await.unwrap(),
+        IdempotencyClaim::Claimed
+    );
+    assert_eq!(
+        idempotency::claim(&conn, "k", "fp", LEASE).await.unwrap(),
+        IdempotencyClaim::InFlight {
+            fingerprint: "fp".to_owned()
+        }
+    );
+
+    assert!(
+        idempotency::complete(&conn, "k", "response", TTL)
+            .await
+            .unwrap()
+    );
+    assert_eq!(
+        idempotency::claim(&conn, "k", "other", LEASE)
+            .await
+            .unwrap(),
+        IdempotencyClaim::Completed {
+            fingerprint: "fp".to_owned(),
+            response: "response".to_owned(),
+        }
+    );
+
+    // Completed records stay until they expire
+    assert!(!idempotency::release(&conn, "k").await.unwrap());
+}
+
+#[tokio::test]
+async fn released_and_expired_keys_can_be_claimed_again() {
+    let db = setup("memdb_idempotency_release").await;
+    let conn = db.conn().unwrap();
+
+    idempotency::claim(&conn, "released", "fp", LEASE)
+        .await
+        .unwrap();
+    assert!(idempotency::release(&conn, "released").await.unwrap());
+    assert_eq!(
+        idempotency::claim(&conn, "released", "fp", LEASE)
+            .await
+            .unwrap(),
+        IdempotencyClaim::Claimed
+    );
+
+    idempotency::claim(&conn, "expired", "fp", Duration::ZERO)
+        .await
+        .unwrap();
+    assert_eq!(
+        idempotency::claim(&conn, "expired", "fp", LEASE)
+            .await
+            .unwrap(),
+        IdempotencyClaim::Claimed
+    );
+}
+
+#[tokio::test]
+async fn completed_records_outlive_the_lease() {
+    let db = setup("memdb_idempotency_lease").await;
+    let conn = db.conn().unwrap();
+
+    // The lease has run out by the time the response is stored
+    idempotency::claim(&conn, "k", "fp", Duration::ZERO)
+        .await
+        .unwrap();
+    assert!(
+        idempotency::complete(&conn, "k", "response", TTL)
+            .await
+            .unwrap()
+    );
+
+    assert_eq!(idempotency::purge_expired(&conn).await.unwrap(), 0);
+    assert!(matches!(
+        idempotency::claim(&conn, "k", "fp", LEASE).await.unwrap(),
+        IdempotencyClaim::Completed { .. }
+    ));
+}
+
+#[tokio::test]
+async fn purge_removes_only_expired_records() {
+    let db = setup("memdb_idempotency_purge").await;
+    let conn = db.conn().unwrap();
+
+    idempotency::claim(&conn, "live", "fp", LEASE)
+        .await
+        .unwrap();
+    idempotency::claim(&conn, "expired", "fp", Duration::ZERO)
+        .await
+        .unwrap();
+
+    assert_eq!(idempotency::purge_expired(&conn).await.unwrap(), 1);
+    assert!(matches!(
+        idempotency::claim(&conn, "live", "fp", LEASE)
+            .await
+            .unwrap(),
+        IdempotencyClaim::InFlight { .. }
+    ));
+}
@@ -0,0 +1,60 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Lease tests.
+
+use std::time::Duration;
+
+use modkit_db::lease;
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::{ConnectOpts, Db, connect_db};
+
+const TTL: Duration = Duration::from_secs(30);
+
+async fn setup(name: &str) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(
+        &format!("sqlite:file:{name}?mode=memory&cache=shared"),
+        opts,
+    )
+    .await
+    .expect("connect");
+    run_migrations_for_testing(&db, vec![lease::migration()])
+        .await
+        .expect("migrate");
+    db
+}
+
+#[tokio::test]
+async fn lease_excludes_other_holders_until_released() {
+    let db = setup("memdb_lease_exclusive").await;
+    let conn = db.conn().unwrap();
+
+    assert!(lease::acquire(&conn, "job", "a", TTL).await.unwrap());
+    assert!(!lease::acquire(&conn, "job", "b", TTL).await.unwrap());
+    // Renewal by the holder
+    assert!(lease::acquire(&conn, "job", "a", TTL).await.unwrap());
+
+    assert!(!lease::release(&conn, "job", "b").await.unwrap());
+    assert!(lease::release(&conn, "job", "a").await.unwrap());
+    assert!(lease::acquire(&conn, "job", "b", TTL).await.unwrap());
+}
+
+#[tokio::test]
+async fn expired_lease_is_taken_over() {
+    let db = setup("memdb_lease_expired").await;
+    let conn = db.conn().unwrap();
+
+    assert!(
+        lease::acquire(&conn, "job", "a", Duration::ZERO)
+            .await
+            .unwrap()
+    );
+    assert!(lease::acquire(&conn, "job", "b", TTL).await.unwrap());
+
+    // The previous holder can neither renew nor release it any more
+    assert!(!lease::acquire(&conn, "job", "a", TTL).await.unwrap());
+    assert!(!lease::release(&conn, "job", "a").await.unwrap());
+}
@@ -5,9 +5,13 @@
 
 #![cfg(feature = "sqlite")]
 
+mod audit;
 mod concurrency_tests;
+mod idempotency;
+mod lease;
 mod manager;
 mod options;
+mod outbox;
 mod pooling_tests;
 mod secure_insert_tenant_validation;
 mod secure_update_tenant_safety;
@@ -0,0 +1,357 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Transactional outbox tests.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use modkit_db::migration_runner::run_migrations_for_testing;
+use modkit_db::outbox::{
+    Outbox, OutboxMessage, OutboxRelay, OutboxRelayConfig, OutboxSink, OutboxStatus, SinkError,
+};
+use modkit_db::{ConnectOpts, DBProvider, Db, DbError, connect_db};
+
+#[derive(Default)]
+struct RecordingSink {
+    delivered: Mutex<Vec<(String, String)>>,
+    /// Number of upcoming calls that fail with a retryable error.
+    fail_next: Mutex<u32>,
+    /// Topic that is rejected permanently.
+    poison_topic: Option<&'static str>,
+    /// Time each delivery takes.
+    delay: Duration,
+}
+
+#[async_trait::async_trait]
+impl OutboxSink for RecordingSink {
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), SinkError> {
+        tokio::time::sleep(self.delay).await;
+        if self.poison_topic == Some(message.topic.as_str()) {
+            return Err(SinkError::Permanent("poison".to_owned()));
+        }
+        {
+            let mut fail = self.fail_next.lock().unwrap();
+            if *fail > 0 {
+                *fail -= 1;
+                return Err(SinkError::Retryable("broker down".to_owned()));
+            }
+        }
+        self.delivered
+            .lock()
+            .unwrap()
+            .push((message.topic.clone(), message.payload.clone()));
+        Ok(())
+    }
+}
+
+async fn setup(name: &str) -> Db {
+    let opts = ConnectOpts {
+        max_conns: Some(1),
+        ..Default::default()
+    };
+    let db = connect_db(
+        &format!("sqlite:file:{name}?mode=memory&cache=shared"),
+        opts,
+    )
+    .await
+    .expect("connect");
+    run_migrations_for_testing(
+        &db,
+        vec![
+            modkit_db::outbox::migration(),
+            modkit_db::lease::migration(),
+        ],
+    )
+    .await
+    .expect("migrate");
+    db
+}
+
+fn relay(db: &Db, sink: Arc<RecordingSink>, config: OutboxRelayConfig) -> OutboxRelay {
+    OutboxRelay::new(
+        DBProvider::<DbError>::new(db.clone()),
+        Outbox::new("test-module"),
+        sink,
+        config,
+    )
+}
+
+#[tokio::test]
+async fn enqueue_commits_and_rolls_back_with_transaction() {
+    let db = setup("memdb_outbox_tx").await;
+    let outbox = Outbox::new("test-module");
+
+    let ob = outbox.clone();
+    let (db, res) = db
+        .transaction(move |tx| {
+            Box::pin(async move {
+                ob.enqueue(tx, "user.created", &serde_json::json!({"id": 1}))
+                    .await?;
+                Ok(())
+            })
+        })
+        .await;
+    res.expect("commit");
+
+    let ob = outbox.clone();
+    let (db, res): (_, anyhow::Result<()>) = db
+        .transaction(move |tx| {
+            Box::pin(async move {
+                ob.enqueue(tx, "user.created", &serde_json::json!({"id": 2}))
+                    .await?;
+                anyhow::bail!("domain failure");
+            })
+        })
+        .await;
+    assert!(res.is_err());
+
+    let sink = Arc::new(RecordingSink::default());
+    let stats = relay(&db, sink.clone(), OutboxRelayConfig::default())
+        .relay_once()
+        .await
+        .expect("relay");
+    assert_eq!(stats.delivered, 1);
+    assert_eq!(
+        sink.delivered.lock().unwrap().as_slice(),
+        &[("user.created".to_owned(), r#"{"id":1}"#.to_owned())]
+    );
+}
+
+#[tokio::test]
+async fn relay_delivers_in_order_and_blocks_on_retry() {
+    let db = setup("memdb_outbox_order").await;
+    let outbox = Outbox::new("test-module");
+    {
+        let conn = db.conn().unwrap();
+        for i in 0..3 {
+            outbox.enqueue(&conn, "evt", &i).await.unwrap();
+        }
+    }
+
+    let sink = Arc::new(RecordingSink {
+        fail_next: Mutex::new(1),
+        ..Default::default()
+    });
+    let config = OutboxRelayConfig {
+        initial_backoff: Duration::ZERO,
+        ..Default::default()
+    };
+    let relay = relay(&db, sink.clone(), config);
+
+    let first = relay.relay_once().await.unwrap();
+    assert_eq!(first.retried, 1);
+    assert_eq!(first.delivered, 0);
+    assert!(sink.delivered.lock().unwrap().is_empty());
+
+    let second = relay.relay_once().await.unwrap();
+    assert_eq!(second.delivered, 3);
+    let payloads: Vec<String> = sink
+        .delivered
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(_, p)| p.clone())
+        .collect();
+    assert_eq!(payloads, vec!["0", "1", "2"]);
+}
+
+#[tokio::test]
+async fn relay_dead_letters_and_requeues() {
+    let db = setup("memdb_outbox_dead").await;
+    let outbox = Outbox::new("test-module");
+    let poison_id = {
+        let conn = db.conn().unwrap();
+        let id = outbox.enqueue(&conn, "poison", &"x").await.unwrap();
+        outbox.enqueue(&conn, "ok", &"y").await.unwrap();
+        id
+    };
+
+    let sink = Arc::new(RecordingSink {
+        poison_topic: Some("poison"),
+        ..Default::default()
+    });
+    let stats = relay(&db, sink.clone(), OutboxRelayConfig::default())
+        .relay_once()
+        .await
+        .unwrap();
+    assert_eq!(stats.dead_lettered, 1);
+    assert_eq!(stats.delivered, 1);
+
+    let conn = db.conn().unwrap();
+    let dead = outbox.dead_letters(&conn, 10).await.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].id, poison_id);
+    assert_eq!(dead[0].status, OutboxStatus::Dead);
+    assert_eq!(dead[0].last_error.as_deref(), Some("poison"));
+
+    assert!(outbox.requeue_dead_letter(&conn, poison_id).await.unwrap());
+    assert!(outbox.dead_letters(&conn, 10).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn relay_exhausts_retries_then_dead_letters() {
+    let db = setup("memdb_outbox_exhaust").await;
+    let outbox = Outbox::new("test-module");
+    {
+        let conn = db.conn().unwrap();
+        outbox.enqueue(&conn, "evt", &1).await.unwrap();
+    }
+
+    let sink = Arc::new(RecordingSink {
+        fail_next: Mutex::new(u32::MAX),
+        ..Default::default()
+    });
+    let config = OutboxRelayConfig {
+        max_attempts: 2,
+        initial_backoff: Duration::ZERO,
+        ..Default::default()
+    };
+    let relay = relay(&db, sink, config);
+
+    assert_eq!(relay.relay_once().await.unwrap().retried, 1);
+    assert_eq!(relay.relay_once().await.unwrap().dead_lettered, 1);
+
+    let conn = db.conn().unwrap();
+    let dead = outbox.dead_letters(&conn, 10).await.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].attempts, 2);
+}
+
+#[tokio::test]
+async fn cleanup_purges_delivered_entries() {
+    let db = setup("memdb_outbox_cleanup").await;
+    let outbox = Outbox::new("test-module");
+    {
+        let conn = db.conn().unwrap();
+        outbox.enqueue(&conn, "evt", &1).await.unwrap();
+    }
+
+    let sink = Arc::new(RecordingSink::default());
+    let config = OutboxRelayConfig {
+        retention: Some(Duration::ZERO),
+        ..Default::default()
+    };
+    let relay = relay(&db, sink, config);
+    assert_eq!(relay.relay_once().await.unwrap().delivered, 1);
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert_eq!(relay.cleanup().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn run_stops_on_cancel() {
+    let db = setup("memdb_outbox_run").await;
+    let outbox = Outbox::new("test-module");
+    {
+        let conn = db.conn().unwrap();
+        outbox.enqueue(&conn, "evt", &1).await.unwrap();
+    }
+
+    let sink = Arc::new(RecordingSink::default());
+    let config = OutboxRelayConfig {
+        poll_interval: Duration::from_millis(10),
+        ..Default::default()
+    };
+    let relay = Arc::new(relay(&db, sink.clone(), config));
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let task = tokio::spawn(relay.run(cancel.clone()));
+
+    for _ in 0..100 {
+        if !sink.delivered.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    cancel.cancel();
+    task.await.unwrap().unwrap();
+    assert_eq!(sink.delivered.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn run_stands_by_while_another_relay_holds_the_lease() {
+    let db = setup("memdb_outbox_standby").await;
+    let outbox = Outbox::new("test-module");
+    let conn = db.conn().unwrap();
+    outbox.enqueue(&conn, "evt", &1).await.unwrap();
+    assert!(
+        modkit_db::lease::acquire(
+            &conn,
+            "test-module:outbox-relay",
+            "other-relay",
+            Duration::from_secs(30)
+        )
+        .await
+        .unwrap()
+    );
+
+    let sink = Arc::new(RecordingSink::default());
+    let config = OutboxRelayConfig {
+        poll_interval: Duration::from_millis(10),
+        ..Default::default()
+    };
+    let relay = Arc::new(relay(&db, sink.clone(), config));
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let task = tokio::spawn(relay.run(cancel.clone()));
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert!(sink.delivered.lock().unwrap().is_empty());
+
+    // Takes over once the other relay lets go
+    assert!(
+        modkit_db::lease::release(&conn, "test-module:outbox-relay", "other-relay")
+            .await
+            .unwrap()
+    );
+    for _ in 0..100 {
+        if !sink.delivered.lock().unwrap().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    cancel.cancel();
+    task.await.unwrap().unwrap();
+    assert_eq!(sink.delivered.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn run_renews_the_lease_within_a_slow_batch() {
+    let db = setup("memdb_outbox_slow_batch").await;
+    let outbox = Outbox::new("test-module");
+    let conn = db.conn().unwrap();
+    for i in 0..6 {
+        outbox.enqueue(&conn, "evt", &i).await.unwrap();
+    }
+
+    // The batch takes several lease TTLs to deliver
+    let sink = Arc::new(RecordingSink {
+        delay: Duration::from_millis(40),
+        ..Default::default()
+    });
+    let config = OutboxRelayConfig {
+        poll_interval: Duration::from_millis(10),
+        lease_ttl: Duration::from_millis(90),
+        ..Default::default()
+    };
+    let relay = Arc::new(relay(&db, sink.clone(), config));
+    let cancel = tokio_util::sync::CancellationToken::new();
+    let task = tokio::spawn(relay.run(cancel.clone()));
+
+    while sink.delivered.lock().unwrap().is_empty() {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    while sink.delivered.lock().unwrap().len() < 6 {
+        assert!(
+            !modkit_db::lease::acquire(
+                &conn,
+                "test-module:outbox-relay",
+                "other-relay",
+                Duration::from_millis(90)
+            )
+            .await
+            .unwrap(),
+            "the lease expired in the middle of a batch"
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    cancel.cancel();
+    task.await.unwrap().unwrap();
+}
@@ -2,6 +2,8 @@ use crate::config::{
     HttpClientConfig, RedirectConfig, RetryConfig, TlsRootConfig, TransportSecurity,
 };
 use crate::error::HttpError;
+#[cfg(feature = "otel")]
+use crate::layers::MetricsLayer;
 use crate::layers::{OtelLayer, RetryLayer, SecureRedirectPolicy, UserAgentLayer};
 use crate::response::ResponseBody;
 use crate::tls;
@@ -140,6 +142,16 @@ impl HttpClientBuilder {
         self
     }
 
+    /// Enable OpenTelemetry request metrics
+    ///
+    /// When enabled (and the `otel` feature is on), records the duration of every request,
+    /// retries included, as `http.client.request.duration`.
+    #[must_use]
+    pub fn with_metrics(mut self) -> Self {
+        self.config.metrics = true;
+        self
+    }
+
     /// Insert an optional auth layer between retry and timeout in the stack.
     ///
     /// Stack position: `… → Retry → **this layer** → Timeout → …`
@@ -371,6 +383,16 @@ impl HttpClientBuilder {
             boxed_service = limited_service.boxed_clone();
         }
 
+        // Conditionally wrap with request metrics (inside the tracing span, outside retries
+        // and load shedding so the recorded duration is what the caller observes)
+        #[cfg(feature = "otel")]
+        if self.config.metrics {
+            let metrics_service = ServiceBuilder::new()
+                .layer(MetricsLayer::new())
+                .service(boxed_service);
+            boxed_service = metrics_service.boxed_clone();
+        }
+
         // Conditionally wrap with OTEL tracing layer (outermost layer before buffer)
         // Applied last so it sees the final request after UserAgent and other modifications.
         // Creates spans, records status, and injects trace context headers.
@@ -522,8 +544,8 @@ mod tests {
 
     #[test]
     fn test_builder_timeout() {
-        let builder = HttpClientBuilder::new().timeout(Duration::from_secs(60));
-        assert_eq!(builder.config.request_timeout, Duration::from_secs(60));
+        let builder = HttpClientBuilder::new().timeout(Duration::from_mins(1));
+        assert_eq!(builder.config.request_timeout, Duration::from_mins(1));
     }
 
     #[test]
@@ -607,6 +629,18 @@ mod tests {
         assert!(client.is_ok());
     }
 
+    #[test]
+    fn test_builder_metrics() {
+        let builder = HttpClientBuilder::new().with_metrics();
+        assert!(builder.config.metrics);
+    }
+
+    #[tokio::test]
+    async fn test_builder_build_with_metrics() {
+        let client = HttpClientBuilder::new().with_metrics().build();
+        assert!(client.is_ok());
+    }
+
     #[tokio::test]
     async fn test_builder_with_auth_layer() {
         let client = HttpClientBuilder::new()
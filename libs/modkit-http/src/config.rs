@@ -529,6 +529,10 @@ pub struct HttpClientConfig {
     /// Creates spans for outbound requests and injects trace context headers.
     pub otel: bool,
 
+    /// Enable OpenTelemetry request metrics (default: false)
+    /// Records `http.client.request.duration`; requires the `otel` feature.
+    pub metrics: bool,
+
     /// Buffer capacity for concurrent request handling (default: 1024)
     ///
     /// The HTTP client uses an internal buffer to allow multiple concurrent
@@ -581,6 +585,7 @@ impl Default for HttpClientConfig {
             transport: TransportSecurity::TlsOnly,
             tls_roots: TlsRootConfig::default(),
             otel: false,
+            metrics: false,
             buffer_capacity: 1024,
             redirect: RedirectConfig::default(),
             pool_idle_timeout: Some(Duration::from_secs(90)),
@@ -603,6 +608,7 @@ impl HttpClientConfig {
             transport: TransportSecurity::TlsOnly,
             tls_roots: TlsRootConfig::default(),
             otel: false,
+            metrics: false,
             buffer_capacity: 256,
             redirect: RedirectConfig::default(),
             pool_idle_timeout: Some(Duration::from_secs(30)),
@@ -614,7 +620,7 @@ impl HttpClientConfig {
     #[must_use]
     pub fn infra_default() -> Self {
         Self {
-            request_timeout: Duration::from_secs(60),
+            request_timeout: Duration::from_mins(1),
             total_timeout: None,
             max_body_size: 50 * 1024 * 1024, // 50 MB
             user_agent: DEFAULT_USER_AGENT.to_owned(),
@@ -623,9 +629,10 @@ impl HttpClientConfig {
             transport: TransportSecurity::TlsOnly,
             tls_roots: TlsRootConfig::default(),
             otel: false,
+            metrics: false,
             buffer_capacity: 1024,
             redirect: RedirectConfig::default(),
-            pool_idle_timeout: Some(Duration::from_secs(120)),
+            pool_idle_timeout: Some(Duration::from_mins(2)),
             pool_max_idle_per_host: 64,
         }
     }
@@ -664,9 +671,10 @@ impl HttpClientConfig {
             transport: TransportSecurity::TlsOnly,
             tls_roots: TlsRootConfig::default(),
             otel: false,
+            metrics: false,
             buffer_capacity: 256,
             redirect: RedirectConfig::default(),
-            pool_idle_timeout: Some(Duration::from_secs(60)),
+            pool_idle_timeout: Some(Duration::from_mins(1)),
             pool_max_idle_per_host: 4,
         }
     }
@@ -687,6 +695,7 @@ impl HttpClientConfig {
             transport: TransportSecurity::AllowInsecureHttp,
             tls_roots: TlsRootConfig::default(),
             otel: false,
+            metrics: false,
             buffer_capacity: 256,
             redirect: RedirectConfig::for_testing(),
             pool_idle_timeout: Some(Duration::from_secs(10)),
@@ -737,7 +746,7 @@ impl HttpClientConfig {
     #[must_use]
     pub fn sse() -> Self {
         Self {
-            request_timeout: Duration::from_secs(86_400), // 24 hours
+            request_timeout: Duration::from_hours(24), // 24 hours
             total_timeout: None,
             max_body_size: 10 * 1024 * 1024, // 10 MB (only for bytes()/json(), not into_body())
             user_agent: DEFAULT_USER_AGENT.to_owned(),
@@ -746,6 +755,7 @@ impl HttpClientConfig {
             transport: TransportSecurity::TlsOnly,
             tls_roots: TlsRootConfig::default(),
             otel: false,
+            metrics: false,
             buffer_capacity: 64,
             redirect: RedirectConfig::default(),
             pool_idle_timeout: None, // use hyper-util default
@@ -981,6 +991,7 @@ mod tests {
         assert!(config.rate_limit.is_some());
         assert_eq!(config.transport, TransportSecurity::TlsOnly);
         assert!(!config.otel);
+        assert!(!config.metrics);
         assert_eq!(config.buffer_capacity, 1024);
     }
 
@@ -996,7 +1007,7 @@ mod tests {
     #[test]
     fn test_http_client_config_infra_default() {
         let config = HttpClientConfig::infra_default();
-        assert_eq!(config.request_timeout, Duration::from_secs(60));
+        assert_eq!(config.request_timeout, Duration::from_mins(1));
         assert_eq!(config.max_body_size, 50 * 1024 * 1024);
         assert!(config.retry.is_some());
         assert_eq!(config.retry.unwrap().max_retries, 5);
@@ -1032,11 +1043,12 @@ mod tests {
     #[test]
     fn test_http_client_config_sse() {
         let config = HttpClientConfig::sse();
-        assert_eq!(config.request_timeout, Duration::from_secs(86_400));
+        assert_eq!(config.request_timeout, Duration::from_hours(24));
         assert!(config.total_timeout.is_none());
         assert!(config.retry.is_none());
         assert!(config.rate_limit.is_none());
         assert!(!config.otel);
+        assert!(!config.metrics);
         assert_eq!(config.buffer_capacity, 64);
         assert!(config.pool_idle_timeout.is_none());
         assert_eq!(config.pool_max_idle_per_host, 1);
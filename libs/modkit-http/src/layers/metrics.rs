@@ -0,0 +1,122 @@
+use bytes::Bytes;
+use http::{Request, Response};
+use http_body_util::Full;
+use opentelemetry::metrics::Histogram;
+use opentelemetry::{KeyValue, global};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+use tower::{Layer, Service};
+
+/// Tower layer that records OpenTelemetry metrics for outbound HTTP requests
+///
+/// Records `http.client.request.duration` (histogram, seconds) with attributes:
+/// - `http.request.method`: The HTTP method
+/// - `server.address`: The target host
+/// - `http.response.status_code`: The response status (absent on transport errors)
+/// - `error.type`: `"transport"` on errors, the status code for 4xx/5xx
+///
+/// Measurements go to the global meter provider; they are dropped if none is installed.
+#[derive(Clone)]
+pub struct MetricsLayer {
+    duration: Histogram<f64>,
+}
+
+impl MetricsLayer {
+    /// Create a new metrics layer using the global meter provider
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            duration: global::meter("modkit-http")
+                .f64_histogram("http.client.request.duration")
+                .with_description("Duration of outbound HTTP requests")
+                .with_unit("s")
+                .with_boundaries(DURATION_BOUNDARIES.to_vec())
+                .build(),
+        }
+    }
+}
+
+impl Default for MetricsLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Histogram boundaries (seconds), following the HTTP semantic conventions.
+const DURATION_BOUNDARIES: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService {
+            inner,
+            duration: self.duration.clone(),
+        }
+    }
+}
+
+/// Service that records request duration metrics
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    duration: Histogram<f64>,
+}
+
+impl<S, ResBody> Service<Request<Full<Bytes>>> for MetricsService<S>
+where
+    S: Service<Request<Full<Bytes>>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Send + 'static,
+    ResBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+        let mut attributes = vec![
+            KeyValue::new("http.request.method", req.method().to_string()),
+            KeyValue::new(
+                "server.address",
+                req.uri().host().unwrap_or_default().to_owned(),
+            ),
+        ];
+        let duration = self.duration.clone();
+        let started = Instant::now();
+
+        // Swap so we call the instance that was poll_ready'd, leaving a fresh clone
+        // for the next poll_ready cycle. This satisfies the Tower Service contract.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let result = inner.call(req).await;
+
+            match &result {
+                Ok(response) => {
+                    let status = response.status();
+                    attributes.push(KeyValue::new(
+                        "http.response.status_code",
+                        i64::from(status.as_u16()),
+                    ));
+                    if status.is_client_error() || status.is_server_error() {
+                        attributes.push(KeyValue::new("error.type", status.as_str().to_owned()));
+                    }
+                }
+                Err(_) => attributes.push(KeyValue::new("error.type", "transport")),
+            }
+            duration.record(started.elapsed().as_secs_f64(), &attributes);
+
+            result
+        })
+    }
+}
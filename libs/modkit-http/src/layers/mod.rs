@@ -8,13 +8,18 @@
 //! - [`UserAgentLayer`] - Adds User-Agent header to all requests
 //! - [`RetryLayer`] - Implements retry with exponential backoff and jitter
 //! - [`OtelLayer`] - Adds OpenTelemetry tracing spans to outbound requests
+//! - `MetricsLayer` - Records OpenTelemetry request duration metrics (`otel` feature)
 //! - [`SecureRedirectPolicy`] - Security-hardened redirect policy
 
+#[cfg(feature = "otel")]
+mod metrics;
 mod otel;
 mod redirect;
 mod retry;
 mod user_agent;
 
+#[cfg(feature = "otel")]
+pub use metrics::{MetricsLayer, MetricsService};
 pub use otel::{OtelLayer, OtelService};
 pub use redirect::SecureRedirectPolicy;
 pub use retry::{RETRY_ATTEMPT_HEADER, RetryLayer, RetryService};
@@ -62,6 +62,8 @@ pub use config::{
     is_idempotent_method,
 };
 pub use error::{HttpError, InvalidUriKind};
+#[cfg(feature = "otel")]
+pub use layers::{MetricsLayer, MetricsService};
 pub use layers::{
     OtelLayer, OtelService, RETRY_ATTEMPT_HEADER, RetryLayer, RetryService, SecureRedirectPolicy,
     UserAgentLayer, UserAgentService,
@@ -0,0 +1,143 @@
+//! Instrumented client wrapper generation
+//!
+//! Generates `Instrumented<Trait>`, a decorator over `Arc<dyn Trait>` that runs every
+//! method through `modkit::client_hub::instrument_call`, plus its `ClientHub` layer.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{FnArg, ItemTrait, Pat, PathArguments, ReturnType, TraitItem, Type};
+
+pub fn expand_instrumented_client(item: &ItemTrait) -> syn::Result<TokenStream> {
+    if !item.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &item.generics,
+            "instrumented_client does not support generic traits",
+        ));
+    }
+
+    let trait_ident = &item.ident;
+    let trait_name = trait_ident.to_string();
+    let vis = &item.vis;
+    let wrapper = format_ident!("Instrumented{}", trait_ident);
+    // Reuse the trait's `#[async_trait]` (with its arguments) for the wrapper impl
+    let async_trait_attr = item.attrs.iter().find(|a| {
+        a.path()
+            .segments
+            .last()
+            .is_some_and(|s| s.ident == "async_trait")
+    });
+
+    let mut methods = Vec::new();
+    for trait_item in &item.items {
+        let TraitItem::Fn(method) = trait_item else {
+            return Err(syn::Error::new_spanned(
+                trait_item,
+                "instrumented_client supports traits with methods only",
+            ));
+        };
+
+        let mut sig = method.sig.clone();
+        match sig.inputs.first() {
+            Some(FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_none() => {}
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &method.sig,
+                    "instrumented_client methods must take `&self`",
+                ));
+            }
+        }
+
+        let mut args = Vec::new();
+        for (i, input) in sig.inputs.iter_mut().skip(1).enumerate() {
+            let FnArg::Typed(arg) = input else {
+                continue;
+            };
+            let ident = match &*arg.pat {
+                Pat::Ident(p) if p.by_ref.is_none() && p.subpat.is_none() => p.ident.clone(),
+                _ => format_ident!("arg{}", i),
+            };
+            arg.attrs.clear();
+            *arg.pat = syn::parse_quote!(#ident);
+            args.push(ident);
+        }
+
+        let method_ident = &sig.ident;
+        let method_name = method_ident.to_string();
+        let is_ok = if returns_result(&sig.output) {
+            quote!(::std::result::Result::is_ok)
+        } else {
+            quote!(|_| true)
+        };
+        let call = quote!(self.inner.#method_ident(#(#args),*));
+        let body = if sig.asyncness.is_some() {
+            quote! {
+                ::modkit::client_hub::instrument_call(#trait_name, #method_name, #is_ok, #call).await
+            }
+        } else {
+            quote! {
+                ::modkit::client_hub::instrument_sync_call(#trait_name, #method_name, #is_ok, || #call)
+            }
+        };
+        methods.push(quote! {
+            #sig {
+                #body
+            }
+        });
+    }
+
+    let doc = format!(
+        "Instrumented [`{trait_name}`]: every call gets a `client.call` span and a \
+         `modkit.client.call.duration` sample."
+    );
+
+    Ok(quote! {
+        #item
+
+        #[doc = #doc]
+        #[derive(Clone)]
+        #vis struct #wrapper {
+            inner: ::std::sync::Arc<dyn #trait_ident>,
+        }
+
+        impl #wrapper {
+            #[must_use]
+            #vis fn new(inner: ::std::sync::Arc<dyn #trait_ident>) -> Self {
+                Self { inner }
+            }
+
+            /// `ClientHub` layer wrapping the resolved client in this instrumented wrapper.
+            #[must_use]
+            #vis fn layer() -> ::modkit::client_hub::ClientLayer {
+                ::modkit::client_hub::ClientLayer::new::<dyn #trait_ident>(|inner| {
+                    ::std::sync::Arc::new(Self::new(inner))
+                })
+            }
+        }
+
+        #async_trait_attr
+        impl #trait_ident for #wrapper {
+            #(#methods)*
+        }
+
+        ::modkit::inventory::submit! {
+            ::modkit::client_hub::InstrumentedClient::new(
+                ::std::any::type_name::<dyn #trait_ident>,
+                #wrapper::layer,
+            )
+        }
+    })
+}
+
+fn returns_result(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    let Type::Path(path) = &**ty else {
+        return false;
+    };
+    // `Result<..>` and aliases such as `anyhow::Result<..>` or `ApiResult<..>`
+    path.path.segments.last().is_some_and(|s| {
+        s.ident.to_string().ends_with("Result")
+            && matches!(s.arguments, PathArguments::AngleBracketed(_))
+    })
+}
@@ -12,6 +12,7 @@ use syn::{
 mod api_dto;
 mod domain_model;
 mod grpc_client;
+mod instrumented_client;
 mod utils;
 
 /// Configuration parsed from #[module(...)] attribute
@@ -33,6 +34,9 @@ enum Capability {
     System,
     GrpcHub,
     Grpc,
+    Health,
+    ConfigReload,
+    ScheduledJobs,
 }
 
 impl Capability {
@@ -44,6 +48,9 @@ impl Capability {
         "system",
         "grpc_hub",
         "grpc",
+        "health",
+        "config_reload",
+        "scheduled_jobs",
     ];
 
     fn suggest_similar(input: &str) -> Vec<&'static str> {
@@ -71,11 +78,14 @@ impl Capability {
             "system" => Ok(Capability::System),
             "grpc_hub" => Ok(Capability::GrpcHub),
             "grpc" => Ok(Capability::Grpc),
+            "health" => Ok(Capability::Health),
+            "config_reload" => Ok(Capability::ConfigReload),
+            "scheduled_jobs" => Ok(Capability::ScheduledJobs),
             other => {
                 let suggestions = Self::suggest_similar(other);
                 let error_msg = if suggestions.is_empty() {
                     format!(
-                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc"
+                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc, health, config_reload, scheduled_jobs"
                     )
                 } else {
                     format!(
@@ -98,11 +108,14 @@ impl Capability {
             "system" => Ok(Capability::System),
             "grpc_hub" => Ok(Capability::GrpcHub),
             "grpc" => Ok(Capability::Grpc),
+            "health" => Ok(Capability::Health),
+            "config_reload" => Ok(Capability::ConfigReload),
+            "scheduled_jobs" => Ok(Capability::ScheduledJobs),
             other => {
                 let suggestions = Self::suggest_similar(other);
                 let error_msg = if suggestions.is_empty() {
                     format!(
-                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc"
+                        "unknown capability '{other}', expected one of: db, rest, rest_host, stateful, system, grpc_hub, grpc, health, config_reload, scheduled_jobs"
                     )
                 } else {
                     format!(
@@ -590,6 +603,33 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                     {}
                 };
             },
+            Capability::Health => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_HealthCapability_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::HealthCapability,
+                    {}
+                };
+            },
+            Capability::ConfigReload => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_ConfigReloadCapability_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::ConfigReloadCapability,
+                    {}
+                };
+            },
+            Capability::ScheduledJobs => quote! {
+                const _: () = {
+                    #[allow(dead_code)]
+                    fn __modkit_require_ScheduledJobsCapability_impl()
+                    where
+                        #struct_ident #ty_generics: ::modkit::contracts::ScheduledJobsCapability,
+                    {}
+                };
+            },
         };
         cap_asserts.push(q);
     }
@@ -739,6 +779,18 @@ pub fn module(attr: TokenStream, item: TokenStream) -> TokenStream {
                 b.register_grpc_service_with_meta(#name_lit,
                     module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::GrpcServiceCapability>);
             },
+            Capability::Health => quote! {
+                b.register_health_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::HealthCapability>);
+            },
+            Capability::ConfigReload => quote! {
+                b.register_config_reload_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::ConfigReloadCapability>);
+            },
+            Capability::ScheduledJobs => quote! {
+                b.register_scheduled_jobs_with_meta(#name_lit,
+                    module.clone() as ::std::sync::Arc<dyn ::modkit::contracts::ScheduledJobsCapability>);
+            },
         }
     });
 
@@ -1131,6 +1183,46 @@ pub fn grpc_client(attr: TokenStream, item: TokenStream) -> TokenStream {
     }
 }
 
+/// Generate an instrumented wrapper for an SDK client trait
+///
+/// Place above `#[async_trait]` on an object-safe trait. The macro keeps the trait and
+/// generates `Instrumented<Trait>`, which implements it over an `Arc<dyn Trait>`: each
+/// call runs in a `client.call` span and its latency is recorded in the
+/// `modkit.client.call.duration` histogram, labelled by trait and method (and outcome
+/// for methods returning a `Result`).
+///
+/// # Example
+///
+/// ```ignore
+/// #[modkit::instrumented_client]
+/// #[async_trait]
+/// pub trait UsersClient: Send + Sync {
+///     async fn get_user(&self, id: Uuid) -> Result<User, UsersError>;
+/// }
+///
+/// // Wrap one client type...
+/// hub.add_layer(InstrumentedUsersClient::layer());
+/// // ...or every client type with an instrumented wrapper
+/// hub.add_layer(modkit::client_hub::ClientLayer::instrumented());
+/// ```
+#[proc_macro_attribute]
+pub fn instrumented_client(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        return syn::Error::new(
+            Span::call_site(),
+            "instrumented_client does not take arguments",
+        )
+        .to_compile_error()
+        .into();
+    }
+    let input = parse_macro_input!(item as syn::ItemTrait);
+
+    match instrumented_client::expand_instrumented_client(&input) {
+        Ok(expanded) => TokenStream::from(expanded),
+        Err(e) => TokenStream::from(e.to_compile_error()),
+    }
+}
+
 /// Generates API DTO (Data Transfer Object) boilerplate for REST API types.
 ///
 /// This macro automatically derives the necessary traits and attributes for types
@@ -0,0 +1,128 @@
+//! `$apply` model: filter, group and aggregate transformations (`OData` Data Aggregation).
+//!
+//! Only the subset needed for reporting is modelled: any number of `filter(...)`
+//! steps followed by one `groupby(...)` or `aggregate(...)`, e.g.
+//! `filter(status ne 'draft')/groupby((status),aggregate(amount with sum as total))`.
+
+use crate::ast;
+
+/// Aggregation method of an [`Aggregate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AggregateMethod {
+    /// `$count as alias`: number of rows in the group.
+    Count,
+    Sum,
+    Min,
+    Max,
+    Average,
+    CountDistinct,
+}
+
+impl AggregateMethod {
+    /// Parse an `OData` method name (`sum`, `min`, `max`, `average`, `countdistinct`).
+    #[must_use]
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "sum" => Some(Self::Sum),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "average" => Some(Self::Average),
+            "countdistinct" => Some(Self::CountDistinct),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Count => "$count",
+            Self::Sum => "sum",
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Average => "average",
+            Self::CountDistinct => "countdistinct",
+        }
+    }
+}
+
+/// One aggregate expression: `amount with sum as total` or `$count as total`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct Aggregate {
+    /// Aggregated property (lowercased); `None` for `$count`.
+    pub field: Option<String>,
+    pub method: AggregateMethod,
+    /// Name of the aggregated value in the result rows.
+    pub alias: String,
+}
+
+impl Aggregate {
+    pub fn new(
+        field: impl Into<String>,
+        method: AggregateMethod,
+        alias: impl Into<String>,
+    ) -> Self {
+        Self {
+            field: Some(field.into().to_lowercase()),
+            method,
+            alias: alias.into(),
+        }
+    }
+
+    /// `$count as alias`.
+    pub fn count(alias: impl Into<String>) -> Self {
+        Self {
+            field: None,
+            method: AggregateMethod::Count,
+            alias: alias.into(),
+        }
+    }
+}
+
+/// Parsed `$apply`: pre-aggregation filter, grouping properties and aggregates.
+///
+/// Without `group_by` the aggregates are computed over all matching rows.
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct Apply {
+    /// Combined `filter(...)` steps, applied before grouping.
+    pub filter: Option<Box<ast::Expr>>,
+    /// `groupby((...))` properties (lowercased).
+    pub group_by: Vec<String>,
+    pub aggregates: Vec<Aggregate>,
+}
+
+impl Apply {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a `filter(...)` step; several steps are combined with `and`.
+    pub fn with_filter(mut self, expr: ast::Expr) -> Self {
+        self.filter = Some(Box::new(match self.filter.take() {
+            Some(prev) => ast::Expr::And(prev, Box::new(expr)),
+            None => expr,
+        }));
+        self
+    }
+
+    pub fn with_group_by(mut self, fields: Vec<String>) -> Self {
+        self.group_by = fields.into_iter().map(|f| f.to_lowercase()).collect();
+        self
+    }
+
+    pub fn with_aggregate(mut self, aggregate: Aggregate) -> Self {
+        self.aggregates.push(aggregate);
+        self
+    }
+
+    /// Names of the properties in each result row: grouping properties, then aliases.
+    #[must_use]
+    pub fn output_names(&self) -> Vec<&str> {
+        self.group_by
+            .iter()
+            .map(String::as_str)
+            .chain(self.aggregates.iter().map(|a| a.alias.as_str()))
+            .collect()
+    }
+}
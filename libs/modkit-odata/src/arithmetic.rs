@@ -0,0 +1,257 @@
+//! Infix arithmetic operators (`add`, `sub`, `mul`, `div`, `mod`) in `$filter`.
+//!
+//! The `odata_params` grammar has no arithmetic, so the filter string is rewritten
+//! before parsing: `price mul 2 add 1 gt 10` becomes `add(mul(price, 2), 1) gt 10`.
+//! `mul`, `div` and `mod` bind tighter than `add` and `sub`, which bind tighter than
+//! comparisons; operators of one level associate to the left. The parsed AST thus
+//! carries arithmetic as `Function("add", [a, b])` and so on.
+//!
+//! The call form itself (`add(price, 1)`) is not `OData` and is rejected.
+
+use std::borrow::Cow;
+
+const ARITHMETIC: [&str; 5] = ["add", "sub", "mul", "div", "mod"];
+const KEYWORDS: [&str; 10] = ["and", "or", "not", "in", "eq", "ne", "gt", "ge", "lt", "le"];
+
+#[derive(Clone, Copy)]
+enum Token<'a> {
+    Word(&'a str),
+    /// String literal including its quotes.
+    Str(&'a str),
+    Open,
+    Close,
+    Comma,
+}
+
+enum Item<'a> {
+    /// Literal, call or parenthesized group, already rewritten.
+    Operand(String),
+    Word(&'a str),
+}
+
+impl Item<'_> {
+    fn is_operand(&self) -> bool {
+        match self {
+            Item::Operand(_) => true,
+            Item::Word(w) => !KEYWORDS.contains(w) && !ARITHMETIC.contains(w),
+        }
+    }
+
+    fn text(&self) -> &str {
+        match self {
+            Item::Operand(s) => s,
+            Item::Word(w) => w,
+        }
+    }
+}
+
+/// Rewrite infix arithmetic in `raw` into the call form understood by the parser.
+///
+/// Filters without arithmetic are returned unchanged; malformed ones are left for the
+/// parser to reject.
+pub fn rewrite(raw: &str) -> Result<Cow<'_, str>, String> {
+    let Some(tokens) = tokenize(raw) else {
+        return Ok(Cow::Borrowed(raw));
+    };
+    let has_arithmetic = tokens.iter().any(
+        |t| matches!(t, Token::Word(w) if ARITHMETIC.contains(&w.to_ascii_lowercase().as_str())),
+    );
+    if !has_arithmetic {
+        return Ok(Cow::Borrowed(raw));
+    }
+
+    let mut rewriter = Rewriter { tokens, pos: 0 };
+    let parts = rewriter.list()?;
+    if rewriter.pos != rewriter.tokens.len() {
+        return Err("unbalanced parentheses".to_owned());
+    }
+    Ok(Cow::Owned(parts.join(", ")))
+}
+
+/// Split `raw` into words, string literals, parentheses and commas.
+///
+/// Returns `None` for an unterminated string literal.
+fn tokenize(raw: &str) -> Option<Vec<Token<'_>>> {
+    let mut tokens = Vec::new();
+    let mut chars = raw.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        match c {
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            ',' => tokens.push(Token::Comma),
+            '\'' => {
+                let mut end = None;
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '\'' if chars.peek().is_some_and(|&(_, c)| c == '\'') => {
+                            chars.next();
+                        }
+                        '\'' => {
+                            end = Some(i + 1);
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                tokens.push(Token::Str(&raw[start..end?]));
+            }
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut end = raw.len();
+                while let Some(&(i, c)) = chars.peek() {
+                    if c.is_whitespace() || matches!(c, '(' | ')' | ',' | '\'') {
+                        end = i;
+                        break;
+                    }
+                    chars.next();
+                }
+                tokens.push(Token::Word(&raw[start..end]));
+            }
+        }
+    }
+    Some(tokens)
+}
+
+struct Rewriter<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+}
+
+impl<'a> Rewriter<'a> {
+    /// Comma-separated expressions up to a closing parenthesis or the end.
+    fn list(&mut self) -> Result<Vec<String>, String> {
+        let mut parts = vec![self.sequence()?];
+        while matches!(self.tokens.get(self.pos), Some(Token::Comma)) {
+            self.pos += 1;
+            parts.push(self.sequence()?);
+        }
+        Ok(parts)
+    }
+
+    /// Contents of a parenthesized list whose `(` was just consumed.
+    fn group(&mut self) -> Result<String, String> {
+        let parts = self.list()?;
+        if !matches!(self.tokens.get(self.pos), Some(Token::Close)) {
+            return Err("unbalanced parentheses".to_owned());
+        }
+        self.pos += 1;
+        Ok(parts.join(", "))
+    }
+
+    fn sequence(&mut self) -> Result<String, String> {
+        let mut items: Vec<Item<'a>> = Vec::new();
+        while let Some(&token) = self.tokens.get(self.pos) {
+            self.pos += 1;
+            let item = match token {
+                Token::Close | Token::Comma => {
+                    self.pos -= 1;
+                    break;
+                }
+                Token::Str(s) => Item::Operand(s.to_owned()),
+                Token::Open => Item::Operand(format!("({})", self.group()?)),
+                Token::Word(w) => {
+                    let is_operator =
+                        ARITHMETIC.contains(&w) && items.last().is_some_and(Item::is_operand);
+                    let is_call = !is_operator
+                        && !KEYWORDS.contains(&w)
+                        && matches!(self.tokens.get(self.pos), Some(Token::Open));
+                    if !is_call {
+                        Item::Word(w)
+                    } else if ARITHMETIC.contains(&w.to_ascii_lowercase().as_str()) {
+                        return Err(format!(
+                            "`{w}` is an infix operator, write `a {} b`",
+                            w.to_ascii_lowercase()
+                        ));
+                    } else {
+                        self.pos += 1;
+                        Item::Operand(format!("{w}({})", self.group()?))
+                    }
+                }
+            };
+            items.push(item);
+        }
+        Ok(fold(&items))
+    }
+}
+
+/// Turn each run of `operand (operator operand)*` into nested calls.
+fn fold(items: &[Item<'_>]) -> String {
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < items.len() {
+        let first = &items[i];
+        i += 1;
+        if !first.is_operand() {
+            out.push(first.text().to_owned());
+            continue;
+        }
+
+        // `sum` holds the `add`/`sub` chain left of `term`, which collects `mul`/`div`/`mod`.
+        let mut sum = String::new();
+        let mut sum_op: Option<&str> = None;
+        let mut term = first.text().to_owned();
+        while let (Some(Item::Word(op)), Some(rhs)) = (items.get(i), items.get(i + 1)) {
+            if !ARITHMETIC.contains(op) || !rhs.is_operand() {
+                break;
+            }
+            if matches!(*op, "add" | "sub") {
+                sum = match sum_op {
+                    Some(prev) => format!("{prev}({sum}, {term})"),
+                    None => std::mem::take(&mut term),
+                };
+                sum_op = Some(op);
+                rhs.text().clone_into(&mut term);
+            } else {
+                term = format!("{op}({term}, {})", rhs.text());
+            }
+            i += 2;
+        }
+        out.push(match sum_op {
+            Some(op) => format!("{op}({sum}, {term})"),
+            None => term,
+        });
+    }
+    out.join(" ")
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::rewrite;
+
+    #[test]
+    fn leaves_filters_without_arithmetic_alone() {
+        let raw = "name eq 'a add b'  and  tolower(city) eq 'mod'";
+        assert_eq!(rewrite(raw).unwrap(), raw);
+    }
+
+    #[test]
+    fn applies_precedence_and_left_associativity() {
+        assert_eq!(
+            rewrite("price mul 2 add 1 gt 10").unwrap(),
+            "add(mul(price, 2), 1) gt 10"
+        );
+        assert_eq!(
+            rewrite("a sub b sub c add d div 2 mod 3 eq 0").unwrap(),
+            "add(sub(sub(a, b), c), mod(div(d, 2), 3)) eq 0"
+        );
+        assert_eq!(
+            rewrite("(price add 5) mul 2 gt 10 and length(name) add 1 lt 5").unwrap(),
+            "mul((add(price, 5)), 2) gt 10 and add(length(name), 1) lt 5"
+        );
+        assert_eq!(
+            rewrite("score in (1 add 1, 3)").unwrap(),
+            "score in (add(1, 1), 3)"
+        );
+    }
+
+    #[test]
+    fn rejects_the_call_form() {
+        let err = rewrite("add(price, 5) gt 10").unwrap_err();
+        assert!(err.contains("infix"), "{err}");
+        assert!(rewrite("price add 5) gt 10").is_err());
+    }
+}
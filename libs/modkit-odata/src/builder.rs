@@ -51,7 +51,8 @@
 
 use crate::schema::{AsFieldKey, AsFieldName, FieldRef, Schema};
 use crate::{
-    ODataOrderBy, ODataQuery, OrderKey, SortDir, ast::Expr, pagination::short_filter_hash,
+    ExpandItem, ODataOrderBy, ODataQuery, OrderKey, SortDir, ast::Expr,
+    pagination::short_filter_hash,
 };
 use std::marker::PhantomData;
 
@@ -75,6 +76,8 @@ pub struct QueryBuilder<S: Schema> {
     order: Vec<OrderKey>,
     select: Option<Vec<S::Field>>,
     limit: Option<u64>,
+    count: bool,
+    expand: Vec<ExpandItem>,
     _phantom: PhantomData<S>,
 }
 
@@ -87,6 +90,8 @@ impl<S: Schema> QueryBuilder<S> {
             order: Vec::new(),
             select: None,
             limit: None,
+            count: false,
+            expand: Vec::new(),
             _phantom: PhantomData,
         }
     }
@@ -167,6 +172,34 @@ impl<S: Schema> QueryBuilder<S> {
         self
     }
 
+    /// Request the total number of matching items (`$count=true`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.count(true)
+    /// ```
+    #[must_use]
+    pub fn count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    /// Add a related entity set to `$expand`.
+    ///
+    /// Can be called multiple times to expand several navigation properties.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// builder.expand(ExpandItem::new("addresses").with_select(vec!["city".into()]))
+    /// ```
+    #[must_use]
+    pub fn expand(mut self, item: ExpandItem) -> Self {
+        self.expand.push(item);
+        self
+    }
+
     /// Build the final `ODataQuery` with computed filter hash.
     ///
     /// The filter hash is computed using the stable hashing algorithm from
@@ -200,7 +233,7 @@ impl<S: Schema> QueryBuilder<S> {
             query = query.with_select(names);
         }
 
-        query
+        query.with_count(self.count).with_expand(self.expand)
     }
 }
 
@@ -458,6 +491,26 @@ mod tests {
         assert_eq!(query.limit, Some(50));
     }
 
+    #[test]
+    fn test_count() {
+        let query = QueryBuilder::<UserSchema>::new().build();
+        assert!(!query.count);
+
+        let query = QueryBuilder::<UserSchema>::new().count(true).build();
+        assert!(query.count);
+    }
+
+    #[test]
+    fn test_expand() {
+        let query = QueryBuilder::<UserSchema>::new()
+            .expand(ExpandItem::new("Addresses").with_expand(vec![ExpandItem::new("city")]))
+            .build();
+
+        assert_eq!(query.expand.len(), 1);
+        assert_eq!(query.expand[0].name, "addresses");
+        assert_eq!(query.expand[0].depth(), 2);
+    }
+
     #[test]
     fn test_full_query_build() {
         let user_id = uuid::Uuid::new_v4();
@@ -0,0 +1,50 @@
+//! `$expand` model: related entity sets requested alongside the main collection.
+//!
+//! Each [`ExpandItem`] names a navigation property and may carry its own nested
+//! query options, e.g. `addresses($select=street,city_id;$filter=kind eq 'home';$expand=city)`.
+
+use crate::ast;
+
+/// One navigation property in `$expand`, with its nested query options.
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct ExpandItem {
+    /// Navigation property name (lowercased).
+    pub name: String,
+    /// Nested `$select` applied to the related items.
+    pub select: Option<Vec<String>>,
+    /// Nested `$filter` applied to the related items.
+    pub filter: Option<Box<ast::Expr>>,
+    /// Nested `$expand` of the related entity.
+    pub expand: Vec<ExpandItem>,
+}
+
+impl ExpandItem {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into().to_lowercase(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_select(mut self, fields: Vec<String>) -> Self {
+        self.select = Some(fields);
+        self
+    }
+
+    pub fn with_filter(mut self, expr: ast::Expr) -> Self {
+        self.filter = Some(Box::new(expr));
+        self
+    }
+
+    pub fn with_expand(mut self, items: Vec<ExpandItem>) -> Self {
+        self.expand = items;
+        self
+    }
+
+    /// Nesting depth of this item (1 for an item without nested `$expand`).
+    #[must_use]
+    pub fn depth(&self) -> usize {
+        1 + self.expand.iter().map(ExpandItem::depth).max().unwrap_or(0)
+    }
+}
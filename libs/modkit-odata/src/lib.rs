@@ -1,6 +1,10 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+pub mod apply;
+#[cfg(feature = "with-odata-params")]
+mod arithmetic;
 pub mod builder;
 pub mod errors;
+pub mod expand;
 pub mod filter;
 pub mod limits;
 pub mod page;
@@ -8,7 +12,9 @@ pub mod pagination;
 pub mod problem_mapping;
 pub mod schema;
 
+pub use apply::{Aggregate, AggregateMethod, Apply};
 pub use builder::QueryBuilder;
+pub use expand::ExpandItem;
 pub use limits::ODataLimits;
 pub use page::{Page, PageInfo};
 pub use pagination::{normalize_filter_for_hash, short_filter_hash};
@@ -273,6 +279,8 @@ impl std::fmt::Display for ODataOrderBy {
 /// - `InvalidFilter` → 422 `gts...~hx.odata.errors.invalid_filter.v1`
 /// - `InvalidOrderByField` → 422 `gts...~hx.odata.errors.invalid_orderby.v1`
 /// - Cursor errors → 422 `gts...~hx.odata.errors.invalid_cursor.v1`
+/// - `InvalidExpand` → 422 `gts...~hx.odata.errors.invalid_expand.v1`
+/// - `InvalidApply` → 422 `gts...~hx.odata.errors.invalid_apply.v1`
 #[derive(thiserror::Error, Debug, Clone)]
 pub enum Error {
     // Filter parsing and validation errors
@@ -283,6 +291,14 @@ pub enum Error {
     #[error("unsupported $orderby field: {0}")]
     InvalidOrderByField(String),
 
+    // Expand parsing and validation errors
+    #[error("invalid $expand: {0}")]
+    InvalidExpand(String),
+
+    // Apply (aggregation) parsing and validation errors
+    #[error("invalid $apply: {0}")]
+    InvalidApply(String),
+
     // Pagination and cursor errors
     #[error("ORDER_MISMATCH")]
     OrderMismatch,
@@ -466,6 +482,12 @@ pub struct ODataQuery {
     pub cursor: Option<CursorV1>,
     pub filter_hash: Option<String>,
     pub select: Option<Vec<String>>,
+    /// `$count=true`: the total number of matching items is requested.
+    pub count: bool,
+    /// `$expand`: related entity sets to return with each item.
+    pub expand: Vec<ExpandItem>,
+    /// `$apply`: group and aggregate instead of listing items.
+    pub apply: Option<Apply>,
 }
 
 impl ODataQuery {
@@ -503,6 +525,21 @@ impl ODataQuery {
         self
     }
 
+    pub fn with_count(mut self, count: bool) -> Self {
+        self.count = count;
+        self
+    }
+
+    pub fn with_expand(mut self, items: Vec<ExpandItem>) -> Self {
+        self.expand = items;
+        self
+    }
+
+    pub fn with_apply(mut self, apply: Apply) -> Self {
+        self.apply = Some(apply);
+        self
+    }
+
     /// Get filter as AST
     #[must_use]
     pub fn filter(&self) -> Option<&ast::Expr> {
@@ -532,6 +569,18 @@ impl ODataQuery {
     pub fn selected_fields(&self) -> Option<&[String]> {
         self.select.as_deref()
     }
+
+    /// Check if `$expand` is present
+    #[must_use]
+    pub fn has_expand(&self) -> bool {
+        !self.expand.is_empty()
+    }
+
+    /// Check if `$apply` is present
+    #[must_use]
+    pub fn has_apply(&self) -> bool {
+        self.apply.is_some()
+    }
 }
 
 impl From<Option<ast::Expr>> for ODataQuery {
@@ -634,6 +683,7 @@ impl ParsedFilter {
 ///
 /// This function encapsulates the parsing logic and node counting,
 /// abstracting away the underlying `odata_params` dependency.
+/// Infix arithmetic (`price add 5`) becomes `Function("add", [price, 5])`.
 ///
 /// # Errors
 /// - `Error::InvalidFilter` if the filter string is malformed or parsing fails
@@ -662,6 +712,7 @@ pub fn parse_filter_string(raw: &str) -> Result<ParsedFilter, Error> {
         }
     }
 
+    let raw = arithmetic::rewrite(raw).map_err(Error::InvalidFilter)?;
     let ast_src = od::parse_str(raw).map_err(|e| Error::InvalidFilter(format!("{e:?}")))?;
 
     let node_count = count_ast_nodes(&ast_src);
@@ -6,6 +6,12 @@ pub struct PageInfo {
     pub next_cursor: Option<String>,
     pub prev_cursor: Option<String>,
     pub limit: u64,
+    /// Total number of items matching the filter; present only when `$count=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_count: Option<u64>,
+    /// `true` when counting stopped at the server's cap, i.e. `total_count` is a lower bound.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub total_count_capped: bool,
 }
 
 #[cfg_attr(feature = "with-utoipa", derive(utoipa::ToSchema))]
@@ -31,6 +37,8 @@ impl<T> Page<T> {
                 next_cursor: None,
                 prev_cursor: None,
                 limit,
+                total_count: None,
+                total_count_capped: false,
             },
         }
     }
@@ -12,9 +12,9 @@ impl From<Error> for Problem {
     fn from(err: Error) -> Self {
         use Error::{
             CursorInvalidBase64, CursorInvalidDirection, CursorInvalidFields, CursorInvalidJson,
-            CursorInvalidKeys, CursorInvalidVersion, Db, FilterMismatch, InvalidCursor,
-            InvalidFilter, InvalidLimit, InvalidOrderByField, OrderMismatch, OrderWithCursor,
-            ParsingUnavailable,
+            CursorInvalidKeys, CursorInvalidVersion, Db, FilterMismatch, InvalidApply,
+            InvalidCursor, InvalidExpand, InvalidFilter, InvalidLimit, InvalidOrderByField,
+            OrderMismatch, OrderWithCursor, ParsingUnavailable,
         };
 
         match err {
@@ -26,6 +26,14 @@ impl From<Error> for Problem {
             InvalidOrderByField(field) => ErrorCode::odata_errors_invalid_orderby_v1()
                 .as_problem(format!("Unsupported $orderby field: {field}")),
 
+            // Expand parsing and validation errors → 422
+            InvalidExpand(msg) => ErrorCode::odata_errors_invalid_expand_v1()
+                .as_problem(format!("Invalid $expand: {msg}")),
+
+            // Apply parsing and validation errors → 422
+            InvalidApply(msg) => ErrorCode::odata_errors_invalid_apply_v1()
+                .as_problem(format!("Invalid $apply: {msg}")),
+
             // All cursor-related errors → 422
             InvalidCursor
             | CursorInvalidBase64
@@ -97,6 +105,32 @@ mod tests {
         assert!(problem.code.contains("invalid_orderby"));
     }
 
+    #[test]
+    fn test_expand_error_converts_to_problem() {
+        use http::StatusCode;
+
+        let err = Error::InvalidExpand("unknown navigation property: pets".to_owned());
+        let problem: Problem = err.into();
+
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.title, "Invalid Expand");
+        assert!(problem.detail.contains("pets"));
+        assert!(problem.code.contains("invalid_expand"));
+    }
+
+    #[test]
+    fn test_apply_error_converts_to_problem() {
+        use http::StatusCode;
+
+        let err = Error::InvalidApply("unknown aggregation method: median".to_owned());
+        let problem: Problem = err.into();
+
+        assert_eq!(problem.status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(problem.title, "Invalid Apply");
+        assert!(problem.detail.contains("median"));
+        assert!(problem.code.contains("invalid_apply"));
+    }
+
     #[test]
     fn test_cursor_error_converts_to_problem() {
         use http::StatusCode;
@@ -94,6 +94,8 @@ pin_project! {
     {
         base_query: ODataQuery,
         next_cursor: Option<String>,
+        total_count: Option<u64>,
+        total_count_capped: bool,
         buffer: VecDeque<T>,
         done: bool,
         fetcher: F,
@@ -125,12 +127,29 @@ where
         Self {
             base_query,
             next_cursor: None,
+            total_count: None,
+            total_count_capped: false,
             buffer: VecDeque::new(),
             done: false,
             fetcher,
             current_fetch: None,
         }
     }
+
+    /// Total number of matching items, as reported by the first page.
+    ///
+    /// Only available when the query requested `$count=true` (see
+    /// `QueryBuilder::count`) and the first page has been fetched.
+    #[must_use]
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count
+    }
+
+    /// `true` if the server stopped counting at its cap, i.e. `total_count` is a lower bound.
+    #[must_use]
+    pub fn total_count_capped(&self) -> bool {
+        self.total_count_capped
+    }
 }
 
 impl<T, E, F, Fut> Stream for CursorPager<T, E, F, Fut>
@@ -159,6 +178,14 @@ where
 
                         this.next_cursor.clone_from(&page.page_info.next_cursor);
 
+                        // The total does not change between pages: count once, then
+                        // stop asking the server for it.
+                        if let Some(total) = page.page_info.total_count {
+                            *this.total_count = Some(total);
+                            *this.total_count_capped = page.page_info.total_count_capped;
+                            this.base_query.count = false;
+                        }
+
                         if this.next_cursor.is_none() {
                             *this.done = true;
                         }
@@ -212,6 +239,8 @@ pin_project! {
     {
         base_query: ODataQuery,
         next_cursor: Option<String>,
+        total_count: Option<u64>,
+        total_count_capped: bool,
         done: bool,
         fetcher: F,
         #[pin]
@@ -242,11 +271,28 @@ where
         Self {
             base_query,
             next_cursor: None,
+            total_count: None,
+            total_count_capped: false,
             done: false,
             fetcher,
             current_fetch: None,
         }
     }
+
+    /// Total number of matching items, as reported by the first page.
+    ///
+    /// Only available when the query requested `$count=true` (see
+    /// `QueryBuilder::count`) and the first page has been fetched.
+    #[must_use]
+    pub fn total_count(&self) -> Option<u64> {
+        self.total_count
+    }
+
+    /// `true` if the server stopped counting at its cap, i.e. `total_count` is a lower bound.
+    #[must_use]
+    pub fn total_count_capped(&self) -> bool {
+        self.total_count_capped
+    }
 }
 
 impl<T, E, F, Fut> Stream for PagesPager<T, E, F, Fut>
@@ -271,6 +317,14 @@ where
 
                         this.next_cursor.clone_from(&page.page_info.next_cursor);
 
+                        // The total does not change between pages: count once, then
+                        // stop asking the server for it.
+                        if let Some(total) = page.page_info.total_count {
+                            *this.total_count = Some(total);
+                            *this.total_count_capped = page.page_info.total_count_capped;
+                            this.base_query.count = false;
+                        }
+
                         if this.next_cursor.is_none() {
                             *this.done = true;
                         }
@@ -378,6 +432,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor.clone()),
                 prev_cursor: None,
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -396,6 +452,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: Some(encoded_cursor),
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -427,6 +485,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 10,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -465,6 +525,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -518,6 +580,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor.clone()),
                 prev_cursor: None,
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -530,6 +594,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: Some(encoded_cursor),
                 limit: 2,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -564,6 +630,8 @@ mod tests {
                 next_cursor: None,
                 prev_cursor: None,
                 limit: 10,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -592,6 +660,8 @@ mod tests {
                 next_cursor: Some("invalid_cursor_string".to_owned()),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -628,6 +698,8 @@ mod tests {
                 next_cursor: Some("invalid_cursor_string".to_owned()),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -675,6 +747,8 @@ mod tests {
                 next_cursor: Some(encoded_cursor),
                 prev_cursor: None,
                 limit: 1,
+                total_count: None,
+                total_count_capped: false,
             },
         );
 
@@ -733,4 +807,63 @@ mod tests {
         // If we don't poll immediately after installing the future, this would be 0.
         assert_eq!(polls.load(Ordering::SeqCst), 1);
     }
+    #[tokio::test]
+    async fn test_pages_pager_counts_once() {
+        use modkit_odata::{CursorV1, SortDir};
+
+        let cursor = CursorV1 {
+            k: vec!["1".to_owned()],
+            o: SortDir::Asc,
+            s: "+id".to_owned(),
+            f: None,
+            d: "fwd".to_owned(),
+        };
+        let user = User {
+            id: 1,
+            name: "Alice".to_owned(),
+        };
+        let page1 = Page::new(
+            vec![user.clone()],
+            PageInfo {
+                next_cursor: Some(cursor.encode().unwrap()),
+                prev_cursor: None,
+                limit: 1,
+                total_count: Some(2),
+                total_count_capped: false,
+            },
+        );
+        let page2 = Page::new(
+            vec![User { id: 2, ..user }],
+            PageInfo {
+                next_cursor: None,
+                prev_cursor: None,
+                limit: 1,
+                total_count: None,
+                total_count_capped: false,
+            },
+        );
+
+        let fetcher = FakeFetcher::new(vec![page1, page2]);
+        let count_flags = Arc::new(Mutex::new(Vec::new()));
+        let flags = count_flags.clone();
+        let mut pager = Box::pin(PagesPager::new(
+            ODataQuery::new().with_limit(1).with_count(true),
+            {
+                move |q: ODataQuery| {
+                    flags.lock().unwrap().push(q.count);
+                    let fetcher = fetcher.clone();
+                    async move { fetcher.fetch(q) }
+                }
+            },
+        ));
+
+        assert_eq!(pager.total_count(), None);
+        while let Some(page) = pager.next().await {
+            page.unwrap();
+        }
+
+        assert_eq!(pager.total_count(), Some(2));
+        assert!(!pager.total_count_capped());
+        assert_eq!(*count_flags.lock().unwrap(), vec![true, false]);
+    }
 }
@@ -0,0 +1,436 @@
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+//! Server TLS shared by the HTTP and gRPC servers.
+//!
+//! [`ServerTls`] loads a PEM certificate chain and private key (plus an optional client CA
+//! bundle for mutual TLS) into a rustls config, re-reads the files when they change on disk
+//! ([`ServerTls::watch`]) and turns a `TcpListener` into TLS connections
+//! ([`ServerTls::accept_loop`], [`ServerTls::incoming`]). New connections always use the
+//! latest certificates; established ones keep theirs.
+
+use std::io;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context;
+use arc_swap::ArcSwap;
+use parking_lot::Mutex;
+use rustls::RootCertStore;
+use rustls::server::WebPkiClientVerifier;
+use rustls_pki_types::pem::PemObject;
+use rustls_pki_types::{CertificateDer, PrivateKeyDer};
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::server::TlsStream;
+use tokio_stream::Stream;
+use tokio_stream::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+/// Time allowed for a TLS handshake before the connection is dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Handshaken connections buffered until the server picks them up.
+const ACCEPT_BACKLOG: usize = 64;
+
+fn default_reload_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+/// Whether clients must present a certificate when a client CA is configured.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAuth {
+    /// Reject connections without a valid client certificate.
+    #[default]
+    Required,
+    /// Accept anonymous clients; verify the certificate when one is presented.
+    Optional,
+}
+
+/// Server TLS settings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ServerTlsConfig {
+    /// PEM certificate chain, leaf first.
+    pub cert_path: PathBuf,
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1).
+    pub key_path: PathBuf,
+    /// PEM bundle of CAs trusted for client certificates; enables mutual TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ca_path: Option<PathBuf>,
+    /// Client certificate policy when `client_ca_path` is set.
+    #[serde(default)]
+    pub client_auth: ClientAuth,
+    /// How often the files are checked for changes (default 30s).
+    #[serde(
+        default = "default_reload_interval",
+        with = "modkit_utils::humantime_serde"
+    )]
+    pub reload_interval: Duration,
+}
+
+impl ServerTlsConfig {
+    /// TLS with the given certificate chain and key, without client authentication.
+    #[must_use]
+    pub fn new(cert_path: impl Into<PathBuf>, key_path: impl Into<PathBuf>) -> Self {
+        Self {
+            cert_path: cert_path.into(),
+            key_path: key_path.into(),
+            client_ca_path: None,
+            client_auth: ClientAuth::default(),
+            reload_interval: default_reload_interval(),
+        }
+    }
+
+    /// Verify client certificates against the CA bundle at `path`.
+    #[must_use]
+    pub fn with_client_ca(mut self, path: impl Into<PathBuf>, auth: ClientAuth) -> Self {
+        self.client_ca_path = Some(path.into());
+        self.client_auth = auth;
+        self
+    }
+
+    fn files(&self) -> impl Iterator<Item = &Path> {
+        [
+            Some(&self.cert_path),
+            Some(&self.key_path),
+            self.client_ca_path.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(PathBuf::as_path)
+    }
+}
+
+/// Modification times of the configured files, used to detect rotation.
+type FilesStamp = Vec<Option<SystemTime>>;
+
+/// Hot-reloadable server TLS state.
+pub struct ServerTls {
+    config: ServerTlsConfig,
+    alpn_protocols: Vec<Vec<u8>>,
+    current: ArcSwap<rustls::ServerConfig>,
+    stamp: Mutex<FilesStamp>,
+}
+
+impl ServerTls {
+    /// Loads the certificates and key; `alpn_protocols` are offered in order of preference.
+    ///
+    /// # Errors
+    /// Returns an error if a file cannot be read or does not hold valid PEM data.
+    pub fn load(config: ServerTlsConfig, alpn_protocols: &[&[u8]]) -> anyhow::Result<Arc<Self>> {
+        let alpn_protocols: Vec<Vec<u8>> = alpn_protocols.iter().map(|p| p.to_vec()).collect();
+        let stamp = files_stamp(&config);
+        let server_config = build_server_config(&config, &alpn_protocols)?;
+        tracing::info!(
+            cert = %config.cert_path.display(),
+            mutual = config.client_ca_path.is_some(),
+            "Server TLS loaded"
+        );
+        Ok(Arc::new(Self {
+            config,
+            alpn_protocols,
+            current: ArcSwap::from_pointee(server_config),
+            stamp: Mutex::new(stamp),
+        }))
+    }
+
+    /// Whether clients are verified against a CA bundle.
+    #[must_use]
+    pub fn is_mutual(&self) -> bool {
+        self.config.client_ca_path.is_some()
+    }
+
+    /// Reloads the files if any of them changed since the last (attempted) load.
+    ///
+    /// Returns `true` if new certificates are now in use. On error the previous
+    /// certificates stay active.
+    ///
+    /// # Errors
+    /// Returns an error if the changed files cannot be loaded.
+    pub fn reload_if_changed(&self) -> anyhow::Result<bool> {
+        let stamp = files_stamp(&self.config);
+        {
+            let mut last = self.stamp.lock();
+            if *last == stamp {
+                return Ok(false);
+            }
+            // Recorded up front so a broken file is reported once, not on every check
+            *last = stamp;
+        }
+        self.current.store(Arc::new(build_server_config(
+            &self.config,
+            &self.alpn_protocols,
+        )?));
+        tracing::info!(cert = %self.config.cert_path.display(), "Server TLS reloaded");
+        Ok(true)
+    }
+
+    /// Polls the files every `reload_interval` until `cancel` fires.
+    pub fn watch(self: &Arc<Self>, cancel: CancellationToken) -> tokio::task::JoinHandle<()> {
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(this.config.reload_interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    () = cancel.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if let Err(e) = this.reload_if_changed() {
+                            tracing::warn!(error = %e, "Server TLS reload failed; keeping previous certificates");
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    /// Accepts TCP connections and yields them with the peer address once the TLS
+    /// handshake completes.
+    ///
+    /// Handshakes run concurrently and are bounded by a timeout, so a slow or
+    /// misbehaving client cannot stall the listener. The accept loop stops when
+    /// the returned receiver is dropped.
+    pub fn accept_loop(
+        self: &Arc<Self>,
+        listener: TcpListener,
+    ) -> mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)> {
+        let (tx, rx) = mpsc::channel(ACCEPT_BACKLOG);
+        let this = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                let (stream, peer) = tokio::select! {
+                    () = tx.closed() => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok(conn) => conn,
+                        Err(e) => {
+                            tracing::warn!(error = %e, "TLS accept failed");
+                            tokio::time::sleep(Duration::from_millis(100)).await;
+                            continue;
+                        }
+                    },
+                };
+                _ = stream.set_nodelay(true);
+                let acceptor = TlsAcceptor::from(this.current.load_full());
+                let tx = tx.clone();
+                tokio::spawn(async move {
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(tls)) => {
+                            _ = tx.send((tls, peer)).await;
+                        }
+                        Ok(Err(e)) => tracing::debug!(%peer, error = %e, "TLS handshake failed"),
+                        Err(_) => tracing::debug!(%peer, "TLS handshake timed out"),
+                    }
+                });
+            }
+        });
+        rx
+    }
+
+    /// [`accept_loop`](Self::accept_loop) as a stream of connections, e.g. for tonic's
+    /// `Server::serve_with_incoming_shutdown`.
+    pub fn incoming(
+        self: &Arc<Self>,
+        listener: TcpListener,
+    ) -> impl Stream<Item = io::Result<TlsStream<TcpStream>>> + Send + 'static {
+        ReceiverStream::new(self.accept_loop(listener)).map(|(tls, _)| Ok(tls))
+    }
+}
+
+impl std::fmt::Debug for ServerTls {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerTls")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+fn files_stamp(config: &ServerTlsConfig) -> FilesStamp {
+    config
+        .files()
+        .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/// Reads all certificates of a PEM file.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or holds no certificate.
+pub fn load_certs(path: &Path) -> anyhow::Result<Vec<CertificateDer<'static>>> {
+    let certs = CertificateDer::pem_file_iter(path)
+        .and_then(Iterator::collect::<Result<Vec<_>, _>>)
+        .with_context(|| format!("failed to read certificates from '{}'", path.display()))?;
+    if certs.is_empty() {
+        anyhow::bail!("no certificates found in '{}'", path.display());
+    }
+    Ok(certs)
+}
+
+fn build_server_config(
+    config: &ServerTlsConfig,
+    alpn_protocols: &[Vec<u8>],
+) -> anyhow::Result<rustls::ServerConfig> {
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let certs = load_certs(&config.cert_path)?;
+    let key = PrivateKeyDer::from_pem_file(&config.key_path).with_context(|| {
+        format!(
+            "failed to read private key from '{}'",
+            config.key_path.display()
+        )
+    })?;
+
+    let builder = rustls::ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_safe_default_protocol_versions()?;
+    let builder = match &config.client_ca_path {
+        None => builder.with_no_client_auth(),
+        Some(ca_path) => {
+            let mut roots = RootCertStore::empty();
+            for cert in load_certs(ca_path)? {
+                roots.add(cert).with_context(|| {
+                    format!("invalid CA certificate in '{}'", ca_path.display())
+                })?;
+            }
+            let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider);
+            let verifier = match config.client_auth {
+                ClientAuth::Required => verifier,
+                ClientAuth::Optional => verifier.allow_unauthenticated(),
+            };
+            builder.with_client_cert_verifier(verifier.build()?)
+        }
+    };
+
+    let mut server_config = builder
+        .with_single_cert(certs, key)
+        .context("certificate does not match private key")?;
+    server_config.alpn_protocols = alpn_protocols.to_vec();
+    Ok(server_config)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsConnector;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/tls")
+            .join(name)
+    }
+
+    /// Echoes one chunk back on every accepted connection.
+    async fn start(config: ServerTlsConfig) -> SocketAddr {
+        let tls = ServerTls::load(config, &[b"h2"]).unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut connections = tls.accept_loop(listener);
+        tokio::spawn(async move {
+            while let Some((mut stream, _)) = connections.recv().await {
+                tokio::spawn(async move {
+                    let mut buf = [0; 16];
+                    if let Ok(n) = stream.read(&mut buf).await {
+                        _ = stream.write_all(&buf[..n]).await;
+                        _ = stream.shutdown().await;
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    async fn echo(addr: SocketAddr, client_cert: bool) -> io::Result<Vec<u8>> {
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(load_certs(&fixture("ca.crt")).unwrap().remove(0))
+            .unwrap();
+        let builder = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::aws_lc_rs::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots);
+        let mut config = if client_cert {
+            builder
+                .with_client_auth_cert(
+                    load_certs(&fixture("client.crt")).unwrap(),
+                    PrivateKeyDer::from_pem_file(fixture("client.key")).unwrap(),
+                )
+                .unwrap()
+        } else {
+            builder.with_no_client_auth()
+        };
+        config.alpn_protocols = vec![b"h2".to_vec()];
+
+        let stream = TcpStream::connect(addr).await?;
+        let mut tls = TlsConnector::from(Arc::new(config))
+            .connect("localhost".try_into().unwrap(), stream)
+            .await?;
+        assert_eq!(tls.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+        tls.write_all(b"ping").await?;
+        let mut buf = Vec::new();
+        tls.read_to_end(&mut buf).await?;
+        Ok(buf)
+    }
+
+    #[tokio::test]
+    async fn client_certificate_required() {
+        let config = ServerTlsConfig::new(fixture("server.crt"), fixture("server.key"))
+            .with_client_ca(fixture("ca.crt"), ClientAuth::Required);
+        let addr = start(config).await;
+
+        // TLS 1.3 reports a rejected client certificate on the first read
+        assert!(echo(addr, false).await.is_err());
+        assert_eq!(echo(addr, true).await.unwrap(), b"ping");
+    }
+
+    #[tokio::test]
+    async fn client_certificate_optional() {
+        let config = ServerTlsConfig::new(fixture("server.crt"), fixture("server.key"))
+            .with_client_ca(fixture("ca.crt"), ClientAuth::Optional);
+        let addr = start(config).await;
+
+        assert_eq!(echo(addr, false).await.unwrap(), b"ping");
+        assert_eq!(echo(addr, true).await.unwrap(), b"ping");
+    }
+
+    #[test]
+    fn rejects_mismatched_key() {
+        let config = ServerTlsConfig::new(fixture("server.crt"), fixture("client.key"));
+        let err = ServerTls::load(config, &[b"h2"]).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{err:#}");
+    }
+
+    #[test]
+    fn reload_only_when_files_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert = dir.path().join("tls.crt");
+        let key = dir.path().join("tls.key");
+        std::fs::copy(fixture("server.crt"), &cert).unwrap();
+        std::fs::copy(fixture("server.key"), &key).unwrap();
+
+        let tls = ServerTls::load(ServerTlsConfig::new(&cert, &key), &[b"h2"]).unwrap();
+        assert!(!tls.reload_if_changed().unwrap());
+
+        // A broken rotation keeps the previous certificates and is reported once
+        std::fs::write(&cert, "not a certificate").unwrap();
+        bump_mtime(&cert);
+        assert!(tls.reload_if_changed().is_err());
+        assert!(!tls.reload_if_changed().unwrap());
+
+        std::fs::copy(fixture("server-rotated.crt"), &cert).unwrap();
+        std::fs::copy(fixture("server-rotated.key"), &key).unwrap();
+        bump_mtime(&cert);
+        assert!(tls.reload_if_changed().unwrap());
+    }
+
+    fn bump_mtime(path: &Path) {
+        let file = std::fs::File::options().write(true).open(path).unwrap();
+        let later = SystemTime::now() + Duration::from_secs(5);
+        file.set_modified(later).unwrap();
+    }
+}
@@ -4,6 +4,7 @@
 //! - Configurable connect and RPC timeouts
 //! - HTTP/2 keepalive settings for connection health
 //! - Tracing spans around connection establishment
+//! - TLS and mutual TLS (see [`crate::tls`])
 //!
 //! **Note:** This module is responsible only for transport-level configuration.
 //! For RPC-level retry logic with exponential backoff, see the [`crate::rpc_retry`] module.
@@ -12,6 +13,8 @@ use std::time::Duration;
 use tonic::transport::{Channel, Endpoint};
 use tracing::Instrument;
 
+use crate::tls::GrpcClientTlsConfig;
+
 fn duration_to_i64_ms(duration: Duration) -> i64 {
     i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
 }
@@ -57,6 +60,9 @@ pub struct GrpcClientConfig {
 
     /// Enable OpenTelemetry tracing.
     pub enable_tracing: bool,
+
+    /// TLS settings; plaintext if `None`. Use an `https://` URI with TLS.
+    pub tls: Option<GrpcClientTlsConfig>,
 }
 
 impl Default for GrpcClientConfig {
@@ -70,6 +76,7 @@ impl Default for GrpcClientConfig {
             service_name: "grpc_client",
             enable_metrics: true,
             enable_tracing: true,
+            tls: None,
         }
     }
 }
@@ -114,6 +121,15 @@ impl GrpcClientConfig {
         self.enable_tracing = false;
         self
     }
+
+    /// Connect over TLS.
+    ///
+    /// The certificate files are read on every connect, so reconnecting picks up
+    /// rotated client certificates.
+    pub fn with_tls(mut self, tls: GrpcClientTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
 }
 
 /// Build a tonic `Endpoint` with timeouts and keepalive settings.
@@ -125,11 +141,9 @@ impl GrpcClientConfig {
 /// - HTTP/2 keepalive interval (30 seconds)
 /// - Keepalive timeout (10 seconds)
 /// - Keep alive while idle
-fn build_endpoint(
-    uri: String,
-    cfg: &GrpcClientConfig,
-) -> Result<Endpoint, tonic::transport::Error> {
-    let endpoint = Endpoint::from_shared(uri)?
+/// - TLS, if configured
+fn build_endpoint(uri: String, cfg: &GrpcClientConfig) -> anyhow::Result<Endpoint> {
+    let mut endpoint = Endpoint::from_shared(uri)?
         .connect_timeout(cfg.connect_timeout)
         .timeout(cfg.rpc_timeout)
         .tcp_keepalive(Some(Duration::from_secs(30)))
@@ -137,6 +151,10 @@ fn build_endpoint(
         .keep_alive_timeout(Duration::from_secs(10))
         .keep_alive_while_idle(true);
 
+    if let Some(tls) = &cfg.tls {
+        endpoint = endpoint.tls_config(tls.load()?)?;
+    }
+
     Ok(endpoint)
 }
 
@@ -1,6 +1,7 @@
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 pub mod client;
 pub mod rpc_retry;
+pub mod tls;
 
 #[cfg(windows)]
 pub mod windows_named_pipe;
@@ -8,6 +9,11 @@ pub mod windows_named_pipe;
 #[cfg(windows)]
 pub use windows_named_pipe::{NamedPipeConnection, NamedPipeIncoming, create_named_pipe_incoming};
 
+pub use tls::{
+    ClientAuth, GRPC_ALPN_PROTOCOLS, GrpcClientTlsConfig, PeerIdentity, ServerTls, ServerTlsConfig,
+    peer_identity,
+};
+
 pub const SECCTX_METADATA_KEY: &str = "x-secctx-bin";
 
 use modkit_security::{SecurityContext, decode_bin, encode_bin};
@@ -0,0 +1,215 @@
+//! TLS and mutual TLS for gRPC servers and clients.
+//!
+//! Server side: [`ServerTls`] (from `modkit-tls`, loaded with the `h2` ALPN protocol) turns
+//! a `TcpListener` into a stream of TLS connections ([`ServerTls::incoming`]) for
+//! `Server::serve_with_incoming_shutdown` and picks up rotated certificates.
+//!
+//! Client side: [`GrpcClientTlsConfig`] is set on
+//! [`GrpcClientConfig`](crate::client::GrpcClientConfig) and read on every connect, so
+//! a reconnect picks up rotated client certificates.
+//!
+//! Services read the authenticated client certificate with [`peer_identity`].
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use rustls_pki_types::CertificateDer;
+use tonic::transport::{Certificate, ClientTlsConfig, Identity};
+use x509_parser::extensions::GeneralName;
+
+pub use modkit_tls::{ClientAuth, ServerTls, ServerTlsConfig};
+
+/// ALPN protocols offered by gRPC servers; pass to [`ServerTls::load`].
+pub const GRPC_ALPN_PROTOCOLS: &[&[u8]] = &[b"h2"];
+
+/// Env var with the CA bundle (PEM) `OoP` modules use to verify the host's gRPC server.
+pub const MODKIT_GRPC_TLS_CA_ENV: &str = "MODKIT_GRPC_TLS_CA";
+/// Env var with the client certificate chain (PEM) `OoP` modules present for mTLS.
+pub const MODKIT_GRPC_TLS_CERT_ENV: &str = "MODKIT_GRPC_TLS_CERT";
+/// Env var with the client private key (PEM) `OoP` modules present for mTLS.
+pub const MODKIT_GRPC_TLS_KEY_ENV: &str = "MODKIT_GRPC_TLS_KEY";
+/// Env var overriding the server name (SNI) `OoP` modules verify.
+pub const MODKIT_GRPC_TLS_SERVER_NAME_ENV: &str = "MODKIT_GRPC_TLS_SERVER_NAME";
+
+/// Client TLS settings for [`GrpcClientConfig`](crate::client::GrpcClientConfig).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct GrpcClientTlsConfig {
+    /// PEM CA bundle used to verify the server; the OS trust store if `None`.
+    pub ca_path: Option<PathBuf>,
+    /// PEM client certificate chain presented for mutual TLS.
+    pub cert_path: Option<PathBuf>,
+    /// PEM private key of the client certificate.
+    pub key_path: Option<PathBuf>,
+    /// Server name to verify and send as SNI; defaults to the URI host.
+    pub server_name: Option<String>,
+}
+
+impl GrpcClientTlsConfig {
+    /// TLS verified against the OS trust store, without a client certificate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Verify the server against the CA bundle at `path`.
+    pub fn with_ca(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_path = Some(path.into());
+        self
+    }
+
+    /// Present a client certificate (mutual TLS).
+    pub fn with_identity(
+        mut self,
+        cert_path: impl Into<PathBuf>,
+        key_path: impl Into<PathBuf>,
+    ) -> Self {
+        self.cert_path = Some(cert_path.into());
+        self.key_path = Some(key_path.into());
+        self
+    }
+
+    /// Verify and send `name` instead of the URI host.
+    pub fn with_server_name(mut self, name: impl Into<String>) -> Self {
+        self.server_name = Some(name.into());
+        self
+    }
+
+    /// Reads the `MODKIT_GRPC_TLS_*` environment variables; `None` if none is set.
+    #[must_use]
+    pub fn from_env() -> Option<Self> {
+        let var = |name| std::env::var_os(name).filter(|v| !v.is_empty());
+        let tls = Self {
+            ca_path: var(MODKIT_GRPC_TLS_CA_ENV).map(PathBuf::from),
+            cert_path: var(MODKIT_GRPC_TLS_CERT_ENV).map(PathBuf::from),
+            key_path: var(MODKIT_GRPC_TLS_KEY_ENV).map(PathBuf::from),
+            server_name: var(MODKIT_GRPC_TLS_SERVER_NAME_ENV).and_then(|v| v.into_string().ok()),
+        };
+        (tls != Self::default()).then_some(tls)
+    }
+
+    /// Reads the configured files into a tonic client TLS config.
+    pub(crate) fn load(&self) -> anyhow::Result<ClientTlsConfig> {
+        let read = |path: &Path| {
+            std::fs::read(path).with_context(|| format!("failed to read '{}'", path.display()))
+        };
+
+        let mut tls = match &self.ca_path {
+            Some(path) => ClientTlsConfig::new().ca_certificate(Certificate::from_pem(read(path)?)),
+            None => ClientTlsConfig::new().with_native_roots(),
+        };
+        match (&self.cert_path, &self.key_path) {
+            (Some(cert), Some(key)) => {
+                tls = tls.identity(Identity::from_pem(read(cert)?, read(key)?));
+            }
+            (None, None) => {}
+            _ => anyhow::bail!("client TLS needs both a certificate and a private key"),
+        }
+        if let Some(name) = &self.server_name {
+            tls = tls.domain_name(name.clone());
+        }
+        Ok(tls)
+    }
+}
+
+/// Identity of a client authenticated by its TLS certificate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerIdentity {
+    /// Subject common name (CN).
+    pub common_name: Option<String>,
+    /// DNS subject alternative names.
+    pub dns_names: Vec<String>,
+    /// URI subject alternative names (e.g. SPIFFE IDs).
+    pub uri_names: Vec<String>,
+    /// The leaf certificate, for checks beyond names.
+    pub certificate: CertificateDer<'static>,
+}
+
+impl PeerIdentity {
+    /// Parses the names out of a DER certificate; `None` if it cannot be parsed.
+    #[must_use]
+    pub fn from_certificate(cert: &CertificateDer<'_>) -> Option<Self> {
+        let (_, parsed) = x509_parser::parse_x509_certificate(cert).ok()?;
+        let common_name = parsed
+            .subject()
+            .iter_common_name()
+            .find_map(|cn| cn.as_str().ok())
+            .map(str::to_owned);
+        let (mut dns_names, mut uri_names) = (Vec::new(), Vec::new());
+        if let Some(san) = parsed.subject_alternative_name().ok()? {
+            for name in &san.value.general_names {
+                match name {
+                    GeneralName::DNSName(dns) => dns_names.push((*dns).to_owned()),
+                    GeneralName::URI(uri) => uri_names.push((*uri).to_owned()),
+                    _ => {}
+                }
+            }
+        }
+        Some(Self {
+            common_name,
+            dns_names,
+            uri_names,
+            certificate: cert.clone().into_owned(),
+        })
+    }
+
+    /// Whether `name` is the common name or one of the subject alternative names.
+    #[must_use]
+    pub fn has_name(&self, name: &str) -> bool {
+        self.common_name.as_deref() == Some(name)
+            || self.dns_names.iter().any(|n| n == name)
+            || self.uri_names.iter().any(|n| n == name)
+    }
+}
+
+/// Identity of the client certificate presented on the request's TLS connection.
+///
+/// `None` for plaintext connections and TLS clients without a certificate.
+pub fn peer_identity<T>(request: &tonic::Request<T>) -> Option<PeerIdentity> {
+    let certs = request.peer_certs()?;
+    PeerIdentity::from_certificate(certs.first()?)
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../modkit-tls/tests/fixtures/tls")
+            .join(name)
+    }
+
+    #[test]
+    fn peer_identity_reads_names() {
+        let cert = modkit_tls::load_certs(&fixture("client.crt"))
+            .unwrap()
+            .remove(0);
+        let identity = PeerIdentity::from_certificate(&cert).unwrap();
+
+        assert_eq!(identity.common_name.as_deref(), Some("calculator"));
+        assert_eq!(identity.dns_names, ["calculator.local"]);
+        assert_eq!(
+            identity.uri_names,
+            ["spiffe://cyberfabric/module/calculator"]
+        );
+        assert!(identity.has_name("spiffe://cyberfabric/module/calculator"));
+        assert!(!identity.has_name("grpc-hub"));
+    }
+
+    #[test]
+    fn client_tls_needs_key_with_cert() {
+        let tls = GrpcClientTlsConfig {
+            cert_path: Some(fixture("client.crt")),
+            ..GrpcClientTlsConfig::new().with_ca(fixture("ca.crt"))
+        };
+        assert!(tls.load().is_err());
+        assert!(
+            GrpcClientTlsConfig::new()
+                .with_ca(fixture("ca.crt"))
+                .with_identity(fixture("client.crt"), fixture("client.key"))
+                .load()
+                .is_ok()
+        );
+    }
+}
@@ -0,0 +1,108 @@
+//! Entity tags and conditional request helpers.
+//!
+//! The API gateway derives `ETag`s from the response body for operations declared with
+//! `with_etag()` and answers `If-None-Match` itself. Handlers that know a version of the
+//! resource (a revision counter, `updated_at`, ...) can send it as the `ETag` instead, and
+//! unsafe operations check `If-Match` against the current version with [`check_if_match`]
+//! before changing anything.
+//!
+//! ```ignore
+//! use modkit::api::http_cache::{check_if_match, entity_tag};
+//!
+//! async fn get_user(...) -> ApiResult<impl IntoResponse> {
+//!     let user = svc.get(id).await?;
+//!     Ok(([(header::ETAG, entity_tag(user.revision))], Json(user)))
+//! }
+//!
+//! async fn update_user(headers: HeaderMap, ...) -> ApiResult<impl IntoResponse> {
+//!     let user = svc.get(id).await?;
+//!     check_if_match(&headers, &entity_tag(user.revision))?;
+//!     ...
+//! }
+//! ```
+
+use std::fmt::Display;
+
+use axum::http::{HeaderMap, header};
+
+use super::problem::precondition_failed;
+use crate::result::ApiResult;
+
+/// Strong entity tag for a resource version, e.g. `"42"`.
+pub fn entity_tag(version: impl Display) -> String {
+    format!("\"{version}\"")
+}
+
+/// Whether an `If-Match`/`If-None-Match` header value matches `etag`.
+///
+/// The value is `*` or a comma-separated list of entity tags. With `weak` set, tags match
+/// regardless of their `W/` prefix (weak comparison); otherwise only identical strong tags
+/// match (strong comparison).
+#[must_use]
+pub fn etag_matches(header_value: &str, etag: &str, weak: bool) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_owned();
+    let is_weak = |tag: &str| tag.trim().starts_with("W/");
+    header_value.split(',').map(str::trim).any(|candidate| {
+        candidate == "*"
+            || if weak {
+                opaque(candidate) == opaque(etag)
+            } else {
+                !is_weak(candidate) && !is_weak(etag) && candidate == etag.trim()
+            }
+    })
+}
+
+/// Checks an `If-Match` precondition against the current entity tag of the resource.
+///
+/// Requests without `If-Match` pass.
+///
+/// # Errors
+/// Returns a `412 Precondition Failed` problem if `If-Match` does not match `current`.
+#[allow(clippy::result_large_err)]
+pub fn check_if_match(headers: &HeaderMap, current: &str) -> ApiResult {
+    match headers.get(header::IF_MATCH).map(|v| v.to_str()) {
+        None => Ok(()),
+        Some(Ok(value)) if etag_matches(value, current, false) => Ok(()),
+        Some(_) => Err(precondition_failed(
+            "The resource was modified; If-Match does not match its current ETag",
+        )),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use axum::http::{HeaderValue, StatusCode};
+
+    #[test]
+    fn entity_tag_is_quoted() {
+        assert_eq!(entity_tag(42), "\"42\"");
+    }
+
+    #[test]
+    fn weak_and_strong_comparison() {
+        assert!(etag_matches("\"a\"", "\"a\"", false));
+        assert!(etag_matches("\"x\", \"a\"", "\"a\"", false));
+        assert!(etag_matches("*", "\"a\"", false));
+        assert!(!etag_matches("\"b\"", "\"a\"", false));
+        assert!(!etag_matches("W/\"a\"", "\"a\"", false));
+        assert!(!etag_matches("\"a\"", "W/\"a\"", false));
+
+        assert!(etag_matches("W/\"a\"", "\"a\"", true));
+        assert!(etag_matches("\"a\"", "W/\"a\"", true));
+        assert!(!etag_matches("W/\"b\"", "W/\"a\"", true));
+    }
+
+    #[test]
+    fn if_match_precondition() {
+        let mut headers = HeaderMap::new();
+        assert!(check_if_match(&headers, "\"1\"").is_ok());
+
+        headers.insert(header::IF_MATCH, HeaderValue::from_static("\"1\""));
+        assert!(check_if_match(&headers, "\"1\"").is_ok());
+
+        let problem = check_if_match(&headers, "\"2\"").unwrap_err();
+        assert_eq!(problem.status, StatusCode::PRECONDITION_FAILED);
+    }
+}
@@ -6,6 +6,7 @@
 
 pub mod api_dto;
 pub mod error_layer;
+pub mod http_cache;
 pub mod odata;
 pub mod openapi_registry;
 pub mod operation_builder;
@@ -23,12 +24,12 @@ pub use error_layer::{
 };
 pub use openapi_registry::{OpenApiInfo, OpenApiRegistry, OpenApiRegistryImpl, ensure_schema};
 pub use operation_builder::{
-    Missing, OperationBuilder, OperationSpec, ParamLocation, ParamSpec, Present, RateLimitSpec,
-    ResponseSpec, state,
+    AuditSpec, DeprecationSpec, HttpCacheSpec, Missing, OperationBuilder, OperationSpec,
+    ParamLocation, ParamSpec, Present, RateLimitSpec, ResponseSpec, state,
 };
 pub use problem::{
     APPLICATION_PROBLEM_JSON, Problem, ValidationError, bad_request, conflict, internal_error,
-    not_found,
+    not_found, precondition_failed,
 };
 pub use select::{apply_select, page_to_projected_json, project_json};
 pub use trace_layer::{WithRequestContext, WithTraceContext};
@@ -1,6 +1,9 @@
 use axum::extract::{FromRequestParts, Query};
 use axum::http::request::Parts;
-use modkit_odata::{CursorV1, Error as ODataError, ODataOrderBy, OrderKey, SortDir};
+use modkit_odata::{
+    Aggregate, AggregateMethod, Apply, CursorV1, Error as ODataError, ExpandItem, ODataOrderBy,
+    OrderKey, SortDir,
+};
 use serde::Deserialize;
 
 // Re-export types from modkit-odata for convenience and better DX
@@ -19,6 +22,12 @@ pub struct ODataParams {
     pub orderby: Option<String>,
     #[serde(rename = "$select")]
     pub select: Option<String>,
+    #[serde(rename = "$count")]
+    pub count: Option<String>,
+    #[serde(rename = "$expand")]
+    pub expand: Option<String>,
+    #[serde(rename = "$apply")]
+    pub apply: Option<String>,
     pub limit: Option<u64>,
     pub cursor: Option<String>,
 }
@@ -29,6 +38,12 @@ pub const MAX_ORDERBY_LEN: usize = 1024;
 pub const MAX_ORDER_FIELDS: usize = 10;
 pub const MAX_SELECT_LEN: usize = 2048;
 pub const MAX_SELECT_FIELDS: usize = 100;
+pub const MAX_EXPAND_LEN: usize = 4 * 1024;
+pub const MAX_EXPAND_DEPTH: usize = 5;
+pub const MAX_EXPAND_ITEMS: usize = 20;
+pub const MAX_APPLY_LEN: usize = 4 * 1024;
+pub const MAX_GROUPBY_FIELDS: usize = 10;
+pub const MAX_AGGREGATES: usize = 20;
 
 /// Parse $select string into a list of field names.
 /// Format: "field1, field2, field3, ..."
@@ -76,6 +91,20 @@ pub fn parse_select(raw: &str) -> Result<Vec<String>, crate::api::problem::Probl
     Ok(fields)
 }
 
+/// Parse $count value.
+/// Format: "true" | "false" (case-insensitive).
+///
+/// # Errors
+/// Returns a `Problem` if the value is not a boolean literal.
+#[allow(clippy::result_large_err)]
+pub fn parse_count(raw: &str) -> Result<bool, crate::api::problem::Problem> {
+    match raw.trim() {
+        v if v.eq_ignore_ascii_case("true") => Ok(true),
+        v if v.eq_ignore_ascii_case("false") => Ok(false),
+        _ => Err(crate::api::bad_request("$count must be 'true' or 'false'")),
+    }
+}
+
 /// Parse $orderby string into `ODataOrderBy`.
 /// Format: "field1 [asc|desc], field2 [asc|desc], ..."
 /// Default direction is asc if not specified.
@@ -134,8 +163,327 @@ pub fn parse_orderby(raw: &str) -> Result<ODataOrderBy, modkit_odata::Error> {
     Ok(ODataOrderBy(keys))
 }
 
+/// Parse $expand string into a list of navigation properties.
+/// Format: "nav1, nav2($select=a,b;$filter=expr;$expand=nav3)"
+/// Names are case-insensitive; nested options are separated by `;`.
+///
+/// # Errors
+/// Returns `modkit_odata::Error::InvalidExpand` if the expand string is invalid,
+/// or `modkit_odata::Error::InvalidFilter` if a nested `$filter` cannot be parsed.
+pub fn parse_expand(raw: &str) -> Result<Vec<ExpandItem>, modkit_odata::Error> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    if raw.len() > MAX_EXPAND_LEN {
+        return Err(ODataError::InvalidExpand("expand too long".into()));
+    }
+
+    let mut total = 0;
+    parse_expand_items(raw, 1, &mut total)
+}
+
+fn parse_expand_items(
+    raw: &str,
+    depth: usize,
+    total: &mut usize,
+) -> Result<Vec<ExpandItem>, modkit_odata::Error> {
+    if depth > MAX_EXPAND_DEPTH {
+        return Err(ODataError::InvalidExpand("expand nested too deeply".into()));
+    }
+
+    let mut items: Vec<ExpandItem> = Vec::new();
+    for part in split_expand(raw, ',')? {
+        let part = part.trim();
+        if part.is_empty() {
+            continue;
+        }
+
+        *total += 1;
+        if *total > MAX_EXPAND_ITEMS {
+            return Err(ODataError::InvalidExpand(
+                "too many expanded properties".into(),
+            ));
+        }
+
+        let (name, options) = match part.find('(') {
+            Some(open) => {
+                let Some(inner) = part[open + 1..].strip_suffix(')') else {
+                    return Err(ODataError::InvalidExpand(format!(
+                        "invalid expand clause: {part}"
+                    )));
+                };
+                (part[..open].trim(), Some(inner))
+            }
+            None => (part, None),
+        };
+
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(ODataError::InvalidExpand(format!(
+                "invalid navigation property: {name}"
+            )));
+        }
+
+        let mut item = ExpandItem::new(name);
+        if items.iter().any(|i| i.name == item.name) {
+            return Err(ODataError::InvalidExpand(format!(
+                "duplicate navigation property: {}",
+                item.name
+            )));
+        }
+
+        for option in options
+            .map(|o| split_expand(o, ';'))
+            .transpose()?
+            .unwrap_or_default()
+        {
+            let option = option.trim();
+            if option.is_empty() {
+                continue;
+            }
+            let Some((key, value)) = option.split_once('=') else {
+                return Err(ODataError::InvalidExpand(format!(
+                    "invalid expand option: {option}"
+                )));
+            };
+            let value = value.trim();
+            match key.trim() {
+                "$select" if item.select.is_none() => {
+                    let fields: Vec<String> = value
+                        .split(',')
+                        .map(|f| f.trim().to_lowercase())
+                        .filter(|f| !f.is_empty())
+                        .collect();
+                    if fields.is_empty() || fields.len() > MAX_SELECT_FIELDS {
+                        return Err(ODataError::InvalidExpand(format!(
+                            "invalid $select for {}",
+                            item.name
+                        )));
+                    }
+                    item = item.with_select(fields);
+                }
+                "$filter" if item.filter.is_none() => {
+                    let parsed = modkit_odata::parse_filter_string(value)?;
+                    if parsed.node_count() > MAX_NODES {
+                        return Err(ODataError::InvalidFilter("filter too complex".into()));
+                    }
+                    item = item.with_filter(parsed.into_expr());
+                }
+                "$expand" if item.expand.is_empty() => {
+                    item = item.with_expand(parse_expand_items(value, depth + 1, total)?);
+                }
+                other => {
+                    return Err(ODataError::InvalidExpand(format!(
+                        "unsupported or repeated expand option: {other}"
+                    )));
+                }
+            }
+        }
+
+        items.push(item);
+    }
+
+    Ok(items)
+}
+
+/// Split on `sep` outside of parentheses and single-quoted string literals.
+///
+/// Returns `None` on unbalanced parentheses or quotes.
+fn split_top_level(raw: &str, sep: char) -> Option<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_quotes = false;
+    let mut start = 0;
+
+    for (i, c) in raw.char_indices() {
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.checked_sub(1)?,
+            c if c == sep && !in_quotes && depth == 0 => {
+                parts.push(&raw[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    if depth != 0 || in_quotes {
+        return None;
+    }
+    parts.push(&raw[start..]);
+    Some(parts)
+}
+
+fn split_expand(raw: &str, sep: char) -> Result<Vec<&str>, modkit_odata::Error> {
+    split_top_level(raw, sep)
+        .ok_or_else(|| ODataError::InvalidExpand("unbalanced parentheses or quotes".into()))
+}
+
+/// Parse `$apply` into filter, grouping and aggregate transformations.
+///
+/// Supported form: `filter(expr)` steps separated by `/`, optionally followed by one
+/// `groupby((p1,p2)[,aggregate(...)])` or `aggregate(...)` step. Aggregates are
+/// `prop with sum|min|max|average|countdistinct as alias` or `$count as alias`.
+///
+/// # Errors
+/// Returns `modkit_odata::Error::InvalidApply` if the apply string is invalid,
+/// or `modkit_odata::Error::InvalidFilter` if a `filter(...)` step cannot be parsed.
+pub fn parse_apply(raw: &str) -> Result<Apply, modkit_odata::Error> {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return Err(ODataError::InvalidApply("apply cannot be empty".into()));
+    }
+    if raw.len() > MAX_APPLY_LEN {
+        return Err(ODataError::InvalidApply("apply too long".into()));
+    }
+
+    let mut apply = Apply::new();
+    let mut grouped = false;
+    for step in split_apply(raw, '/')? {
+        let step = step.trim();
+        if grouped {
+            return Err(ODataError::InvalidApply(format!(
+                "no transformation allowed after groupby/aggregate: {step}"
+            )));
+        }
+
+        let (name, args) = apply_call(step)?;
+        match name.to_ascii_lowercase().as_str() {
+            "filter" => {
+                let parsed = modkit_odata::parse_filter_string(args)?;
+                if parsed.node_count() > MAX_NODES {
+                    return Err(ODataError::InvalidFilter("filter too complex".into()));
+                }
+                apply = apply.with_filter(parsed.into_expr());
+            }
+            "groupby" => {
+                let parts = split_apply(args, ',')?;
+                let Some(props) = parts[0]
+                    .trim()
+                    .strip_prefix('(')
+                    .and_then(|p| p.strip_suffix(')'))
+                else {
+                    return Err(ODataError::InvalidApply(format!(
+                        "groupby properties must be parenthesized: {args}"
+                    )));
+                };
+                let props: Vec<String> =
+                    props.split(',').map(|p| p.trim().to_lowercase()).collect();
+                if props.len() > MAX_GROUPBY_FIELDS {
+                    return Err(ODataError::InvalidApply(
+                        "too many groupby properties".into(),
+                    ));
+                }
+                for (i, prop) in props.iter().enumerate() {
+                    ensure_apply_name(prop)?;
+                    if props[..i].contains(prop) {
+                        return Err(ODataError::InvalidApply(format!(
+                            "duplicate groupby property: {prop}"
+                        )));
+                    }
+                }
+                apply = apply.with_group_by(props);
+
+                match &parts[1..] {
+                    [] => {}
+                    [nested] => {
+                        let (name, args) = apply_call(nested.trim())?;
+                        if !name.eq_ignore_ascii_case("aggregate") {
+                            return Err(ODataError::InvalidApply(format!(
+                                "unsupported groupby transformation: {name}"
+                            )));
+                        }
+                        apply = parse_aggregates(apply, args)?;
+                    }
+                    _ => {
+                        return Err(ODataError::InvalidApply(
+                            "groupby takes properties and one aggregate()".into(),
+                        ));
+                    }
+                }
+                grouped = true;
+            }
+            "aggregate" => {
+                apply = parse_aggregates(apply, args)?;
+                grouped = true;
+            }
+            other => {
+                return Err(ODataError::InvalidApply(format!(
+                    "unsupported transformation: {other}"
+                )));
+            }
+        }
+    }
+
+    if !grouped {
+        return Err(ODataError::InvalidApply(
+            "groupby or aggregate transformation is required".into(),
+        ));
+    }
+    Ok(apply)
+}
+
+fn split_apply(raw: &str, sep: char) -> Result<Vec<&str>, modkit_odata::Error> {
+    split_top_level(raw, sep)
+        .ok_or_else(|| ODataError::InvalidApply("unbalanced parentheses or quotes".into()))
+}
+
+/// Split `name(args)` into its name and argument text.
+fn apply_call(step: &str) -> Result<(&str, &str), modkit_odata::Error> {
+    step.split_once('(')
+        .and_then(|(name, rest)| Some((name.trim(), rest.strip_suffix(')')?)))
+        .ok_or_else(|| ODataError::InvalidApply(format!("invalid transformation: {step}")))
+}
+
+fn ensure_apply_name(name: &str) -> Result<(), modkit_odata::Error> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(ODataError::InvalidApply(format!("invalid name: {name}")));
+    }
+    Ok(())
+}
+
+fn parse_aggregates(mut apply: Apply, raw: &str) -> Result<Apply, modkit_odata::Error> {
+    for item in split_apply(raw, ',')? {
+        let tokens: Vec<&str> = item.split_whitespace().collect();
+        let aggregate = match tokens.as_slice() {
+            ["$count", "as", alias] => Aggregate::count(*alias),
+            [field, "with", method, "as", alias] => {
+                ensure_apply_name(field)?;
+                let method = AggregateMethod::parse(method).ok_or_else(|| {
+                    ODataError::InvalidApply(format!("unknown aggregation method: {method}"))
+                })?;
+                Aggregate::new(*field, method, *alias)
+            }
+            _ => {
+                return Err(ODataError::InvalidApply(format!(
+                    "invalid aggregate expression: {}",
+                    item.trim()
+                )));
+            }
+        };
+
+        ensure_apply_name(&aggregate.alias)?;
+        if apply.output_names().contains(&aggregate.alias.as_str()) {
+            return Err(ODataError::InvalidApply(format!(
+                "duplicate result name: {}",
+                aggregate.alias
+            )));
+        }
+        apply = apply.with_aggregate(aggregate);
+    }
+
+    if apply.aggregates.is_empty() || apply.aggregates.len() > MAX_AGGREGATES {
+        return Err(ODataError::InvalidApply(format!(
+            "aggregate takes 1 to {MAX_AGGREGATES} expressions"
+        )));
+    }
+    Ok(apply)
+}
+
 /// Extract and validate full `OData` query from request parts.
-/// - Parses $filter, $orderby, limit, cursor
+/// - Parses $filter, $orderby, $select, $count, $expand, $apply, limit, cursor
 /// - Enforces budgets and validates formats
 /// - Returns unified `ODataQuery`
 ///
@@ -231,12 +579,36 @@ where
         query = query.with_limit(limit);
     }
 
-    // Parse select
+    // Parse expand
+    if let Some(raw_expand) = params.expand.as_ref() {
+        let items = parse_expand(raw_expand)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, "/", None))?;
+        query = query.with_expand(items);
+    }
+
+    // Parse select; expanded navigation properties are always kept in the projection
     if let Some(raw_select) = params.select.as_ref() {
-        let fields = parse_select(raw_select)?;
+        let mut fields = parse_select(raw_select)?;
+        for item in &query.expand {
+            if !fields.contains(&item.name) {
+                fields.push(item.name.clone());
+            }
+        }
         query = query.with_select(fields);
     }
 
+    // Parse count
+    if let Some(raw_count) = params.count.as_ref() {
+        query = query.with_count(parse_count(raw_count)?);
+    }
+
+    // Parse apply
+    if let Some(raw_apply) = params.apply.as_ref() {
+        let apply = parse_apply(raw_apply)
+            .map_err(|e| crate::api::odata::odata_error_to_problem(&e, "/", None))?;
+        query = query.with_apply(apply);
+    }
+
     Ok(query)
 }
 
@@ -129,6 +129,47 @@ mod tests {
         assert!(query.cursor.is_none());
     }
 
+    #[tokio::test]
+    async fn test_extract_odata_query_filter_functions() {
+        use modkit_odata::ast::Expr;
+
+        fn functions(e: &Expr, out: &mut Vec<String>) {
+            match e {
+                Expr::And(a, b) | Expr::Or(a, b) | Expr::Compare(a, _, b) => {
+                    functions(a, out);
+                    functions(b, out);
+                }
+                Expr::Function(name, args) => {
+                    out.push(name.clone());
+                    for a in args {
+                        functions(a, out);
+                    }
+                }
+                _ => {}
+            }
+        }
+        // tolower(name) eq 'bob' and year(created_at) ge 2024 and score add 5 gt 10 and created_at lt now()
+        let uri = "/?%24filter=tolower(name)%20eq%20%27bob%27%20and%20year(created_at)%20ge%202024\
+                   %20and%20score%20add%205%20gt%2010%20and%20created_at%20lt%20now()";
+
+        let request = Request::builder().uri(uri).body(()).unwrap();
+
+        let (mut parts, _body) = request.into_parts();
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+
+        let mut names = Vec::new();
+        functions(query.filter.as_deref().unwrap(), &mut names);
+        names.sort();
+        assert_eq!(names, ["add", "now", "tolower", "year"]);
+
+        // Arithmetic is only accepted in infix form.
+        let uri = "/?%24filter=add(score%2C%205)%20gt%2010";
+        let request = Request::builder().uri(uri).body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        assert!(extract_odata_query(&mut parts, &()).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_extract_odata_query_orderby_only() {
         let uri = "/?%24orderby=created_at%20desc%2C%20id%20asc";
@@ -227,6 +268,237 @@ mod tests {
         let _problem_response = result.unwrap_err();
     }
 
+    #[test]
+    fn test_parse_count() {
+        assert!(parse_count("true").unwrap());
+        assert!(parse_count(" TRUE ").unwrap());
+        assert!(!parse_count("false").unwrap());
+        assert!(parse_count("yes").is_err());
+        assert!(parse_count("").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_count() {
+        let request = Request::builder()
+            .uri("/?%24count=true&limit=5")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert!(query.count);
+        assert_eq!(query.limit, Some(5));
+
+        let request = Request::builder().uri("/").body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert!(!query.count);
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_invalid_count() {
+        let request = Request::builder().uri("/?%24count=1").body(()).unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let result = extract_odata_query(&mut parts, &()).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_expand_simple() {
+        let items = parse_expand("Addresses, city").unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].name, "addresses");
+        assert_eq!(items[1].name, "city");
+        assert!(items[0].select.is_none());
+        assert!(items[0].filter.is_none());
+
+        assert!(parse_expand("  ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_expand_nested_options() {
+        let items = parse_expand(
+            "addresses($select=street, city_id;$filter=street eq 'a;b(c)';$expand=city($select=name))",
+        )
+        .unwrap();
+        assert_eq!(items.len(), 1);
+
+        let addresses = &items[0];
+        assert_eq!(
+            addresses.select.as_deref(),
+            Some(&["street".to_owned(), "city_id".to_owned()][..])
+        );
+        assert!(addresses.filter.is_some());
+        assert_eq!(addresses.expand.len(), 1);
+        assert_eq!(addresses.expand[0].name, "city");
+        assert_eq!(
+            addresses.expand[0].select.as_deref(),
+            Some(&["name".to_owned()][..])
+        );
+        assert_eq!(addresses.depth(), 2);
+    }
+
+    #[test]
+    fn test_parse_expand_invalid() {
+        for raw in [
+            "addresses(",
+            "addresses($select=a",
+            "addresses)",
+            "addresses($top=1)",
+            "addresses($select=a;$select=b)",
+            "addresses, ADDRESSES",
+            "addr-esses",
+            "addresses($filter='unterminated)",
+        ] {
+            assert!(
+                matches!(
+                    parse_expand(raw),
+                    Err(modkit_odata::Error::InvalidExpand(_))
+                ),
+                "expected InvalidExpand for {raw}"
+            );
+        }
+
+        assert!(matches!(
+            parse_expand("addresses($filter=invalid syntax here)"),
+            Err(modkit_odata::Error::InvalidFilter(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_expand_limits() {
+        let too_long = "a".repeat(MAX_EXPAND_LEN + 1);
+        assert!(parse_expand(&too_long).is_err());
+
+        let too_deep = (0..=MAX_EXPAND_DEPTH).fold(String::from("leaf"), |acc, i| {
+            format!("n{i}($expand={acc})")
+        });
+        assert!(matches!(
+            parse_expand(&too_deep),
+            Err(modkit_odata::Error::InvalidExpand(_))
+        ));
+
+        let too_many: Vec<String> = (0..=MAX_EXPAND_ITEMS).map(|i| format!("n{i}")).collect();
+        assert!(matches!(
+            parse_expand(&too_many.join(",")),
+            Err(modkit_odata::Error::InvalidExpand(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_expand_kept_in_select() {
+        let request = Request::builder()
+            .uri("/?%24select=id&%24expand=addresses(%24select%3Dstreet)")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        assert!(query.has_expand());
+        assert_eq!(query.expand[0].name, "addresses");
+        assert_eq!(
+            query.selected_fields(),
+            Some(&["id".to_owned(), "addresses".to_owned()][..])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_invalid_expand() {
+        let request = Request::builder()
+            .uri("/?%24expand=addresses(")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let problem = extract_odata_query(&mut parts, &()).await.unwrap_err();
+        assert_eq!(problem.status, http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[test]
+    fn test_parse_apply_groupby_aggregate() {
+        use modkit_odata::{Aggregate, AggregateMethod};
+
+        let apply = parse_apply(
+            "filter(status ne 'a/b')/filter(amount gt 0)/groupby((Status, region),\
+             aggregate(amount with sum as total, id with countdistinct as ids, $count as n))",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            apply.filter.as_deref(),
+            Some(modkit_odata::ast::Expr::And(_, _))
+        ));
+        assert_eq!(apply.group_by, ["status", "region"]);
+        assert_eq!(
+            apply.aggregates,
+            [
+                Aggregate::new("amount", AggregateMethod::Sum, "total"),
+                Aggregate::new("id", AggregateMethod::CountDistinct, "ids"),
+                Aggregate::count("n"),
+            ]
+        );
+
+        let apply = parse_apply("aggregate(amount with average as avg)").unwrap();
+        assert!(apply.group_by.is_empty());
+        assert_eq!(apply.aggregates.len(), 1);
+
+        let apply = parse_apply("groupby((status))").unwrap();
+        assert!(apply.aggregates.is_empty());
+    }
+
+    #[test]
+    fn test_parse_apply_invalid() {
+        for raw in [
+            "",
+            "filter(amount eq 1)",
+            "groupby(status)",
+            "groupby((status))/filter(amount eq 1)",
+            "groupby((status),aggregate(amount with median as m))",
+            "groupby((status),aggregate(amount as m))",
+            "groupby((status),aggregate(amount with sum as status))",
+            "groupby((status),aggregate($count as n, $count as n))",
+            "groupby((status,status))",
+            "groupby((status),topcount(1))",
+            "compute(a add 1 as b)",
+            "groupby((status)",
+        ] {
+            let err = parse_apply(raw).unwrap_err();
+            assert!(
+                matches!(err, modkit_odata::Error::InvalidApply(_)),
+                "{raw}: {err:?}"
+            );
+        }
+
+        assert!(matches!(
+            parse_apply("filter(amount eq)/aggregate($count as n)").unwrap_err(),
+            modkit_odata::Error::InvalidFilter(_)
+        ));
+        assert!(parse_apply(&format!("groupby(({}))", "a".repeat(MAX_APPLY_LEN))).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_odata_query_apply() {
+        let request = Request::builder()
+            .uri("/?%24apply=groupby((status)%2Caggregate(amount%20with%20sum%20as%20total))")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let query = extract_odata_query(&mut parts, &()).await.unwrap();
+        let apply = query.apply.unwrap();
+        assert_eq!(apply.output_names(), ["status", "total"]);
+
+        let request = Request::builder()
+            .uri("/?%24apply=groupby(status)")
+            .body(())
+            .unwrap();
+        let (mut parts, _body) = request.into_parts();
+
+        let problem = extract_odata_query(&mut parts, &()).await.unwrap_err();
+        assert_eq!(problem.status, http::StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
     #[tokio::test]
     async fn test_odata_extractor() {
         let uri = "/?%24filter=email%20eq%20%27test%40example.com%27&limit=10";
@@ -11,6 +11,7 @@ use std::sync::Arc;
 use utoipa::openapi::{
     OpenApi, OpenApiBuilder, Ref, RefOr, Required,
     content::ContentBuilder,
+    header::HeaderBuilder,
     info::InfoBuilder,
     path::{
         HttpMethod, OperationBuilder as UOperationBuilder, ParameterBuilder, ParameterIn,
@@ -27,6 +28,141 @@ use crate::api::{operation_builder, problem};
 /// Type alias for schema collections used in API operations.
 type SchemaCollection = Vec<(String, RefOr<Schema>)>;
 
+/// Responses of an operation, including the caching headers and `304` declared for it.
+fn build_responses(spec: &operation_builder::OperationSpec) -> utoipa::openapi::Responses {
+    let mut responses = ResponsesBuilder::new();
+    for r in &spec.responses {
+        let is_json_like = r.content_type == "application/json"
+            || r.content_type == problem::APPLICATION_PROBLEM_JSON
+            || r.content_type == "text/event-stream";
+        let mut resp = if is_json_like {
+            if let Some(name) = &r.schema_name {
+                // Manually build content to preserve the correct content type
+                let content = ContentBuilder::new()
+                    .schema(Some(RefOr::Ref(Ref::new(format!(
+                        "#/components/schemas/{name}"
+                    )))))
+                    .build();
+                ResponseBuilder::new()
+                    .description(&r.description)
+                    .content(r.content_type, content)
+                    .build()
+            } else {
+                let content = ContentBuilder::new()
+                    .schema(Some(Schema::Object(ObjectBuilder::new().build())))
+                    .build();
+                ResponseBuilder::new()
+                    .description(&r.description)
+                    .content(r.content_type, content)
+                    .build()
+            }
+        } else {
+            let schema = Schema::Object(
+                ObjectBuilder::new()
+                    .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
+                    .format(Some(SchemaFormat::Custom(r.content_type.into())))
+                    .build(),
+            );
+            let content = ContentBuilder::new().schema(Some(schema)).build();
+            ResponseBuilder::new()
+                .description(&r.description)
+                .content(r.content_type, content)
+                .build()
+        };
+        if let Some(cache) = &spec.http_cache
+            && (200..300).contains(&r.status)
+        {
+            add_cache_headers(&mut resp, cache);
+        }
+        if let Some(deprecation) = &spec.deprecation {
+            add_deprecation_headers(&mut resp, deprecation);
+        }
+        responses = responses.response(r.status.to_string(), resp);
+    }
+    if spec.http_cache.as_ref().is_some_and(|c| c.etag) && spec.method == http::Method::GET {
+        responses = responses.response(
+            "304",
+            ResponseBuilder::new().description("Not Modified").build(),
+        );
+    }
+    responses.build()
+}
+
+/// Document the `ETag` and `Cache-Control` headers the gateway adds to successful responses.
+fn add_cache_headers(
+    resp: &mut utoipa::openapi::Response,
+    cache: &operation_builder::HttpCacheSpec,
+) {
+    let string_schema = || {
+        Schema::Object(
+            ObjectBuilder::new()
+                .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
+                .build(),
+        )
+    };
+    if cache.etag {
+        resp.headers.insert(
+            "ETag".to_owned(),
+            HeaderBuilder::new()
+                .schema(string_schema())
+                .description(Some("Entity tag of the returned representation"))
+                .build(),
+        );
+    }
+    if let Some(cache_control) = &cache.cache_control {
+        resp.headers.insert(
+            "Cache-Control".to_owned(),
+            HeaderBuilder::new()
+                .schema(string_schema())
+                .description(Some(format!("Caching directives: `{cache_control}`")))
+                .build(),
+        );
+    }
+}
+
+/// Document the lifecycle headers the gateway adds to responses of deprecated operations.
+fn add_deprecation_headers(
+    resp: &mut utoipa::openapi::Response,
+    deprecation: &operation_builder::DeprecationSpec,
+) {
+    let string_schema = || {
+        Schema::Object(
+            ObjectBuilder::new()
+                .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
+                .build(),
+        )
+    };
+    if let Some(since) = deprecation.since {
+        resp.headers.insert(
+            "Deprecation".to_owned(),
+            HeaderBuilder::new()
+                .schema(string_schema())
+                .description(Some(format!("Deprecated since {}", since.to_rfc3339())))
+                .build(),
+        );
+    }
+    if let Some(sunset) = deprecation.sunset {
+        resp.headers.insert(
+            "Sunset".to_owned(),
+            HeaderBuilder::new()
+                .schema(string_schema())
+                .description(Some(format!("Removed after {}", sunset.to_rfc3339())))
+                .build(),
+        );
+    }
+    if deprecation.link.is_some() || deprecation.successor.is_some() {
+        resp.headers.insert(
+            "Link".to_owned(),
+            HeaderBuilder::new()
+                .schema(string_schema())
+                .description(Some(
+                    "Deprecation documentation and successor version".to_owned(),
+                ))
+                .build(),
+        );
+    }
+}
+
 /// `OpenAPI` document metadata (title, version, description)
 #[derive(Debug, Clone)]
 pub struct OpenApiInfo {
@@ -124,9 +260,21 @@ impl OpenApiRegistryImpl {
                 op = op.tag(tag.clone());
             }
 
+            if spec.deprecation.is_some() {
+                op = op.deprecated(Some(utoipa::openapi::Deprecated::True));
+            }
+
             // Vendor extensions
             let mut ext = utoipa::openapi::extensions::Extensions::default();
 
+            // Sunset
+            if let Some(sunset) = spec.deprecation.as_ref().and_then(|d| d.sunset) {
+                ext.insert(
+                    "x-sunset".to_owned(),
+                    serde_json::json!(sunset.to_rfc3339()),
+                );
+            }
+
             // Rate limit
             if let Some(rl) = spec.rate_limit.as_ref() {
                 ext.insert("x-rate-limit-rps".to_owned(), serde_json::json!(rl.rps));
@@ -148,6 +296,11 @@ impl OpenApiRegistryImpl {
             {
                 ext.insert("x-odata-orderby".to_owned(), value);
             }
+            if let Some(apply) = spec.vendor_extensions.x_odata_apply.as_ref()
+                && let Ok(value) = serde_json::to_value(apply)
+            {
+                ext.insert("x-odata-apply".to_owned(), value);
+            }
 
             if !ext.is_empty() {
                 op = op.extensions(Some(ext));
@@ -246,54 +399,13 @@ impl OpenApiRegistryImpl {
             }
 
             // Responses
-            let mut responses = ResponsesBuilder::new();
-            for r in &spec.responses {
-                let is_json_like = r.content_type == "application/json"
-                    || r.content_type == problem::APPLICATION_PROBLEM_JSON
-                    || r.content_type == "text/event-stream";
-                let resp = if is_json_like {
-                    if let Some(name) = &r.schema_name {
-                        // Manually build content to preserve the correct content type
-                        let content = ContentBuilder::new()
-                            .schema(Some(RefOr::Ref(Ref::new(format!(
-                                "#/components/schemas/{name}"
-                            )))))
-                            .build();
-                        ResponseBuilder::new()
-                            .description(&r.description)
-                            .content(r.content_type, content)
-                            .build()
-                    } else {
-                        let content = ContentBuilder::new()
-                            .schema(Some(Schema::Object(ObjectBuilder::new().build())))
-                            .build();
-                        ResponseBuilder::new()
-                            .description(&r.description)
-                            .content(r.content_type, content)
-                            .build()
-                    }
-                } else {
-                    let schema = Schema::Object(
-                        ObjectBuilder::new()
-                            .schema_type(SchemaType::Type(utoipa::openapi::schema::Type::String))
-                            .format(Some(SchemaFormat::Custom(r.content_type.into())))
-                            .build(),
-                    );
-                    let content = ContentBuilder::new().schema(Some(schema)).build();
-                    ResponseBuilder::new()
-                        .description(&r.description)
-                        .content(r.content_type, content)
-                        .build()
-                };
-                responses = responses.response(r.status.to_string(), resp);
-            }
-            op = op.responses(responses.build());
+            op = op.responses(build_responses(&spec));
 
             // Add security requirement if operation requires authentication
             if spec.authenticated {
                 let sec_req = utoipa::openapi::security::SecurityRequirement::new(
                     "bearerAuth",
-                    Vec::<String>::new(),
+                    spec.required_scopes.clone(),
                 );
                 op = op.security(sec_req);
             }
@@ -437,6 +549,11 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            required_scopes: Vec::new(),
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: None,
         };
 
         registry.register_operation(&spec);
@@ -500,6 +617,11 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            required_scopes: Vec::new(),
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: None,
         };
 
         registry.register_operation(&spec);
@@ -517,6 +639,102 @@ mod tests {
         assert_eq!(get_op.get("summary").unwrap(), "Get user by ID");
     }
 
+    #[test]
+    fn test_build_openapi_lists_required_scopes() {
+        let registry = OpenApiRegistryImpl::new();
+        let spec = OperationSpec {
+            method: Method::GET,
+            path: "/events/v1/events".to_owned(),
+            operation_id: Some("list_events".to_owned()),
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![ResponseSpec {
+                status: 200,
+                content_type: "application/json",
+                description: "Events".to_owned(),
+                schema_name: None,
+            }],
+            handler_id: "get_events".to_owned(),
+            authenticated: true,
+            is_public: false,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+            license_requirement: None,
+            required_scopes: vec!["read:events".to_owned(), "admin".to_owned()],
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: None,
+        };
+
+        registry.register_operation(&spec);
+        let doc = registry.build_openapi(&OpenApiInfo::default()).unwrap();
+        let json = serde_json::to_value(&doc).unwrap();
+
+        let security = json
+            .pointer("/paths/~1events~1v1~1events/get/security")
+            .unwrap();
+        assert_eq!(
+            security,
+            &serde_json::json!([{ "bearerAuth": ["read:events", "admin"] }])
+        );
+    }
+
+    #[test]
+    fn test_build_openapi_marks_deprecated_operations() {
+        use chrono::TimeZone;
+
+        let registry = OpenApiRegistryImpl::new();
+        let sunset = chrono::Utc.with_ymd_and_hms(2027, 6, 30, 0, 0, 0).unwrap();
+        let spec = OperationSpec {
+            method: Method::GET,
+            path: "/events/v1/events".to_owned(),
+            operation_id: Some("list_events".to_owned()),
+            summary: None,
+            description: None,
+            tags: vec![],
+            params: vec![],
+            request_body: None,
+            responses: vec![ResponseSpec {
+                status: 200,
+                content_type: "application/json",
+                description: "Events".to_owned(),
+                schema_name: None,
+            }],
+            handler_id: "get_events".to_owned(),
+            authenticated: true,
+            is_public: false,
+            rate_limit: None,
+            allowed_request_content_types: None,
+            vendor_extensions: VendorExtensions::default(),
+            license_requirement: None,
+            required_scopes: Vec::new(),
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: Some(operation_builder::DeprecationSpec {
+                since: Some(sunset - chrono::Duration::days(180)),
+                sunset: Some(sunset),
+                link: None,
+                successor: None,
+            }),
+        };
+
+        registry.register_operation(&spec);
+        let doc = registry.build_openapi(&OpenApiInfo::default()).unwrap();
+        let json = serde_json::to_value(&doc).unwrap();
+
+        let op = json.pointer("/paths/~1events~1v1~1events/get").unwrap();
+        assert_eq!(op["deprecated"], true);
+        assert_eq!(op["x-sunset"], "2027-06-30T00:00:00+00:00");
+        assert!(op.pointer("/responses/200/headers/Deprecation").is_some());
+        assert!(op.pointer("/responses/200/headers/Sunset").is_some());
+    }
+
     #[test]
     fn test_ensure_schema_raw() {
         let registry = OpenApiRegistryImpl::new();
@@ -560,6 +778,11 @@ mod tests {
             allowed_request_content_types: Some(vec!["application/octet-stream"]),
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            required_scopes: Vec::new(),
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: None,
         };
 
         registry.register_operation(&spec);
@@ -639,6 +862,11 @@ mod tests {
             allowed_request_content_types: None,
             vendor_extensions: VendorExtensions::default(),
             license_requirement: None,
+            required_scopes: Vec::new(),
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: None,
         };
         spec.vendor_extensions.x_odata_filter = Some(filter);
         spec.vendor_extensions.x_odata_orderby = Some(order_by);
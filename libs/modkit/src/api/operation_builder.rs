@@ -13,6 +13,7 @@
 
 use crate::api::{api_dto, problem};
 use axum::{Router, handler::Handler, routing::MethodRouter};
+use chrono::{DateTime, Utc};
 use http::Method;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -185,6 +186,35 @@ pub struct ResponseSpec {
     pub schema_name: Option<String>,
 }
 
+/// HTTP caching metadata for an operation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct HttpCacheSpec {
+    /// Send an `ETag` (handler-supplied or derived from the body) and answer conditional requests
+    pub etag: bool,
+    /// `Cache-Control` directives for successful responses, e.g. `private, max-age=60`
+    pub cache_control: Option<String>,
+}
+
+/// Audit metadata for an operation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AuditSpec {
+    /// Additional field names masked in the audit record, on top of the gateway's redaction rules
+    pub redact: Vec<String>,
+}
+
+/// Deprecation and sunset metadata for an operation
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DeprecationSpec {
+    /// When the operation was deprecated (`Deprecation` header, omitted when unset)
+    pub since: Option<DateTime<Utc>>,
+    /// When the operation stops being served (`Sunset` header)
+    pub sunset: Option<DateTime<Utc>>,
+    /// Documentation of the deprecation (`Link` with `rel="deprecation"`)
+    pub link: Option<String>,
+    /// Path or URL of the replacement (`Link` with `rel="successor-version"`)
+    pub successor: Option<String>,
+}
+
 /// License requirement specification for an operation
 #[derive(Clone, Debug)]
 pub struct LicenseReqSpec {
@@ -221,6 +251,17 @@ pub struct OperationSpec {
     /// `OpenAPI` vendor extensions (x-*)
     pub vendor_extensions: VendorExtensions,
     pub license_requirement: Option<LicenseReqSpec>,
+    /// Token scopes of which the caller needs any to invoke this operation.
+    /// Empty means no scope requirement; checked by the gateway when scope checks are enabled.
+    pub required_scopes: Vec<String>,
+    /// Whether the gateway honours an `Idempotency-Key` request header for this operation.
+    pub idempotency_key: bool,
+    /// Optional `ETag`/`Cache-Control` metadata applied by the gateway to successful responses
+    pub http_cache: Option<HttpCacheSpec>,
+    /// Record every invocation in the gateway audit log when set
+    pub audit: Option<AuditSpec>,
+    /// Marks the operation deprecated; the gateway adds lifecycle headers to its responses
+    pub deprecation: Option<DeprecationSpec>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -229,6 +270,8 @@ pub struct VendorExtensions {
     pub x_odata_filter: Option<ODataPagination<BTreeMap<String, Vec<String>>>>,
     #[serde(rename = "x-odata-orderby", skip_serializing_if = "Option::is_none")]
     pub x_odata_orderby: Option<ODataPagination<Vec<String>>>,
+    #[serde(rename = "x-odata-apply", skip_serializing_if = "Option::is_none")]
+    pub x_odata_apply: Option<ODataPagination<BTreeMap<String, Vec<String>>>>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -272,6 +315,22 @@ pub trait OperationBuilderODataExt<S, H, R> {
     fn with_odata_orderby<T>(self) -> Self
     where
         T: modkit_odata::filter::FilterField;
+
+    /// Adds optional `$count` query parameter to `OpenAPI`.
+    #[must_use]
+    fn with_odata_count(self) -> Self;
+
+    /// Adds optional `$expand` query parameter to `OpenAPI`, listing the
+    /// navigation properties that can be expanded.
+    #[must_use]
+    fn with_odata_expand(self, relations: &[&str]) -> Self;
+
+    /// Adds optional `$apply` query parameter to `OpenAPI`, listing the fields that
+    /// can be grouped by and the aggregation methods allowed for each of them.
+    #[must_use]
+    fn with_odata_apply<T>(self, fields: &[T]) -> Self
+    where
+        T: modkit_odata::filter::FilterField;
 }
 
 impl<S, H, R, A, L> OperationBuilderODataExt<S, H, R> for OperationBuilder<H, R, S, A, L>
@@ -376,6 +435,81 @@ where
         self.spec.vendor_extensions.x_odata_orderby = Some(order_by);
         self
     }
+
+    fn with_odata_count(mut self) -> Self {
+        self.spec.params.push(ParamSpec {
+            name: "$count".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(
+                "OData v4 count: when true, page_info.total_count carries the number of matching items"
+                    .to_owned(),
+            ),
+            param_type: "boolean".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_expand(mut self, relations: &[&str]) -> Self {
+        let mut description = "OData v4 expand expression".to_owned();
+        if !relations.is_empty() {
+            description.push_str("\n\n**Expandable properties:** ");
+            description.push_str(&relations.join(", "));
+        }
+        self.spec.params.push(ParamSpec {
+            name: "$expand".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(description),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
+    fn with_odata_apply<T>(mut self, fields: &[T]) -> Self
+    where
+        T: modkit_odata::filter::FilterField,
+    {
+        use modkit_odata::filter::FieldKind;
+        use std::fmt::Write as _;
+
+        let mut apply = self
+            .spec
+            .vendor_extensions
+            .x_odata_apply
+            .unwrap_or_default();
+
+        let mut description = "OData v4 aggregation, e.g. \
+            `filter(...)/groupby((field),aggregate(field with sum as total, $count as count))`"
+            .to_owned();
+        for field in fields {
+            let name = field.name().to_owned();
+            let methods: Vec<String> = match field.kind() {
+                FieldKind::I64 | FieldKind::F64 | FieldKind::Decimal => {
+                    vec!["groupby", "sum", "average", "min", "max", "countdistinct"]
+                }
+                FieldKind::String | FieldKind::DateTimeUtc | FieldKind::Date | FieldKind::Time => {
+                    vec!["groupby", "min", "max", "countdistinct"]
+                }
+                FieldKind::Bool | FieldKind::Uuid => vec!["groupby", "countdistinct"],
+            }
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+            _ = write!(description, "\n- {}: {}", name, methods.join("|"));
+            apply.allowed_fields.insert(name, methods);
+        }
+        self.spec.params.push(ParamSpec {
+            name: "$apply".to_owned(),
+            location: ParamLocation::Query,
+            required: false,
+            description: Some(description),
+            param_type: "string".to_owned(),
+        });
+        self.spec.vendor_extensions.x_odata_apply = Some(apply);
+        self
+    }
 }
 
 // Re-export from openapi_registry for backward compatibility
@@ -437,6 +571,11 @@ impl<S> OperationBuilder<Missing, Missing, S, AuthNotSet> {
                 allowed_request_content_types: None,
                 vendor_extensions: VendorExtensions::default(),
                 license_requirement: None,
+                required_scopes: Vec::new(),
+                idempotency_key: false,
+                http_cache: None,
+                audit: None,
+                deprecation: None,
             },
             method_router: (), // no router in Missing state
             _has_handler: PhantomData,
@@ -509,6 +648,159 @@ where
         self
     }
 
+    /// Require the caller's token to carry any of the given scopes (OAuth-style, e.g. `read:events`).
+    ///
+    /// Tokens with the `*` scope always pass. The gateway rejects other requests with 403
+    /// before any PDP call when gateway scope checks are enabled; the scopes are also listed
+    /// on the operation's security requirement in `OpenAPI`.
+    pub fn require_scopes(mut self, scopes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.spec.required_scopes = scopes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Accept an optional `Idempotency-Key` request header.
+    ///
+    /// The gateway records the first response per caller, route and key and replays it
+    /// when the client retries with the same key and body. Intended for unsafe operations
+    /// such as `POST` that create resources.
+    pub fn with_idempotency_key(mut self) -> Self {
+        self.spec.idempotency_key = true;
+        self.spec.params.push(ParamSpec {
+            name: "Idempotency-Key".to_owned(),
+            location: ParamLocation::Header,
+            required: false,
+            description: Some(
+                "Client-chosen key that makes retries of this request safe".to_owned(),
+            ),
+            param_type: "string".to_owned(),
+        });
+        self
+    }
+
+    /// Send an `ETag` with successful responses and answer conditional requests.
+    ///
+    /// The gateway keeps an `ETag` set by the handler (see [`http_cache::entity_tag`]) or
+    /// derives a weak one from the body, and answers `GET`s with `304 Not Modified` for a
+    /// matching `If-None-Match` and `412 Precondition Failed` for a non-matching `If-Match`.
+    /// `If-Match` uses strong comparison, so on `GET`s only handler-supplied tags (or `*`)
+    /// can match it. Unsafe operations check `If-Match` themselves with
+    /// [`http_cache::check_if_match`]. Calling this more than once has no further effect.
+    ///
+    /// [`http_cache::entity_tag`]: crate::api::http_cache::entity_tag
+    /// [`http_cache::check_if_match`]: crate::api::http_cache::check_if_match
+    pub fn with_etag(mut self) -> Self {
+        self.spec
+            .http_cache
+            .get_or_insert_with(HttpCacheSpec::default)
+            .etag = true;
+        let headers: &[(&str, &str)] = if self.spec.method == Method::GET {
+            &[
+                (
+                    "If-None-Match",
+                    "Entity tags of cached representations; answered with 304 on match",
+                ),
+                (
+                    "If-Match",
+                    "Entity tags the representation must match (strong comparison: body-derived \
+                     weak ETags never match); answered with 412 otherwise",
+                ),
+            ]
+        } else {
+            &[(
+                "If-Match",
+                "Entity tag of the version being modified; answered with 412 if stale",
+            )]
+        };
+        for (name, description) in headers {
+            let declared =
+                self.spec.params.iter().any(|p| {
+                    p.location == ParamLocation::Header && p.name.eq_ignore_ascii_case(name)
+                });
+            if declared {
+                continue;
+            }
+            self.spec.params.push(ParamSpec {
+                name: (*name).to_owned(),
+                location: ParamLocation::Header,
+                required: false,
+                description: Some((*description).to_owned()),
+                param_type: "string".to_owned(),
+            });
+        }
+        self
+    }
+
+    /// Set `Cache-Control` directives for successful responses, e.g. `private, max-age=60`.
+    ///
+    /// A `Cache-Control` header set by the handler takes precedence.
+    pub fn cache_control(mut self, directives: impl Into<String>) -> Self {
+        self.spec
+            .http_cache
+            .get_or_insert_with(HttpCacheSpec::default)
+            .cache_control = Some(directives.into());
+        self
+    }
+
+    /// Record every invocation of this operation in the gateway audit log.
+    ///
+    /// The record names the caller, the operation and the resource ids from the path, along
+    /// with the outcome and latency. Intended for mutating and security-relevant operations.
+    pub fn auditable(mut self) -> Self {
+        self.spec.audit.get_or_insert_with(AuditSpec::default);
+        self
+    }
+
+    /// Mark the operation auditable and mask the given fields in its audit records.
+    ///
+    /// Field names are matched case-insensitively in path parameters and request bodies.
+    pub fn audit_redact(mut self, fields: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.spec
+            .audit
+            .get_or_insert_with(AuditSpec::default)
+            .redact
+            .extend(fields.into_iter().map(Into::into));
+        self
+    }
+
+    fn deprecation_mut(&mut self) -> &mut DeprecationSpec {
+        self.spec.deprecation.get_or_insert_default()
+    }
+
+    /// Mark the operation deprecated, without a date.
+    ///
+    /// The operation is flagged `deprecated` in `OpenAPI` and the gateway counts its use
+    /// per tenant. The methods below set the date and links sent as response headers;
+    /// without [`deprecated_since`](Self::deprecated_since) no `Deprecation` header is sent.
+    pub fn deprecated(mut self) -> Self {
+        self.deprecation_mut();
+        self
+    }
+
+    /// Mark the operation deprecated as of `since`, sent as the `Deprecation` header (RFC 9745).
+    pub fn deprecated_since(mut self, since: DateTime<Utc>) -> Self {
+        self.deprecation_mut().since = Some(since);
+        self
+    }
+
+    /// Mark the operation deprecated and announce its removal at `at`, sent as the
+    /// `Sunset` header (RFC 8594). The gateway can refuse to start once the date has passed.
+    pub fn sunset(mut self, at: DateTime<Utc>) -> Self {
+        self.deprecation_mut().sunset = Some(at);
+        self
+    }
+
+    /// Mark the operation deprecated and link to documentation about it.
+    pub fn deprecation_link(mut self, url: impl Into<String>) -> Self {
+        self.deprecation_mut().link = Some(url.into());
+        self
+    }
+
+    /// Mark the operation deprecated in favour of the operation at `path`, e.g. its `/v2/` sibling.
+    pub fn successor_version(mut self, path: impl Into<String>) -> Self {
+        self.deprecation_mut().successor = Some(path.into());
+        self
+    }
+
     /// Set the operation summary
     pub fn summary(mut self, text: impl Into<String>) -> Self {
         self.spec.summary = Some(text.into());
@@ -757,7 +1049,7 @@ where
     pub fn octet_stream_request(mut self, description: Option<&str>) -> Self {
         self.spec.request_body = Some(RequestBodySpec {
             content_type: "application/octet-stream",
-            description: description.map(ToString::to_string),
+            description: description.map(str::to_owned),
             schema: RequestBodySchema::Binary,
             required: true,
         });
@@ -1774,6 +2066,56 @@ mod tests {
         assert_eq!(builder.spec.path, "/tests/v1/simple");
     }
 
+    #[test]
+    fn odata_apply_documents_aggregatable_fields() {
+        use modkit_odata::filter::{FieldKind, FilterField};
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        enum OrderField {
+            Status,
+            Amount,
+            Id,
+        }
+        impl FilterField for OrderField {
+            const FIELDS: &'static [Self] = &[Self::Status, Self::Amount, Self::Id];
+            fn name(&self) -> &'static str {
+                match self {
+                    Self::Status => "status",
+                    Self::Amount => "amount",
+                    Self::Id => "id",
+                }
+            }
+            fn kind(&self) -> FieldKind {
+                match self {
+                    Self::Status => FieldKind::String,
+                    Self::Amount => FieldKind::Decimal,
+                    Self::Id => FieldKind::Uuid,
+                }
+            }
+        }
+
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/orders")
+            .with_odata_apply(&[OrderField::Status, OrderField::Amount]);
+
+        let param = builder.spec.params.last().unwrap();
+        assert_eq!(param.name, "$apply");
+        assert!(
+            param
+                .description
+                .as_deref()
+                .unwrap()
+                .contains("- amount: groupby|sum")
+        );
+
+        let apply = builder.spec.vendor_extensions.x_odata_apply.unwrap();
+        assert_eq!(
+            apply.allowed_fields.get("status").unwrap(),
+            &["groupby", "min", "max", "countdistinct"]
+        );
+        assert!(apply.allowed_fields["amount"].contains(&"average".to_owned()));
+        assert!(!apply.allowed_fields.contains_key("id"));
+    }
+
     #[test]
     fn standard_errors() {
         let registry = MockRegistry::new();
@@ -1826,6 +2168,105 @@ mod tests {
         assert!(!builder.spec.is_public);
     }
 
+    #[test]
+    fn require_scopes_sets_spec() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
+            .authenticated()
+            .require_scopes(["read:events", "write:events"]);
+
+        assert_eq!(
+            builder.spec.required_scopes,
+            vec!["read:events".to_owned(), "write:events".to_owned()]
+        );
+    }
+
+    #[test]
+    fn with_idempotency_key_sets_spec() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::post("/tests/v1/test")
+            .authenticated()
+            .with_idempotency_key();
+
+        assert!(builder.spec.idempotency_key);
+        let param = builder
+            .spec
+            .params
+            .iter()
+            .find(|p| p.name == "Idempotency-Key")
+            .expect("Idempotency-Key header param");
+        assert_eq!(param.location, ParamLocation::Header);
+        assert!(!param.required);
+    }
+
+    #[test]
+    fn auditable_sets_spec() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::delete("/tests/v1/test/{id}")
+            .authenticated()
+            .auditable()
+            .audit_redact(["ssn"]);
+
+        assert_eq!(
+            builder.spec.audit,
+            Some(AuditSpec {
+                redact: vec!["ssn".to_owned()],
+            })
+        );
+    }
+
+    #[test]
+    fn deprecation_sets_spec() {
+        let sunset = Utc::now() + chrono::Duration::days(90);
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
+            .public()
+            .sunset(sunset)
+            .successor_version("/tests/v2/test");
+
+        let spec = builder.spec.deprecation.unwrap();
+        assert_eq!(spec.sunset, Some(sunset));
+        assert_eq!(spec.successor.as_deref(), Some("/tests/v2/test"));
+        assert_eq!(spec.link, None);
+
+        let plain = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
+            .public()
+            .deprecated();
+        assert_eq!(
+            plain.spec.deprecation.unwrap().since,
+            None,
+            "no implicit date"
+        );
+
+        let since = Utc::now() - chrono::Duration::days(30);
+        let dated = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
+            .public()
+            .deprecated_since(since)
+            .deprecated();
+        assert_eq!(dated.spec.deprecation.unwrap().since, Some(since));
+    }
+
+    #[test]
+    fn with_etag_and_cache_control_set_spec() {
+        let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
+            .public()
+            .with_etag()
+            .cache_control("private, max-age=60")
+            .with_etag();
+
+        assert_eq!(
+            builder.spec.http_cache,
+            Some(HttpCacheSpec {
+                etag: true,
+                cache_control: Some("private, max-age=60".to_owned()),
+            })
+        );
+        let headers: Vec<_> = builder
+            .spec
+            .params
+            .iter()
+            .filter(|p| p.location == ParamLocation::Header)
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(headers, ["If-None-Match", "If-Match"]);
+    }
+
     #[test]
     fn require_license_features_none() {
         let builder = OperationBuilder::<Missing, Missing, ()>::get("/tests/v1/test")
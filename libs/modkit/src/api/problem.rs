@@ -20,6 +20,14 @@ pub fn conflict(detail: impl Into<String>) -> Problem {
     Problem::new(StatusCode::CONFLICT, "Conflict", detail)
 }
 
+pub fn precondition_failed(detail: impl Into<String>) -> Problem {
+    Problem::new(
+        StatusCode::PRECONDITION_FAILED,
+        "Precondition Failed",
+        detail,
+    )
+}
+
 pub fn internal_error(detail: impl Into<String>) -> Problem {
     Problem::new(
         StatusCode::INTERNAL_SERVER_ERROR,
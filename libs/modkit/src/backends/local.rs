@@ -2,19 +2,21 @@
 
 use anyhow::{Context, Result, bail};
 use async_trait::async_trait;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
 use std::path::Path;
 use std::process::Stdio;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::process::{Child, Command};
 use tokio::task::JoinHandle;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use super::log_forwarder::{StreamKind, spawn_stream_forwarder};
+use super::supervisor::{RestartDecision, RestartTracker};
 use super::{BackendKind, InstanceHandle, ModuleRuntimeBackend, OopModuleConfig};
+use crate::runtime::ModuleManager;
 
 /// Grace period before force-killing processes on shutdown
 const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
@@ -25,6 +27,9 @@ const INSTANCE_STOP_GRACE_PERIOD: Duration = Duration::from_secs(2);
 /// Timeout for waiting on forwarder tasks during shutdown
 const FORWARDER_DRAIN_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// How often tracked processes are checked for exits
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Send graceful termination signal to a child process.
 ///
 /// # Returns
@@ -153,20 +158,36 @@ struct LocalInstance {
     stdout_forwarder: Option<JoinHandle<()>>,
     /// Task handle for stderr log forwarder
     stderr_forwarder: Option<JoinHandle<()>>,
+    /// Config used to respawn the instance after it exits
+    config: OopModuleConfig,
 }
 
 /// Map key type for instances - uses Uuid directly
 type InstanceMap = HashMap<Uuid, LocalInstance>;
 
+/// State shared between the backend and its background tasks
+struct Shared {
+    instances: RwLock<InstanceMap>,
+    cancel: CancellationToken,
+    /// Restart history per module
+    restarts: Mutex<HashMap<String, RestartTracker>>,
+    /// Restarts waiting out their backoff, by the ID of the exited instance
+    pending_restarts: Mutex<HashMap<Uuid, CancellationToken>>,
+    /// Receives restart counts and crash-loop failures, if attached
+    manager: RwLock<Option<Arc<ModuleManager>>>,
+}
+
 /// Backend that spawns modules as local child processes and manages their lifecycle.
 ///
+/// Exited processes are restarted according to each module's [`RestartPolicy`](super::RestartPolicy)
+/// with exponential backoff; a module that exceeds its restart budget is marked failed.
+///
 /// When the cancellation token is triggered, the backend will:
 /// 1. Send termination signal to all processes (SIGTERM on Unix, `TerminateProcess` on Windows)
 /// 2. Wait up to 5 seconds for graceful shutdown
 /// 3. Force kill any remaining processes
 pub struct LocalProcessBackend {
-    instances: Arc<RwLock<InstanceMap>>,
-    cancel: CancellationToken,
+    shared: Arc<Shared>,
 }
 
 impl LocalProcessBackend {
@@ -175,26 +196,37 @@ impl LocalProcessBackend {
     /// When the token is cancelled, all spawned processes will be gracefully stopped.
     #[must_use]
     pub fn new(cancel: CancellationToken) -> Self {
-        let backend = Self {
-            instances: Arc::new(RwLock::new(HashMap::new())),
+        let shared = Arc::new(Shared {
+            instances: RwLock::new(HashMap::new()),
             cancel: cancel.clone(),
-        };
+            restarts: Mutex::new(HashMap::new()),
+            pending_restarts: Mutex::new(HashMap::new()),
+            manager: RwLock::new(None),
+        });
 
         // Spawn background task to handle shutdown
-        let instances = Arc::clone(&backend.instances);
+        let on_shutdown = Arc::clone(&shared);
         tokio::spawn(async move {
             cancel.cancelled().await;
             tracing::info!("LocalProcessBackend: shutdown signal received, stopping all processes");
-            Self::shutdown_all_instances(instances).await;
+            Self::shutdown_all_instances(&on_shutdown).await;
         });
 
-        backend
+        // Spawn background task that restarts exited processes
+        tokio::spawn(Self::supervise(Arc::clone(&shared)));
+
+        Self { shared }
+    }
+
+    /// Report restarts and crash loops to `manager` (surfaced by the module orchestrator).
+    pub fn set_module_manager(&self, manager: Arc<ModuleManager>) {
+        *self.shared.manager.write() = Some(manager);
     }
 
     /// Gracefully stop all tracked instances with timeout.
-    async fn shutdown_all_instances(instances: Arc<RwLock<InstanceMap>>) {
+    async fn shutdown_all_instances(shared: &Shared) {
         let mut all_instances: Vec<LocalInstance> = {
-            let mut guard = instances.write();
+            let mut guard = shared.instances.write();
             guard.drain().map(|(_, inst)| inst).collect()
         };
 
@@ -223,11 +255,149 @@ impl LocalProcessBackend {
 
         tracing::info!("All OoP module processes stopped");
     }
-}
 
-#[async_trait]
-impl ModuleRuntimeBackend for LocalProcessBackend {
-    async fn spawn_instance(&self, cfg: &OopModuleConfig) -> Result<InstanceHandle> {
+    /// Poll tracked processes for exits and apply each module's restart policy.
+    ///
+    /// Instances removed via `stop_instance` or shutdown are no longer tracked
+    /// and therefore never restarted.
+    async fn supervise(shared: Arc<Shared>) {
+        let mut ticker = tokio::time::interval(EXIT_POLL_INTERVAL);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                () = shared.cancel.cancelled() => break,
+                _ = ticker.tick() => {}
+            }
+            for (inst, exit, success) in Self::take_exited(&shared) {
+                tracing::debug!(
+                    module = %inst.handle.module,
+                    instance_id = %inst.handle.instance_id,
+                    pid = ?inst.handle.pid,
+                    "OoP module process exited"
+                );
+                Self::on_exit(&shared, inst.handle.instance_id, inst.config, exit, success);
+            }
+        }
+    }
+
+    /// Remove exited instances from the map, with their exit description and success flag.
+    fn take_exited(shared: &Shared) -> Vec<(LocalInstance, String, bool)> {
+        let mut instances = shared.instances.write();
+        let exited: Vec<_> = instances
+            .iter_mut()
+            .filter_map(|(id, inst)| match inst.child.try_wait() {
+                Ok(Some(status)) => Some((*id, status.to_string(), status.success())),
+                Ok(None) => None,
+                Err(e) => {
+                    tracing::warn!(
+                        module = %inst.handle.module,
+                        instance_id = %inst.handle.instance_id,
+                        error = %e,
+                        "Failed to check OoP module process status"
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        exited
+            .into_iter()
+            .filter_map(|(id, exit, success)| {
+                instances.remove(&id).map(|inst| (inst, exit, success))
+            })
+            .collect()
+    }
+
+    /// Apply the restart policy after an exit (or a failed respawn) of `config.name`.
+    ///
+    /// A pending restart is dropped if `stop_instance` is called for `instance_id` during
+    /// the backoff.
+    fn on_exit(
+        shared: &Arc<Shared>,
+        instance_id: Uuid,
+        config: OopModuleConfig,
+        exit: String,
+        success: bool,
+    ) {
+        if shared.cancel.is_cancelled() {
+            return;
+        }
+
+        let module = config.name.clone();
+        let decision = shared
+            .restarts
+            .lock()
+            .entry(module.clone())
+            .or_default()
+            .on_exit(&config.restart, success, Instant::now());
+        let manager = shared.manager.read().clone();
+
+        match decision {
+            RestartDecision::Stop => {
+                tracing::info!(module = %module, exit = %exit, "OoP module exited, not restarting");
+            }
+            RestartDecision::CrashLoop => {
+                tracing::error!(
+                    module = %module,
+                    exit = %exit,
+                    max_restarts = config.restart.max_restarts,
+                    window = ?config.restart.window,
+                    "OoP module is crash-looping, giving up"
+                );
+                if let Some(manager) = manager {
+                    manager.mark_failed(&module, exit);
+                }
+            }
+            RestartDecision::Restart { delay, attempt } => {
+                tracing::warn!(
+                    module = %module,
+                    exit = %exit,
+                    attempt,
+                    delay_ms = u64::try_from(delay.as_millis()).unwrap_or(u64::MAX),
+                    "OoP module exited, restarting"
+                );
+                if let Some(manager) = manager {
+                    manager.record_restart(&module, exit);
+                }
+
+                let stopped = shared.cancel.child_token();
+                shared
+                    .pending_restarts
+                    .lock()
+                    .insert(instance_id, stopped.clone());
+                let shared = Arc::clone(shared);
+                tokio::spawn(async move {
+                    tokio::select! {
+                        () = stopped.cancelled() => return,
+                        () = tokio::time::sleep(delay) => {}
+                    }
+                    // Stopped at the last moment: `stop_instance` already took the entry
+                    if shared
+                        .pending_restarts
+                        .lock()
+                        .remove(&instance_id)
+                        .is_none()
+                    {
+                        return;
+                    }
+                    if let Err(e) = Self::spawn_process(&shared, &config) {
+                        tracing::error!(module = %config.name, error = %e, "Failed to restart OoP module");
+                        // A failed respawn counts as another failed exit
+                        Self::on_exit(
+                            &shared,
+                            instance_id,
+                            config,
+                            format!("respawn failed: {e}"),
+                            false,
+                        );
+                    }
+                });
+            }
+        }
+    }
+
+    /// Spawn a process for `cfg` and start tracking it.
+    fn spawn_process(shared: &Shared, cfg: &OopModuleConfig) -> Result<InstanceHandle> {
         // Verify backend kind
         if cfg.backend != BackendKind::LocalProcess {
             bail!(
@@ -278,7 +448,7 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
 
         // Spawn log forwarder tasks for stdout/stderr with cancellation support
         let module_name = cfg.name.clone();
-        let cancel = self.cancel.clone();
+        let cancel = shared.cancel.clone();
         let stdout_forwarder = child.stdout.take().map(|stdout| {
             spawn_stream_forwarder(
                 stdout,
@@ -311,12 +481,12 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
             instance_id,
             backend: BackendKind::LocalProcess,
             pid,
-            created_at: std::time::Instant::now(),
+            created_at: Instant::now(),
         };
 
         // Store in instances map
         {
-            let mut instances = self.instances.write();
+            let mut instances = shared.instances.write();
             instances.insert(
                 instance_id,
                 LocalInstance {
@@ -324,16 +494,24 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
                     child,
                     stdout_forwarder,
                     stderr_forwarder,
+                    config: cfg.clone(),
                 },
             );
         }
 
         Ok(handle)
     }
+}
+
+#[async_trait]
+impl ModuleRuntimeBackend for LocalProcessBackend {
+    async fn spawn_instance(&self, cfg: &OopModuleConfig) -> Result<InstanceHandle> {
+        Self::spawn_process(&self.shared, cfg)
+    }
 
     async fn stop_instance(&self, handle: &InstanceHandle) -> Result<()> {
         let local = {
-            let mut instances = self.instances.write();
+            let mut instances = self.shared.instances.write();
             instances.remove(&handle.instance_id)
         };
 
@@ -348,6 +526,18 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
 
             // we do not await forwarders here, they'll stop on their own via CancellationToken and pipe close;
             // shutdown_all_instances handles draining for global shutdown
+        } else if let Some(pending) = self
+            .shared
+            .pending_restarts
+            .lock()
+            .remove(&handle.instance_id)
+        {
+            pending.cancel();
+            tracing::info!(
+                module = %handle.module,
+                instance_id = %handle.instance_id,
+                "Cancelled pending restart of stopped OoP module instance"
+            );
         } else {
             tracing::debug!(
                 module = %handle.module,
@@ -360,7 +550,7 @@ impl ModuleRuntimeBackend for LocalProcessBackend {
     }
 
     async fn list_instances(&self, module: &str) -> Result<Vec<InstanceHandle>> {
-        let instances = self.instances.read();
+        let instances = self.shared.instances.read();
 
         let result = instances
             .values()
@@ -538,6 +728,117 @@ mod tests {
         assert_eq!(instances.len(), 0);
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_crashing_process_is_restarted_until_crash_loop() {
+        use crate::backends::{RestartMode, RestartPolicy};
+
+        let backend = test_backend();
+        let manager = Arc::new(ModuleManager::new());
+        backend.set_module_manager(manager.clone());
+
+        let mut cfg = OopModuleConfig::new("crashing_module", BackendKind::LocalProcess);
+        cfg.binary = Some(PathBuf::from("/bin/sh"));
+        cfg.args = vec!["-c".to_owned(), "exit 3".to_owned()];
+        cfg.restart = RestartPolicy {
+            mode: RestartMode::OnFailure,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_millis(20),
+            max_restarts: 2,
+            window: Duration::from_secs(30),
+        };
+        backend.spawn_instance(&cfg).await.expect("should spawn");
+
+        let supervision = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                if let Some(s) = manager.supervision("crashing_module")
+                    && s.failed
+                {
+                    return s;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("crash loop should be detected");
+
+        assert_eq!(supervision.restart_count, 2);
+        assert!(supervision.last_exit.unwrap().contains('3'));
+        let instances = backend.list_instances("crashing_module").await.unwrap();
+        assert!(instances.is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_clean_exit_is_not_restarted_on_failure_policy() {
+        use crate::backends::{RestartMode, RestartPolicy};
+
+        let backend = test_backend();
+        let manager = Arc::new(ModuleManager::new());
+        backend.set_module_manager(manager.clone());
+
+        let mut cfg = OopModuleConfig::new("clean_module", BackendKind::LocalProcess);
+        cfg.binary = Some(PathBuf::from("/bin/sh"));
+        cfg.args = vec!["-c".to_owned(), "exit 0".to_owned()];
+        cfg.restart = RestartPolicy {
+            mode: RestartMode::OnFailure,
+            ..RestartPolicy::default()
+        };
+        backend.spawn_instance(&cfg).await.expect("should spawn");
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !backend
+                .list_instances("clean_module")
+                .await
+                .unwrap()
+                .is_empty()
+            {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("exited instance should be untracked");
+        assert!(manager.supervision("clean_module").is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stop_during_backoff_cancels_restart() {
+        use crate::backends::{RestartMode, RestartPolicy};
+
+        let backend = test_backend();
+        let mut cfg = OopModuleConfig::new("stopped_module", BackendKind::LocalProcess);
+        cfg.binary = Some(PathBuf::from("/bin/sh"));
+        cfg.args = vec!["-c".to_owned(), "exit 3".to_owned()];
+        cfg.restart = RestartPolicy {
+            mode: RestartMode::OnFailure,
+            initial_backoff: Duration::from_millis(500),
+            ..RestartPolicy::default()
+        };
+        let handle = backend.spawn_instance(&cfg).await.expect("should spawn");
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !backend
+                .shared
+                .pending_restarts
+                .lock()
+                .contains_key(&handle.instance_id)
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("restart should be scheduled");
+        backend.stop_instance(&handle).await.unwrap();
+
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        let instances = backend.list_instances("stopped_module").await.unwrap();
+        assert!(
+            instances.is_empty(),
+            "stopped instance must not be respawned"
+        );
+    }
+
     mod send_terminate_signal_tests {
         #[cfg(unix)]
         use {super::send_terminate_signal, std::time::Duration};
@@ -6,9 +6,12 @@ use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
 use uuid::Uuid;
 
+use crate::runtime::ModuleManager;
+
 /// The kind of backend used to spawn and manage module instances
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackendKind {
@@ -19,6 +22,7 @@ pub enum BackendKind {
 }
 
 /// Configuration for an out-of-process module
+#[derive(Clone)]
 pub struct OopModuleConfig {
     pub name: String,
     pub binary: Option<PathBuf>,
@@ -27,6 +31,10 @@ pub struct OopModuleConfig {
     pub working_directory: Option<String>,
     pub backend: BackendKind,
     pub version: Option<String>,
+    /// Restart policy applied when the instance exits
+    pub restart: RestartPolicy,
+    /// Pre-started instance to adopt (`Static` backend)
+    pub instance: Option<StaticInstance>,
 }
 
 impl OopModuleConfig {
@@ -39,6 +47,8 @@ impl OopModuleConfig {
             working_directory: None,
             backend,
             version: None,
+            restart: RestartPolicy::default(),
+            instance: None,
         }
     }
 }
@@ -80,6 +90,7 @@ pub struct OopSpawnConfig {
     pub args: Vec<String>,
     pub env: HashMap<String, String>,
     pub working_directory: Option<String>,
+    pub restart: RestartPolicy,
 }
 
 /// A type-erased backend for spawning `OoP` modules.
@@ -92,12 +103,19 @@ pub trait OopBackend: Send + Sync {
 
     /// Shutdown all spawned instances (called during stop phase).
     async fn shutdown_all(&self);
+
+    /// Report restarts and crash loops of supervised instances to `manager`.
+    fn attach_module_manager(&self, _manager: Arc<ModuleManager>) {}
 }
 
 pub mod local;
 pub mod log_forwarder;
+pub mod static_instances;
+pub mod supervisor;
 
 pub use local::LocalProcessBackend;
+pub use static_instances::{StaticBackend, StaticInstance};
+pub use supervisor::{RestartDecision, RestartMode, RestartPolicy, RestartTracker};
 
 /// Adapter that implements `OopBackend` trait for `LocalProcessBackend`.
 ///
@@ -110,11 +128,16 @@ impl OopBackend for LocalProcessBackend {
         oop_config.args = config.args;
         oop_config.env = config.env;
         oop_config.working_directory = config.working_directory;
+        oop_config.restart = config.restart;
 
         self.spawn_instance(&oop_config).await?;
         Ok(())
     }
 
+    fn attach_module_manager(&self, manager: Arc<ModuleManager>) {
+        self.set_module_manager(manager);
+    }
+
     async fn shutdown_all(&self) {
         // The LocalProcessBackend already handles shutdown via its cancellation token
         // when the token is triggered, it automatically stops all instances.
@@ -0,0 +1,192 @@
+//! Static backend for pre-started module instances
+//!
+//! Instances are started outside the host (systemd units, sidecar containers) and
+//! listed in the module config. The backend does not own their processes: it
+//! registers each instance into the [`ModuleManager`] as pinned, so it becomes
+//! routable once the process heartbeats under the configured instance ID and is
+//! quarantined (but kept) whenever heartbeats stop.
+
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use uuid::Uuid;
+
+use super::{BackendKind, InstanceHandle, ModuleRuntimeBackend, OopModuleConfig};
+use crate::runtime::{Endpoint, ModuleInstance, ModuleManager};
+
+/// A pre-started module instance (`runtime.instances` in the module config)
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StaticInstance {
+    /// Instance ID the process registers and heartbeats with (`MODKIT_INSTANCE_ID`)
+    pub instance_id: Uuid,
+    /// gRPC endpoint of the instance (e.g., `http://10.0.0.5:50061`)
+    pub endpoint: String,
+    /// Version reported for the instance until it registers itself
+    #[serde(default)]
+    pub version: Option<String>,
+    /// gRPC services served at `endpoint`, routable before the process registers
+    #[serde(default)]
+    pub grpc_services: Vec<String>,
+}
+
+impl StaticInstance {
+    fn to_module_instance(&self, module: &str) -> ModuleInstance {
+        let endpoint = Endpoint::from_uri(self.endpoint.clone());
+        let mut instance = ModuleInstance::new(module, self.instance_id)
+            .with_control(endpoint.clone())
+            .pinned();
+        if let Some(version) = &self.version {
+            instance = instance.with_version(version.clone());
+        }
+        for service in &self.grpc_services {
+            instance = instance.with_grpc_service(service.clone(), endpoint.clone());
+        }
+        instance
+    }
+}
+
+/// Backend that adopts externally managed instances instead of spawning them
+pub struct StaticBackend {
+    manager: Arc<ModuleManager>,
+    instances: RwLock<HashMap<Uuid, InstanceHandle>>,
+}
+
+impl StaticBackend {
+    #[must_use]
+    pub fn new(manager: Arc<ModuleManager>) -> Self {
+        Self {
+            manager,
+            instances: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ModuleRuntimeBackend for StaticBackend {
+    async fn spawn_instance(&self, cfg: &OopModuleConfig) -> Result<InstanceHandle> {
+        if cfg.backend != BackendKind::Static {
+            bail!(
+                "StaticBackend can only adopt Static instances, got {:?}",
+                cfg.backend
+            );
+        }
+        let instance = cfg
+            .instance
+            .as_ref()
+            .context("instance must be set for Static backend")?;
+
+        self.manager
+            .register_instance(Arc::new(instance.to_module_instance(&cfg.name)));
+        tracing::info!(
+            module = %cfg.name,
+            instance_id = %instance.instance_id,
+            endpoint = %instance.endpoint,
+            "Registered static OoP module instance"
+        );
+
+        let handle = InstanceHandle {
+            module: cfg.name.clone(),
+            instance_id: instance.instance_id,
+            backend: BackendKind::Static,
+            pid: None,
+            created_at: Instant::now(),
+        };
+        self.instances
+            .write()
+            .insert(instance.instance_id, handle.clone());
+        Ok(handle)
+    }
+
+    /// Removes the instance from the directory; the process itself keeps running.
+    async fn stop_instance(&self, handle: &InstanceHandle) -> Result<()> {
+        self.instances.write().remove(&handle.instance_id);
+        self.manager.deregister(&handle.module, handle.instance_id);
+        Ok(())
+    }
+
+    async fn list_instances(&self, module: &str) -> Result<Vec<InstanceHandle>> {
+        Ok(self
+            .instances
+            .read()
+            .values()
+            .filter(|h| h.module == module)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::runtime::InstanceState;
+
+    fn static_cfg(module: &str, instance_id: Uuid) -> OopModuleConfig {
+        let mut cfg = OopModuleConfig::new(module, BackendKind::Static);
+        cfg.instance = Some(StaticInstance {
+            instance_id,
+            endpoint: "http://10.0.0.5:50061".to_owned(),
+            version: Some("1.2.0".to_owned()),
+            grpc_services: vec!["calculator.v1.Calculator".to_owned()],
+        });
+        cfg
+    }
+
+    #[tokio::test]
+    async fn adopted_instance_is_routed_after_heartbeat() {
+        let manager = Arc::new(ModuleManager::new());
+        let backend = StaticBackend::new(manager.clone());
+        let id = Uuid::new_v4();
+
+        let handle = backend
+            .spawn_instance(&static_cfg("calculator", id))
+            .await
+            .unwrap();
+        assert_eq!(handle.backend, BackendKind::Static);
+        assert_eq!(handle.pid, None);
+
+        let inst = &manager.instances_of("calculator")[0];
+        assert!(inst.is_pinned());
+        assert_eq!(inst.version.as_deref(), Some("1.2.0"));
+        assert_eq!(inst.state(), InstanceState::Registered);
+        assert!(
+            manager
+                .pick_service_round_robin("calculator.v1.Calculator")
+                .is_none()
+        );
+
+        manager.update_heartbeat("calculator", id, Instant::now());
+        let (_, _, ep) = manager
+            .pick_service_round_robin("calculator.v1.Calculator")
+            .unwrap();
+        assert_eq!(ep.uri, "http://10.0.0.5:50061");
+
+        assert_eq!(backend.list_instances("calculator").await.unwrap().len(), 1);
+        backend.stop_instance(&handle).await.unwrap();
+        assert!(
+            backend
+                .list_instances("calculator")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(manager.instances_of("calculator").is_empty());
+    }
+
+    #[tokio::test]
+    async fn rejects_other_backends_and_missing_instance() {
+        let backend = StaticBackend::new(Arc::new(ModuleManager::new()));
+
+        let mut cfg = static_cfg("calculator", Uuid::new_v4());
+        cfg.backend = BackendKind::LocalProcess;
+        assert!(backend.spawn_instance(&cfg).await.is_err());
+
+        let cfg = OopModuleConfig::new("calculator", BackendKind::Static);
+        assert!(backend.spawn_instance(&cfg).await.is_err());
+    }
+}
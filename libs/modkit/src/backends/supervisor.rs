@@ -0,0 +1,230 @@
+//! Restart supervision for out-of-process module instances.
+//!
+//! A [`RestartPolicy`] decides whether an exited instance is restarted, and with
+//! which backoff. A [`RestartTracker`] keeps the recent restarts of one module and
+//! detects crash loops: more than `max_restarts` restarts within `window`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// When an exited instance is restarted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RestartMode {
+    /// Restart after any exit, including a clean one.
+    Always,
+    /// Restart after a non-zero exit code or a signal.
+    OnFailure,
+    /// Never restart.
+    #[default]
+    Never,
+}
+
+/// Per-module restart policy (`execution.restart` in the module config).
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct RestartPolicy {
+    /// When to restart: `always`, `on_failure` or `never`.
+    pub mode: RestartMode,
+    /// Delay before the first restart; doubled on each further restart within `window`.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub initial_backoff: Duration,
+    /// Upper bound for the restart delay.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub max_backoff: Duration,
+    /// Restarts allowed within `window`; one more exit marks the module as crash-looping.
+    pub max_restarts: u32,
+    /// Sliding window for counting restarts.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub window: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            mode: RestartMode::Never,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(30),
+            max_restarts: 5,
+            window: Duration::from_mins(1),
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// A policy that never restarts.
+    #[must_use]
+    pub fn never() -> Self {
+        Self {
+            mode: RestartMode::Never,
+            ..Self::default()
+        }
+    }
+}
+
+/// What to do after an instance exited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartDecision {
+    /// Restart after `delay`; `attempt` counts all restarts of the module.
+    Restart { delay: Duration, attempt: u32 },
+    /// The policy does not restart this exit.
+    Stop,
+    /// Too many restarts within the window; the module is considered failed.
+    CrashLoop,
+}
+
+/// Restart history of a single module.
+#[derive(Debug, Default)]
+pub struct RestartTracker {
+    recent: VecDeque<Instant>,
+    total: u32,
+}
+
+impl RestartTracker {
+    /// Decide how to handle an exit at `now`; `success` is a clean (zero) exit.
+    pub fn on_exit(
+        &mut self,
+        policy: &RestartPolicy,
+        success: bool,
+        now: Instant,
+    ) -> RestartDecision {
+        match policy.mode {
+            RestartMode::Never => return RestartDecision::Stop,
+            RestartMode::OnFailure if success => return RestartDecision::Stop,
+            RestartMode::OnFailure | RestartMode::Always => {}
+        }
+
+        while self
+            .recent
+            .front()
+            .is_some_and(|t| now.saturating_duration_since(*t) > policy.window)
+        {
+            self.recent.pop_front();
+        }
+        if self.recent.len() >= policy.max_restarts as usize {
+            return RestartDecision::CrashLoop;
+        }
+
+        let exponent = u32::try_from(self.recent.len()).unwrap_or(u32::MAX).min(16);
+        let delay = policy
+            .initial_backoff
+            .saturating_mul(1 << exponent)
+            .min(policy.max_backoff);
+
+        self.recent.push_back(now);
+        self.total += 1;
+        RestartDecision::Restart {
+            delay,
+            attempt: self.total,
+        }
+    }
+
+    /// Restarts performed so far.
+    #[must_use]
+    pub fn total(&self) -> u32 {
+        self.total
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn policy(mode: RestartMode) -> RestartPolicy {
+        RestartPolicy {
+            mode,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(350),
+            max_restarts: 3,
+            window: Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn mode_decides_which_exits_restart() {
+        let now = Instant::now();
+        let mut t = RestartTracker::default();
+        assert_eq!(
+            t.on_exit(&policy(RestartMode::Never), false, now),
+            RestartDecision::Stop
+        );
+        assert_eq!(
+            t.on_exit(&policy(RestartMode::OnFailure), true, now),
+            RestartDecision::Stop
+        );
+        assert!(matches!(
+            t.on_exit(&policy(RestartMode::OnFailure), false, now),
+            RestartDecision::Restart { attempt: 1, .. }
+        ));
+        assert!(matches!(
+            t.on_exit(&policy(RestartMode::Always), true, now),
+            RestartDecision::Restart { attempt: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn backoff_grows_until_crash_loop() {
+        let p = policy(RestartMode::OnFailure);
+        let now = Instant::now();
+        let mut t = RestartTracker::default();
+
+        let delays: Vec<_> = (0..3)
+            .map(|_| match t.on_exit(&p, false, now) {
+                RestartDecision::Restart { delay, .. } => delay,
+                other => panic!("unexpected {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            delays,
+            vec![
+                Duration::from_millis(100),
+                Duration::from_millis(200),
+                Duration::from_millis(350)
+            ]
+        );
+        assert_eq!(t.on_exit(&p, false, now), RestartDecision::CrashLoop);
+        assert_eq!(t.total(), 3);
+    }
+
+    #[test]
+    fn restarts_outside_window_are_forgotten() {
+        let p = policy(RestartMode::OnFailure);
+        let start = Instant::now();
+        let mut t = RestartTracker::default();
+        for _ in 0..3 {
+            t.on_exit(&p, false, start);
+        }
+
+        let later = start + Duration::from_secs(11);
+        assert_eq!(
+            t.on_exit(&p, false, later),
+            RestartDecision::Restart {
+                delay: Duration::from_millis(100),
+                attempt: 4
+            }
+        );
+    }
+
+    #[test]
+    fn policy_parses_humantime_durations() {
+        let p: RestartPolicy = serde_json::from_value(serde_json::json!({
+            "mode": "always",
+            "initial_backoff": "500ms",
+            "max_restarts": 10,
+        }))
+        .unwrap();
+        assert_eq!(p.mode, RestartMode::Always);
+        assert_eq!(p.initial_backoff, Duration::from_millis(500));
+        assert_eq!(p.max_backoff, Duration::from_secs(30));
+        assert_eq!(p.max_restarts, 10);
+
+        let p: RestartPolicy = serde_json::from_value(serde_json::json!({})).unwrap();
+        assert_eq!(
+            p.mode,
+            RestartMode::Never,
+            "existing deployments keep no restarts"
+        );
+    }
+}
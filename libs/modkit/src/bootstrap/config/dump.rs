@@ -81,6 +81,7 @@ pub fn render_effective_modules_config(app: &AppConfig) -> Result<serde_json::Va
                     "type": match runtime_config.mod_type {
                         RuntimeKind::Local => "local",
                         RuntimeKind::Oop => "oop",
+                        RuntimeKind::Static => "static",
                     }
                 }),
             );
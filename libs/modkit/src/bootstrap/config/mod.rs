@@ -13,7 +13,8 @@ use std::path::{Path, PathBuf};
 use tracing::Level;
 
 use crate::ConfigProvider;
-use crate::telemetry::TracingConfig;
+use crate::backends::{RestartPolicy, StaticInstance};
+use crate::telemetry::{MetricsConfig, TracingConfig};
 use url::Url;
 
 // Re-export dump functions
@@ -45,6 +46,9 @@ pub struct ModuleRuntime {
     /// Execution configuration for `OoP` modules.
     #[serde(default)]
     pub execution: Option<ExecutionConfig>,
+    /// Pre-started instances for `static` modules.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instances: Vec<StaticInstance>,
 }
 
 /// Execution configuration for out-of-process modules.
@@ -62,6 +66,9 @@ pub struct ExecutionConfig {
     /// Environment variables to set for the process.
     #[serde(default)]
     pub environment: HashMap<String, String>,
+    /// Restart policy for the process (defaults to `on_failure` with backoff).
+    #[serde(default)]
+    pub restart: RestartPolicy,
 }
 
 /// Module runtime kind.
@@ -71,6 +78,8 @@ pub enum RuntimeKind {
     #[default]
     Local,
     Oop,
+    /// Out-of-process module whose instances are started outside the host.
+    Static,
 }
 
 /// Main application configuration with strongly-typed global sections
@@ -87,6 +96,9 @@ pub struct AppConfig {
     pub logging: LoggingConfig,
     /// Tracing configuration (optional, disabled if None).
     pub tracing: Option<TracingConfig>,
+    /// Metrics configuration (optional, disabled if None).
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
     /// Directory containing per-module YAML files (optional).
     #[serde(default)]
     pub modules_dir: Option<String>,
@@ -103,6 +115,7 @@ impl Default for AppConfig {
             database: None,
             logging: default_logging_config(),
             tracing: None, // Disabled by default
+            metrics: None,
             modules_dir: None,
             modules: HashMap::new(),
         }
@@ -2697,6 +2710,33 @@ logging:
         assert!(modules.contains_key("module_b"));
         assert!(modules.contains_key("module_c"));
     }
+
+    #[test]
+    fn test_static_runtime_lists_instances() {
+        let mut app = create_minimal_app();
+        app.modules.insert(
+            "calculator".to_owned(),
+            serde_json::json!({
+                "runtime": {
+                    "type": "static",
+                    "instances": [{
+                        "instance_id": "6f1c2a4e-8d1b-4c52-9a0e-3b7d5f2e1c90",
+                        "endpoint": "http://10.0.0.5:50061",
+                        "version": "1.2.0",
+                        "grpc_services": ["calculator.v1.Calculator"],
+                    }],
+                },
+            }),
+        );
+
+        let runtime = get_module_runtime_config(&app, "calculator")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(runtime.mod_type, RuntimeKind::Static));
+        assert_eq!(runtime.instances.len(), 1);
+        assert_eq!(runtime.instances[0].endpoint, "http://10.0.0.5:50061");
+        assert_eq!(runtime.instances[0].version.as_deref(), Some("1.2.0"));
+    }
 }
 
 // Note: DB trait implementations and helper functions removed since we now use DbManager
@@ -1,6 +1,8 @@
 use super::super::config::{ConsoleFormat, LoggingConfig, Section};
+use crate::telemetry::log_levels::{LogLevels, install_log_levels};
 use anyhow::Context;
 use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::io;
 use std::io::Write;
 use std::path::Path;
@@ -15,6 +17,10 @@ pub type OtelLayer = tracing_opentelemetry::OpenTelemetryLayer<
 >;
 #[cfg(not(feature = "otel"))]
 pub type OtelLayer = ();
+#[cfg(feature = "otel")]
+pub use crate::telemetry::logs::OtelLogsLayer;
+#[cfg(not(feature = "otel"))]
+pub type OtelLogsLayer = ();
 
 // Keep a guard for non-blocking console to avoid being dropped.
 static CONSOLE_GUARD: std::sync::OnceLock<tracing_appender::non_blocking::WorkerGuard> =
@@ -181,8 +187,17 @@ fn create_rotating_writer_at_path(
 // ================= public init (drop-in API kept) =================
 
 /// Unified initializer used by both functions above.
+///
+/// `logs_layer` forwards events to the OTLP log exporter (see
+/// [`init_log_export`](crate::telemetry::logs::init_log_export)). Levels of a `logging` section
+/// can be changed at runtime through [`log_levels`](crate::telemetry::log_levels::log_levels).
 #[allow(unknown_lints, de1301_no_print_macros)] // runs before tracing subscriber is installed
-pub fn init_logging_unified(cfg: &LoggingConfig, base_dir: &Path, otel_layer: Option<OtelLayer>) {
+pub fn init_logging_unified(
+    cfg: &LoggingConfig,
+    base_dir: &Path,
+    otel_layer: Option<OtelLayer>,
+    logs_layer: Option<OtelLogsLayer>,
+) {
     // Bridge `log` → `tracing` *before* installing the subscriber
     if let Err(e) = tracing_log::LogTracer::init() {
         eprintln!("LogTracer init skipped: {e}");
@@ -192,7 +207,7 @@ pub fn init_logging_unified(cfg: &LoggingConfig, base_dir: &Path, otel_layer: Op
 
     if data.crate_sections.is_empty() && data.default_section.is_none() {
         // Minimal fallback (INFO to console; honors RUST_LOG)
-        init_minimal(otel_layer);
+        init_minimal(otel_layer, logs_layer);
         return;
     }
 
@@ -213,13 +228,17 @@ pub fn init_logging_unified(cfg: &LoggingConfig, base_dir: &Path, otel_layer: Op
         file_router,
         console_format,
         otel_layer,
+        logs_layer,
     );
 }
 
 // ================= generic targets builder =================
 
+use crate::telemetry::log_levels::ApplyOverrides;
+use std::collections::BTreeMap;
 use tracing::level_filters::LevelFilter;
-use tracing_subscriber::filter::Targets;
+use tracing_subscriber::filter::{EnvFilter, Targets};
+use tracing_subscriber::reload;
 
 /// Noisy crates that should be filtered to WARN level to avoid debug spam
 const NOISY_CRATES: &[&str] = &["h2"];
@@ -359,6 +378,75 @@ fn stderr_supports_ansi() -> bool {
     supports_color::on(supports_color::Stream::Stderr).is_some_and(|level| level.has_basic)
 }
 
+// ================= reloadable filters =================
+
+type ReloadTargets = Box<dyn Fn(Targets) -> Result<(), reload::Error> + Send + Sync>;
+type ReloadEnv = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Reload handles of the installed filters; per-target overrides are applied on top of the
+/// configured console and file targets (and appended to `RUST_LOG` directives, if set).
+#[derive(Default)]
+struct FilterHandles {
+    console: Vec<ReloadTargets>,
+    file: Vec<ReloadTargets>,
+    env: Option<ReloadEnv>,
+}
+
+impl FilterHandles {
+    fn console<S: 'static>(&mut self, targets: Targets) -> reload::Layer<Targets, S> {
+        let (layer, handle) = reload::Layer::new(targets);
+        self.console.push(Box::new(move |t| handle.reload(t)));
+        layer
+    }
+
+    fn file<S: 'static>(&mut self, targets: Targets) -> reload::Layer<Targets, S> {
+        let (layer, handle) = reload::Layer::new(targets);
+        self.file.push(Box::new(move |t| handle.reload(t)));
+        layer
+    }
+
+    fn env<S: 'static>(&mut self, env: Option<EnvFilter>) -> Option<reload::Layer<EnvFilter, S>> {
+        let (layer, handle) = reload::Layer::new(env?);
+        self.env = Some(Box::new(move |f| handle.reload(f)));
+        Some(layer)
+    }
+
+    fn into_apply(self, console: Targets, file: Targets, env: Option<String>) -> ApplyOverrides {
+        Box::new(move |overrides| {
+            let console = with_overrides(&console, overrides);
+            for reload in &self.console {
+                reload(console.clone()).map_err(|e| e.to_string())?;
+            }
+            let file = with_overrides(&file, overrides);
+            for reload in &self.file {
+                reload(file.clone()).map_err(|e| e.to_string())?;
+            }
+            if let (Some(reload), Some(base)) = (&self.env, &env) {
+                let mut directives = base.clone();
+                for (target, level) in overrides {
+                    _ = write!(directives, ",{target}={level}");
+                }
+                let env = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+                reload(env).map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// `base` with the levels of overridden targets replaced.
+fn with_overrides(base: &Targets, overrides: &BTreeMap<String, LevelFilter>) -> Targets {
+    let mut targets = Targets::new();
+    if let Some(level) = base.default_level() {
+        targets = targets.with_default(level);
+    }
+    let configured = base
+        .iter()
+        .filter(|(target, _)| !overrides.contains_key(*target))
+        .map(|(target, level)| (target.to_owned(), level));
+    targets.with_targets(configured.chain(overrides.clone()))
+}
+
 // ================= registry & layers =================
 
 fn install_subscriber(
@@ -367,12 +455,17 @@ fn install_subscriber(
     file_router: MultiFileRouter,
     console_format: ConsoleFormat,
     #[cfg_attr(not(feature = "otel"), allow(unused_variables))] otel_layer: Option<OtelLayer>,
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))] logs_layer: Option<OtelLogsLayer>,
 ) {
     use tracing_subscriber::{EnvFilter, Registry, fmt, layer::SubscriberExt};
 
     // RUST_LOG acts as a global upper-bound for console/file if present.
     // If not set, we don't clamp here — YAML targets drive levels.
     let env: Option<EnvFilter> = EnvFilter::try_from_default_env().ok();
+    let env_directives = env.as_ref().map(ToString::to_string);
+
+    // Every filter is reloadable so levels can be overridden at runtime (see `LogLevels`)
+    let mut handles = FilterHandles::default();
 
     // Console writer (non-blocking stderr)
     let (nb_stderr, guard) = tracing_appender::non_blocking(std::io::stderr());
@@ -389,7 +482,7 @@ fn install_subscriber(
                     .with_target(true)
                     .with_level(true)
                     .with_timer(fmt::time::UtcTime::rfc_3339())
-                    .with_filter(console_targets.clone()),
+                    .with_filter(handles.console(console_targets.clone())),
             ),
             None,
         ),
@@ -403,7 +496,7 @@ fn install_subscriber(
                     .with_target(true)
                     .with_level(true)
                     .with_timer(fmt::time::UtcTime::rfc_3339())
-                    .with_filter(console_targets.clone()),
+                    .with_filter(handles.console(console_targets.clone())),
             ),
         ),
     };
@@ -420,13 +513,13 @@ fn install_subscriber(
                 .with_level(true)
                 .with_timer(fmt::time::UtcTime::rfc_3339())
                 .with_writer(file_router)
-                .with_filter(file_targets.clone()),
+                .with_filter(handles.file(file_targets.clone())),
         )
     };
 
     // Build subscriber:
     // 1) OTEL first (because your OtelLayer is bound to `Registry`);
-    //    also filter OTEL by the SAME console targets from YAML.
+    //    also filter OTEL (spans and exported logs) by the SAME console targets from YAML.
     // 2) Then EnvFilter (caps console/file if RUST_LOG is set).
     // 3) Then console (text or json) + file fmt layers.
     let subscriber = {
@@ -434,23 +527,40 @@ fn install_subscriber(
 
         #[cfg(feature = "otel")]
         let base = {
-            let otel_opt = otel_layer.map(|otel| otel.with_filter(console_targets.clone()));
-            base.with(otel_opt)
+            let otel_opt =
+                otel_layer.map(|otel| otel.with_filter(handles.console(console_targets.clone())));
+            let logs_opt =
+                logs_layer.map(|logs| logs.with_filter(handles.console(console_targets.clone())));
+            base.with(otel_opt).with(logs_opt)
         };
         #[cfg(not(feature = "otel"))]
         let base = base;
 
-        let base = base.with(env);
+        let base = base.with(handles.env(env));
         base.with(console_text)
             .with(console_json)
             .with(file_layer_opt)
     };
 
-    _ = subscriber.try_init();
+    // `log` is already bridged above, so install the subscriber alone
+    if tracing::subscriber::set_global_default(subscriber).is_ok() {
+        let default_level = console_targets.default_level().unwrap_or(LevelFilter::OFF);
+        let configured = console_targets
+            .iter()
+            .map(|(target, level)| (target.to_owned(), level))
+            .collect();
+        let apply = handles.into_apply(
+            console_targets.clone(),
+            file_targets.clone(),
+            env_directives,
+        );
+        install_log_levels(LogLevels::new(default_level, configured, apply));
+    }
 }
 
 fn init_minimal(
     #[cfg_attr(not(feature = "otel"), allow(unused_variables))] otel: Option<OtelLayer>,
+    #[cfg_attr(not(feature = "otel"), allow(unused_variables))] logs: Option<OtelLogsLayer>,
 ) {
     use tracing_subscriber::{EnvFilter, Registry, fmt, layer::SubscriberExt};
 
@@ -467,7 +577,7 @@ fn init_minimal(
         let base = Registry::default();
 
         #[cfg(feature = "otel")]
-        let base = base.with(otel);
+        let base = base.with(otel).with(logs);
         #[cfg(not(feature = "otel"))]
         let base = base;
 
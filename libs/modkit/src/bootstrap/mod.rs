@@ -31,4 +31,4 @@ pub use config::{
 pub use oop::{OopRunOptions, run_oop_with_options};
 
 mod run;
-pub use run::{run_migrate, run_server};
+pub use run::{FileConfigSource, run_migrate, run_server, run_server_with_reload};
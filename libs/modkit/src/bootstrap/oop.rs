@@ -36,6 +36,7 @@
 //!         verbose: 0,
 //!         print_config: false,
 //!         heartbeat_interval_secs: 5,
+//!         directory_tls: None,
 //!     };
 //!
 //!     run_oop_with_options(opts).await
@@ -58,10 +59,13 @@ use super::config::{
 };
 use crate::bootstrap::host::init_logging_unified;
 use crate::runtime::{
-    ClientRegistration, DbOptions, MODKIT_DIRECTORY_ENDPOINT_ENV, RunOptions, ShutdownOptions, run,
-    shutdown,
+    ClientRegistration, DbOptions, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_INSTANCE_ID_ENV,
+    RunOptions, ShutdownOptions, run, shutdown,
 };
-use cf_system_sdks::directory::{DirectoryClient, DirectoryGrpcClient};
+use cf_system_sdks::directory::{
+    DirectoryClient, DirectoryGrpcClient, EventBusClient, EventBusGrpcClient,
+};
+use modkit_transport_grpc::{GrpcClientTlsConfig, client::GrpcClientConfig};
 
 /// Configuration options for `OoP` module bootstrap
 #[derive(Debug, Clone)]
@@ -69,7 +73,7 @@ pub struct OopRunOptions {
     /// Logical module name (e.g., "`file-parser`")
     pub module_name: String,
 
-    /// Instance ID (defaults to a random UUID if None)
+    /// Instance ID (defaults to `MODKIT_INSTANCE_ID`, or a random UUID if None)
     pub instance_id: Option<Uuid>,
 
     /// Directory service gRPC endpoint (e.g., "<http://127.0.0.1:50051>")
@@ -86,6 +90,12 @@ pub struct OopRunOptions {
 
     /// Heartbeat interval in seconds (default: 5)
     pub heartbeat_interval_secs: u64,
+
+    /// TLS for the directory connection; requires an `https://` endpoint.
+    ///
+    /// Defaults to the `MODKIT_GRPC_TLS_*` environment variables (CA bundle,
+    /// client certificate and key for mutual TLS, server name).
+    pub directory_tls: Option<GrpcClientTlsConfig>,
 }
 
 impl Default for OopRunOptions {
@@ -106,10 +116,24 @@ impl Default for OopRunOptions {
             verbose: 0,
             print_config: false,
             heartbeat_interval_secs: 5,
+            directory_tls: GrpcClientTlsConfig::from_env(),
         }
     }
 }
 
+/// Instance ID assigned by the host through `MODKIT_INSTANCE_ID`.
+///
+/// Pre-started (static) instances are listed in the host config under a fixed ID, so an
+/// invalid value is an error rather than a reason to register under a random one.
+fn instance_id_from_env() -> Result<Option<Uuid>> {
+    let Ok(id) = std::env::var(MODKIT_INSTANCE_ID_ENV) else {
+        return Ok(None);
+    };
+    Uuid::parse_str(id.trim())
+        .map(Some)
+        .with_context(|| format!("{MODKIT_INSTANCE_ID_ENV} is not a valid UUID: '{id}'"))
+}
+
 /// Builds the final configuration and `DbOptions` for an `OoP` module.
 ///
 /// Configuration merge strategy (for each section):
@@ -385,6 +409,7 @@ fn merge_json_objects(target: &mut serde_json::Value, source: &serde_json::Value
 ///         verbose: 1,
 ///         print_config: false,
 ///         heartbeat_interval_secs: 5,
+///         directory_tls: None,
 ///     };
 ///
 ///     run_oop_with_options(opts).await
@@ -404,7 +429,10 @@ fn merge_json_objects(target: &mut serde_json::Value, source: &serde_json::Value
 )]
 pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
     // Generate instance ID if not provided
-    let instance_id = opts.instance_id.unwrap_or_else(Uuid::new_v4);
+    let instance_id = match opts.instance_id {
+        Some(id) => id,
+        None => instance_id_from_env()?.unwrap_or_else(Uuid::new_v4),
+    };
 
     // Create root cancellation token for the entire process.
     // This token drives shutdown for the module runtime and all background tasks.
@@ -470,8 +498,26 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
     #[cfg(not(feature = "otel"))]
     let otel_layer = None;
 
+    // OTLP log export follows the master's tracing config too; the provider flushes on drop
+    #[cfg(feature = "otel")]
+    let (logs_layer, _logger_provider) =
+        match rendered_config.as_ref().and_then(|rc| rc.tracing.as_ref()) {
+            Some(tc) if tc.log_export_enabled() => {
+                let (layer, provider) = crate::telemetry::init_log_export(tc)?;
+                (Some(layer), Some(provider))
+            }
+            _ => (None, None),
+        };
+    #[cfg(not(feature = "otel"))]
+    let logs_layer = None;
+
     // Initialize logging with MERGED config (master base + local override)
-    init_logging_unified(&merged_logging, &config.server.home_dir, otel_layer);
+    init_logging_unified(
+        &merged_logging,
+        &config.server.home_dir,
+        otel_layer,
+        logs_layer,
+    );
 
     // Now we can log - report what we received from master
     if let Some(ref rc) = rendered_config {
@@ -513,11 +559,19 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
         "Connecting to directory service at {}",
         opts.directory_endpoint
     );
-    let directory_client = DirectoryGrpcClient::connect(&opts.directory_endpoint).await?;
+    let directory_cfg = grpc_client_config("directory", opts.directory_tls.as_ref());
+    let directory_client =
+        DirectoryGrpcClient::connect_with_retry(&opts.directory_endpoint, &directory_cfg).await?;
     let directory_api: Arc<dyn DirectoryClient> = Arc::new(directory_client);
 
     info!("Successfully connected to directory service");
 
+    // The host's event bus is served next to the directory; the local bus relays through it
+    let event_bus_cfg = grpc_client_config("event_bus", opts.directory_tls.as_ref());
+    let event_bus_client =
+        EventBusGrpcClient::connect_with_retry(&opts.directory_endpoint, &event_bus_cfg).await?;
+    let event_bus_api: Arc<dyn EventBusClient> = Arc::new(event_bus_client);
+
     // Start heartbeat loop in background using a child token from the root.
     // This allows the heartbeat to be cancelled when the root token is cancelled.
     let heartbeat_directory = Arc::clone(&directory_api);
@@ -561,17 +615,20 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
     // Keep a reference to directory_api for deregistration after shutdown
     // Run the module lifecycle with the root cancellation token.
     // Shutdown is driven by the signal handler spawned above, not by ShutdownOptions::Signals.
-    // The DirectoryClient (gRPC client) is injected into the ClientHub so modules can access it.
+    // The DirectoryClient (gRPC client) is injected into the ClientHub so modules can access it;
+    // the EventBusClient becomes the upstream of the runtime's event bus.
     info!("Starting module lifecycle");
     let run_options = RunOptions {
         modules_cfg: config_provider,
         db: db_options,
         shutdown: ShutdownOptions::Token(cancel.clone()),
-        clients: vec![ClientRegistration::new::<dyn DirectoryClient>(
-            directory_api,
-        )],
+        clients: vec![
+            ClientRegistration::new::<dyn DirectoryClient>(directory_api),
+            ClientRegistration::new::<dyn EventBusClient>(event_bus_api),
+        ],
         instance_id,
         oop: None, // OoP modules don't spawn other OoP modules
+        config_source: None,
     };
 
     let result = run(run_options).await;
@@ -585,6 +642,18 @@ pub async fn run_oop_with_options(opts: OopRunOptions) -> Result<()> {
     result
 }
 
+/// Client settings for connections to the host's directory endpoint.
+fn grpc_client_config(
+    service_name: &'static str,
+    tls: Option<&GrpcClientTlsConfig>,
+) -> GrpcClientConfig {
+    let cfg = GrpcClientConfig::new(service_name);
+    match tls {
+        Some(tls) => cfg.with_tls(tls.clone()),
+        None => cfg,
+    }
+}
+
 #[allow(unknown_lints, de1301_no_print_macros)] // direct stdout config print before exit
 fn print_config(config: &AppConfig) {
     match config.to_yaml() {
@@ -3,7 +3,8 @@ use super::host::normalize_path;
 use super::{AppConfig, RuntimeKind};
 use crate::backends::LocalProcessBackend;
 use crate::runtime::{
-    DbOptions, OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions, run, shutdown,
+    ConfigSource, DbOptions, ModuleSections, OopModuleSpawnConfig, OopSpawnOptions, RunOptions,
+    ShutdownOptions, StaticModuleConfig, run, shutdown,
 };
 use figment::Figment;
 use figment::providers::Serialized;
@@ -36,6 +37,47 @@ fn spawn_signal_handler(cancel: CancellationToken, context: &str) {
     }));
 }
 
+/// Reloads module sections from the YAML file the server was started with.
+///
+/// Uses the same layering as startup (YAML, `APP__` env overrides, `modules_dir` files)
+/// and watches the main file plus the YAML files in `modules_dir`.
+pub struct FileConfigSource {
+    path: PathBuf,
+    modules_dir: Option<PathBuf>,
+}
+
+impl FileConfigSource {
+    #[must_use]
+    pub fn new(path: PathBuf, modules_dir: Option<PathBuf>) -> Self {
+        Self { path, modules_dir }
+    }
+}
+
+impl ConfigSource for FileConfigSource {
+    fn load(&self) -> anyhow::Result<ModuleSections> {
+        Ok(AppConfig::load_layered(&self.path)?.modules)
+    }
+
+    fn watched_files(&self) -> Vec<PathBuf> {
+        let mut files = vec![self.path.clone()];
+        if let Some(dir) = &self.modules_dir
+            && let Ok(entries) = std::fs::read_dir(dir)
+        {
+            files.extend(
+                entries
+                    .filter_map(Result::ok)
+                    .map(|e| e.path())
+                    .filter(|p| {
+                        p.extension().and_then(|e| e.to_str()).is_some_and(|e| {
+                            e.eq_ignore_ascii_case("yaml") || e.eq_ignore_ascii_case("yml")
+                        })
+                    }),
+            );
+        }
+        files
+    }
+}
+
 /// # Errors
 ///
 /// Returns an error if:
@@ -43,6 +85,25 @@ fn spawn_signal_handler(cancel: CancellationToken, context: &str) {
 /// - Problems with the database or third-party services
 /// - An issue during runtime or shutdown
 pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
+    run_server_inner(config, None).await
+}
+
+/// Like [`run_server`], but reloads module configuration from `config_path` on `SIGHUP`
+/// and whenever the file (or a file in `modules_dir`) changes.
+///
+/// # Errors
+///
+/// Same as [`run_server`].
+pub async fn run_server_with_reload(config: AppConfig, config_path: PathBuf) -> anyhow::Result<()> {
+    let modules_dir = config.modules_dir.as_ref().map(PathBuf::from);
+    let source = FileConfigSource::new(config_path, modules_dir);
+    run_server_inner(config, Some(Arc::new(source))).await
+}
+
+async fn run_server_inner(
+    config: AppConfig,
+    config_source: Option<Arc<dyn ConfigSource>>,
+) -> anyhow::Result<()> {
     tracing::info!("Initializing modules...");
 
     // Generate process-level instance ID once at startup.
@@ -77,6 +138,7 @@ pub async fn run_server(config: AppConfig) -> anyhow::Result<()> {
         clients: vec![],
         instance_id,
         oop: oop_options,
+        config_source,
     };
 
     let result = run(run_options).await;
@@ -178,7 +240,8 @@ fn resolve_db_options(config: &AppConfig) -> anyhow::Result<DbOptions> {
 
 /// Build `OoP` spawn configuration from `AppConfig`.
 ///
-/// This collects all modules with `type=oop` and prepares their spawn configuration.
+/// This collects all modules with `type=oop` and prepares their spawn configuration,
+/// plus the pre-started instances of modules with `type=static`.
 /// The actual spawning happens in the `HostRuntime` after the start phase.
 fn build_oop_spawn_options(
     config: &AppConfig,
@@ -186,24 +249,56 @@ fn build_oop_spawn_options(
 ) -> anyhow::Result<Option<OopSpawnOptions>> {
     let home_dir = PathBuf::from(&config.server.home_dir);
     let mut modules = Vec::new();
+    let mut static_modules = Vec::new();
 
     for module_name in config.modules.keys() {
         if let Some(spawn_config) = try_build_oop_module_config(config, module_name, &home_dir)? {
             modules.push(spawn_config);
         }
+        if let Some(static_config) = try_build_static_module_config(config, module_name)? {
+            static_modules.push(static_config);
+        }
     }
 
-    if modules.is_empty() {
+    if modules.is_empty() && static_modules.is_empty() {
         Ok(None)
     } else {
-        tracing::info!(count = modules.len(), "Prepared OoP modules for spawning");
+        tracing::info!(
+            count = modules.len(),
+            static_count = static_modules.len(),
+            "Prepared OoP modules for spawning"
+        );
         Ok(Some(OopSpawnOptions {
             modules,
+            static_modules,
             backend: Box::new(backend),
         }))
     }
 }
 
+/// Try to build the static module config if module is of type `Static`
+fn try_build_static_module_config(
+    config: &AppConfig,
+    module_name: &str,
+) -> anyhow::Result<Option<StaticModuleConfig>> {
+    let Some(runtime_cfg) = get_module_runtime_config(config, module_name)? else {
+        return Ok(None);
+    };
+
+    if !matches!(runtime_cfg.mod_type, RuntimeKind::Static) {
+        return Ok(None);
+    }
+
+    if runtime_cfg.instances.is_empty() {
+        anyhow::bail!("module '{module_name}' is type=static but lists no instances");
+    }
+
+    Ok(Some(StaticModuleConfig {
+        module_name: module_name.to_owned(),
+        instances: runtime_cfg.instances,
+    }))
+}
+
 /// Try to build `OoP` module spawn config if module is of type `OoP`
 fn try_build_oop_module_config(
     config: &AppConfig,
@@ -243,5 +338,6 @@ fn try_build_oop_module_config(
         env,
         working_directory: exec_cfg.working_directory.clone(),
         rendered_config_json: rendered_json,
+        restart: exec_cfg.restart.clone(),
     }))
 }
@@ -11,12 +11,23 @@
 //! - Value = `Arc<T>` stored as `Box<dyn Any + Send + Sync>` (downcast on read).
 //! - Sync hot path: `get()` is non-async; no hidden per-entry cells or lazy slots.
 //!
+//! Layers:
+//! - [`ClientLayer`]s decorate clients (metrics, spans, timeouts, circuit breaking) once,
+//!   instead of in every SDK wrapper. A layer targets one interface type, or every type
+//!   with an instrumented wrapper ([`ClientLayer::instrumented`]); it is added for all
+//!   scopes ([`ClientHub::add_layer`]) or a single one ([`ClientHub::add_scoped_layer`]).
+//! - Layers wrap at registration time and re-wrap existing clients when added, so `get()`
+//!   stays a map lookup. The first layer added is the innermost.
+//!
 //! Notes:
 //! - Re-registering overwrites the previous value atomically; existing Arcs held by consumers remain valid.
 //! - For testing, just register a mock under the same trait type.
 
 use parking_lot::RwLock;
-use std::{any::Any, collections::HashMap, fmt, sync::Arc};
+use std::{any::Any, collections::HashMap, fmt, sync::Arc, sync::LazyLock};
+
+mod instrument;
+pub use instrument::{InstrumentedClient, instrument_call, instrument_sync_call};
 
 /// Stable type key for trait objects — uses fully-qualified `type_name::<T>()`.
 #[derive(Clone, Eq, PartialEq, Hash)]
@@ -112,17 +123,104 @@ pub enum ClientHubError {
 
 type Boxed = Box<dyn Any + Send + Sync>;
 
+type WrapFn = dyn Fn(&TypeKey, Boxed) -> Boxed + Send + Sync;
+
+/// A decorator applied to clients as they are registered in (or already held by) the hub.
+///
+/// Layers must not call back into the hub.
+#[derive(Clone)]
+pub struct ClientLayer {
+    wrap: Arc<WrapFn>,
+}
+
+impl ClientLayer {
+    /// A layer for the interface type `T` (e.g., `dyn my::Api`); other types pass through.
+    pub fn new<T>(layer: impl Fn(Arc<T>) -> Arc<T> + Send + Sync + 'static) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        Self {
+            wrap: Arc::new(move |_, boxed: Boxed| match boxed.downcast::<Arc<T>>() {
+                Ok(client) => Box::new(layer(*client)),
+                Err(other) => other,
+            }),
+        }
+    }
+
+    /// A layer for every interface type with a `#[modkit::instrumented_client]` wrapper:
+    /// each call gets a span and a latency sample labelled by trait and method.
+    #[must_use]
+    pub fn instrumented() -> Self {
+        static WRAPPERS: LazyLock<HashMap<&'static str, ClientLayer>> = LazyLock::new(|| {
+            inventory::iter::<InstrumentedClient>
+                .into_iter()
+                .map(|c| ((c.type_name)(), (c.layer)()))
+                .collect()
+        });
+        Self {
+            wrap: Arc::new(|type_key, boxed| match WRAPPERS.get(type_key.0) {
+                Some(layer) => layer.apply(type_key, boxed),
+                None => boxed,
+            }),
+        }
+    }
+
+    fn apply(&self, type_key: &TypeKey, boxed: Boxed) -> Boxed {
+        (self.wrap)(type_key, boxed)
+    }
+}
+
+impl fmt::Debug for ClientLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientLayer").finish_non_exhaustive()
+    }
+}
+
+/// A registered client: the original (for re-wrapping) and the layered one handed out.
+struct Entry {
+    original: Arc<dyn Fn() -> Boxed + Send + Sync>,
+    resolved: Boxed,
+}
+
+impl Entry {
+    fn new<T>(client: Arc<T>) -> Self
+    where
+        T: ?Sized + Send + Sync + 'static,
+    {
+        Self {
+            resolved: Box::new(client.clone()),
+            original: Arc::new(move || Box::new(client.clone())),
+        }
+    }
+
+    /// Re-applies `layers` (in order) to the original client.
+    fn apply_layers<'a>(
+        &mut self,
+        type_key: &TypeKey,
+        layers: impl Iterator<Item = &'a ClientLayer>,
+    ) {
+        self.resolved = layers.fold((self.original)(), |boxed, layer| {
+            layer.apply(type_key, boxed)
+        });
+    }
+}
+
 /// Internal map type for the client hub.
-type ClientMap = HashMap<TypeKey, Boxed>;
+type ClientMap = HashMap<TypeKey, Entry>;
 
 /// Internal map type for the scoped client hub.
-type ScopedClientMap = HashMap<ScopedKey, Boxed>;
+type ScopedClientMap = HashMap<ScopedKey, Entry>;
+
+/// Layers with the scope they are limited to (`None` = all scopes and unscoped clients).
+type Layers = Vec<(Option<ClientScope>, ClientLayer)>;
 
 /// Type-safe registry of clients keyed by interface type.
 #[derive(Default)]
 pub struct ClientHub {
     map: RwLock<ClientMap>,
     scoped_map: RwLock<ScopedClientMap>,
+    /// Lock order: `layers` before `map` / `scoped_map`.
+    layers: RwLock<Layers>,
 }
 
 impl ClientHub {
@@ -132,10 +230,21 @@ impl ClientHub {
         Self {
             map: RwLock::new(HashMap::new()),
             scoped_map: RwLock::new(HashMap::new()),
+            layers: RwLock::new(Vec::new()),
         }
     }
 }
 
+fn layers_for<'a>(
+    layers: &'a Layers,
+    scope: Option<&'a ClientScope>,
+) -> impl Iterator<Item = &'a ClientLayer> {
+    layers
+        .iter()
+        .filter(move |(only, _)| only.is_none() || only.as_ref() == scope)
+        .map(|(_, layer)| layer)
+}
+
 impl ClientHub {
     /// Register a client under the interface type `T`.
     /// `T` can be a trait object like `dyn my_module::api::MyClient`.
@@ -144,8 +253,11 @@ impl ClientHub {
         T: ?Sized + Send + Sync + 'static,
     {
         let type_key = TypeKey::of::<T>();
+        let layers = self.layers.read();
+        let mut entry = Entry::new(client);
+        entry.apply_layers(&type_key, layers_for(&layers, None));
         let mut w = self.map.write();
-        w.insert(type_key, Box::new(client));
+        w.insert(type_key, entry);
     }
 
     /// Register a scoped client under the interface type `T`.
@@ -160,8 +272,33 @@ impl ClientHub {
             type_key: TypeKey::of::<T>(),
             scope,
         };
+        let layers = self.layers.read();
+        let mut entry = Entry::new(client);
+        entry.apply_layers(&key.type_key, layers_for(&layers, Some(&key.scope)));
         let mut w = self.scoped_map.write();
-        w.insert(key, Box::new(client));
+        w.insert(key, entry);
+    }
+
+    /// Add a layer for all clients, scoped or not; existing clients are re-wrapped.
+    pub fn add_layer(&self, layer: ClientLayer) {
+        self.push_layer(None, layer);
+    }
+
+    /// Add a layer for clients registered under `scope`; existing ones are re-wrapped.
+    pub fn add_scoped_layer(&self, scope: ClientScope, layer: ClientLayer) {
+        self.push_layer(Some(scope), layer);
+    }
+
+    fn push_layer(&self, scope: Option<ClientScope>, layer: ClientLayer) {
+        let mut layers = self.layers.write();
+        layers.push((scope, layer));
+
+        for (key, entry) in self.map.write().iter_mut() {
+            entry.apply_layers(key, layers_for(&layers, None));
+        }
+        for (key, entry) in self.scoped_map.write().iter_mut() {
+            entry.apply_layers(&key.type_key, layers_for(&layers, Some(&key.scope)));
+        }
     }
 
     /// Fetch a client by interface type `T`.
@@ -176,12 +313,12 @@ impl ClientHub {
         let type_key = TypeKey::of::<T>();
         let r = self.map.read();
 
-        let boxed = r.get(&type_key).ok_or(ClientHubError::NotFound {
+        let entry = r.get(&type_key).ok_or(ClientHubError::NotFound {
             type_key: type_key.clone(),
         })?;
 
         // Stored value is exactly `Arc<T>`; downcast is safe and cheap.
-        if let Some(arc_t) = boxed.downcast_ref::<Arc<T>>() {
+        if let Some(arc_t) = entry.resolved.downcast_ref::<Arc<T>>() {
             return Ok(arc_t.clone());
         }
         Err(ClientHubError::TypeMismatch { type_key })
@@ -202,12 +339,12 @@ impl ClientHub {
         };
         let r = self.scoped_map.read();
 
-        let boxed = r.get(&key).ok_or_else(|| ClientHubError::ScopedNotFound {
+        let entry = r.get(&key).ok_or_else(|| ClientHubError::ScopedNotFound {
             type_key: key.type_key.clone(),
             scope: key.scope.clone(),
         })?;
 
-        if let Some(arc_t) = boxed.downcast_ref::<Arc<T>>() {
+        if let Some(arc_t) = entry.resolved.downcast_ref::<Arc<T>>() {
             return Ok(arc_t.clone());
         }
         Err(ClientHubError::ScopedTypeMismatch {
@@ -228,23 +365,23 @@ impl ClientHub {
             scope: scope.clone(),
         };
         let r = self.scoped_map.read();
-        let boxed = r.get(&key)?;
+        let entry = r.get(&key)?;
 
-        boxed.downcast_ref::<Arc<T>>().cloned()
+        entry.resolved.downcast_ref::<Arc<T>>().cloned()
     }
 
-    /// Remove a client by interface type; returns the removed client if it was present.
+    /// Remove a client by interface type; returns the removed (unlayered) client if it was present.
     pub fn remove<T>(&self) -> Option<Arc<T>>
     where
         T: ?Sized + Send + Sync + 'static,
     {
         let type_key = TypeKey::of::<T>();
         let mut w = self.map.write();
-        let boxed = w.remove(&type_key)?;
-        boxed.downcast::<Arc<T>>().ok().map(|b| *b)
+        let entry = w.remove(&type_key)?;
+        (entry.original)().downcast::<Arc<T>>().ok().map(|b| *b)
     }
 
-    /// Remove a scoped client by interface type + scope; returns the removed client if it was present.
+    /// Remove a scoped client by interface type + scope; returns the removed (unlayered) client if it was present.
     pub fn remove_scoped<T>(&self, scope: &ClientScope) -> Option<Arc<T>>
     where
         T: ?Sized + Send + Sync + 'static,
@@ -254,11 +391,11 @@ impl ClientHub {
             scope: scope.clone(),
         };
         let mut w = self.scoped_map.write();
-        let boxed = w.remove(&key)?;
-        boxed.downcast::<Arc<T>>().ok().map(|b| *b)
+        let entry = w.remove(&key)?;
+        (entry.original)().downcast::<Arc<T>>().ok().map(|b| *b)
     }
 
-    /// Clear everything (useful in tests).
+    /// Clear all clients (useful in tests); layers stay installed.
     pub fn clear(&self) {
         self.map.write().clear();
         self.scoped_map.write().clear();
@@ -395,4 +532,67 @@ mod tests {
         let got = hub.try_get_scoped::<str>(&scope);
         assert!(got.is_none());
     }
+
+    struct Tagged {
+        tag: usize,
+        inner: Arc<dyn TestApi>,
+    }
+    #[async_trait::async_trait]
+    impl TestApi for Tagged {
+        async fn id(&self) -> usize {
+            self.inner.id().await * 10 + self.tag
+        }
+    }
+
+    fn tag_layer(tag: usize) -> ClientLayer {
+        ClientLayer::new::<dyn TestApi>(move |inner| Arc::new(Tagged { tag, inner }))
+    }
+
+    #[tokio::test]
+    async fn layers_wrap_new_and_existing_clients_in_order() {
+        let hub = ClientHub::new();
+        hub.register::<dyn TestApi>(Arc::new(ImplA(1)));
+        hub.register::<str>(Arc::from("untouched"));
+
+        hub.add_layer(tag_layer(2));
+        assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 12);
+
+        // The first layer added is the innermost
+        hub.add_layer(tag_layer(3));
+        assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 123);
+
+        hub.register::<dyn TestApi>(Arc::new(ImplA(4)));
+        assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 423);
+        assert_eq!(&*hub.get::<str>().unwrap(), "untouched");
+
+        // Removing hands back the client as registered
+        let removed = hub.remove::<dyn TestApi>().unwrap();
+        assert_eq!(removed.id().await, 4);
+    }
+
+    #[tokio::test]
+    async fn scoped_layers_apply_to_their_scope_only() {
+        let hub = ClientHub::new();
+        let scope_a = ClientScope::new("a");
+        let scope_b = ClientScope::new("b");
+        hub.register::<dyn TestApi>(Arc::new(ImplA(1)));
+        hub.register_scoped::<dyn TestApi>(scope_a.clone(), Arc::new(ImplA(2)));
+
+        hub.add_scoped_layer(scope_a.clone(), tag_layer(5));
+        hub.add_layer(tag_layer(6));
+        hub.register_scoped::<dyn TestApi>(scope_b.clone(), Arc::new(ImplA(3)));
+
+        assert_eq!(hub.get::<dyn TestApi>().unwrap().id().await, 16);
+        assert_eq!(
+            hub.get_scoped::<dyn TestApi>(&scope_a).unwrap().id().await,
+            256
+        );
+        assert_eq!(
+            hub.try_get_scoped::<dyn TestApi>(&scope_b)
+                .unwrap()
+                .id()
+                .await,
+            36
+        );
+    }
 }
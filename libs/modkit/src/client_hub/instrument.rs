@@ -0,0 +1,98 @@
+//! Per-call instrumentation behind `#[modkit::instrumented_client]` wrappers
+//!
+//! Each call runs in a `client.call` span (`rpc.service` = trait, `rpc.method` = method)
+//! and its latency is recorded in the `modkit.client.call.duration` histogram
+//! (seconds) with `client.trait`, `client.method` and `outcome` attributes.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use tracing::Instrument;
+
+use super::ClientLayer;
+
+/// Instrumented wrapper of an interface type, collected by [`ClientLayer::instrumented`].
+///
+/// Submitted through `inventory` by the `#[modkit::instrumented_client]` macro.
+pub struct InstrumentedClient {
+    pub(super) type_name: fn() -> &'static str,
+    pub(super) layer: fn() -> ClientLayer,
+}
+
+impl InstrumentedClient {
+    /// `type_name` is `std::any::type_name::<dyn Trait>`, `layer` wraps `dyn Trait` clients.
+    #[must_use]
+    pub const fn new(type_name: fn() -> &'static str, layer: fn() -> ClientLayer) -> Self {
+        Self { type_name, layer }
+    }
+}
+
+inventory::collect!(InstrumentedClient);
+
+/// Runs an async client call inside a span and records its latency.
+pub async fn instrument_call<F>(
+    client: &'static str,
+    method: &'static str,
+    is_ok: impl FnOnce(&F::Output) -> bool,
+    call: F,
+) -> F::Output
+where
+    F: Future,
+{
+    let span = tracing::debug_span!(
+        "client.call",
+        rpc.service = client,
+        rpc.method = method,
+        otel.kind = "client"
+    );
+    let started = Instant::now();
+    let out = call.instrument(span).await;
+    record(client, method, started.elapsed(), is_ok(&out));
+    out
+}
+
+/// Runs a blocking client call inside a span and records its latency.
+pub fn instrument_sync_call<T>(
+    client: &'static str,
+    method: &'static str,
+    is_ok: impl FnOnce(&T) -> bool,
+    call: impl FnOnce() -> T,
+) -> T {
+    let span = tracing::debug_span!(
+        "client.call",
+        rpc.service = client,
+        rpc.method = method,
+        otel.kind = "client"
+    );
+    let started = Instant::now();
+    let out = span.in_scope(call);
+    record(client, method, started.elapsed(), is_ok(&out));
+    out
+}
+
+#[cfg(feature = "otel")]
+fn record(client: &'static str, method: &'static str, elapsed: Duration, ok: bool) {
+    use std::sync::LazyLock;
+
+    static CALL_DURATION: LazyLock<opentelemetry::metrics::Histogram<f64>> = LazyLock::new(|| {
+        opentelemetry::global::meter("modkit")
+            .f64_histogram("modkit.client.call.duration")
+            .with_unit("s")
+            .with_description("Latency of calls through ClientHub clients")
+            .build()
+    });
+
+    CALL_DURATION.record(
+        elapsed.as_secs_f64(),
+        &[
+            opentelemetry::KeyValue::new("client.trait", client),
+            opentelemetry::KeyValue::new("client.method", method),
+            opentelemetry::KeyValue::new("outcome", if ok { "ok" } else { "error" }),
+        ],
+    );
+}
+
+#[cfg(not(feature = "otel"))]
+fn record(client: &'static str, method: &'static str, elapsed: Duration, ok: bool) {
+    _ = (client, method, elapsed, ok);
+}
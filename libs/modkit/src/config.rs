@@ -121,6 +121,95 @@ pub fn module_config_required<T: DeserializeOwned>(
     Ok(config)
 }
 
+/// A changed module section, delivered to
+/// [`ConfigReloadCapability::on_config_change`](crate::contracts::ConfigReloadCapability::on_config_change).
+///
+/// Sections are the raw module entries (`modules.<name>`); a missing section is `null`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigChange {
+    module: String,
+    previous: serde_json::Value,
+    current: serde_json::Value,
+}
+
+/// Serves a single module section to the typed loaders.
+struct SectionProvider<'a> {
+    module: &'a str,
+    raw: &'a serde_json::Value,
+}
+
+impl ConfigProvider for SectionProvider<'_> {
+    fn get_module_config(&self, module_name: &str) -> Option<&serde_json::Value> {
+        (module_name == self.module && !self.raw.is_null()).then_some(self.raw)
+    }
+}
+
+impl ConfigChange {
+    #[must_use]
+    pub fn new(
+        module: impl Into<String>,
+        previous: serde_json::Value,
+        current: serde_json::Value,
+    ) -> Self {
+        Self {
+            module: module.into(),
+            previous,
+            current,
+        }
+    }
+
+    #[must_use]
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    /// Raw section before the change.
+    #[must_use]
+    pub fn previous_raw(&self) -> &serde_json::Value {
+        &self.previous
+    }
+
+    /// Raw section after the change.
+    #[must_use]
+    pub fn current_raw(&self) -> &serde_json::Value {
+        &self.current
+    }
+
+    /// Typed new configuration, loaded like [`module_config_or_default`].
+    ///
+    /// # Errors
+    /// Returns `ConfigError::InvalidConfig` if the new `config` section cannot be deserialized.
+    pub fn config<T: DeserializeOwned + Default>(&self) -> Result<T, ConfigError> {
+        let provider = SectionProvider {
+            module: &self.module,
+            raw: &self.current,
+        };
+        module_config_or_default(&provider, &self.module)
+    }
+
+    /// Typed previous configuration, loaded like [`module_config_or_default`].
+    ///
+    /// # Errors
+    /// Returns `ConfigError::InvalidConfig` if the previous `config` section cannot be deserialized.
+    pub fn previous_config<T: DeserializeOwned + Default>(&self) -> Result<T, ConfigError> {
+        let provider = SectionProvider {
+            module: &self.module,
+            raw: &self.previous,
+        };
+        module_config_or_default(&provider, &self.module)
+    }
+
+    /// The change that restores the previous section.
+    #[must_use]
+    pub fn reversed(&self) -> Self {
+        Self::new(
+            self.module.clone(),
+            self.current.clone(),
+            self.previous.clone(),
+        )
+    }
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
@@ -381,4 +470,46 @@ mod tests {
             "missing 'config' section in module 'test'"
         );
     }
+
+    // ========== Tests for ConfigChange ==========
+
+    #[test]
+    fn test_config_change_typed_access() {
+        let change = ConfigChange::new(
+            "test_module",
+            json!({ "config": { "api_key": "old", "timeout_ms": 100 } }),
+            json!({ "config": { "api_key": "new", "timeout_ms": 200 } }),
+        );
+
+        let current: TestConfig = change.config().unwrap();
+        let previous: TestConfig = change.previous_config().unwrap();
+        assert_eq!(current.api_key, "new");
+        assert_eq!(previous.timeout_ms, 100);
+
+        let reversed = change.reversed();
+        assert_eq!(reversed.config::<TestConfig>().unwrap().api_key, "old");
+    }
+
+    #[test]
+    fn test_config_change_removed_section_uses_defaults() {
+        let change = ConfigChange::new(
+            "test_module",
+            json!({ "config": { "api_key": "old" } }),
+            serde_json::Value::Null,
+        );
+        assert_eq!(
+            change.config::<TestConfig>().unwrap(),
+            TestConfig::default()
+        );
+
+        let invalid = ConfigChange::new(
+            "test_module",
+            serde_json::Value::Null,
+            json!({ "config": { "timeout_ms": "soon" } }),
+        );
+        assert!(matches!(
+            invalid.config::<TestConfig>(),
+            Err(ConfigError::InvalidConfig { .. })
+        ));
+    }
 }
@@ -208,6 +208,25 @@ impl ModuleCtx {
         })
     }
 
+    /// Get a meter scoped to this module for registering its own counters and histograms.
+    ///
+    /// Instruments record nothing until metrics are enabled (`metrics.enabled` in the app
+    /// config), so create them in `init()`, after the telemetry pipeline is installed.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let created = ctx.meter().u64_counter("users.created").build();
+    /// created.add(1, &[KeyValue::new("source", "signup")]);
+    /// ```
+    #[cfg(feature = "otel")]
+    #[must_use]
+    pub fn meter(&self) -> opentelemetry::metrics::Meter {
+        opentelemetry::global::meter_with_scope(
+            opentelemetry::InstrumentationScope::builder(self.module_name.to_string()).build(),
+        )
+    }
+
     #[must_use]
     pub fn current_module(&self) -> Option<&str> {
         Some(&self.module_name)
@@ -96,6 +96,73 @@ pub trait ApiGatewayCapability: Send + Sync + 'static {
 pub trait RunnableCapability: Send + Sync {
     async fn start(&self, cancel: CancellationToken) -> anyhow::Result<()>;
     async fn stop(&self, cancel: CancellationToken) -> anyhow::Result<()>;
+
+    /// Lifecycle status of the background task, if tracked.
+    ///
+    /// `WithLifecycle` reports it, so module health includes it automatically.
+    fn status(&self) -> Option<crate::lifecycle::Status> {
+        None
+    }
+}
+
+/// Health capability: liveness and readiness checks aggregated into `/healthz` and `/readyz`.
+///
+/// Checks should be cheap (e.g., a DB ping or a cached upstream state); the runtime
+/// runs them concurrently and marks a module `down` when a check exceeds its timeout.
+#[async_trait]
+pub trait HealthCapability: Send + Sync {
+    /// Is the module alive? A failing liveness check asks the orchestrator to restart the process.
+    ///
+    /// Default implementation reports no checks (alive).
+    async fn liveness(&self) -> crate::health::HealthReport {
+        crate::health::HealthReport::up()
+    }
+
+    /// Can the module serve traffic (DB reachable, upstream clients connected, ...)?
+    async fn readiness(&self) -> crate::health::HealthReport;
+}
+
+/// Config reload capability: apply changed module configuration without a restart.
+#[async_trait]
+pub trait ConfigReloadCapability: Send + Sync {
+    /// Check the module's new configuration without applying it.
+    ///
+    /// Every changed module is validated before any module applies its change, so a
+    /// refusal here leaves all modules untouched. Default implementation accepts everything.
+    ///
+    /// # Errors
+    /// Returns an error if the new configuration is invalid.
+    async fn validate_config_change(
+        &self,
+        _change: &crate::config::ConfigChange,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Apply the module's new configuration.
+    ///
+    /// Called only when the module's section changed and every module validated its change.
+    /// Returning an error still rejects the whole reload: modules that already applied it are
+    /// called again with [`ConfigChange::reversed`](crate::config::ConfigChange::reversed).
+    ///
+    /// # Errors
+    /// Returns an error if the new configuration is invalid or cannot be applied.
+    async fn on_config_change(&self, change: &crate::config::ConfigChange) -> anyhow::Result<()>;
+}
+
+/// Scheduled jobs capability: periodic work (cache refresh, cleanup) run by the runtime.
+///
+/// Jobs are collected after the start phase and stopped before it, each under a
+/// child `CancellationToken`; see [`crate::jobs`] for scheduling semantics.
+pub trait ScheduledJobsCapability: Send + Sync {
+    /// Jobs of this module. The context gives access to clients and the module's database.
+    ///
+    /// # Errors
+    /// Returns an error if a job cannot be built (e.g., an invalid cron expression).
+    fn jobs(
+        &self,
+        ctx: &crate::context::ModuleCtx,
+    ) -> anyhow::Result<Vec<crate::jobs::ScheduledJob>>;
 }
 
 /// Represents a gRPC service registration callback used by the gRPC hub.
@@ -126,6 +193,14 @@ pub trait GrpcServiceCapability: Send + Sync {
         &self,
         ctx: &crate::context::ModuleCtx,
     ) -> anyhow::Result<Vec<RegisterGrpcServiceFn>>;
+
+    /// Encoded `FileDescriptorSet`s describing the exported services.
+    ///
+    /// Served by `grpc-hub` through `grpc.reflection.v1` when reflection is enabled;
+    /// typically `tonic::include_file_descriptor_set!` output from the SDK's build script.
+    fn file_descriptor_sets(&self) -> Vec<&'static [u8]> {
+        Vec::new()
+    }
 }
 
 /// gRPC Hub capability: hosts the gRPC server.
@@ -0,0 +1,764 @@
+//! In-process event bus with typed topics.
+//!
+//! Events are serde types identified by their GTS schema, which is also the topic
+//! name. The runtime publishes one [`EventBus`] in the `ClientHub`; modules publish
+//! with [`EventBus::publish`] and consume with [`EventBus::subscribe`].
+//!
+//! Every subscriber has a bounded queue. A publisher waits while a queue is full
+//! instead of dropping events, and a [`Delivery`] dropped without [`Delivery::ack`]
+//! (e.g. because the handler returned early) is delivered again by the next `recv`,
+//! so in-process delivery is at-least-once.
+//!
+//! Remote subscribers attached through the bus's [`EventBusClient`] implementation
+//! (out-of-process modules) never make a publisher wait: when their queue is full the
+//! event is dropped for them and counted in [`EventBus::dropped_count`]. The next event
+//! that reaches them carries the number dropped before it in [`EventRecord::missed`].
+//!
+//! In an out-of-process module the bus relays through the host's bus via an
+//! [`EventBusClient`] upstream: publishes are sent to the host, and each topic is
+//! bridged from the host on its first local subscription. The host acknowledges events
+//! as soon as they are streamed, so delivery there is at-most-once: events dropped in
+//! the host show up as [`Delivery::missed`], and events published while the bridge
+//! reconnects are lost without a signal.
+
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::ops::Deref;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use futures_util::stream;
+use gts::{GtsID, GtsSchema};
+use parking_lot::RwLock;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use crate::telemetry::ThrottledLog;
+
+pub use cf_system_sdks::directory::{EventBusClient, EventRecord, EventStream};
+
+/// Default capacity of a subscriber's queue.
+pub const DEFAULT_QUEUE_CAPACITY: usize = 256;
+
+const BRIDGE_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const BRIDGE_MAX_BACKOFF: Duration = Duration::from_secs(10);
+const DROP_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// An event type; its GTS schema identifier is the topic it is published on.
+pub trait Event: GtsSchema + Serialize + DeserializeOwned + Send + Sync + 'static {}
+
+impl<T> Event for T where T: GtsSchema + Serialize + DeserializeOwned + Send + Sync + 'static {}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EventBusError {
+    #[error("invalid topic '{0}': expected a GTS schema identifier")]
+    InvalidTopic(String),
+    #[error("failed to encode event for topic '{topic}'")]
+    Encode {
+        topic: String,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("failed to publish to the host event bus")]
+    Upstream(#[source] anyhow::Error),
+}
+
+/// Publish/subscribe hub for events between modules.
+#[derive(Default)]
+pub struct EventBus {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    topics: RwLock<HashMap<String, Vec<Subscriber>>>,
+    queue_capacity: usize,
+    upstream: RwLock<Option<Upstream>>,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        Self {
+            topics: RwLock::new(HashMap::new()),
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            upstream: RwLock::new(None),
+        }
+    }
+}
+
+struct Subscriber {
+    name: String,
+    tx: mpsc::Sender<EventRecord>,
+    /// Set for remote subscribers, which drop events instead of blocking the publisher.
+    overflow: Option<Arc<Overflow>>,
+}
+
+/// Events dropped for a remote subscriber because its queue was full.
+struct Overflow {
+    dropped: AtomicU64,
+    /// Dropped since the last event that made it into the queue.
+    gap: AtomicU64,
+    log: ThrottledLog,
+}
+
+struct Upstream {
+    client: Arc<dyn EventBusClient>,
+    cancel: CancellationToken,
+    bridged: HashSet<String>,
+}
+
+impl EventBus {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `capacity` (at least 1) for the queue of every new subscriber.
+    #[must_use]
+    pub fn with_queue_capacity(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                queue_capacity: capacity.max(1),
+                ..Inner::default()
+            }),
+        }
+    }
+
+    /// Relay through the host's bus: publishes go to `client`, and subscribed topics
+    /// are bridged from it until `cancel` fires.
+    pub fn set_upstream(&self, client: Arc<dyn EventBusClient>, cancel: CancellationToken) {
+        let topics: Vec<(String, String)> = self
+            .inner
+            .topics
+            .read()
+            .iter()
+            .filter_map(|(topic, subs)| subs.first().map(|s| (topic.clone(), s.name.clone())))
+            .collect();
+        *self.inner.upstream.write() = Some(Upstream {
+            client,
+            cancel,
+            bridged: HashSet::new(),
+        });
+        for (topic, subscriber) in topics {
+            Inner::ensure_bridged(&self.inner, &topic, &subscriber);
+        }
+    }
+
+    /// Publish `event` on its schema's topic.
+    ///
+    /// Waits while a subscriber's queue is full.
+    ///
+    /// # Errors
+    /// Returns an error if the event cannot be encoded or the host bus rejects it.
+    pub async fn publish<E: Event>(&self, event: &E) -> Result<(), EventBusError> {
+        let topic = E::SCHEMA_ID;
+        let payload = serde_json::to_string(event).map_err(|source| EventBusError::Encode {
+            topic: topic.to_owned(),
+            source,
+        })?;
+        self.publish_record(EventRecord {
+            id: Uuid::now_v7().to_string(),
+            topic: topic.to_owned(),
+            payload,
+            source: None,
+            missed: 0,
+        })
+        .await
+    }
+
+    /// Publish an already encoded event.
+    ///
+    /// # Errors
+    /// Returns an error if the topic is not a GTS schema identifier or the host bus
+    /// rejects the event.
+    pub async fn publish_record(&self, mut record: EventRecord) -> Result<(), EventBusError> {
+        validate_topic(&record.topic)?;
+        record.missed = 0;
+        let upstream = self
+            .inner
+            .upstream
+            .read()
+            .as_ref()
+            .map(|u| Arc::clone(&u.client));
+        if let Some(client) = upstream {
+            client
+                .publish(record)
+                .await
+                .map_err(EventBusError::Upstream)
+        } else {
+            self.inner.deliver(record).await;
+            Ok(())
+        }
+    }
+
+    /// Subscribe to events of type `E`; `subscriber` names the consumer in logs.
+    ///
+    /// # Errors
+    /// Returns an error if the schema identifier of `E` is not a valid topic.
+    pub fn subscribe<E: Event>(&self, subscriber: &str) -> Result<Subscription<E>, EventBusError> {
+        Ok(Subscription {
+            raw: self.subscribe_raw(E::SCHEMA_ID, subscriber)?,
+            _event: PhantomData,
+        })
+    }
+
+    /// Subscribe to encoded events of `topic`.
+    ///
+    /// # Errors
+    /// Returns an error if the topic is not a GTS schema identifier.
+    pub fn subscribe_raw(
+        &self,
+        topic: &str,
+        subscriber: &str,
+    ) -> Result<RawSubscription, EventBusError> {
+        self.subscribe_with(topic, subscriber, None)
+    }
+
+    fn subscribe_with(
+        &self,
+        topic: &str,
+        subscriber: &str,
+        overflow: Option<Arc<Overflow>>,
+    ) -> Result<RawSubscription, EventBusError> {
+        validate_topic(topic)?;
+        let (tx, rx) = mpsc::channel(self.inner.queue_capacity);
+        let remote = overflow.is_some();
+        self.inner
+            .topics
+            .write()
+            .entry(topic.to_owned())
+            .or_default()
+            .push(Subscriber {
+                name: subscriber.to_owned(),
+                tx,
+                overflow,
+            });
+        Inner::ensure_bridged(&self.inner, topic, subscriber);
+
+        tracing::debug!(topic, subscriber, remote, "Event subscription created");
+        Ok(RawSubscription {
+            topic: topic.to_owned(),
+            rx,
+            pending: None,
+        })
+    }
+
+    /// Number of live subscribers of `topic`.
+    #[must_use]
+    pub fn subscriber_count(&self, topic: &str) -> usize {
+        self.inner
+            .topics
+            .read()
+            .get(topic)
+            .map_or(0, |subs| subs.iter().filter(|s| !s.tx.is_closed()).count())
+    }
+
+    /// Number of `topic` events dropped for live remote subscribers whose queue was full.
+    #[must_use]
+    pub fn dropped_count(&self, topic: &str) -> u64 {
+        self.inner.topics.read().get(topic).map_or(0, |subs| {
+            subs.iter()
+                .filter_map(|s| s.overflow.as_ref())
+                .map(|o| o.dropped.load(Ordering::Relaxed))
+                .sum()
+        })
+    }
+}
+
+impl Inner {
+    /// Deliver `record` to every live subscriber of its topic.
+    ///
+    /// Waits for queue space of local subscribers; remote ones drop the event instead and
+    /// learn about the gap from the next event they receive.
+    async fn deliver(&self, record: EventRecord) {
+        type Target = (String, mpsc::Sender<EventRecord>, Option<Arc<Overflow>>);
+        let targets: Vec<Target> = self
+            .topics
+            .read()
+            .get(&record.topic)
+            .map(|subs| {
+                subs.iter()
+                    .map(|s| (s.name.clone(), s.tx.clone(), s.overflow.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut closed = false;
+        for (name, tx, overflow) in targets {
+            if let Some(overflow) = overflow {
+                let mut remote = record.clone();
+                remote.missed = overflow.gap.swap(0, Ordering::Relaxed);
+                match tx.try_send(remote) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(remote)) => {
+                        overflow.gap.fetch_add(remote.missed + 1, Ordering::Relaxed);
+                        let dropped = overflow.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                        if overflow.log.should_log() {
+                            tracing::warn!(
+                                topic = %record.topic,
+                                subscriber = %name,
+                                dropped,
+                                "Remote subscriber queue full, dropping events"
+                            );
+                        }
+                    }
+                    Err(mpsc::error::TrySendError::Closed(_)) => closed = true,
+                }
+                continue;
+            }
+            if tx.capacity() == 0 {
+                tracing::debug!(topic = %record.topic, subscriber = %name, "Subscriber queue full, waiting");
+            }
+            closed |= tx.send(record.clone()).await.is_err();
+        }
+
+        if closed && let Some(subs) = self.topics.write().get_mut(&record.topic) {
+            subs.retain(|s| !s.tx.is_closed());
+        }
+    }
+
+    /// Start relaying `topic` from the upstream bus, once per topic.
+    fn ensure_bridged(inner: &Arc<Self>, topic: &str, subscriber: &str) {
+        let (client, cancel) = {
+            let mut upstream = inner.upstream.write();
+            let Some(upstream) = upstream.as_mut() else {
+                return;
+            };
+            if !upstream.bridged.insert(topic.to_owned()) {
+                return;
+            }
+            (Arc::clone(&upstream.client), upstream.cancel.clone())
+        };
+        tokio::spawn(bridge(
+            Arc::downgrade(inner),
+            client,
+            topic.to_owned(),
+            subscriber.to_owned(),
+            cancel,
+        ));
+    }
+}
+
+/// Relay `topic` from the upstream bus into local subscribers, reconnecting with backoff.
+async fn bridge(
+    inner: std::sync::Weak<Inner>,
+    client: Arc<dyn EventBusClient>,
+    topic: String,
+    subscriber: String,
+    cancel: CancellationToken,
+) {
+    let mut backoff = BRIDGE_INITIAL_BACKOFF;
+    loop {
+        let stream = tokio::select! {
+            () = cancel.cancelled() => return,
+            r = client.subscribe(&topic, &subscriber) => r,
+        };
+        match stream {
+            Ok(mut events) => {
+                tracing::info!(topic = %topic, "Bridging events from host event bus");
+                backoff = BRIDGE_INITIAL_BACKOFF;
+                loop {
+                    let item = tokio::select! {
+                        () = cancel.cancelled() => return,
+                        item = events.next() => item,
+                    };
+                    match item {
+                        Some(Ok(record)) => {
+                            let Some(inner) = inner.upgrade() else {
+                                return;
+                            };
+                            if record.missed > 0 {
+                                tracing::warn!(
+                                    topic = %topic,
+                                    missed = record.missed,
+                                    "Host event bus dropped events for this module"
+                                );
+                            }
+                            inner.deliver(record).await;
+                        }
+                        Some(Err(e)) => {
+                            tracing::warn!(topic = %topic, error = %e, "Host event stream failed");
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::warn!(topic = %topic, error = %e, "Failed to subscribe on host event bus");
+            }
+        }
+
+        tokio::select! {
+            () = cancel.cancelled() => return,
+            () = tokio::time::sleep(backoff) => {}
+        }
+        backoff = (backoff * 2).min(BRIDGE_MAX_BACKOFF);
+    }
+}
+
+fn validate_topic(topic: &str) -> Result<(), EventBusError> {
+    if topic.ends_with('~') && GtsID::is_valid(topic) {
+        Ok(())
+    } else {
+        Err(EventBusError::InvalidTopic(topic.to_owned()))
+    }
+}
+
+/// Host side of the gRPC bridge: `OoP` modules publish and subscribe through this.
+///
+/// Subscriptions made here are remote: a slow or stuck process loses events instead of
+/// stalling publishers in the host. Events are acknowledged once streamed, and the next
+/// streamed event reports how many were dropped before it in [`EventRecord::missed`].
+#[async_trait]
+impl EventBusClient for EventBus {
+    async fn publish(&self, event: EventRecord) -> anyhow::Result<()> {
+        Ok(self.publish_record(event).await?)
+    }
+
+    async fn subscribe(&self, topic: &str, subscriber: &str) -> anyhow::Result<EventStream> {
+        let overflow = Arc::new(Overflow {
+            dropped: AtomicU64::new(0),
+            gap: AtomicU64::new(0),
+            log: ThrottledLog::new(DROP_LOG_INTERVAL),
+        });
+        let sub = self.subscribe_with(topic, subscriber, Some(overflow))?;
+        Ok(Box::pin(stream::unfold(sub, |mut sub| async move {
+            let record = {
+                let delivery = sub.recv().await?;
+                let record = delivery.record().clone();
+                delivery.ack();
+                record
+            };
+            Some((Ok(record), sub))
+        })))
+    }
+}
+
+/// Subscription to encoded events of one topic.
+pub struct RawSubscription {
+    topic: String,
+    rx: mpsc::Receiver<EventRecord>,
+    pending: Option<(EventRecord, u32)>,
+}
+
+impl RawSubscription {
+    /// Topic of this subscription.
+    #[must_use]
+    pub fn topic(&self) -> &str {
+        &self.topic
+    }
+
+    /// Wait for the next event; an unacknowledged previous event comes first.
+    ///
+    /// Returns `None` once the bus is gone.
+    pub async fn recv(&mut self) -> Option<RawDelivery<'_>> {
+        let (record, attempt) = match self.pending.take() {
+            Some(pending) => pending,
+            None => (self.rx.recv().await?, 1),
+        };
+        Some(RawDelivery {
+            slot: &mut self.pending,
+            record: Some(record),
+            attempt,
+        })
+    }
+}
+
+/// An encoded event handed to a subscriber; redelivered unless acknowledged.
+pub struct RawDelivery<'a> {
+    slot: &'a mut Option<(EventRecord, u32)>,
+    record: Option<EventRecord>,
+    attempt: u32,
+}
+
+impl RawDelivery<'_> {
+    /// The delivered event.
+    #[must_use]
+    pub fn record(&self) -> &EventRecord {
+        // Only `ack` and `drop` take the record, and both consume the delivery
+        self.record.as_ref().unwrap_or_else(|| unreachable!())
+    }
+
+    /// How often this event has been delivered to this subscriber (starting at 1).
+    #[must_use]
+    pub fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    /// Mark the event as processed.
+    pub fn ack(mut self) {
+        self.record = None;
+    }
+}
+
+impl Drop for RawDelivery<'_> {
+    fn drop(&mut self) {
+        if let Some(record) = self.record.take() {
+            *self.slot = Some((record, self.attempt + 1));
+        }
+    }
+}
+
+/// Subscription to events of type `E`.
+pub struct Subscription<E> {
+    raw: RawSubscription,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E: Event> Subscription<E> {
+    /// Wait for the next event; an unacknowledged previous event comes first.
+    ///
+    /// Events whose payload does not decode as `E` are logged and skipped.
+    /// Returns `None` once the bus is gone.
+    pub async fn recv(&mut self) -> Option<Delivery<'_, E>> {
+        let event = loop {
+            let (record, _) = if let Some(pending) = &self.raw.pending {
+                pending
+            } else {
+                let record = self.raw.rx.recv().await?;
+                self.raw.pending.insert((record, 1))
+            };
+            match serde_json::from_str::<E>(&record.payload) {
+                Ok(event) => break event,
+                Err(e) => {
+                    tracing::warn!(
+                        topic = %record.topic,
+                        event_id = %record.id,
+                        error = %e,
+                        "Skipping event that does not match its schema"
+                    );
+                    self.raw.pending = None;
+                }
+            }
+        };
+        let raw = self.raw.recv().await?;
+        Some(Delivery { raw, event })
+    }
+}
+
+/// An event handed to a subscriber; redelivered unless acknowledged.
+pub struct Delivery<'a, E> {
+    raw: RawDelivery<'a>,
+    event: E,
+}
+
+impl<E> Delivery<'_, E> {
+    /// Unique identifier of the event.
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.raw.record().id
+    }
+
+    /// How often this event has been delivered to this subscriber (starting at 1).
+    #[must_use]
+    pub fn attempt(&self) -> u32 {
+        self.raw.attempt()
+    }
+
+    /// Events of this topic lost right before this one on the way from the host bus.
+    ///
+    /// Always 0 in the host process. In an out-of-process module a non-zero value means
+    /// the host dropped events for it; resynchronize state derived from the topic.
+    #[must_use]
+    pub fn missed(&self) -> u64 {
+        self.raw.record().missed
+    }
+
+    /// Mark the event as processed.
+    pub fn ack(self) {
+        self.raw.ack();
+    }
+}
+
+impl<E> Deref for Delivery<'_, E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        &self.event
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use tokio::time::timeout;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct TenantChanged {
+        tenant_id: u32,
+    }
+
+    impl GtsSchema for TenantChanged {
+        const SCHEMA_ID: &'static str = "gts.x.core.tenants.tenant_changed.v1~";
+
+        fn gts_schema_with_refs() -> serde_json::Value {
+            serde_json::json!({ "type": "object" })
+        }
+    }
+
+    const WAIT: Duration = Duration::from_secs(2);
+
+    #[tokio::test]
+    async fn every_subscriber_receives_published_events() {
+        let bus = EventBus::new();
+        let mut cache_a = bus.subscribe::<TenantChanged>("cache-a").unwrap();
+        let mut cache_b = bus.subscribe::<TenantChanged>("cache-b").unwrap();
+
+        bus.publish(&TenantChanged { tenant_id: 7 }).await.unwrap();
+
+        for sub in [&mut cache_a, &mut cache_b] {
+            let delivery = sub.recv().await.unwrap();
+            assert_eq!(*delivery, TenantChanged { tenant_id: 7 });
+            assert_eq!(delivery.attempt(), 1);
+            delivery.ack();
+        }
+        assert_eq!(bus.subscriber_count(TenantChanged::SCHEMA_ID), 2);
+    }
+
+    #[tokio::test]
+    async fn unacknowledged_event_is_redelivered() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe::<TenantChanged>("cache").unwrap();
+        bus.publish(&TenantChanged { tenant_id: 1 }).await.unwrap();
+        bus.publish(&TenantChanged { tenant_id: 2 }).await.unwrap();
+
+        let first_id = {
+            let delivery = sub.recv().await.unwrap();
+            delivery.id().to_owned()
+            // dropped without ack: handler failed
+        };
+        let delivery = sub.recv().await.unwrap();
+        assert_eq!(delivery.id(), first_id);
+        assert_eq!(delivery.tenant_id, 1);
+        assert_eq!(delivery.attempt(), 2);
+        delivery.ack();
+
+        assert_eq!(sub.recv().await.unwrap().tenant_id, 2);
+    }
+
+    #[tokio::test]
+    async fn full_queue_applies_backpressure() {
+        let bus = EventBus::with_queue_capacity(1);
+        let mut sub = bus.subscribe::<TenantChanged>("slow").unwrap();
+        bus.publish(&TenantChanged { tenant_id: 1 }).await.unwrap();
+
+        let blocked = timeout(
+            Duration::from_millis(100),
+            bus.publish(&TenantChanged { tenant_id: 2 }),
+        )
+        .await;
+        assert!(blocked.is_err(), "publish must wait for queue space");
+
+        let (published, received) =
+            tokio::join!(bus.publish(&TenantChanged { tenant_id: 3 }), async {
+                let d = sub.recv().await.unwrap();
+                let id = d.tenant_id;
+                d.ack();
+                id
+            });
+        published.unwrap();
+        assert_eq!(received, 1);
+        assert_eq!(sub.recv().await.unwrap().tenant_id, 3);
+    }
+
+    #[tokio::test]
+    async fn full_remote_queue_drops_events() {
+        let bus = EventBus::with_queue_capacity(1);
+        let mut remote = EventBusClient::subscribe(&bus, TenantChanged::SCHEMA_ID, "oop-cache")
+            .await
+            .unwrap();
+        for tenant_id in 1..=3 {
+            timeout(WAIT, bus.publish(&TenantChanged { tenant_id }))
+                .await
+                .expect("publish must not wait for a remote subscriber")
+                .unwrap();
+        }
+        assert_eq!(bus.dropped_count(TenantChanged::SCHEMA_ID), 2);
+
+        let record = remote.next().await.unwrap().unwrap();
+        assert_eq!(record.payload, r#"{"tenant_id":1}"#);
+        assert_eq!(record.missed, 0);
+
+        // The next event that gets through reports the gap before it
+        bus.publish(&TenantChanged { tenant_id: 4 }).await.unwrap();
+        let record = remote.next().await.unwrap().unwrap();
+        assert_eq!(record.payload, r#"{"tenant_id":4}"#);
+        assert_eq!(record.missed, 2);
+    }
+
+    #[tokio::test]
+    async fn undecodable_events_are_skipped() {
+        let bus = EventBus::new();
+        let mut sub = bus.subscribe::<TenantChanged>("cache").unwrap();
+        for payload in [r#"{"unexpected":true}"#, r#"{"tenant_id":5}"#] {
+            bus.publish_record(EventRecord {
+                id: Uuid::now_v7().to_string(),
+                topic: TenantChanged::SCHEMA_ID.to_owned(),
+                payload: payload.to_owned(),
+                source: None,
+                missed: 0,
+            })
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(sub.recv().await.unwrap().tenant_id, 5);
+    }
+
+    #[tokio::test]
+    async fn topics_must_be_gts_schema_ids() {
+        let bus = EventBus::new();
+        assert!(matches!(
+            bus.subscribe_raw("tenant_changed", "cache"),
+            Err(EventBusError::InvalidTopic(_))
+        ));
+        assert!(matches!(
+            bus.subscribe_raw("gts.x.core.tenants.tenant_changed.v1~x.y.z.w.v1", "cache"),
+            Err(EventBusError::InvalidTopic(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn upstream_relays_publishes_and_bridges_topics() {
+        let host = Arc::new(EventBus::new());
+        let mut host_sub = host.subscribe::<TenantChanged>("host-cache").unwrap();
+
+        let cancel = CancellationToken::new();
+        let oop = EventBus::new();
+        let mut oop_sub = oop.subscribe::<TenantChanged>("oop-cache").unwrap();
+        oop.set_upstream(host.clone(), cancel.clone());
+
+        // Wait until the bridge subscribed on the host
+        timeout(WAIT, async {
+            while host.subscriber_count(TenantChanged::SCHEMA_ID) < 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // Published in the OoP module, delivered on both sides exactly once
+        oop.publish(&TenantChanged { tenant_id: 9 }).await.unwrap();
+        let host_event = timeout(WAIT, host_sub.recv()).await.unwrap().unwrap();
+        assert_eq!(host_event.tenant_id, 9);
+        host_event.ack();
+        let oop_event = timeout(WAIT, oop_sub.recv()).await.unwrap().unwrap();
+        assert_eq!(oop_event.tenant_id, 9);
+        oop_event.ack();
+        assert!(
+            timeout(Duration::from_millis(100), oop_sub.recv())
+                .await
+                .is_err()
+        );
+
+        cancel.cancel();
+    }
+}
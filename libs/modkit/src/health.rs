@@ -0,0 +1,426 @@
+//! Module health: liveness/readiness reports and their per-module aggregation.
+//!
+//! Modules opt in with the `health` capability and implement
+//! [`HealthCapability`](crate::contracts::HealthCapability). The runtime builds a
+//! [`HealthRegistry`] over all modules, folds in the lifecycle [`Status`] of stateful
+//! modules and publishes it in the `ClientHub`, where the REST host picks it up to
+//! serve `/healthz` (liveness) and `/readyz` (readiness).
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::contracts::{HealthCapability, RunnableCapability};
+use crate::lifecycle::Status;
+use crate::registry::{HealthCap, ModuleRegistry, RunnableCap};
+
+/// Default timeout for a single module check.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Health of a check, a module or the whole process. Ordered from best to worst.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    /// Working with reduced functionality; probes still succeed.
+    Degraded,
+    Down,
+}
+
+impl HealthStatus {
+    /// Whether a probe reporting this status should succeed.
+    #[must_use]
+    pub fn is_passing(self) -> bool {
+        self != HealthStatus::Down
+    }
+}
+
+/// One named check with optional free-form details.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HealthCheck {
+    pub name: String,
+    pub status: HealthStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+/// Result of a module's liveness or readiness checks.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[must_use]
+pub struct HealthReport {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthReport {
+    /// A report without checks (healthy).
+    pub fn up() -> Self {
+        Self::default()
+    }
+
+    /// Add a check.
+    pub fn with_check(
+        mut self,
+        name: impl Into<String>,
+        status: HealthStatus,
+        details: Option<Value>,
+    ) -> Self {
+        self.checks.push(HealthCheck {
+            name: name.into(),
+            status,
+            details,
+        });
+        self
+    }
+
+    /// Add a check from a probe result: `Ok` is `up`, `Err` is `down` with the error in details.
+    pub fn with_result<T, E: Display>(
+        self,
+        name: impl Into<String>,
+        result: &Result<T, E>,
+    ) -> Self {
+        match result {
+            Ok(_) => self.with_check(name, HealthStatus::Up, None),
+            Err(e) => self.with_check(
+                name,
+                HealthStatus::Down,
+                Some(json!({ "error": e.to_string() })),
+            ),
+        }
+    }
+
+    /// Worst status of all checks; `up` without checks.
+    #[must_use]
+    pub fn status(&self) -> HealthStatus {
+        self.checks
+            .iter()
+            .map(|c| c.status)
+            .max()
+            .unwrap_or(HealthStatus::Up)
+    }
+}
+
+/// Health of one module in a [`HealthSummary`].
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ModuleHealth {
+    pub status: HealthStatus,
+    /// Lifecycle status of stateful modules (`stopped`, `starting`, `running`, `stopping`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lifecycle: Option<&'static str>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<HealthCheck>,
+}
+
+/// Aggregated health with per-module breakdown, as served by `/healthz` and `/readyz`.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct HealthSummary {
+    pub status: HealthStatus,
+    pub modules: BTreeMap<&'static str, ModuleHealth>,
+}
+
+#[derive(Clone, Copy)]
+enum Probe {
+    Liveness,
+    Readiness,
+}
+
+struct HealthEntry {
+    name: &'static str,
+    checks: Option<Arc<dyn HealthCapability>>,
+    runnable: Option<Arc<dyn RunnableCapability>>,
+}
+
+/// Aggregates module health checks and lifecycle status.
+///
+/// Readiness stays `down` until the runtime has started all modules and turns
+/// `down` again once shutdown begins (see [`set_serving`](Self::set_serving)).
+pub struct HealthRegistry {
+    modules: Vec<HealthEntry>,
+    check_timeout: Duration,
+    serving: AtomicBool,
+}
+
+impl Default for HealthRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthRegistry {
+    /// Empty registry; not serving until [`set_serving`](Self::set_serving) is called.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            modules: Vec::new(),
+            check_timeout: DEFAULT_CHECK_TIMEOUT,
+            serving: AtomicBool::new(false),
+        }
+    }
+
+    /// Collect modules with the `health` or `stateful` capability.
+    #[must_use]
+    pub fn from_registry(registry: &ModuleRegistry) -> Self {
+        registry
+            .modules()
+            .iter()
+            .fold(Self::new(), |health, entry| {
+                health.with_module(
+                    entry.name(),
+                    entry.caps().query::<HealthCap>(),
+                    entry.caps().query::<RunnableCap>(),
+                )
+            })
+    }
+
+    /// Add a module; modules with neither checks nor a runnable are skipped.
+    #[must_use]
+    pub fn with_module(
+        mut self,
+        name: &'static str,
+        checks: Option<Arc<dyn HealthCapability>>,
+        runnable: Option<Arc<dyn RunnableCapability>>,
+    ) -> Self {
+        if checks.is_some() || runnable.is_some() {
+            self.modules.push(HealthEntry {
+                name,
+                checks,
+                runnable,
+            });
+        }
+        self
+    }
+
+    /// Timeout for a single module check (default: [`DEFAULT_CHECK_TIMEOUT`]).
+    #[must_use]
+    pub fn with_check_timeout(mut self, timeout: Duration) -> Self {
+        self.check_timeout = timeout;
+        self
+    }
+
+    /// Mark whether the process accepts traffic; set by the runtime after start and before stop.
+    pub fn set_serving(&self, serving: bool) {
+        self.serving.store(serving, Ordering::Release);
+    }
+
+    #[must_use]
+    pub fn is_serving(&self) -> bool {
+        self.serving.load(Ordering::Acquire)
+    }
+
+    /// Liveness of all modules.
+    ///
+    /// Only the modules' own liveness checks count: a stateful module whose task has
+    /// exited is reported by readiness, since restarting the process would not fix it.
+    pub async fn liveness(&self) -> HealthSummary {
+        self.collect(Probe::Liveness).await
+    }
+
+    /// Readiness of all modules. Stateful modules must be `running`.
+    pub async fn readiness(&self) -> HealthSummary {
+        let mut summary = self.collect(Probe::Readiness).await;
+        if !self.is_serving() {
+            summary.status = HealthStatus::Down;
+        }
+        summary
+    }
+
+    async fn collect(&self, probe: Probe) -> HealthSummary {
+        let modules: BTreeMap<_, _> = join_all(
+            self.modules
+                .iter()
+                .map(|entry| async move { (entry.name, self.module_health(entry, probe).await) }),
+        )
+        .await
+        .into_iter()
+        .collect();
+
+        let status = modules
+            .values()
+            .map(|m| m.status)
+            .max()
+            .unwrap_or(HealthStatus::Up);
+        HealthSummary { status, modules }
+    }
+
+    async fn module_health(&self, entry: &HealthEntry, probe: Probe) -> ModuleHealth {
+        let report = match &entry.checks {
+            Some(checks) => {
+                let run = async {
+                    match probe {
+                        Probe::Liveness => checks.liveness().await,
+                        Probe::Readiness => checks.readiness().await,
+                    }
+                };
+                tokio::time::timeout(self.check_timeout, run)
+                    .await
+                    .unwrap_or_else(|_| {
+                        HealthReport::up().with_check(
+                            "timeout",
+                            HealthStatus::Down,
+                            Some(json!({ "timeout_ms": self.check_timeout.as_millis() })),
+                        )
+                    })
+            }
+            None => HealthReport::up(),
+        };
+
+        let lifecycle = entry.runnable.as_ref().and_then(|r| r.status());
+        let lifecycle_status = match (probe, lifecycle) {
+            (Probe::Liveness, _) | (_, None | Some(Status::Running)) => HealthStatus::Up,
+            (Probe::Readiness, Some(_)) => HealthStatus::Down,
+        };
+
+        ModuleHealth {
+            status: lifecycle_status.max(report.status()),
+            lifecycle: lifecycle.map(Status::as_str),
+            checks: report.checks,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use tokio_util::sync::CancellationToken;
+
+    struct Checks(HealthReport);
+
+    #[async_trait]
+    impl HealthCapability for Checks {
+        async fn readiness(&self) -> HealthReport {
+            self.0.clone()
+        }
+    }
+
+    struct Slow;
+
+    #[async_trait]
+    impl HealthCapability for Slow {
+        async fn readiness(&self) -> HealthReport {
+            std::future::pending().await
+        }
+    }
+
+    struct Task(Status);
+
+    #[async_trait]
+    impl RunnableCapability for Task {
+        async fn start(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+            Ok(())
+        }
+        async fn stop(&self, _cancel: CancellationToken) -> anyhow::Result<()> {
+            Ok(())
+        }
+        fn status(&self) -> Option<Status> {
+            Some(self.0)
+        }
+    }
+
+    fn checks(report: HealthReport) -> Arc<dyn HealthCapability> {
+        Arc::new(Checks(report))
+    }
+
+    fn task(status: Status) -> Arc<dyn RunnableCapability> {
+        Arc::new(Task(status))
+    }
+
+    #[tokio::test]
+    async fn readiness_aggregates_worst_module_status() {
+        let registry = HealthRegistry::new()
+            .with_module(
+                "users",
+                Some(checks(HealthReport::up().with_check(
+                    "db",
+                    HealthStatus::Up,
+                    None,
+                ))),
+                None,
+            )
+            .with_module(
+                "search",
+                Some(checks(HealthReport::up().with_check(
+                    "index",
+                    HealthStatus::Degraded,
+                    None,
+                ))),
+                Some(task(Status::Running)),
+            );
+        registry.set_serving(true);
+
+        let summary = registry.readiness().await;
+        assert_eq!(summary.status, HealthStatus::Degraded);
+        assert!(summary.status.is_passing());
+        assert_eq!(summary.modules["users"].status, HealthStatus::Up);
+        assert_eq!(summary.modules["search"].lifecycle, Some("running"));
+
+        let failing = registry.with_module(
+            "billing",
+            Some(checks(HealthReport::up().with_result(
+                "upstream",
+                &Err::<(), _>("connection refused"),
+            ))),
+            None,
+        );
+        failing.set_serving(true);
+        let summary = failing.readiness().await;
+        assert_eq!(summary.status, HealthStatus::Down);
+        assert_eq!(
+            serde_json::to_value(&summary.modules["billing"]).unwrap(),
+            json!({
+                "status": "down",
+                "checks": [{
+                    "name": "upstream",
+                    "status": "down",
+                    "details": { "error": "connection refused" }
+                }]
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn lifecycle_status_is_folded_in() {
+        let registry =
+            HealthRegistry::new().with_module("worker", None, Some(task(Status::Starting)));
+        registry.set_serving(true);
+        assert_eq!(registry.liveness().await.status, HealthStatus::Up);
+        assert_eq!(registry.readiness().await.status, HealthStatus::Down);
+
+        // An exited task makes the module unready; the process stays alive
+        let exited = HealthRegistry::new().with_module("worker", None, Some(task(Status::Stopped)));
+        exited.set_serving(true);
+        assert_eq!(exited.liveness().await.status, HealthStatus::Up);
+        assert_eq!(exited.readiness().await.status, HealthStatus::Down);
+        assert_eq!(
+            exited.readiness().await.modules["worker"].lifecycle,
+            Some("stopped")
+        );
+    }
+
+    #[tokio::test]
+    async fn readiness_is_down_until_serving() {
+        let registry = HealthRegistry::new();
+        assert_eq!(registry.readiness().await.status, HealthStatus::Down);
+        assert_eq!(registry.liveness().await.status, HealthStatus::Up);
+        registry.set_serving(true);
+        assert_eq!(registry.readiness().await.status, HealthStatus::Up);
+    }
+
+    #[tokio::test]
+    async fn slow_checks_time_out() {
+        let registry = HealthRegistry::new()
+            .with_module("slow", Some(Arc::new(Slow)), None)
+            .with_check_timeout(Duration::from_millis(50));
+        registry.set_serving(true);
+
+        let summary = registry.readiness().await;
+        assert_eq!(summary.status, HealthStatus::Down);
+        assert_eq!(summary.modules["slow"].checks[0].name, "timeout");
+    }
+}
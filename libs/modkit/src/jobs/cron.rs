@@ -0,0 +1,324 @@
+//! Five-field cron expressions (`minute hour day-of-month month day-of-week`), evaluated in UTC
+//!
+//! Supports `*`, single values, ranges (`1-5`), steps (`*/15`, `10-40/10`, `5/20`),
+//! comma-separated lists and the `@yearly`, `@monthly`, `@weekly`, `@daily` and
+//! `@hourly` shorthands. Day-of-week is `0-7` with both `0` and `7` meaning Sunday.
+//! As in classic cron, when both day fields are restricted a day matches if
+//! *either* field matches.
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Timelike, Utc};
+
+/// How far ahead `next_after` searches before giving up (covers Feb 29 on a given weekday).
+const SEARCH_YEARS: i32 = 30;
+
+/// Error returned for a malformed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid cron expression '{expr}': {reason}")]
+pub struct CronParseError {
+    expr: String,
+    reason: String,
+}
+
+struct Field {
+    name: &'static str,
+    min: u32,
+    max: u32,
+}
+
+const MINUTE: Field = Field {
+    name: "minute",
+    min: 0,
+    max: 59,
+};
+const HOUR: Field = Field {
+    name: "hour",
+    min: 0,
+    max: 23,
+};
+const DAY_OF_MONTH: Field = Field {
+    name: "day-of-month",
+    min: 1,
+    max: 31,
+};
+const MONTH: Field = Field {
+    name: "month",
+    min: 1,
+    max: 12,
+};
+const DAY_OF_WEEK: Field = Field {
+    name: "day-of-week",
+    min: 0,
+    max: 7,
+};
+
+impl Field {
+    fn value(&self, s: &str) -> Result<u32, String> {
+        let v: u32 = s
+            .parse()
+            .map_err(|_| format!("'{s}' is not a number in the {} field", self.name))?;
+        if v < self.min || v > self.max {
+            return Err(format!(
+                "{v} is out of range {}-{} in the {} field",
+                self.min, self.max, self.name
+            ));
+        }
+        Ok(v)
+    }
+
+    /// Parses one field into a bitmask of allowed values.
+    fn parse(&self, spec: &str) -> Result<u64, String> {
+        let mut mask = 0u64;
+        for part in spec.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => {
+                    let step = step
+                        .parse::<usize>()
+                        .ok()
+                        .filter(|s| *s > 0)
+                        .ok_or_else(|| {
+                            format!("invalid step '{step}' in the {} field", self.name)
+                        })?;
+                    (range, step)
+                }
+                None => (part, 1),
+            };
+            let (lo, hi) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((lo, hi)) = range.split_once('-') {
+                (self.value(lo)?, self.value(hi)?)
+            } else {
+                let v = self.value(range)?;
+                // `5/20` means "from 5 to the end, every 20"
+                (v, if step > 1 { self.max } else { v })
+            };
+            if lo > hi {
+                return Err(format!(
+                    "range {lo}-{hi} is reversed in the {} field",
+                    self.name
+                ));
+            }
+            for v in (lo..=hi).step_by(step) {
+                mask |= 1 << v;
+            }
+        }
+        Ok(mask)
+    }
+}
+
+/// A parsed cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    source: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Day-of-month / day-of-week fields start with `*` (unrestricted)
+    any_day_of_month: bool,
+    any_day_of_week: bool,
+}
+
+impl CronExpr {
+    /// Parses a cron expression.
+    ///
+    /// # Errors
+    /// Returns [`CronParseError`] if the expression does not have five valid fields.
+    pub fn parse(expr: &str) -> Result<Self, CronParseError> {
+        let err = |reason: String| CronParseError {
+            expr: expr.to_owned(),
+            reason,
+        };
+        let trimmed = expr.trim();
+        let expanded = match trimmed {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            other => other,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(err(format!("expected 5 fields, got {}", fields.len())));
+        };
+
+        let mut days_of_week = DAY_OF_WEEK.parse(dow).map_err(err)?;
+        // 7 is an alias for Sunday
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+
+        Ok(Self {
+            source: trimmed.to_owned(),
+            minutes: MINUTE.parse(minute).map_err(err)?,
+            hours: HOUR.parse(hour).map_err(err)?,
+            days_of_month: DAY_OF_MONTH.parse(dom).map_err(err)?,
+            months: MONTH.parse(month).map_err(err)?,
+            days_of_week,
+            any_day_of_month: dom.starts_with('*'),
+            any_day_of_week: dow.starts_with('*'),
+        })
+    }
+
+    /// The expression as written.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// The first matching minute strictly after `after`, or `None` if the
+    /// expression never matches (e.g., `0 0 30 2 *`).
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let start = after.naive_utc();
+        let mut t =
+            start.date().and_hms_opt(start.hour(), start.minute(), 0)? + TimeDelta::minutes(1);
+        let last_year = t.year() + SEARCH_YEARS;
+
+        while t.year() <= last_year {
+            if !bit(self.months, t.month()) {
+                t = first_of_next_month(t.date())?;
+            } else if !self.day_matches(t.date()) {
+                t = t.date().succ_opt()?.and_time(NaiveTime::MIN);
+            } else if !bit(self.hours, t.hour()) {
+                t = t.date().and_hms_opt(t.hour(), 0, 0)? + TimeDelta::hours(1);
+            } else if !bit(self.minutes, t.minute()) {
+                t += TimeDelta::minutes(1);
+            } else {
+                return Some(t.and_utc());
+            }
+        }
+        None
+    }
+
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let dom = bit(self.days_of_month, date.day());
+        let dow = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.any_day_of_month || self.any_day_of_week {
+            dom && dow
+        } else {
+            dom || dow
+        }
+    }
+}
+
+fn bit(mask: u64, v: u32) -> bool {
+    mask & (1 << v) != 0
+}
+
+fn first_of_next_month(date: NaiveDate) -> Option<NaiveDateTime> {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    Some(NaiveDate::from_ymd_opt(year, month, 1)?.and_time(NaiveTime::MIN))
+}
+
+impl FromStr for CronExpr {
+    type Err = CronParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CronExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn next(expr: &str, after: &str) -> String {
+        CronExpr::parse(expr)
+            .unwrap()
+            .next_after(at(after))
+            .unwrap()
+            .to_rfc3339()
+    }
+
+    #[test]
+    fn next_matching_minute() {
+        assert_eq!(
+            next("*/15 * * * *", "2026-03-01T10:07:30Z"),
+            "2026-03-01T10:15:00+00:00"
+        );
+        // Strictly after: an exact match moves on to the next slot
+        assert_eq!(
+            next("*/15 * * * *", "2026-03-01T10:15:00Z"),
+            "2026-03-01T10:30:00+00:00"
+        );
+        assert_eq!(
+            next("30 2 * * *", "2026-03-01T10:07:00Z"),
+            "2026-03-02T02:30:00+00:00"
+        );
+        assert_eq!(
+            next("@monthly", "2026-12-15T00:00:00Z"),
+            "2027-01-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 9-17/4 * * 1-5", "2026-03-06T18:00:00Z"),
+            "2026-03-09T09:00:00+00:00"
+        );
+        assert_eq!(
+            next("0 0 29 2 *", "2026-03-01T00:00:00Z"),
+            "2028-02-29T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn restricted_day_fields_match_either() {
+        // 2026-03-02 is a Monday; the 13th is a Friday
+        let expr = "0 0 13 * 1";
+        assert_eq!(
+            next(expr, "2026-03-01T12:00:00Z"),
+            "2026-03-02T00:00:00+00:00"
+        );
+        assert_eq!(
+            next(expr, "2026-03-09T12:00:00Z"),
+            "2026-03-13T00:00:00+00:00"
+        );
+        // Sunday as 7
+        assert_eq!(
+            next("0 0 * * 7", "2026-03-02T00:00:00Z"),
+            "2026-03-08T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn impossible_expression_never_matches() {
+        let expr = CronExpr::parse("0 0 30 2 *").unwrap();
+        assert!(expr.next_after(at("2026-01-01T00:00:00Z")).is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for bad in [
+            "* * * *",
+            "60 * * * *",
+            "* 24 * * *",
+            "* * 0 * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "a * * * *",
+            "@often",
+        ] {
+            let err = CronExpr::parse(bad).unwrap_err();
+            assert!(err.to_string().contains(bad), "{err}");
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Scheduled jobs: periodic module work run by the runtime
+//!
+//! Modules declare jobs through [`ScheduledJobsCapability`](crate::contracts::ScheduledJobsCapability)
+//! instead of hand-rolling a `Lifecycle` loop. The runtime's [`JobScheduler`]
+//! runs each job on its interval or cron schedule under a `CancellationToken`,
+//! adds optional random jitter, skips a tick while the previous run is still in
+//! flight and, for [`single_runner`](ScheduledJob::single_runner) jobs, holds a
+//! `modkit_db::lease` row in the module's database so only one instance runs the job
+//! at a time.
+//!
+//! ```ignore
+//! impl ScheduledJobsCapability for MyModule {
+//!     fn jobs(&self, _ctx: &ModuleCtx) -> anyhow::Result<Vec<ScheduledJob>> {
+//!         let cache = self.cache.clone();
+//!         Ok(vec![
+//!             ScheduledJob::every("refresh-cache", Duration::from_secs(60), move |_cancel| {
+//!                 let cache = cache.clone();
+//!                 async move { cache.refresh().await }
+//!             })
+//!             .with_jitter(Duration::from_secs(5)),
+//!             ScheduledJob::cron("purge-expired", "0 3 * * *", |_cancel| async { Ok(()) })?
+//!                 .single_runner(),
+//!         ])
+//!     }
+//! }
+//! ```
+
+mod cron;
+mod scheduler;
+
+pub use cron::{CronExpr, CronParseError};
+pub use scheduler::{JobLock, JobLockGuard, JobOutcome, JobScheduler, JobStatus};
+
+use chrono::{DateTime, Utc};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// Future returned by a job handler
+pub type JobFuture = Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>;
+
+type JobFn = Arc<dyn Fn(CancellationToken) -> JobFuture + Send + Sync>;
+
+/// When a job runs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Schedule {
+    /// Fixed interval, first run one interval after start
+    Every(Duration),
+    /// Cron expression (UTC)
+    Cron(CronExpr),
+}
+
+impl Schedule {
+    /// Next run time strictly after `after`; `None` if the schedule never fires again.
+    #[must_use]
+    pub fn next_after(&self, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Schedule::Every(interval) => {
+                after.checked_add_signed(chrono::TimeDelta::from_std(*interval).ok()?)
+            }
+            Schedule::Cron(expr) => expr.next_after(after),
+        }
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Schedule::Every(interval) if interval.subsec_nanos() == 0 => {
+                write!(f, "every {}s", interval.as_secs())
+            }
+            Schedule::Every(interval) => write!(f, "every {}ms", interval.as_millis()),
+            Schedule::Cron(expr) => write!(f, "cron {expr}"),
+        }
+    }
+}
+
+/// A periodic job declared by a module
+#[must_use]
+#[derive(Clone)]
+pub struct ScheduledJob {
+    pub(crate) name: String,
+    pub(crate) schedule: Schedule,
+    pub(crate) jitter: Duration,
+    pub(crate) single_runner: bool,
+    pub(crate) run: JobFn,
+}
+
+impl ScheduledJob {
+    /// A job with an explicit [`Schedule`].
+    ///
+    /// The handler receives a token cancelled on shutdown; long runs should observe it.
+    pub fn new<F, Fut>(name: impl Into<String>, schedule: Schedule, handler: F) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Self {
+            name: name.into(),
+            schedule,
+            jitter: Duration::ZERO,
+            single_runner: false,
+            run: Arc::new(move |cancel| Box::pin(handler(cancel))),
+        }
+    }
+
+    /// A job running every `interval`.
+    pub fn every<F, Fut>(name: impl Into<String>, interval: Duration, handler: F) -> Self
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Self::new(name, Schedule::Every(interval), handler)
+    }
+
+    /// A job running on a cron schedule (see [`CronExpr`]).
+    ///
+    /// # Errors
+    /// Returns [`CronParseError`] if `expr` is not a valid cron expression.
+    pub fn cron<F, Fut>(
+        name: impl Into<String>,
+        expr: &str,
+        handler: F,
+    ) -> Result<Self, CronParseError>
+    where
+        F: Fn(CancellationToken) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = anyhow::Result<()>> + Send + 'static,
+    {
+        Ok(Self::new(
+            name,
+            Schedule::Cron(CronExpr::parse(expr)?),
+            handler,
+        ))
+    }
+
+    /// Delay each run by a random amount up to `max`, so replicas do not fire in lockstep.
+    pub fn with_jitter(mut self, max: Duration) -> Self {
+        self.jitter = max;
+        self
+    }
+
+    /// Run the job on at most one instance in the cluster at a time.
+    ///
+    /// Requires a database for the module with `modkit_db::lease::migration()` applied:
+    /// the run holds the lease `<module>:job:<name>`, renewed while it is in progress,
+    /// and instances that cannot take it skip the tick. If the lease is taken over or
+    /// cannot be renewed before it expires, the run's cancellation token is cancelled.
+    /// A lease left by a crashed instance expires after a minute. A replica firing after the run finished is not
+    /// excluded, so the job must tolerate an occasional second run of the same tick.
+    pub fn single_runner(mut self) -> Self {
+        self.single_runner = true;
+        self
+    }
+
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[must_use]
+    pub fn schedule(&self) -> &Schedule {
+        &self.schedule
+    }
+}
+
+impl fmt::Debug for ScheduledJob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduledJob")
+            .field("name", &self.name)
+            .field("schedule", &self.schedule)
+            .field("jitter", &self.jitter)
+            .field("single_runner", &self.single_runner)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,649 @@
+//! Runs scheduled jobs and tracks their last-run status
+
+use async_trait::async_trait;
+use chrono::{DateTime, TimeDelta, Utc};
+use futures_util::FutureExt;
+use parking_lot::Mutex;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+
+use super::{Schedule, ScheduledJob};
+
+/// Cluster-wide mutual exclusion for single-runner jobs
+///
+/// Implemented for the module's `DBProvider` as a `modkit_db::lease` named
+/// `<module>:job:<name>`.
+#[async_trait]
+pub trait JobLock: Send + Sync {
+    /// Try to take the lock for one run without waiting; `None` if another instance holds it.
+    async fn try_acquire(
+        &self,
+        module: &str,
+        job: &str,
+    ) -> anyhow::Result<Option<Box<dyn JobLockGuard>>>;
+}
+
+/// Lock held for the duration of one single-runner run
+#[async_trait]
+pub trait JobLockGuard: Send {
+    /// Cancelled if the lock is lost before [`release`](Self::release), e.g. a lease that
+    /// could not be renewed; the run is then cancelled too. Never cancelled by default.
+    fn lost(&self) -> CancellationToken {
+        CancellationToken::new()
+    }
+
+    async fn release(self: Box<Self>);
+}
+
+/// How long a job lease outlives its last renewal; a crashed runner frees the job after this
+#[cfg(feature = "db")]
+const JOB_LEASE_TTL: Duration = Duration::from_mins(1);
+
+#[cfg(feature = "db")]
+#[async_trait]
+impl JobLock for modkit_db::DBProvider<modkit_db::DbError> {
+    async fn try_acquire(
+        &self,
+        module: &str,
+        job: &str,
+    ) -> anyhow::Result<Option<Box<dyn JobLockGuard>>> {
+        let name = format!("{module}:job:{job}");
+        let holder = uuid::Uuid::now_v7().to_string();
+        if !modkit_db::lease::acquire(&self.conn()?, &name, &holder, JOB_LEASE_TTL).await? {
+            return Ok(None);
+        }
+
+        // Keep renewing while the run is in progress; give up before the lease can expire
+        // under a run that is still going
+        let db = self.clone();
+        let (lease, owner) = (name.clone(), holder.clone());
+        let lost = CancellationToken::new();
+        let on_lost = lost.clone();
+        let renew = tokio::spawn(async move {
+            let mut renewed_at = tokio::time::Instant::now();
+            loop {
+                tokio::time::sleep(JOB_LEASE_TTL / 3).await;
+                let renewed = match db.conn() {
+                    Ok(conn) => {
+                        modkit_db::lease::acquire(&conn, &lease, &owner, JOB_LEASE_TTL).await
+                    }
+                    Err(e) => Err(e),
+                };
+                match renewed {
+                    Ok(true) => renewed_at = tokio::time::Instant::now(),
+                    Ok(false) => {
+                        tracing::warn!(lease = %lease, "Single-runner job lease was taken over; cancelling the run");
+                        break;
+                    }
+                    Err(e) if renewed_at.elapsed() + JOB_LEASE_TTL / 3 >= JOB_LEASE_TTL => {
+                        tracing::warn!(lease = %lease, error = %e, "Single-runner job lease is about to expire; cancelling the run");
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::warn!(lease = %lease, error = %e, "Failed to renew single-runner job lease");
+                    }
+                }
+            }
+            on_lost.cancel();
+        });
+
+        Ok(Some(Box::new(DbJobLease {
+            db: self.clone(),
+            name,
+            holder,
+            lost,
+            renew,
+        })))
+    }
+}
+
+#[cfg(feature = "db")]
+struct DbJobLease {
+    db: modkit_db::DBProvider<modkit_db::DbError>,
+    name: String,
+    holder: String,
+    lost: CancellationToken,
+    renew: tokio::task::JoinHandle<()>,
+}
+
+#[cfg(feature = "db")]
+#[async_trait]
+impl JobLockGuard for DbJobLease {
+    fn lost(&self) -> CancellationToken {
+        self.lost.clone()
+    }
+
+    async fn release(self: Box<Self>) {
+        self.renew.abort();
+        let released = match self.db.conn() {
+            Ok(conn) => modkit_db::lease::release(&conn, &self.name, &self.holder).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = released {
+            // The lease expires on its own
+            tracing::warn!(lease = %self.name, error = %e, "Failed to release single-runner job lease");
+        }
+    }
+}
+
+/// Result of the last tick of a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobOutcome {
+    Succeeded,
+    Failed,
+    /// The previous run was still in progress, or another instance held the single-runner lock
+    Skipped,
+}
+
+impl JobOutcome {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            JobOutcome::Succeeded => "succeeded",
+            JobOutcome::Failed => "failed",
+            JobOutcome::Skipped => "skipped",
+        }
+    }
+}
+
+/// Last-run status of a scheduled job
+#[derive(Debug, Clone)]
+pub struct JobStatus {
+    pub module: String,
+    pub name: String,
+    /// Human-readable schedule (e.g., `every 60s`, `cron 0 3 * * *`)
+    pub schedule: String,
+    pub single_runner: bool,
+    pub running: bool,
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_started: Option<DateTime<Utc>>,
+    pub last_finished: Option<DateTime<Utc>>,
+    pub last_outcome: Option<JobOutcome>,
+    /// Error of the last failed run, or why the last tick was skipped
+    pub last_detail: Option<String>,
+    pub run_count: u64,
+    pub failure_count: u64,
+    pub skip_count: u64,
+}
+
+struct JobEntry {
+    module: String,
+    job: ScheduledJob,
+    lock: Option<Arc<dyn JobLock>>,
+    status: Mutex<JobStatus>,
+}
+
+impl JobEntry {
+    fn started(&self) {
+        let mut status = self.status.lock();
+        status.running = true;
+        status.last_started = Some(Utc::now());
+        status.run_count += 1;
+    }
+
+    fn finished(&self, result: anyhow::Result<()>) {
+        let mut status = self.status.lock();
+        status.running = false;
+        status.last_finished = Some(Utc::now());
+        match result {
+            Ok(()) => {
+                status.last_outcome = Some(JobOutcome::Succeeded);
+                status.last_detail = None;
+                tracing::debug!(module = %self.module, job = %self.job.name, "Scheduled job succeeded");
+            }
+            Err(e) => {
+                status.last_outcome = Some(JobOutcome::Failed);
+                status.last_detail = Some(format!("{e:#}"));
+                status.failure_count += 1;
+                tracing::warn!(
+                    module = %self.module,
+                    job = %self.job.name,
+                    error = %format!("{e:#}"),
+                    "Scheduled job failed"
+                );
+            }
+        }
+    }
+
+    fn skipped(&self, reason: &str) {
+        let mut status = self.status.lock();
+        status.last_outcome = Some(JobOutcome::Skipped);
+        status.last_detail = Some(reason.to_owned());
+        status.skip_count += 1;
+        tracing::debug!(module = %self.module, job = %self.job.name, reason, "Scheduled job tick skipped");
+    }
+}
+
+/// Runs the jobs declared through `ScheduledJobsCapability`
+///
+/// Published in the `ClientHub` by the host runtime; the module orchestrator
+/// reads [`statuses`](Self::statuses) to report last-run status.
+pub struct JobScheduler {
+    cancel: CancellationToken,
+    jobs: Mutex<Vec<Arc<JobEntry>>>,
+    tasks: Mutex<JoinSet<()>>,
+}
+
+impl JobScheduler {
+    /// Jobs stop when `cancel` is cancelled; their handlers receive child tokens of it.
+    #[must_use]
+    pub fn new(cancel: CancellationToken) -> Self {
+        Self {
+            cancel,
+            jobs: Mutex::new(Vec::new()),
+            tasks: Mutex::new(JoinSet::new()),
+        }
+    }
+
+    /// Starts running `job` for `module`.
+    ///
+    /// `lock` is the module's cluster-wide lock; it is used only by single-runner jobs.
+    ///
+    /// # Errors
+    /// Returns an error if the job has a zero interval, its name is already used by
+    /// the module, or it is single-runner and no lock is available.
+    pub fn schedule(
+        &self,
+        module: &str,
+        job: ScheduledJob,
+        lock: Option<Arc<dyn JobLock>>,
+    ) -> anyhow::Result<()> {
+        if job.schedule == Schedule::Every(Duration::ZERO) {
+            anyhow::bail!(
+                "job '{}' of module '{module}' has a zero interval",
+                job.name
+            );
+        }
+        let lock = if job.single_runner {
+            Some(lock.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "job '{}' of module '{module}' is single-runner but the module has no database for the lock",
+                    job.name
+                )
+            })?)
+        } else {
+            None
+        };
+
+        let entry = {
+            let mut jobs = self.jobs.lock();
+            if jobs
+                .iter()
+                .any(|e| e.module == module && e.job.name == job.name)
+            {
+                anyhow::bail!("job '{}' is declared twice by module '{module}'", job.name);
+            }
+            let entry = Arc::new(JobEntry {
+                module: module.to_owned(),
+                status: Mutex::new(JobStatus {
+                    module: module.to_owned(),
+                    name: job.name.clone(),
+                    schedule: job.schedule.to_string(),
+                    single_runner: job.single_runner,
+                    running: false,
+                    next_run: None,
+                    last_started: None,
+                    last_finished: None,
+                    last_outcome: None,
+                    last_detail: None,
+                    run_count: 0,
+                    failure_count: 0,
+                    skip_count: 0,
+                }),
+                job,
+                lock,
+            });
+            jobs.push(entry.clone());
+            entry
+        };
+
+        tracing::info!(
+            module,
+            job = %entry.job.name,
+            schedule = %entry.job.schedule,
+            single_runner = entry.job.single_runner,
+            "Scheduled job registered"
+        );
+        self.tasks
+            .lock()
+            .spawn(drive(entry, self.cancel.child_token()));
+        Ok(())
+    }
+
+    /// Status of all jobs, in registration order.
+    #[must_use]
+    pub fn statuses(&self) -> Vec<JobStatus> {
+        self.jobs
+            .lock()
+            .iter()
+            .map(|e| e.status.lock().clone())
+            .collect()
+    }
+
+    /// Status of the jobs of `module`, in registration order.
+    #[must_use]
+    pub fn statuses_of(&self, module: &str) -> Vec<JobStatus> {
+        self.jobs
+            .lock()
+            .iter()
+            .filter(|e| e.module == module)
+            .map(|e| e.status.lock().clone())
+            .collect()
+    }
+
+    /// Cancels all jobs and waits up to `timeout` for in-flight runs; runs still going are aborted.
+    pub async fn shutdown(&self, timeout: Duration) {
+        self.cancel.cancel();
+        let mut tasks = std::mem::take(&mut *self.tasks.lock());
+        let drained = tokio::time::timeout(timeout, async {
+            while tasks.join_next().await.is_some() {}
+        })
+        .await;
+        if drained.is_err() {
+            tracing::warn!(
+                remaining = tasks.len(),
+                "Scheduled jobs did not stop within {timeout:?}; aborting"
+            );
+            tasks.shutdown().await;
+        }
+    }
+}
+
+/// Timer loop of one job; runs are spawned so a slow run does not delay the schedule.
+async fn drive(entry: Arc<JobEntry>, cancel: CancellationToken) {
+    let mut run = JoinSet::new();
+    let mut last_tick: Option<DateTime<Utc>> = None;
+
+    loop {
+        let now = Utc::now();
+        // Keep the cadence of the previous tick unless it fell behind
+        let Some(tick) = last_tick
+            .and_then(|t| entry.job.schedule.next_after(t))
+            .filter(|t| *t > now)
+            .or_else(|| entry.job.schedule.next_after(now))
+        else {
+            tracing::warn!(module = %entry.module, job = %entry.job.name, "Schedule never fires again; job stopped");
+            break;
+        };
+        last_tick = Some(tick);
+
+        let fire_at = tick + jitter(entry.job.jitter);
+        entry.status.lock().next_run = Some(fire_at);
+        tokio::select! {
+            () = cancel.cancelled() => break,
+            () = tokio::time::sleep((fire_at - now).to_std().unwrap_or_default()) => {}
+        }
+
+        while run.try_join_next().is_some() {}
+        if run.is_empty() {
+            run.spawn(run_once(entry.clone(), cancel.clone()));
+        } else {
+            entry.skipped("previous run is still in progress");
+        }
+    }
+
+    entry.status.lock().next_run = None;
+    while run.join_next().await.is_some() {}
+}
+
+async fn run_once(entry: Arc<JobEntry>, cancel: CancellationToken) {
+    let guard = match &entry.lock {
+        None => None,
+        Some(lock) => match lock.try_acquire(&entry.module, &entry.job.name).await {
+            Ok(Some(guard)) => Some(guard),
+            Ok(None) => {
+                entry.skipped("another instance holds the single-runner lock");
+                return;
+            }
+            Err(e) => {
+                entry.started();
+                entry.finished(Err(e.context("failed to take the single-runner lock")));
+                return;
+            }
+        },
+    };
+
+    // A run that lost its lock is cancelled, so it does not overlap the next holder's
+    let cancel = cancel.child_token();
+    if let Some(lost) = guard.as_ref().map(|g| g.lost()) {
+        let cancel = cancel.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                () = lost.cancelled() => cancel.cancel(),
+                () = cancel.cancelled() => {}
+            }
+        });
+    }
+
+    entry.started();
+    let result = AssertUnwindSafe((entry.job.run)(cancel.clone()))
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| Err(anyhow::anyhow!("job panicked")));
+    entry.finished(result);
+    cancel.cancel();
+
+    if let Some(guard) = guard {
+        guard.release().await;
+    }
+}
+
+fn jitter(max: Duration) -> TimeDelta {
+    if max.is_zero() {
+        return TimeDelta::zero();
+    }
+    TimeDelta::from_std(rand::random_range(Duration::ZERO..=max)).unwrap_or_default()
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    const TICK: Duration = Duration::from_millis(20);
+
+    struct MemoryLock(Arc<AtomicBool>);
+
+    #[async_trait]
+    impl JobLock for MemoryLock {
+        async fn try_acquire(
+            &self,
+            _module: &str,
+            _job: &str,
+        ) -> anyhow::Result<Option<Box<dyn JobLockGuard>>> {
+            if self.0.swap(true, Ordering::SeqCst) {
+                Ok(None)
+            } else {
+                Ok(Some(Box::new(MemoryGuard(self.0.clone()))))
+            }
+        }
+    }
+
+    struct MemoryGuard(Arc<AtomicBool>);
+
+    #[async_trait]
+    impl JobLockGuard for MemoryGuard {
+        async fn release(self: Box<Self>) {
+            self.0.store(false, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn interval_job_runs_and_reports_status() {
+        let scheduler = JobScheduler::new(CancellationToken::new());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counter = runs.clone();
+        scheduler
+            .schedule(
+                "cache",
+                ScheduledJob::every("refresh", TICK, move |_| {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    async { Ok(()) }
+                }),
+                None,
+            )
+            .unwrap();
+
+        tokio::time::sleep(TICK * 8).await;
+        let status = &scheduler.statuses_of("cache")[0];
+        assert!(runs.load(Ordering::SeqCst) >= 2);
+        assert_eq!(status.run_count, runs.load(Ordering::SeqCst) as u64);
+        assert_eq!(status.last_outcome, Some(JobOutcome::Succeeded));
+        assert_eq!(status.schedule, "every 20ms");
+        assert!(status.next_run.is_some());
+        assert!(scheduler.statuses_of("other").is_empty());
+
+        scheduler.shutdown(Duration::from_secs(1)).await;
+        let count = runs.load(Ordering::SeqCst);
+        tokio::time::sleep(TICK * 3).await;
+        assert_eq!(runs.load(Ordering::SeqCst), count);
+        assert!(scheduler.statuses()[0].next_run.is_none());
+    }
+
+    #[tokio::test]
+    async fn overlapping_ticks_are_skipped_and_failures_recorded() {
+        let scheduler = JobScheduler::new(CancellationToken::new());
+        scheduler
+            .schedule(
+                "cache",
+                ScheduledJob::every("slow", TICK, |cancel: CancellationToken| async move {
+                    tokio::select! {
+                        () = cancel.cancelled() => {}
+                        () = tokio::time::sleep(TICK * 5) => {}
+                    }
+                    anyhow::bail!("upstream unavailable")
+                }),
+                None,
+            )
+            .unwrap();
+
+        tokio::time::sleep(TICK * 9).await;
+        let status = &scheduler.statuses()[0];
+        assert!(status.skip_count >= 2, "{status:?}");
+        assert!(status.run_count <= 2, "{status:?}");
+        assert!(status.failure_count >= 1, "{status:?}");
+
+        // Shutdown cancels the in-flight run instead of waiting for it
+        scheduler.shutdown(Duration::from_secs(1)).await;
+        let status = &scheduler.statuses()[0];
+        assert!(!status.running);
+        assert_eq!(status.last_outcome, Some(JobOutcome::Failed));
+        assert_eq!(status.last_detail.as_deref(), Some("upstream unavailable"));
+    }
+
+    #[tokio::test]
+    async fn single_runner_job_runs_on_one_instance_at_a_time() {
+        let held = Arc::new(AtomicBool::new(false));
+        let active = Arc::new(AtomicUsize::new(0));
+        let max_active = Arc::new(AtomicUsize::new(0));
+
+        let replicas: Vec<JobScheduler> = (0..2)
+            .map(|_| {
+                let scheduler = JobScheduler::new(CancellationToken::new());
+                let (active, max_active) = (active.clone(), max_active.clone());
+                let job = ScheduledJob::every("purge", TICK, move |_| {
+                    let (active, max_active) = (active.clone(), max_active.clone());
+                    async move {
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_active.fetch_max(now, Ordering::SeqCst);
+                        tokio::time::sleep(TICK * 2).await;
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    }
+                })
+                .single_runner();
+                scheduler
+                    .schedule("cleanup", job, Some(Arc::new(MemoryLock(held.clone()))))
+                    .unwrap();
+                scheduler
+            })
+            .collect();
+
+        tokio::time::sleep(TICK * 10).await;
+        for scheduler in &replicas {
+            scheduler.shutdown(Duration::from_secs(1)).await;
+        }
+
+        assert_eq!(max_active.load(Ordering::SeqCst), 1);
+        let statuses: Vec<JobStatus> = replicas.iter().flat_map(JobScheduler::statuses).collect();
+        assert!(statuses.iter().map(|s| s.run_count).sum::<u64>() >= 2);
+        assert!(statuses.iter().map(|s| s.skip_count).sum::<u64>() >= 1);
+        assert!(!held.load(Ordering::SeqCst));
+    }
+
+    struct LosingLock(CancellationToken);
+
+    #[async_trait]
+    impl JobLock for LosingLock {
+        async fn try_acquire(
+            &self,
+            _module: &str,
+            _job: &str,
+        ) -> anyhow::Result<Option<Box<dyn JobLockGuard>>> {
+            Ok(Some(Box::new(LosingGuard(self.0.clone()))))
+        }
+    }
+
+    struct LosingGuard(CancellationToken);
+
+    #[async_trait]
+    impl JobLockGuard for LosingGuard {
+        fn lost(&self) -> CancellationToken {
+            self.0.clone()
+        }
+
+        async fn release(self: Box<Self>) {}
+    }
+
+    #[tokio::test]
+    async fn run_is_cancelled_when_its_lock_is_lost() {
+        let scheduler = JobScheduler::new(CancellationToken::new());
+        let lost = CancellationToken::new();
+        let job = ScheduledJob::every("purge", TICK, |cancel: CancellationToken| async move {
+            tokio::select! {
+                () = cancel.cancelled() => anyhow::bail!("cancelled"),
+                () = tokio::time::sleep(Duration::from_secs(5)) => Ok(()),
+            }
+        })
+        .single_runner();
+        scheduler
+            .schedule("cleanup", job, Some(Arc::new(LosingLock(lost.clone()))))
+            .unwrap();
+
+        tokio::time::sleep(TICK * 3).await;
+        assert!(scheduler.statuses()[0].running);
+        lost.cancel();
+        tokio::time::sleep(TICK).await;
+        let status = &scheduler.statuses()[0];
+        assert_eq!(status.last_outcome, Some(JobOutcome::Failed));
+        assert_eq!(status.last_detail.as_deref(), Some("cancelled"));
+        scheduler.shutdown(Duration::from_secs(1)).await;
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_jobs() {
+        let scheduler = JobScheduler::new(CancellationToken::new());
+        let noop = |_| async { Ok(()) };
+
+        let zero = ScheduledJob::every("zero", Duration::ZERO, noop);
+        assert!(scheduler.schedule("m", zero, None).is_err());
+
+        let unlocked = ScheduledJob::every("purge", TICK, noop).single_runner();
+        let err = scheduler.schedule("m", unlocked, None).unwrap_err();
+        assert!(err.to_string().contains("no database"));
+
+        scheduler
+            .schedule("m", ScheduledJob::every("dup", TICK, noop), None)
+            .unwrap();
+        assert!(
+            scheduler
+                .schedule("m", ScheduledJob::every("dup", TICK, noop), None)
+                .is_err()
+        );
+        scheduler.shutdown(Duration::from_secs(1)).await;
+    }
+}
@@ -79,7 +79,9 @@ pub use crate::contracts::{GrpcServiceCapability, RegisterGrpcServiceFn};
 
 // Configuration module
 pub mod config;
-pub use config::{ConfigError, ConfigProvider, module_config_or_default, module_config_required};
+pub use config::{
+    ConfigChange, ConfigError, ConfigProvider, module_config_or_default, module_config_required,
+};
 
 // Context module
 pub mod context;
@@ -90,11 +92,11 @@ pub mod client_hub;
 pub mod registry;
 
 // Re-export main types
-pub use client_hub::ClientHub;
+pub use client_hub::{ClientHub, ClientLayer};
 pub use registry::ModuleRegistry;
 
 // Re-export the macros from the proc-macro crate
-pub use modkit_macros::{lifecycle, module};
+pub use modkit_macros::{instrumented_client, lifecycle, module};
 
 // Core module contracts and traits
 pub mod contracts;
@@ -117,6 +119,9 @@ pub use http::sse::SseBroadcaster;
 pub mod telemetry;
 
 pub mod backends;
+pub mod events;
+pub mod health;
+pub mod jobs;
 pub mod lifecycle;
 pub mod plugins;
 pub mod runtime;
@@ -147,13 +152,16 @@ pub use modkit_sdk::{Secured, WithSecurityContext};
 
 pub use backends::{
     BackendKind, InstanceHandle, LocalProcessBackend, ModuleRuntimeBackend, OopBackend,
-    OopModuleConfig, OopSpawnConfig,
+    OopModuleConfig, OopSpawnConfig, RestartMode, RestartPolicy, StaticBackend, StaticInstance,
 };
+pub use events::{Delivery, Event, EventBus, EventBusError, Subscription};
+pub use health::{HealthRegistry, HealthReport, HealthStatus};
+pub use jobs::{JobScheduler, JobStatus, Schedule, ScheduledJob};
 pub use lifecycle::{Lifecycle, Runnable, Status, StopReason, WithLifecycle};
 pub use plugins::GtsPluginSelector;
 pub use runtime::{
-    DbOptions, Endpoint, ModuleInstance, ModuleManager, OopModuleSpawnConfig, OopSpawnOptions,
-    RunOptions, ShutdownOptions, run,
+    ConfigReloader, DbOptions, Endpoint, ModuleInstance, ModuleManager, OopModuleSpawnConfig,
+    OopSpawnOptions, RunOptions, ShutdownOptions, StaticModuleConfig, run,
 };
 
 #[cfg(feature = "bootstrap")]
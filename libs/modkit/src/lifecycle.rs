@@ -58,6 +58,16 @@ impl Status {
             _ => Status::Stopped,
         }
     }
+    #[inline]
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Status::Stopped => "stopped",
+            Status::Starting => "starting",
+            Status::Running => "running",
+            Status::Stopping => "stopping",
+        }
+    }
 }
 
 /// Reason why a task stopped.
@@ -617,6 +627,10 @@ impl<T: Runnable> crate::contracts::RunnableCapability for WithLifecycle<T> {
             }
         }
     }
+
+    fn status(&self) -> Option<Status> {
+        Some(self.lc.status())
+    }
 }
 
 impl<T: Runnable> Drop for WithLifecycle<T> {
@@ -633,6 +647,17 @@ impl<T: Runnable> Drop for WithLifecycle<T> {
     }
 }
 
+// ----- Outbox relay ----------------------------------------------------------
+
+/// Lets modules run the `modkit-db` outbox relay as `WithLifecycle<OutboxRelay>`.
+#[cfg(feature = "db")]
+#[async_trait]
+impl Runnable for modkit_db::outbox::OutboxRelay {
+    async fn run(self: Arc<Self>, cancel: CancellationToken) -> TaskResult<()> {
+        modkit_db::outbox::OutboxRelay::run(self, cancel).await
+    }
+}
+
 // ----- Tests -----------------------------------------------------------------
 
 #[cfg(test)]
@@ -695,7 +720,7 @@ mod tests {
         assert_eq!(wrapper.status(), Status::Stopped);
         assert_eq!(wrapper.inner().count(), 0);
 
-        let wrapper = wrapper.with_stop_timeout(Duration::from_secs(60));
+        let wrapper = wrapper.with_stop_timeout(Duration::from_mins(1));
         assert_eq!(wrapper.stop_timeout.as_secs(), 60);
     }
 
@@ -884,7 +909,7 @@ mod tests {
         tokio::time::sleep(Duration::from_millis(50)).await;
 
         // Stop should handle the panic gracefully
-        let reason = lc.stop(Duration::from_millis(1000)).await.unwrap();
+        let reason = lc.stop(Duration::from_secs(1)).await.unwrap();
 
         // The task panicked, but stop should complete successfully
         // The exact reason depends on timing, but it should not hang or fail
@@ -25,6 +25,9 @@ pub enum Capability {
     System(Arc<dyn contracts::SystemCapability>),
     GrpcHub(Arc<dyn contracts::GrpcHubCapability>),
     GrpcService(Arc<dyn contracts::GrpcServiceCapability>),
+    Health(Arc<dyn contracts::HealthCapability>),
+    ConfigReload(Arc<dyn contracts::ConfigReloadCapability>),
+    ScheduledJobs(Arc<dyn contracts::ScheduledJobsCapability>),
 }
 
 impl std::fmt::Debug for Capability {
@@ -38,6 +41,13 @@ impl std::fmt::Debug for Capability {
             Capability::System(_) => write!(f, "System(<impl SystemCapability>)"),
             Capability::GrpcHub(_) => write!(f, "GrpcHub(<impl GrpcHubCapability>)"),
             Capability::GrpcService(_) => write!(f, "GrpcService(<impl GrpcServiceCapability>)"),
+            Capability::Health(_) => write!(f, "Health(<impl HealthCapability>)"),
+            Capability::ConfigReload(_) => {
+                write!(f, "ConfigReload(<impl ConfigReloadCapability>)")
+            }
+            Capability::ScheduledJobs(_) => {
+                write!(f, "ScheduledJobs(<impl ScheduledJobsCapability>)")
+            }
         }
     }
 }
@@ -134,6 +144,42 @@ impl CapTag for GrpcServiceCap {
     }
 }
 
+/// Tag for querying `HealthCapability`.
+pub struct HealthCap;
+impl CapTag for HealthCap {
+    type Out = dyn contracts::HealthCapability;
+    fn try_get(cap: &Capability) -> Option<&Arc<Self::Out>> {
+        match cap {
+            Capability::Health(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Tag for querying `ConfigReloadCapability`.
+pub struct ConfigReloadCap;
+impl CapTag for ConfigReloadCap {
+    type Out = dyn contracts::ConfigReloadCapability;
+    fn try_get(cap: &Capability) -> Option<&Arc<Self::Out>> {
+        match cap {
+            Capability::ConfigReload(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
+/// Tag for querying `ScheduledJobsCapability`.
+pub struct ScheduledJobsCap;
+impl CapTag for ScheduledJobsCap {
+    type Out = dyn contracts::ScheduledJobsCapability;
+    fn try_get(cap: &Capability) -> Option<&Arc<Self::Out>> {
+        match cap {
+            Capability::ScheduledJobs(v) => Some(v),
+            _ => None,
+        }
+    }
+}
+
 /// A set of capabilities that a module provides.
 #[derive(Clone)]
 pub struct CapabilitySet {
@@ -186,6 +232,9 @@ impl CapabilitySet {
                 Capability::System(_) => "system",
                 Capability::GrpcHub(_) => "grpc_hub",
                 Capability::GrpcService(_) => "grpc",
+                Capability::Health(_) => "health",
+                Capability::ConfigReload(_) => "config_reload",
+                Capability::ScheduledJobs(_) => "scheduled_jobs",
             })
             .collect()
     }
@@ -303,6 +352,53 @@ impl ModuleRegistry {
         system_mods
     }
 
+    /// Returns modules grouped into dependency levels, in system priority.
+    ///
+    /// A module's level is one more than the highest level among its dependencies,
+    /// so modules within a level never depend on each other and may run concurrently.
+    /// Dependencies always come first; apart from that, system levels come before
+    /// non-system levels. A system module that depends on a non-system module is
+    /// therefore placed with the non-system modules. Within a level the topological
+    /// order is preserved.
+    #[must_use]
+    pub fn levels_by_system_priority(&self) -> Vec<Vec<&ModuleEntry>> {
+        // (runs in the system group, level within its group)
+        let mut placed: HashMap<&'static str, (bool, usize)> = HashMap::new();
+        for entry in &self.modules {
+            let deps: Vec<(bool, usize)> = entry
+                .deps
+                .iter()
+                .filter_map(|dep| placed.get(dep).copied())
+                .collect();
+            let system = entry.caps.has::<SystemCap>() && deps.iter().all(|(system, _)| *system);
+            let level = deps
+                .iter()
+                .filter(|(dep_system, _)| *dep_system == system)
+                .map(|(_, level)| level + 1)
+                .max()
+                .unwrap_or(0);
+            placed.insert(entry.name, (system, level));
+        }
+
+        let mut levels: Vec<Vec<&ModuleEntry>> = Vec::new();
+        for group in [true, false] {
+            let base = levels.len();
+            for entry in &self.modules {
+                let (system, level) = placed[entry.name];
+                if system != group {
+                    continue;
+                }
+                let idx = base + level;
+                if levels.len() <= idx {
+                    levels.resize_with(idx + 1, Vec::new);
+                }
+                levels[idx].push(entry);
+            }
+        }
+        levels.retain(|level| !level.is_empty());
+        levels
+    }
+
     /// Discover via inventory, have registrators fill the builder, then build & topo-sort.
     ///
     /// # Errors
@@ -447,6 +543,39 @@ impl RegistryBuilder {
             .push(Capability::GrpcService(m));
     }
 
+    pub fn register_health_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::HealthCapability>,
+    ) {
+        self.capabilities
+            .entry(name)
+            .or_default()
+            .push(Capability::Health(m));
+    }
+
+    pub fn register_config_reload_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::ConfigReloadCapability>,
+    ) {
+        self.capabilities
+            .entry(name)
+            .or_default()
+            .push(Capability::ConfigReload(m));
+    }
+
+    pub fn register_scheduled_jobs_with_meta(
+        &mut self,
+        name: &'static str,
+        m: Arc<dyn contracts::ScheduledJobsCapability>,
+    ) {
+        self.capabilities
+            .entry(name)
+            .or_default()
+            .push(Capability::ScheduledJobs(m));
+    }
+
     /// Detect cycles in the dependency graph using DFS with path tracking.
     /// Returns the cycle path if found, None otherwise.
     fn detect_cycle_with_path(
@@ -729,6 +858,12 @@ pub enum RegistryError {
         #[source]
         source: anyhow::Error,
     },
+    #[error("scheduling jobs failed for module '{module}'")]
+    ScheduleJobs {
+        module: &'static str,
+        #[source]
+        source: anyhow::Error,
+    },
 
     #[error("DB migration failed for module '{module}'")]
     DbMigrate {
@@ -825,6 +960,7 @@ mod tests {
             Ok(())
         }
     }
+    impl contracts::SystemCapability for DummyCore {}
 
     /* ------------------------------- Tests ---------------------------- */
 
@@ -840,6 +976,45 @@ mod tests {
         assert_eq!(order, vec!["core_a", "core_b"]);
     }
 
+    #[test]
+    fn levels_group_independent_modules() {
+        let mut b = RegistryBuilder::default();
+        b.register_core_with_meta("a", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("b", &[], Arc::new(DummyCore));
+        b.register_core_with_meta("c", &["a"], Arc::new(DummyCore));
+        b.register_core_with_meta("d", &["b", "c"], Arc::new(DummyCore));
+        b.register_core_with_meta("e", &["a"], Arc::new(DummyCore));
+
+        let reg = b.build_topo_sorted().unwrap();
+        let mut levels: Vec<Vec<_>> = reg
+            .levels_by_system_priority()
+            .iter()
+            .map(|level| level.iter().map(|m| m.name).collect())
+            .collect();
+        for level in &mut levels {
+            level.sort_unstable();
+        }
+        assert_eq!(levels, vec![vec!["a", "b"], vec!["c", "e"], vec!["d"]]);
+    }
+
+    #[test]
+    fn levels_put_dependencies_before_system_priority() {
+        let mut b = RegistryBuilder::default();
+        for (name, deps) in [("sys_a", &[][..]), ("user", &[]), ("sys_b", &["user"])] {
+            b.register_core_with_meta(name, deps, Arc::new(DummyCore));
+        }
+        b.register_system_with_meta("sys_a", Arc::new(DummyCore));
+        b.register_system_with_meta("sys_b", Arc::new(DummyCore));
+
+        let reg = b.build_topo_sorted().unwrap();
+        let levels: Vec<Vec<_>> = reg
+            .levels_by_system_priority()
+            .iter()
+            .map(|level| level.iter().map(|m| m.name).collect())
+            .collect();
+        assert_eq!(levels, vec![vec!["sys_a"], vec!["user"], vec!["sys_b"]]);
+    }
+
     #[test]
     fn unknown_dependency_error() {
         let mut b = RegistryBuilder::default();
@@ -0,0 +1,478 @@
+//! Live configuration reload.
+//!
+//! The `ConfigReloader` keeps the last applied section of every registered module.
+//! On reload it loads fresh sections from a [`ConfigSource`], diffs them per module
+//! and hands each change to the module's `ConfigReloadCapability`. A reload is
+//! all-or-nothing: every change is validated before any module applies it, so a change
+//! that is not reloadable or that a module refuses leaves all modules untouched. Should
+//! applying still fail, modules that already applied their change are rolled back.
+//!
+//! Reloads are triggered by `SIGHUP`, by changes to the source's watched files
+//! (polled) or explicitly via [`ConfigReloader::reload`] (e.g., an admin endpoint).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use parking_lot::RwLock;
+use serde_json::Value;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::{ConfigChange, ConfigProvider};
+use crate::contracts::ConfigReloadCapability;
+use crate::registry::{ConfigReloadCap, ModuleRegistry};
+
+/// Per-module raw sections, keyed by module name.
+pub type ModuleSections = HashMap<String, Value>;
+
+/// How often watched files are checked for changes.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Where fresh module configuration comes from (e.g., the YAML file the host was started with).
+pub trait ConfigSource: Send + Sync {
+    /// Load the current per-module sections.
+    ///
+    /// # Errors
+    /// Returns an error if the configuration cannot be read or parsed.
+    fn load(&self) -> anyhow::Result<ModuleSections>;
+
+    /// Files whose modification triggers a reload.
+    fn watched_files(&self) -> Vec<PathBuf> {
+        Vec::new()
+    }
+}
+
+/// Why a reload was rejected. The previous configuration stays active.
+#[derive(Debug, thiserror::Error)]
+pub enum ReloadError {
+    #[error("no configuration source is available for reload")]
+    NoSource,
+    #[error("failed to load configuration: {0:#}")]
+    Source(anyhow::Error),
+    #[error("module '{module}' cannot be reloaded: {reason}")]
+    NotReloadable {
+        module: String,
+        reason: &'static str,
+    },
+    #[error("module '{module}' rejected the new configuration: {source:#}")]
+    Rejected {
+        module: String,
+        #[source]
+        source: anyhow::Error,
+    },
+}
+
+struct ReloadEntry {
+    name: &'static str,
+    handler: Option<Arc<dyn ConfigReloadCapability>>,
+}
+
+/// Applies configuration changes to running modules.
+pub struct ConfigReloader {
+    /// All registered modules, in dependency order.
+    modules: Vec<ReloadEntry>,
+    /// Last applied sections; the lock also serializes reloads.
+    current: tokio::sync::Mutex<ModuleSections>,
+    source: RwLock<Option<Arc<dyn ConfigSource>>>,
+}
+
+impl ConfigReloader {
+    /// Track all registered modules, starting from their sections in `initial`.
+    #[must_use]
+    pub fn new(registry: &ModuleRegistry, initial: &dyn ConfigProvider) -> Self {
+        let mut current = ModuleSections::new();
+        let modules = registry
+            .modules()
+            .iter()
+            .map(|entry| {
+                if let Some(raw) = initial.get_module_config(entry.name()) {
+                    current.insert(entry.name().to_owned(), raw.clone());
+                }
+                ReloadEntry {
+                    name: entry.name(),
+                    handler: entry.caps().query::<ConfigReloadCap>(),
+                }
+            })
+            .collect();
+
+        Self {
+            modules,
+            current: tokio::sync::Mutex::new(current),
+            source: RwLock::new(None),
+        }
+    }
+
+    /// Set the source used by [`reload`](Self::reload) and the file watcher.
+    pub fn set_source(&self, source: Arc<dyn ConfigSource>) {
+        *self.source.write() = Some(source);
+    }
+
+    #[must_use]
+    pub fn has_source(&self) -> bool {
+        self.source.read().is_some()
+    }
+
+    /// Load fresh sections from the source and apply them.
+    ///
+    /// Returns the names of the modules whose configuration changed.
+    ///
+    /// # Errors
+    /// Returns `ReloadError` if there is no source, loading fails or the change is rejected.
+    pub async fn reload(&self) -> Result<Vec<String>, ReloadError> {
+        let source = self.source.read().clone().ok_or(ReloadError::NoSource)?;
+        let sections = source.load().map_err(ReloadError::Source)?;
+        self.apply(&sections).await
+    }
+
+    /// Apply `sections` to all registered modules whose section changed.
+    ///
+    /// Only the `config` part of a section can change at runtime, and only for modules
+    /// with the `config_reload` capability. Sections of unregistered modules are ignored.
+    ///
+    /// # Errors
+    /// Returns `ReloadError` if a change is not reloadable or a module refuses it.
+    pub async fn apply(&self, sections: &ModuleSections) -> Result<Vec<String>, ReloadError> {
+        let mut current = self.current.lock().await;
+
+        let mut changes = Vec::new();
+        for entry in &self.modules {
+            let previous = current.get(entry.name).cloned().unwrap_or(Value::Null);
+            let next = sections.get(entry.name).cloned().unwrap_or(Value::Null);
+            if previous == next {
+                continue;
+            }
+            if without_config(&previous) != without_config(&next) {
+                return Err(ReloadError::NotReloadable {
+                    module: entry.name.to_owned(),
+                    reason: "only the `config` section can change without a restart",
+                });
+            }
+            let Some(handler) = &entry.handler else {
+                return Err(ReloadError::NotReloadable {
+                    module: entry.name.to_owned(),
+                    reason: "module does not support live configuration reload",
+                });
+            };
+            changes.push((handler, ConfigChange::new(entry.name, previous, next)));
+        }
+
+        for (handler, change) in &changes {
+            if let Err(source) = handler.validate_config_change(change).await {
+                return Err(ReloadError::Rejected {
+                    module: change.module().to_owned(),
+                    source,
+                });
+            }
+        }
+
+        for (i, (handler, change)) in changes.iter().enumerate() {
+            if let Err(source) = handler.on_config_change(change).await {
+                for (handler, applied) in changes[..i].iter().rev() {
+                    if let Err(e) = handler.on_config_change(&applied.reversed()).await {
+                        tracing::error!(
+                            module = applied.module(),
+                            error = %e,
+                            "Failed to roll back configuration change"
+                        );
+                    }
+                }
+                return Err(ReloadError::Rejected {
+                    module: change.module().to_owned(),
+                    source,
+                });
+            }
+        }
+
+        let mut modules = Vec::with_capacity(changes.len());
+        for (_, change) in changes {
+            let name = change.module().to_owned();
+            if change.current_raw().is_null() {
+                current.remove(&name);
+            } else {
+                current.insert(name.clone(), change.current_raw().clone());
+            }
+            modules.push(name);
+        }
+        Ok(modules)
+    }
+
+    /// Reload on `SIGHUP` (Unix) and whenever a watched file changes, until `cancel` fires.
+    pub fn spawn_triggers(self: &Arc<Self>, cancel: CancellationToken, interval: Duration) {
+        let reloader = Arc::clone(self);
+        tokio::spawn(async move {
+            #[cfg(unix)]
+            let mut hangup =
+                match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup()) {
+                    Ok(s) => Some(s),
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to install SIGHUP handler");
+                        None
+                    }
+                };
+
+            let mut mtimes = reloader.watched_mtimes();
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                #[cfg(unix)]
+                let hup = async {
+                    match hangup.as_mut() {
+                        Some(s) => {
+                            s.recv().await;
+                        }
+                        None => std::future::pending().await,
+                    }
+                };
+                #[cfg(not(unix))]
+                let hup = std::future::pending::<()>();
+
+                let trigger = tokio::select! {
+                    () = cancel.cancelled() => break,
+                    () = hup => "signal",
+                    _ = ticker.tick() => {
+                        let latest = reloader.watched_mtimes();
+                        if latest == mtimes {
+                            continue;
+                        }
+                        mtimes = latest;
+                        "file change"
+                    }
+                };
+
+                match reloader.reload().await {
+                    Ok(changed) if changed.is_empty() => {
+                        tracing::info!(trigger, "Configuration reloaded: no module changes");
+                    }
+                    Ok(changed) => {
+                        tracing::info!(trigger, modules = ?changed, "Configuration reloaded");
+                    }
+                    Err(e) => tracing::warn!(trigger, error = %e, "Configuration reload rejected"),
+                }
+            }
+        });
+    }
+
+    fn watched_mtimes(&self) -> HashMap<PathBuf, Option<SystemTime>> {
+        let Some(source) = self.source.read().clone() else {
+            return HashMap::new();
+        };
+        source
+            .watched_files()
+            .into_iter()
+            .map(|path| {
+                let mtime = std::fs::metadata(&path).and_then(|m| m.modified()).ok();
+                (path, mtime)
+            })
+            .collect()
+    }
+}
+
+/// A section with its `config` part removed, to detect changes that need a restart.
+fn without_config(section: &Value) -> Value {
+    match section {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(k, _)| k.as_str() != "config")
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        ),
+        Value::Null => Value::Object(serde_json::Map::new()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::contracts::Module;
+    use crate::registry::RegistryBuilder;
+    use async_trait::async_trait;
+    use parking_lot::Mutex;
+    use serde_json::json;
+
+    struct Noop;
+
+    #[async_trait]
+    impl Module for Noop {
+        async fn init(&self, _ctx: &crate::context::ModuleCtx) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Records applied changes; refuses `config.limit` above `max` during validation
+    /// and fails to apply `config.limit` equal to `fail_on`.
+    struct Limited {
+        max: u64,
+        fail_on: Option<u64>,
+        applied: Mutex<Vec<u64>>,
+    }
+
+    #[derive(serde::Deserialize, Default)]
+    struct LimitConfig {
+        #[serde(default)]
+        limit: u64,
+    }
+
+    #[async_trait]
+    impl ConfigReloadCapability for Limited {
+        async fn validate_config_change(&self, change: &ConfigChange) -> anyhow::Result<()> {
+            let cfg: LimitConfig = change.config()?;
+            anyhow::ensure!(
+                cfg.limit <= self.max,
+                "limit {} exceeds {}",
+                cfg.limit,
+                self.max
+            );
+            Ok(())
+        }
+
+        async fn on_config_change(&self, change: &ConfigChange) -> anyhow::Result<()> {
+            let cfg: LimitConfig = change.config()?;
+            anyhow::ensure!(
+                Some(cfg.limit) != self.fail_on,
+                "cannot apply {}",
+                cfg.limit
+            );
+            self.applied.lock().push(cfg.limit);
+            Ok(())
+        }
+    }
+
+    struct Sections(ModuleSections);
+
+    impl ConfigProvider for Sections {
+        fn get_module_config(&self, module_name: &str) -> Option<&Value> {
+            self.0.get(module_name)
+        }
+    }
+
+    fn limited(max: u64) -> Arc<Limited> {
+        Arc::new(Limited {
+            max,
+            fail_on: None,
+            applied: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn sections(entries: &[(&str, Value)]) -> ModuleSections {
+        entries
+            .iter()
+            .map(|(k, v)| ((*k).to_owned(), v.clone()))
+            .collect()
+    }
+
+    fn reloader(a: &Arc<Limited>, b: &Arc<Limited>, initial: &ModuleSections) -> ConfigReloader {
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("a", &[], Arc::new(Noop));
+        builder.register_core_with_meta("b", &["a"], Arc::new(Noop));
+        builder.register_core_with_meta("static", &[], Arc::new(Noop));
+        builder.register_config_reload_with_meta("a", a.clone());
+        builder.register_config_reload_with_meta("b", b.clone());
+        let registry = builder.build_topo_sorted().unwrap();
+        ConfigReloader::new(&registry, &Sections(initial.clone()))
+    }
+
+    #[tokio::test]
+    async fn applies_only_changed_sections() {
+        let (a, b) = (limited(10), limited(10));
+        let initial = sections(&[
+            ("a", json!({ "config": { "limit": 1 } })),
+            ("b", json!({ "config": { "limit": 1 } })),
+        ]);
+        let reloader = reloader(&a, &b, &initial);
+
+        let next = sections(&[
+            ("a", json!({ "config": { "limit": 5 } })),
+            ("b", json!({ "config": { "limit": 1 } })),
+            ("unregistered", json!({ "config": {} })),
+        ]);
+        assert_eq!(reloader.apply(&next).await.unwrap(), vec!["a"]);
+        assert_eq!(*a.applied.lock(), vec![5]);
+        assert!(b.applied.lock().is_empty());
+
+        // Applying the same sections again is a no-op
+        assert!(reloader.apply(&next).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn refusal_applies_nothing_and_keeps_previous_config() {
+        let (a, b) = (limited(10), limited(3));
+        let initial = sections(&[
+            ("a", json!({ "config": { "limit": 1 } })),
+            ("b", json!({ "config": { "limit": 1 } })),
+        ]);
+        let reloader = reloader(&a, &b, &initial);
+
+        let next = sections(&[
+            ("a", json!({ "config": { "limit": 5 } })),
+            ("b", json!({ "config": { "limit": 5 } })),
+        ]);
+        let err = reloader.apply(&next).await.unwrap_err();
+        assert!(matches!(err, ReloadError::Rejected { ref module, .. } if module == "b"));
+        // `b` refused during validation, before `a` applied anything
+        assert!(a.applied.lock().is_empty());
+
+        // The previous config is still the baseline
+        let only_a = sections(&[
+            ("a", json!({ "config": { "limit": 2 } })),
+            ("b", json!({ "config": { "limit": 1 } })),
+        ]);
+        assert_eq!(reloader.apply(&only_a).await.unwrap(), vec!["a"]);
+    }
+
+    #[tokio::test]
+    async fn apply_failure_rolls_back_applied_modules() {
+        let a = limited(10);
+        let b = Arc::new(Limited {
+            max: 10,
+            fail_on: Some(5),
+            applied: Mutex::new(Vec::new()),
+        });
+        let initial = sections(&[
+            ("a", json!({ "config": { "limit": 1 } })),
+            ("b", json!({ "config": { "limit": 1 } })),
+        ]);
+        let reloader = reloader(&a, &b, &initial);
+
+        let next = sections(&[
+            ("a", json!({ "config": { "limit": 5 } })),
+            ("b", json!({ "config": { "limit": 5 } })),
+        ]);
+        let err = reloader.apply(&next).await.unwrap_err();
+        assert!(matches!(err, ReloadError::Rejected { ref module, .. } if module == "b"));
+        // `a` applied 5, then was rolled back to 1
+        assert_eq!(*a.applied.lock(), vec![5, 1]);
+    }
+
+    #[tokio::test]
+    async fn rejects_changes_that_need_a_restart() {
+        let (a, b) = (limited(10), limited(10));
+        let reloader = reloader(&a, &b, &ModuleSections::new());
+
+        let db_change = sections(&[("a", json!({ "database": { "dsn": "sqlite://x.db" } }))]);
+        assert!(matches!(
+            reloader.apply(&db_change).await,
+            Err(ReloadError::NotReloadable { ref module, .. }) if module == "a"
+        ));
+
+        let no_capability = sections(&[("static", json!({ "config": { "x": 1 } }))]);
+        assert!(matches!(
+            reloader.apply(&no_capability).await,
+            Err(ReloadError::NotReloadable { ref module, .. }) if module == "static"
+        ));
+        assert!(a.applied.lock().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reload_requires_a_source() {
+        let (a, b) = (limited(10), limited(10));
+        let reloader = reloader(&a, &b, &ModuleSections::new());
+        assert!(matches!(
+            reloader.reload().await,
+            Err(ReloadError::NoSource)
+        ));
+    }
+}
@@ -7,6 +7,8 @@ use crate::contracts::RegisterGrpcServiceFn;
 pub struct ModuleInstallers {
     pub module_name: String,
     pub installers: Vec<RegisterGrpcServiceFn>,
+    /// Encoded descriptor sets of the services, for server reflection.
+    pub file_descriptor_sets: Vec<&'static [u8]>,
 }
 
 /// Grouped installers for all modules in the process.
@@ -10,8 +10,13 @@
 //! - `post_init` (system modules only; runs after *all* `init` complete)
 //! - REST wiring (modules with REST capability; requires a single REST host)
 //! - gRPC registration (modules with gRPC capability; requires a single gRPC hub)
-//! - start/stop (stateful modules)
+//! - start/stop (stateful modules); readiness (`HealthRegistry`) is on in between
+//! - scheduled jobs (modules with scheduled jobs capability; after start, before stop)
 //! - `OoP` spawn / wait / stop (host-only orchestration)
+//!
+//! DB migrations, `init` and start run by dependency level: independent modules
+//! run concurrently (bounded), and each module's phase duration is logged and
+//! recorded as the `modkit.module.phase.duration` metric.
 
 use axum::Router;
 use std::collections::HashSet;
@@ -20,15 +25,23 @@ use std::sync::Arc;
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
-use crate::backends::OopSpawnConfig;
-use crate::client_hub::ClientHub;
+use crate::backends::{
+    BackendKind, ModuleRuntimeBackend, OopModuleConfig, OopSpawnConfig, StaticBackend,
+};
+use crate::client_hub::{ClientHub, ClientLayer};
 use crate::config::ConfigProvider;
 use crate::context::ModuleContextBuilder;
+use crate::events::{EventBus, EventBusClient};
+use crate::health::HealthRegistry;
+use crate::jobs::{JobLock, JobScheduler};
 use crate::registry::{
     ApiGatewayCap, GrpcHubCap, ModuleEntry, ModuleRegistry, RegistryError, RestApiCap, RunnableCap,
-    SystemCap,
+    ScheduledJobsCap, SystemCap,
+};
+use crate::runtime::{
+    ConfigReloader, ConfigSource, DEFAULT_PHASE_CONCURRENCY, DEFAULT_WATCH_INTERVAL,
+    GrpcInstallerStore, ModuleManager, OopSpawnOptions, StaticModuleConfig, SystemContext,
 };
-use crate::runtime::{GrpcInstallerStore, ModuleManager, OopSpawnOptions, SystemContext};
 
 #[cfg(feature = "db")]
 use crate::registry::DatabaseCap;
@@ -58,6 +71,12 @@ pub const MODKIT_DIRECTORY_ENDPOINT_ENV: &str = "MODKIT_DIRECTORY_ENDPOINT";
 /// Environment variable name for passing rendered module config to `OoP` modules.
 pub const MODKIT_MODULE_CONFIG_ENV: &str = "MODKIT_MODULE_CONFIG";
 
+/// Environment variable name for the instance ID of a pre-started `OoP` module.
+pub const MODKIT_INSTANCE_ID_ENV: &str = "MODKIT_INSTANCE_ID";
+
+/// How long the stop phase waits for in-flight scheduled job runs.
+const JOBS_STOP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// `HostRuntime` owns the lifecycle orchestration for `ModKit`.
 ///
 /// It encapsulates all runtime state and drives modules through the full lifecycle (see module docs).
@@ -74,6 +93,14 @@ pub struct HostRuntime {
     db_options: DbOptions,
     /// `OoP` module spawn configuration and backend
     oop_options: Option<OopSpawnOptions>,
+    /// Aggregated module health, published in the `ClientHub` for the REST host
+    health: Arc<HealthRegistry>,
+    /// Live config reload, published in the `ClientHub` for the admin endpoint
+    config_reloader: Arc<ConfigReloader>,
+    /// Scheduled jobs, published in the `ClientHub` for the module orchestrator
+    jobs: Arc<JobScheduler>,
+    /// Max modules running the db/init/start phases concurrently within a dependency level
+    phase_concurrency: usize,
 }
 
 impl HostRuntime {
@@ -100,6 +127,26 @@ impl HostRuntime {
             DbOptions::None => None,
         };
 
+        // SDK clients with an instrumented wrapper get a span and latency sample per call
+        client_hub.add_layer(ClientLayer::instrumented());
+
+        // Published before init so the REST host can serve /healthz and /readyz
+        let health = Arc::new(HealthRegistry::from_registry(&registry));
+        client_hub.register::<HealthRegistry>(health.clone());
+
+        let config_reloader = Arc::new(ConfigReloader::new(&registry, modules_cfg.as_ref()));
+        client_hub.register::<ConfigReloader>(config_reloader.clone());
+
+        let jobs = Arc::new(JobScheduler::new(cancel.child_token()));
+        client_hub.register::<JobScheduler>(jobs.clone());
+
+        // OoP bootstrap pre-registers the host's bus as upstream; the host has none
+        let event_bus = Arc::new(EventBus::new());
+        if let Ok(upstream) = client_hub.get::<dyn EventBusClient>() {
+            event_bus.set_upstream(upstream, cancel.clone());
+        }
+        client_hub.register::<EventBus>(event_bus);
+
         let ctx_builder = ModuleContextBuilder::new(
             instance_id,
             modules_cfg,
@@ -118,9 +165,27 @@ impl HostRuntime {
             cancel,
             db_options,
             oop_options,
+            health,
+            config_reloader,
+            jobs,
+            phase_concurrency: DEFAULT_PHASE_CONCURRENCY,
         }
     }
 
+    /// Limit how many modules of a dependency level run a phase concurrently (`1` = sequential).
+    #[must_use]
+    pub fn with_phase_concurrency(mut self, limit: usize) -> Self {
+        self.phase_concurrency = limit.max(1);
+        self
+    }
+
+    /// Enable live config reload from `source` (on `SIGHUP` and file changes once started).
+    #[must_use]
+    pub fn with_config_source(self, source: Arc<dyn ConfigSource>) -> Self {
+        self.config_reloader.set_source(source);
+        self
+    }
+
     /// `PRE_INIT` phase: wire runtime internals into system modules.
     ///
     /// This phase runs before init and only for modules with the "system" capability.
@@ -249,7 +314,8 @@ impl HostRuntime {
 
     /// DB MIGRATION phase: run migrations for all modules with DB capability.
     ///
-    /// Runs before init, with system modules processed first.
+    /// Runs before init, with system modules processed first. Modules sharing a
+    /// database migrate one at a time (`SQLite` rejects concurrent writers).
     ///
     /// Modules provide migrations via `DatabaseCapability::migrations()`.
     /// The runtime executes them with a privileged connection that modules
@@ -259,7 +325,13 @@ impl HostRuntime {
     async fn run_db_phase(&self) -> Result<(), RegistryError> {
         tracing::info!("Phase: db (before init)");
 
-        for entry in self.registry.modules_by_system_priority() {
+        let levels = self.levels_with(|e| e.caps.has::<DatabaseCap>());
+        let db_locks = parking_lot::Mutex::new(std::collections::HashMap::<
+            String,
+            Arc<tokio::sync::Mutex<()>>,
+        >::new());
+        let db_locks = &db_locks;
+        super::phase::run_by_level("db", &levels, self.phase_concurrency, |entry| async move {
             // Check for cancellation before processing each module
             if self.cancel.is_cancelled() {
                 tracing::warn!("DB migration phase cancelled by signal");
@@ -274,6 +346,8 @@ impl HostRuntime {
                 .await?
             {
                 Some((db, dbm)) => {
+                    let lock = Arc::clone(db_locks.lock().entry(db.dsn().to_owned()).or_default());
+                    let _serialized = lock.lock().await;
                     Self::migrate_module(entry.name, &db, dbm).await?;
                 }
                 None if db_module.is_some() => {
@@ -284,37 +358,50 @@ impl HostRuntime {
                 }
                 None => {}
             }
-        }
-
-        Ok(())
+            Ok(())
+        })
+        .await
     }
 
-    /// INIT phase: initialize all modules in topological order.
+    /// INIT phase: initialize all modules by dependency level.
     ///
-    /// System modules initialize first, followed by user modules.
+    /// System modules initialize first, followed by user modules. Modules within a
+    /// level do not depend on each other and initialize concurrently.
     async fn run_init_phase(&self) -> Result<(), RegistryError> {
         tracing::info!("Phase: init");
 
-        for entry in self.registry.modules_by_system_priority() {
-            let ctx =
-                self.ctx_builder
-                    .for_module(entry.name)
+        let levels = self.registry.levels_by_system_priority();
+        super::phase::run_by_level(
+            "init",
+            &levels,
+            self.phase_concurrency,
+            |entry| async move {
+                let ctx = self.ctx_builder.for_module(entry.name).await.map_err(|e| {
+                    RegistryError::Init {
+                        module: entry.name,
+                        source: e,
+                    }
+                })?;
+                entry
+                    .core
+                    .init(&ctx)
                     .await
                     .map_err(|e| RegistryError::Init {
                         module: entry.name,
                         source: e,
-                    })?;
-            entry
-                .core
-                .init(&ctx)
-                .await
-                .map_err(|e| RegistryError::Init {
-                    module: entry.name,
-                    source: e,
-                })?;
-        }
+                    })
+            },
+        )
+        .await
+    }
 
-        Ok(())
+    /// Dependency levels restricted to modules matching `pred`.
+    fn levels_with(&self, pred: impl Fn(&ModuleEntry) -> bool) -> Vec<Vec<&ModuleEntry>> {
+        self.registry
+            .levels_by_system_priority()
+            .into_iter()
+            .map(|level| level.into_iter().filter(|e| pred(e)).collect())
+            .collect()
     }
 
     /// `POST_INIT` phase: optional hook after ALL modules completed `init()`.
@@ -499,6 +586,7 @@ impl HostRuntime {
                 modules_data.push(crate::runtime::ModuleInstallers {
                     module_name: module_name.clone(),
                     installers,
+                    file_descriptor_sets: service_module.file_descriptor_sets(),
                 });
             }
 
@@ -515,29 +603,65 @@ impl HostRuntime {
         Ok(())
     }
 
-    /// START phase: start all stateful modules.
+    /// START phase: start all stateful modules by dependency level.
     ///
-    /// System modules start first, followed by user modules.
+    /// System modules start first, followed by user modules. Modules within a
+    /// level do not depend on each other and start concurrently.
     async fn run_start_phase(&self) -> Result<(), RegistryError> {
         tracing::info!("Phase: start");
 
-        for e in self.registry.modules_by_system_priority() {
-            if let Some(s) = e.caps.query::<RunnableCap>() {
-                tracing::debug!(
-                    module = e.name,
-                    is_system = e.caps.has::<SystemCap>(),
-                    "Starting stateful module"
-                );
-                s.start(self.cancel.clone())
-                    .await
-                    .map_err(|source| RegistryError::Start {
-                        module: e.name,
-                        source,
-                    })?;
-                tracing::info!(module = e.name, "Started module");
-            }
+        let levels = self.levels_with(|e| e.caps.has::<RunnableCap>());
+        super::phase::run_by_level("start", &levels, self.phase_concurrency, |e| async move {
+            let Some(s) = e.caps.query::<RunnableCap>() else {
+                return Ok(());
+            };
+            tracing::debug!(
+                module = e.name,
+                is_system = e.caps.has::<SystemCap>(),
+                "Starting stateful module"
+            );
+            s.start(self.cancel.clone())
+                .await
+                .map_err(|source| RegistryError::Start {
+                    module: e.name,
+                    source,
+                })?;
+            tracing::info!(module = e.name, "Started module");
+            Ok(())
+        })
+        .await
+    }
+
+    /// JOBS phase: schedule the jobs declared by modules with `ScheduledJobsCapability`.
+    ///
+    /// Single-runner jobs lock through the module's database.
+    async fn run_jobs_phase(&self) -> Result<(), RegistryError> {
+        let modules: Vec<_> = self
+            .registry
+            .modules()
+            .iter()
+            .filter_map(|e| e.caps.query::<ScheduledJobsCap>().map(|cap| (e.name, cap)))
+            .collect();
+        if modules.is_empty() {
+            return Ok(());
         }
 
+        tracing::info!("Phase: jobs");
+        for (module, cap) in modules {
+            let ctx = self.module_context(module).await?;
+            let map_err = |source| RegistryError::ScheduleJobs { module, source };
+
+            #[cfg(feature = "db")]
+            let lock = ctx.db().map(|db| Arc::new(db) as Arc<dyn JobLock>);
+            #[cfg(not(feature = "db"))]
+            let lock: Option<Arc<dyn JobLock>> = None;
+
+            for job in cap.jobs(&ctx).map_err(map_err)? {
+                self.jobs
+                    .schedule(module, job, lock.clone())
+                    .map_err(map_err)?;
+            }
+        }
         Ok(())
     }
 
@@ -576,15 +700,27 @@ impl HostRuntime {
     /// the real directory endpoint to `OoP` modules.
     async fn run_oop_spawn_phase(&self) -> Result<(), RegistryError> {
         let oop_opts = match &self.oop_options {
-            Some(opts) if !opts.modules.is_empty() => opts,
+            Some(opts) if !opts.modules.is_empty() || !opts.static_modules.is_empty() => opts,
             _ => return Ok(()),
         };
 
         tracing::info!("Phase: oop_spawn");
 
+        // Pre-started instances only need to be known to the directory
+        self.register_static_instances(&oop_opts.static_modules)
+            .await?;
+        if oop_opts.modules.is_empty() {
+            return Ok(());
+        }
+
         // Wait for grpc_hub to publish its endpoint (it runs async in start phase)
         let directory_endpoint = self.wait_for_grpc_hub_endpoint().await;
 
+        // Restart counts and crash loops are surfaced through the module manager
+        oop_opts
+            .backend
+            .attach_module_manager(Arc::clone(&self.module_manager));
+
         for module_cfg in &oop_opts.modules {
             // Build environment with directory endpoint and rendered config
             // Note: User controls --config via execution.args in master config
@@ -606,6 +742,7 @@ impl HostRuntime {
                 args,
                 env,
                 working_directory: module_cfg.working_directory.clone(),
+                restart: module_cfg.restart.clone(),
             };
 
             oop_opts
@@ -627,6 +764,28 @@ impl HostRuntime {
         Ok(())
     }
 
+    /// Register pre-started `OoP` instances into the module manager via `StaticBackend`.
+    async fn register_static_instances(
+        &self,
+        modules: &[StaticModuleConfig],
+    ) -> Result<(), RegistryError> {
+        let backend = StaticBackend::new(Arc::clone(&self.module_manager));
+        for module_cfg in modules {
+            for instance in &module_cfg.instances {
+                let mut cfg = OopModuleConfig::new(&module_cfg.module_name, BackendKind::Static);
+                cfg.instance = Some(instance.clone());
+                backend
+                    .spawn_instance(&cfg)
+                    .await
+                    .map_err(|e| RegistryError::OopSpawn {
+                        module: module_cfg.module_name.clone(),
+                        source: e,
+                    })?;
+            }
+        }
+        Ok(())
+    }
+
     /// Wait for `grpc-hub` to publish its bound endpoint.
     ///
     /// Polls the `GrpcHubModule::bound_endpoint()` with a short interval until available or timeout.
@@ -710,10 +869,10 @@ impl HostRuntime {
     /// 4. Post-init (system modules only)
     /// 5. REST (modules with REST capability)
     /// 6. gRPC (modules with gRPC capability)
-    /// 7. Start (runnable modules)
+    /// 7. Start (runnable modules), then scheduled jobs
     /// 8. `OoP` spawn (out-of-process modules)
     /// 9. Wait for cancellation
-    /// 10. Stop (runnable modules in reverse order)
+    /// 10. Stop (scheduled jobs, then runnable modules in reverse order)
     async fn run_phases_internal(self, mode: RunMode) -> anyhow::Result<()> {
         // Log execution mode
         match mode {
@@ -756,16 +915,24 @@ impl HostRuntime {
         // 6. gRPC registration phase
         self.run_grpc_phase().await?;
 
-        // 7. Start phase
+        // 7. Start phase, then scheduled jobs
         self.run_start_phase().await?;
+        self.run_jobs_phase().await?;
 
         // 8. OoP spawn phase (after grpc_hub is running)
         self.run_oop_spawn_phase().await?;
+        self.health.set_serving(true);
+        if self.config_reloader.has_source() {
+            self.config_reloader
+                .spawn_triggers(self.cancel.clone(), DEFAULT_WATCH_INTERVAL);
+        }
 
         // 9. Wait for cancellation
         self.cancel.cancelled().await;
+        self.health.set_serving(false);
 
-        // 10. Stop phase
+        // 10. Stop phase (jobs first: they use module resources)
+        self.jobs.shutdown(JOBS_STOP_TIMEOUT).await;
         self.run_stop_phase().await?;
 
         Ok(())
@@ -959,6 +1126,94 @@ mod tests {
         }
     }
 
+    fn runtime_for(registry: ModuleRegistry) -> HostRuntime {
+        HostRuntime::new(
+            registry,
+            Arc::new(EmptyConfigProvider),
+            DbOptions::None,
+            Arc::new(ClientHub::new()),
+            CancellationToken::new(),
+            Uuid::new_v4(),
+            None,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_init_runs_independent_modules_concurrently() {
+        /// Both modules of the first level must be in `init` at the same time
+        struct Rendezvous(Arc<tokio::sync::Barrier>);
+
+        #[async_trait::async_trait]
+        impl Module for Rendezvous {
+            async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+                self.0.wait().await;
+                Ok(())
+            }
+        }
+
+        let barrier = Arc::new(tokio::sync::Barrier::new(2));
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("a", &[], Arc::new(Rendezvous(barrier.clone())));
+        builder.register_core_with_meta("b", &[], Arc::new(Rendezvous(barrier)));
+        builder.register_core_with_meta("c", &["a", "b"], Arc::new(DummyCore));
+        let runtime = runtime_for(builder.build_topo_sorted().unwrap());
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), runtime.run_init_phase())
+            .await
+            .expect("independent modules must not init sequentially")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_init_failure_is_deterministic_and_stops_later_levels() {
+        struct Tracked {
+            fail: bool,
+            inits: Arc<AtomicUsize>,
+        }
+
+        #[async_trait::async_trait]
+        impl Module for Tracked {
+            async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+                self.inits.fetch_add(1, Ordering::SeqCst);
+                if self.fail {
+                    anyhow::bail!("Intentional failure")
+                }
+                Ok(())
+            }
+        }
+
+        let inits = Arc::new(AtomicUsize::new(0));
+        let tracked = |fail| {
+            Arc::new(Tracked {
+                fail,
+                inits: inits.clone(),
+            })
+        };
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("a", &[], tracked(false));
+        builder.register_core_with_meta("b", &[], tracked(true));
+        builder.register_core_with_meta("c", &[], tracked(true));
+        builder.register_core_with_meta("d", &["a"], tracked(false));
+        let registry = builder.build_topo_sorted().unwrap();
+        let first_failing = registry
+            .modules()
+            .iter()
+            .map(ModuleEntry::name)
+            .find(|name| *name == "b" || *name == "c")
+            .unwrap();
+        let runtime = runtime_for(registry);
+
+        for _ in 0..3 {
+            let err = runtime.run_init_phase().await.unwrap_err();
+            assert!(
+                matches!(err, RegistryError::Init { module, .. } if module == first_failing),
+                "unexpected error: {err}"
+            );
+        }
+        // `d` depends on `a` and is never initialized
+        assert_eq!(inits.load(Ordering::SeqCst), 9);
+    }
+
     #[tokio::test]
     async fn test_post_init_runs_after_all_init_and_system_first() {
         #[derive(Clone)]
@@ -1053,4 +1308,62 @@ mod tests {
             ]
         );
     }
+
+    #[tokio::test]
+    async fn test_jobs_phase_schedules_declared_jobs() {
+        struct Jobs {
+            single_runner: bool,
+        }
+
+        impl crate::contracts::ScheduledJobsCapability for Jobs {
+            fn jobs(&self, _ctx: &ModuleCtx) -> anyhow::Result<Vec<crate::jobs::ScheduledJob>> {
+                let job =
+                    crate::jobs::ScheduledJob::cron("nightly", "0 3 * * *", |_| async { Ok(()) })?;
+                Ok(vec![if self.single_runner {
+                    job.single_runner()
+                } else {
+                    job
+                }])
+            }
+        }
+
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("cleanup", &[], Arc::new(DummyCore));
+        builder.register_scheduled_jobs_with_meta(
+            "cleanup",
+            Arc::new(Jobs {
+                single_runner: false,
+            }),
+        );
+        let runtime = runtime_for(builder.build_topo_sorted().unwrap());
+        runtime.run_jobs_phase().await.unwrap();
+
+        let jobs = runtime.client_hub.get::<JobScheduler>().unwrap().statuses();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].module, "cleanup");
+        assert_eq!(jobs[0].schedule, "cron 0 3 * * *");
+        runtime
+            .jobs
+            .shutdown(std::time::Duration::from_secs(1))
+            .await;
+
+        // Single-runner jobs need the module's database for the lock
+        let mut builder = RegistryBuilder::default();
+        builder.register_core_with_meta("cleanup", &[], Arc::new(DummyCore));
+        builder.register_scheduled_jobs_with_meta(
+            "cleanup",
+            Arc::new(Jobs {
+                single_runner: true,
+            }),
+        );
+        let runtime = runtime_for(builder.build_topo_sorted().unwrap());
+        let err = runtime.run_jobs_phase().await.unwrap_err();
+        assert!(matches!(
+            err,
+            RegistryError::ScheduleJobs {
+                module: "cleanup",
+                ..
+            }
+        ));
+    }
 }
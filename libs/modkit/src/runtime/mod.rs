@@ -1,6 +1,8 @@
+mod config_reload;
 mod grpc_installers;
 mod host_runtime;
 mod module_manager;
+mod phase;
 mod runner;
 mod system_context;
 
@@ -10,12 +12,20 @@ pub mod shutdown;
 #[cfg(test)]
 mod tests;
 
+pub use config_reload::{
+    ConfigReloader, ConfigSource, DEFAULT_WATCH_INTERVAL, ModuleSections, ReloadError,
+};
 pub use grpc_installers::{GrpcInstallerData, GrpcInstallerStore, ModuleInstallers};
 pub use host_runtime::{
-    DbOptions, HostRuntime, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_MODULE_CONFIG_ENV,
+    DbOptions, HostRuntime, MODKIT_DIRECTORY_ENDPOINT_ENV, MODKIT_INSTANCE_ID_ENV,
+    MODKIT_MODULE_CONFIG_ENV,
+};
+pub use module_manager::{
+    Endpoint, InstanceState, ModuleInstance, ModuleManager, ModuleSupervision,
 };
-pub use module_manager::{Endpoint, InstanceState, ModuleInstance, ModuleManager};
+pub use phase::DEFAULT_PHASE_CONCURRENCY;
 pub use runner::{
-    ClientRegistration, OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions, run,
+    ClientRegistration, OopModuleSpawnConfig, OopSpawnOptions, RunOptions, ShutdownOptions,
+    StaticModuleConfig, run,
 };
 pub use system_context::SystemContext;
@@ -82,6 +82,19 @@ pub enum InstanceState {
 pub struct InstanceRuntimeState {
     pub last_heartbeat: Instant,
     pub state: InstanceState,
+    /// Restarts of the module's process before this instance registered
+    pub restart_count: u32,
+}
+
+/// Supervision status of an out-of-process module, reported by the spawning backend
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleSupervision {
+    /// Restarts performed so far
+    pub restart_count: u32,
+    /// How the last process exited (e.g., "exit status: 1")
+    pub last_exit: Option<String>,
+    /// Crash loop detected; the module is no longer restarted
+    pub failed: bool,
 }
 
 /// Represents a single instance of a module
@@ -93,6 +106,7 @@ pub struct ModuleInstance {
     pub control: Option<Endpoint>,
     pub grpc_services: HashMap<String, Endpoint>,
     pub version: Option<String>,
+    pinned: bool,
     inner: Arc<parking_lot::RwLock<InstanceRuntimeState>>,
 }
 
@@ -104,6 +118,7 @@ impl Clone for ModuleInstance {
             control: self.control.clone(),
             grpc_services: self.grpc_services.clone(),
             version: self.version.clone(),
+            pinned: self.pinned,
             inner: Arc::clone(&self.inner),
         }
     }
@@ -117,9 +132,11 @@ impl ModuleInstance {
             control: None,
             grpc_services: HashMap::new(),
             version: None,
+            pinned: false,
             inner: Arc::new(parking_lot::RwLock::new(InstanceRuntimeState {
                 last_heartbeat: Instant::now(),
                 state: InstanceState::Registered,
+                restart_count: 0,
             })),
         }
     }
@@ -139,6 +156,22 @@ impl ModuleInstance {
         self
     }
 
+    /// Mark this instance as statically configured.
+    ///
+    /// Pinned instances are quarantined but never evicted when heartbeats stop,
+    /// recover on the next heartbeat, and keep their configured endpoints when
+    /// the process registers itself under the same instance ID.
+    pub fn pinned(mut self) -> Self {
+        self.pinned = true;
+        self
+    }
+
+    /// Whether this instance is statically configured (see [`ModuleInstance::pinned`])
+    #[must_use]
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
     /// Get the current state of this instance
     #[must_use]
     pub fn state(&self) -> InstanceState {
@@ -150,6 +183,12 @@ impl ModuleInstance {
     pub fn last_heartbeat(&self) -> Instant {
         self.inner.read().last_heartbeat
     }
+
+    /// Get the number of module restarts before this instance registered
+    #[must_use]
+    pub fn restart_count(&self) -> u32 {
+        self.inner.read().restart_count
+    }
 }
 
 /// Central registry that tracks all running module instances in the system.
@@ -159,6 +198,7 @@ impl ModuleInstance {
 pub struct ModuleManager {
     inner: DashMap<String, Vec<Arc<ModuleInstance>>>,
     rr_counters: DashMap<String, usize>,
+    supervision: DashMap<String, ModuleSupervision>,
     hb_ttl: Duration,
     hb_grace: Duration,
 }
@@ -180,6 +220,7 @@ impl ModuleManager {
         Self {
             inner: DashMap::new(),
             rr_counters: DashMap::new(),
+            supervision: DashMap::new(),
             hb_ttl: Duration::from_secs(15),
             hb_grace: Duration::from_secs(30),
         }
@@ -194,13 +235,20 @@ impl ModuleManager {
     /// Register or update a module instance
     pub fn register_instance(&self, instance: Arc<ModuleInstance>) {
         let module = instance.module.clone();
+        if let Some(sup) = self.supervision.get(&module) {
+            instance.inner.write().restart_count = sup.restart_count;
+        }
         let mut vec = self.inner.entry(module).or_default();
         // replace by instance_id if it already exists
         if let Some(pos) = vec
             .iter()
             .position(|i| i.instance_id == instance.instance_id)
         {
-            vec[pos] = instance;
+            vec[pos] = if vec[pos].pinned && !instance.pinned {
+                Arc::new(merge_pinned(&vec[pos], &instance))
+            } else {
+                instance
+            };
         } else {
             vec.push(instance);
         }
@@ -223,8 +271,11 @@ impl ModuleManager {
         {
             let mut state = inst.inner.write();
             state.last_heartbeat = at;
-            // Transition Registered -> Healthy on first heartbeat
-            if state.state == InstanceState::Registered {
+            // Transition Registered -> Healthy on first heartbeat; pinned instances
+            // also recover from quarantine once heartbeats resume
+            if state.state == InstanceState::Registered
+                || (inst.pinned && state.state == InstanceState::Quarantined)
+            {
                 state.state = InstanceState::Healthy;
             }
         }
@@ -248,6 +299,40 @@ impl ModuleManager {
         }
     }
 
+    /// Record a restart of an out-of-process module after its process exited
+    pub fn record_restart(&self, module: &str, exit: impl Into<String>) {
+        let mut sup = self.supervision.entry(module.to_owned()).or_default();
+        sup.restart_count += 1;
+        sup.last_exit = Some(exit.into());
+        sup.failed = false;
+    }
+
+    /// Mark an out-of-process module as failed (crash loop); its instances are quarantined
+    pub fn mark_failed(&self, module: &str, exit: impl Into<String>) {
+        {
+            let mut sup = self.supervision.entry(module.to_owned()).or_default();
+            sup.last_exit = Some(exit.into());
+            sup.failed = true;
+        }
+        if let Some(vec) = self.inner.get(module) {
+            for inst in vec.iter() {
+                inst.inner.write().state = InstanceState::Quarantined;
+            }
+        }
+    }
+
+    /// Get the supervision status of a module, if its process was ever restarted or failed
+    #[must_use]
+    pub fn supervision(&self, module: &str) -> Option<ModuleSupervision> {
+        self.supervision.get(module).map(|s| s.clone())
+    }
+
+    /// Names of all modules with a supervision status
+    #[must_use]
+    pub fn supervised_modules(&self) -> Vec<String> {
+        self.supervision.iter().map(|e| e.key().clone()).collect()
+    }
+
     /// Remove an instance from the directory
     pub fn deregister(&self, module: &str, instance_id: Uuid) {
         let mut remove_module = false;
@@ -305,7 +390,8 @@ impl ModuleManager {
                 }
 
                 // Evict quarantined instances that exceed grace period
-                if state.state == Quarantined && age >= self.hb_ttl + self.hb_grace {
+                if state.state == Quarantined && !inst.pinned && age >= self.hb_ttl + self.hb_grace
+                {
                     return false; // Remove from directory
                 }
 
@@ -394,6 +480,28 @@ impl ModuleManager {
     }
 }
 
+/// Re-registration of a pinned instance by its process: the registered endpoints
+/// take precedence, configured ones fill the gaps, and the instance stays pinned.
+fn merge_pinned(pinned: &ModuleInstance, registered: &ModuleInstance) -> ModuleInstance {
+    let mut merged = registered.clone();
+    merged.pinned = true;
+    merged.control = registered
+        .control
+        .clone()
+        .or_else(|| pinned.control.clone());
+    merged.version = registered
+        .version
+        .clone()
+        .or_else(|| pinned.version.clone());
+    for (name, ep) in &pinned.grpc_services {
+        merged
+            .grpc_services
+            .entry(name.clone())
+            .or_insert_with(|| ep.clone());
+    }
+    merged
+}
+
 impl Default for ModuleManager {
     fn default() -> Self {
         Self::new()
@@ -731,4 +839,72 @@ mod tests {
         // Endpoints should differ
         assert_ne!(ep1, ep2);
     }
+
+    #[test]
+    fn test_restart_count_carries_over_and_failure_quarantines() {
+        let dir = ModuleManager::new();
+        dir.record_restart("oop_module", "exit status: 1");
+        dir.record_restart("oop_module", "exit status: 1");
+
+        // A restarted process registers a new instance that reports the restarts
+        let id = Uuid::new_v4();
+        dir.register_instance(Arc::new(ModuleInstance::new("oop_module", id)));
+        let inst = &dir.instances_of("oop_module")[0];
+        assert_eq!(inst.restart_count(), 2);
+        assert_eq!(inst.state(), InstanceState::Registered);
+
+        dir.mark_failed("oop_module", "signal: 9 (SIGKILL)");
+        assert_eq!(inst.state(), InstanceState::Quarantined);
+        assert_eq!(
+            dir.supervision("oop_module"),
+            Some(ModuleSupervision {
+                restart_count: 2,
+                last_exit: Some("signal: 9 (SIGKILL)".to_owned()),
+                failed: true,
+            })
+        );
+        assert_eq!(dir.supervised_modules(), vec!["oop_module".to_owned()]);
+        assert!(dir.supervision("other").is_none());
+    }
+
+    #[test]
+    fn test_pinned_instance_survives_eviction_and_reregistration() {
+        let ttl = Duration::from_millis(50);
+        let grace = Duration::from_millis(50);
+        let dir = ModuleManager::new().with_heartbeat_policy(ttl, grace);
+        let id = Uuid::new_v4();
+        let ep = Endpoint::http("10.0.0.5", 50061);
+        dir.register_instance(Arc::new(
+            ModuleInstance::new("static_module", id)
+                .with_control(ep.clone())
+                .with_version("1.0.0")
+                .with_grpc_service("svc", ep)
+                .pinned(),
+        ));
+
+        // Not routable until the process heartbeats
+        assert!(dir.pick_service_round_robin("svc").is_none());
+        let now = Instant::now();
+        dir.update_heartbeat("static_module", id, now);
+        assert!(dir.pick_service_round_robin("svc").is_some());
+
+        // Heartbeats stop: quarantined, but never evicted
+        dir.evict_stale(now + ttl + grace + Duration::from_millis(10));
+        let inst = &dir.instances_of("static_module")[0];
+        assert_eq!(inst.state(), InstanceState::Quarantined);
+        assert!(dir.pick_service_round_robin("svc").is_none());
+
+        // The restarted process registers itself under the same ID
+        dir.register_instance(Arc::new(
+            ModuleInstance::new("static_module", id).with_version("1.0.1"),
+        ));
+        let inst = &dir.instances_of("static_module")[0];
+        assert!(inst.is_pinned());
+        assert_eq!(inst.version.as_deref(), Some("1.0.1"));
+        assert!(inst.control.is_some());
+        assert!(inst.grpc_services.contains_key("svc"));
+
+        dir.update_heartbeat("static_module", id, Instant::now());
+        assert_eq!(inst.state(), InstanceState::Healthy);
+    }
 }
@@ -0,0 +1,134 @@
+//! Concurrent execution of a lifecycle phase by dependency level.
+//!
+//! Modules within a level do not depend on each other, so they run concurrently
+//! (bounded). Levels run one after another; the phase stops after the first level
+//! with a failure. Errors are reported in topological order, so the failing module
+//! named in the error does not depend on task scheduling.
+
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use futures_util::stream;
+
+use crate::registry::{ModuleEntry, RegistryError};
+
+/// Default number of modules that run a phase concurrently.
+pub const DEFAULT_PHASE_CONCURRENCY: usize = 8;
+
+/// Run `op` for every module in `levels`, one level at a time.
+///
+/// # Errors
+/// Returns the error of the first failing module (in topological order) of the first
+/// failing level. Modules of later levels are not run.
+pub(super) async fn run_by_level<'a, F, Fut>(
+    phase: &'static str,
+    levels: &[Vec<&'a ModuleEntry>],
+    concurrency: usize,
+    op: F,
+) -> Result<(), RegistryError>
+where
+    F: Fn(&'a ModuleEntry) -> Fut,
+    Fut: Future<Output = Result<(), RegistryError>>,
+{
+    let metrics = PhaseMetrics::new();
+
+    for (level, entries) in levels.iter().enumerate() {
+        if entries.is_empty() {
+            continue;
+        }
+        tracing::debug!(
+            phase,
+            level,
+            modules = ?entries.iter().map(|e| e.name()).collect::<Vec<_>>(),
+            "Running phase level"
+        );
+
+        let mut tasks = Vec::with_capacity(entries.len());
+        for &entry in entries {
+            tasks.push(timed(entry.name(), op(entry)));
+        }
+        let results: Vec<_> = stream::iter(tasks)
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        let mut first_error = None;
+        for (module, elapsed, result) in results {
+            metrics.record(phase, module, elapsed, result.is_ok());
+            match result {
+                Ok(()) => tracing::info!(
+                    phase,
+                    module,
+                    duration_ms = duration_ms(elapsed),
+                    "Module phase completed"
+                ),
+                Err(e) => {
+                    tracing::error!(
+                        phase,
+                        module,
+                        duration_ms = duration_ms(elapsed),
+                        error = %e,
+                        "Module phase failed"
+                    );
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if let Some(e) = first_error {
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn timed<Fut>(module: &'static str, fut: Fut) -> (&'static str, Duration, Fut::Output)
+where
+    Fut: Future,
+{
+    let started = Instant::now();
+    let result = fut.await;
+    (module, started.elapsed(), result)
+}
+
+fn duration_ms(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Per-module phase durations, exported as the `modkit.module.phase.duration`
+/// histogram (seconds) with `phase`, `module` and `outcome` attributes.
+struct PhaseMetrics {
+    #[cfg(feature = "otel")]
+    duration: opentelemetry::metrics::Histogram<f64>,
+}
+
+impl PhaseMetrics {
+    fn new() -> Self {
+        Self {
+            #[cfg(feature = "otel")]
+            duration: opentelemetry::global::meter("modkit")
+                .f64_histogram("modkit.module.phase.duration")
+                .with_unit("s")
+                .with_description("Duration of a lifecycle phase for a single module")
+                .build(),
+        }
+    }
+
+    #[cfg_attr(not(feature = "otel"), allow(clippy::unused_self))]
+    fn record(&self, phase: &'static str, module: &'static str, elapsed: Duration, ok: bool) {
+        #[cfg(feature = "otel")]
+        self.duration.record(
+            elapsed.as_secs_f64(),
+            &[
+                opentelemetry::KeyValue::new("phase", phase),
+                opentelemetry::KeyValue::new("module", module),
+                opentelemetry::KeyValue::new("outcome", if ok { "ok" } else { "error" }),
+            ],
+        );
+        #[cfg(not(feature = "otel"))]
+        {
+            _ = (phase, module, elapsed, ok);
+        }
+    }
+}
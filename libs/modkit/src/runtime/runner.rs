@@ -14,12 +14,12 @@
 //! - `OoP` modules are spawned after the start phase so that `grpc-hub` is already running
 //!   and the real directory endpoint is known.
 
-use crate::backends::OopBackend;
+use crate::backends::{OopBackend, RestartPolicy, StaticInstance};
 use crate::client_hub::ClientHub;
 use crate::config::ConfigProvider;
 use crate::registry::ModuleRegistry;
 use crate::runtime::shutdown;
-use crate::runtime::{DbOptions, HostRuntime};
+use crate::runtime::{ConfigSource, DbOptions, HostRuntime};
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::{future::Future, pin::Pin, sync::Arc};
@@ -85,12 +85,25 @@ pub struct OopModuleSpawnConfig {
     pub working_directory: Option<String>,
     /// Rendered module config JSON (for `MODKIT_MODULE_CONFIG` env var)
     pub rendered_config_json: String,
+    /// Restart policy applied when the process exits
+    pub restart: RestartPolicy,
+}
+
+/// An `OoP` module served by pre-started instances (`runtime.type: static`).
+#[derive(Clone)]
+pub struct StaticModuleConfig {
+    /// Module name (e.g., "calculator")
+    pub module_name: String,
+    /// Already-running instances of the module
+    pub instances: Vec<StaticInstance>,
 }
 
 /// Options for spawning `OoP` modules.
 pub struct OopSpawnOptions {
     /// List of `OoP` modules to spawn after the start phase
     pub modules: Vec<OopModuleSpawnConfig>,
+    /// Modules whose instances run outside the host; registered, not spawned
+    pub static_modules: Vec<StaticModuleConfig>,
     /// Backend for spawning `OoP` modules (e.g., `LocalProcessBackend`)
     pub backend: Box<dyn OopBackend>,
 }
@@ -119,6 +132,11 @@ pub struct RunOptions {
     /// These modules are spawned after the start phase, once `grpc-hub` is running
     /// and the real directory endpoint is known.
     pub oop: Option<OopSpawnOptions>,
+    /// Source of fresh module configuration for live reload.
+    ///
+    /// When set, the host reloads module configuration on `SIGHUP` and when the
+    /// source's files change. `None` disables the automatic triggers.
+    pub config_source: Option<Arc<dyn ConfigSource>>,
 }
 
 /// Full cycle is orchestrated by `HostRuntime` (see `runtime/host_runtime.rs` docs).
@@ -190,6 +208,10 @@ pub async fn run(opts: RunOptions) -> anyhow::Result<()> {
         opts.instance_id,
         opts.oop,
     );
+    let host = match opts.config_source {
+        Some(source) => host.with_config_source(source),
+        None => host,
+    };
 
     // 6. Run full lifecycle
     host.run_module_phases().await
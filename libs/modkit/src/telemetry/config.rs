@@ -1,6 +1,7 @@
-//! OpenTelemetry tracing configuration types
+//! OpenTelemetry tracing, log export and metrics configuration types
 //!
-//! These types define the configuration structure for OpenTelemetry distributed tracing.
+//! These types define the configuration structure for OpenTelemetry distributed tracing,
+//! OTLP log export and metrics.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -16,6 +17,40 @@ pub struct TracingConfig {
     pub resource: Option<HashMap<String, String>>,
     pub http: Option<HttpOpts>,
     pub logs_correlation: Option<LogsCorrelation>,
+    /// Export logs via OTLP with the same service name and resource attributes
+    #[serde(default)]
+    pub logs: Option<LogsExport>,
+}
+
+impl TracingConfig {
+    /// Whether `logs.enabled` is set.
+    #[must_use]
+    pub fn log_export_enabled(&self) -> bool {
+        self.logs.as_ref().is_some_and(|l| l.enabled)
+    }
+}
+
+/// OTLP log export configuration
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct LogsExport {
+    pub enabled: bool,
+    /// Defaults to the tracing exporter
+    pub exporter: Option<Exporter>,
+}
+
+/// Metrics configuration for the OpenTelemetry meter provider
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct MetricsConfig {
+    pub enabled: bool,
+    pub service_name: Option<String>,
+    /// Push metrics to an OTLP collector (no push if `None`)
+    pub exporter: Option<Exporter>,
+    /// OTLP push interval (default: 60s)
+    pub export_interval_ms: Option<u64>,
+    /// Keep metrics for scraping; the API gateway serves them on `/metrics`
+    #[serde(default)]
+    pub prometheus: bool,
+    pub resource: Option<HashMap<String, String>>,
 }
 
 #[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq, Copy)]
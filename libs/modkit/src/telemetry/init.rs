@@ -22,7 +22,7 @@ use opentelemetry_sdk::{
 };
 
 #[cfg(feature = "otel")]
-use super::config::TracingConfig;
+use super::config::{Exporter, TracingConfig};
 #[cfg(feature = "otel")]
 use crate::telemetry::config::ExporterKind;
 #[cfg(feature = "otel")]
@@ -33,10 +33,19 @@ use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
 /// Build resource with service name and custom attributes
 #[cfg(feature = "otel")]
 fn build_resource(cfg: &TracingConfig) -> Resource {
-    let service_name = cfg.service_name.as_deref().unwrap_or("hyperspot");
+    resource(cfg.service_name.as_deref(), cfg.resource.as_ref())
+}
+
+/// Resource shared by the tracer and meter providers
+#[cfg(feature = "otel")]
+pub(crate) fn resource(
+    service_name: Option<&str>,
+    resource_map: Option<&std::collections::HashMap<String, String>>,
+) -> Resource {
+    let service_name = service_name.unwrap_or("hyperspot");
     let mut attrs = vec![KeyValue::new("service.name", service_name.to_owned())];
 
-    if let Some(resource_map) = &cfg.resource {
+    if let Some(resource_map) = resource_map {
         for (k, v) in resource_map {
             attrs.push(KeyValue::new(k.clone(), v.clone()));
         }
@@ -102,7 +111,7 @@ fn build_http_exporter(
     if let Some(t) = timeout {
         b = b.with_timeout(t);
     }
-    if let Some(hmap) = build_headers_from_cfg_and_env(cfg) {
+    if let Some(hmap) = build_headers_from_cfg_and_env(cfg.exporter.as_ref()) {
         b = b.with_headers(hmap);
     }
     #[allow(clippy::expect_used)]
@@ -122,7 +131,7 @@ fn build_grpc_exporter(
     if let Some(t) = timeout {
         b = b.with_timeout(t);
     }
-    if let Some(md) = build_metadata_from_cfg_and_env(cfg) {
+    if let Some(md) = build_metadata_from_cfg_and_env(cfg.exporter.as_ref()) {
         b = b.with_metadata(md);
     }
     b.build().context("build OTLP gRPC exporter")
@@ -185,14 +194,14 @@ pub fn init_tracing(
 }
 
 #[cfg(feature = "otel")]
-fn build_headers_from_cfg_and_env(
-    cfg: &TracingConfig,
+pub(crate) fn build_headers_from_cfg_and_env(
+    exporter: Option<&Exporter>,
 ) -> Option<std::collections::HashMap<String, String>> {
     use std::collections::HashMap;
     let mut out: HashMap<String, String> = HashMap::new();
 
     // From config file
-    if let Some(exp) = &cfg.exporter
+    if let Some(exp) = exporter
         && let Some(hdrs) = &exp.headers
     {
         for (k, v) in hdrs {
@@ -235,11 +244,11 @@ where
 }
 
 #[cfg(feature = "otel")]
-fn build_metadata_from_cfg_and_env(cfg: &TracingConfig) -> Option<MetadataMap> {
+pub(crate) fn build_metadata_from_cfg_and_env(exporter: Option<&Exporter>) -> Option<MetadataMap> {
     let mut md = MetadataMap::new();
 
     // From config file
-    if let Some(exp) = &cfg.exporter
+    if let Some(exp) = exporter
         && let Some(hdrs) = &exp.headers
     {
         let iter = hdrs.iter().map(|(k, v)| (k.as_str(), v.as_str()));
@@ -325,7 +334,7 @@ pub fn otel_connectivity_probe(cfg: &super::config::TracingConfig) -> anyhow::Re
             .with_http()
             .with_protocol(Protocol::HttpBinary)
             .with_endpoint(endpoint);
-        if let Some(h) = build_headers_from_cfg_and_env(cfg) {
+        if let Some(h) = build_headers_from_cfg_and_env(cfg.exporter.as_ref()) {
             b = b.with_headers(h);
         }
         b.build()
@@ -334,7 +343,7 @@ pub fn otel_connectivity_probe(cfg: &super::config::TracingConfig) -> anyhow::Re
         let mut b = opentelemetry_otlp::SpanExporter::builder()
             .with_tonic()
             .with_endpoint(endpoint);
-        if let Some(md) = build_metadata_from_cfg_and_env(cfg) {
+        if let Some(md) = build_metadata_from_cfg_and_env(cfg.exporter.as_ref()) {
             b = b.with_metadata(md);
         }
         b.build()
@@ -532,7 +541,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_headers_from_cfg_and_env(&cfg);
+        let result = build_headers_from_cfg_and_env(cfg.exporter.as_ref());
         // Should be None if no headers configured and no env var
         // (unless OTEL_EXPORTER_OTLP_HEADERS is set, which we can't control in tests)
         assert!(result.is_none() || result.is_some());
@@ -555,7 +564,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_headers_from_cfg_and_env(&cfg);
+        let result = build_headers_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let result_headers = result.unwrap();
         assert_eq!(
@@ -572,7 +581,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         // Should be None if no headers configured and no env var
         assert!(result.is_none() || result.is_some());
     }
@@ -594,7 +603,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let metadata = result.unwrap();
         assert!(!metadata.is_empty());
@@ -618,7 +627,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let metadata = result.unwrap();
         assert_eq!(metadata.len(), 2);
@@ -642,7 +651,7 @@ mod tests {
             ..Default::default()
         };
 
-        let result = build_metadata_from_cfg_and_env(&cfg);
+        let result = build_metadata_from_cfg_and_env(cfg.exporter.as_ref());
         assert!(result.is_some());
         let metadata = result.unwrap();
         // Should only have the valid header
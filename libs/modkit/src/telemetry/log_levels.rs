@@ -0,0 +1,344 @@
+//! Runtime log level overrides
+//!
+//! Host logging keeps its per-target filters reloadable and installs a [`LogLevels`]
+//! controller, returned by [`log_levels`]. It changes the level of a module (a target prefix
+//! such as `api_gateway` or `sqlx`) on every sink for a limited time, e.g. to debug a
+//! production issue without a restart. Overrides revert on their own when their TTL expires.
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::time::{Duration, SystemTime};
+
+use tokio::time::Instant;
+use tracing::level_filters::LevelFilter;
+
+/// Longest accepted override TTL.
+pub const MAX_OVERRIDE_TTL: Duration = Duration::from_hours(24);
+
+static LOG_LEVELS: OnceLock<LogLevels> = OnceLock::new();
+
+/// The controller of the installed host logging, if any.
+#[must_use]
+pub fn log_levels() -> Option<&'static LogLevels> {
+    LOG_LEVELS.get()
+}
+
+/// Install the process-wide controller; only the first call has an effect.
+pub fn install_log_levels(levels: LogLevels) {
+    _ = LOG_LEVELS.set(levels);
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LogLevelError {
+    #[error("invalid log target '{0}'")]
+    InvalidTarget(String),
+    #[error("override TTL must be between 1s and {}s", MAX_OVERRIDE_TTL.as_secs())]
+    InvalidTtl,
+    #[error("failed to apply log levels: {0}")]
+    Apply(String),
+}
+
+/// An active level override.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelOverride {
+    pub target: String,
+    pub level: LevelFilter,
+    pub expires_at: SystemTime,
+}
+
+/// Reloads the logging filters with the given per-target overrides.
+pub type ApplyOverrides =
+    Box<dyn Fn(&BTreeMap<String, LevelFilter>) -> Result<(), String> + Send + Sync>;
+
+struct Entry {
+    level: LevelFilter,
+    deadline: Instant,
+    expires_at: SystemTime,
+}
+
+struct Inner {
+    default_level: LevelFilter,
+    configured: BTreeMap<String, LevelFilter>,
+    apply: ApplyOverrides,
+    overrides: Mutex<BTreeMap<String, Entry>>,
+}
+
+/// Reads and changes per-target log levels of the running process.
+#[derive(Clone)]
+pub struct LogLevels {
+    inner: Arc<Inner>,
+}
+
+impl LogLevels {
+    /// Controller over filters configured with `default_level` and per-target `configured`
+    /// levels; `apply` reloads them whenever the overrides change.
+    #[must_use]
+    pub fn new(
+        default_level: LevelFilter,
+        configured: BTreeMap<String, LevelFilter>,
+        apply: ApplyOverrides,
+    ) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                default_level,
+                configured,
+                apply,
+                overrides: Mutex::new(BTreeMap::new()),
+            }),
+        }
+    }
+
+    /// Level applied to targets without a configured level.
+    #[must_use]
+    pub fn default_level(&self) -> LevelFilter {
+        self.inner.default_level
+    }
+
+    /// Levels configured per target.
+    #[must_use]
+    pub fn configured(&self) -> &BTreeMap<String, LevelFilter> {
+        &self.inner.configured
+    }
+
+    /// Active overrides, by target.
+    #[must_use]
+    pub fn overrides(&self) -> Vec<LevelOverride> {
+        self.expire();
+        self.lock()
+            .iter()
+            .map(|(target, entry)| LevelOverride {
+                target: target.clone(),
+                level: entry.level,
+                expires_at: entry.expires_at,
+            })
+            .collect()
+    }
+
+    /// Log `target` (and its submodules) at `level` for `ttl`.
+    ///
+    /// Module names are accepted as targets: `api-gateway` becomes `api_gateway`.
+    /// Setting a target again replaces its level and TTL.
+    ///
+    /// # Errors
+    /// Returns an error if the target or TTL is invalid or the filters cannot be reloaded.
+    pub fn set(
+        &self,
+        target: &str,
+        level: LevelFilter,
+        ttl: Duration,
+    ) -> Result<LevelOverride, LogLevelError> {
+        let target = normalize_target(target)?;
+        if ttl < Duration::from_secs(1) || ttl > MAX_OVERRIDE_TTL {
+            return Err(LogLevelError::InvalidTtl);
+        }
+
+        let entry = Entry {
+            level,
+            deadline: Instant::now() + ttl,
+            expires_at: SystemTime::now() + ttl,
+        };
+        let applied = LevelOverride {
+            target: target.clone(),
+            level,
+            expires_at: entry.expires_at,
+        };
+        {
+            let mut overrides = self.lock();
+            let previous = overrides.insert(target.clone(), entry);
+            if let Err(e) = self.apply(&overrides) {
+                match previous {
+                    Some(previous) => overrides.insert(target, previous),
+                    None => overrides.remove(&target),
+                };
+                return Err(e);
+            }
+        }
+        tracing::info!(
+            log_target = %applied.target,
+            level = %level,
+            ttl_secs = ttl.as_secs(),
+            "Log level override set"
+        );
+
+        // Revert on time even if nobody reads the overrides
+        if let Ok(rt) = tokio::runtime::Handle::try_current() {
+            let levels = self.clone();
+            rt.spawn(async move {
+                tokio::time::sleep(ttl).await;
+                levels.expire();
+            });
+        }
+        Ok(applied)
+    }
+
+    /// Revert `target` to its configured level; returns `false` if it had no override.
+    ///
+    /// # Errors
+    /// Returns an error if the target is invalid or the filters cannot be reloaded.
+    pub fn clear(&self, target: &str) -> Result<bool, LogLevelError> {
+        let target = normalize_target(target)?;
+        {
+            let mut overrides = self.lock();
+            if overrides.remove(&target).is_none() {
+                return Ok(false);
+            }
+            self.apply(&overrides)?;
+        }
+        tracing::info!(log_target = %target, "Log level override cleared");
+        Ok(true)
+    }
+
+    /// Drop expired overrides and reload the filters if any was removed.
+    pub fn expire(&self) {
+        let now = Instant::now();
+        let mut overrides = self.lock();
+        let before = overrides.len();
+        overrides.retain(|_, entry| entry.deadline > now);
+        if overrides.len() == before {
+            return;
+        }
+        let result = self.apply(&overrides);
+        drop(overrides);
+        match result {
+            Ok(()) => tracing::info!("Expired log level overrides reverted"),
+            Err(e) => tracing::warn!(error = %e, "Failed to revert expired log level overrides"),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Entry>> {
+        self.inner
+            .overrides
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn apply(&self, overrides: &BTreeMap<String, Entry>) -> Result<(), LogLevelError> {
+        let levels = overrides
+            .iter()
+            .map(|(target, entry)| (target.clone(), entry.level))
+            .collect();
+        (self.inner.apply)(&levels).map_err(LogLevelError::Apply)
+    }
+}
+
+impl std::fmt::Debug for LogLevels {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LogLevels")
+            .field("default_level", &self.inner.default_level)
+            .field("configured", &self.inner.configured)
+            .finish_non_exhaustive()
+    }
+}
+
+fn normalize_target(target: &str) -> Result<String, LogLevelError> {
+    let valid = !target.is_empty()
+        && target
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':'));
+    if valid {
+        Ok(target.replace('-', "_"))
+    } else {
+        Err(LogLevelError::InvalidTarget(target.to_owned()))
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    type Applied = Arc<Mutex<Vec<BTreeMap<String, LevelFilter>>>>;
+
+    fn levels(fail: bool) -> (LogLevels, Applied) {
+        let applied = Applied::default();
+        let record = applied.clone();
+        let levels = LogLevels::new(
+            LevelFilter::INFO,
+            BTreeMap::from([("sqlx".to_owned(), LevelFilter::WARN)]),
+            Box::new(move |overrides| {
+                if fail {
+                    return Err("reload failed".to_owned());
+                }
+                record.lock().unwrap().push(overrides.clone());
+                Ok(())
+            }),
+        );
+        (levels, applied)
+    }
+
+    #[test]
+    fn overrides_applied_and_cleared() {
+        let (levels, applied) = levels(false);
+
+        let set = levels
+            .set("api-gateway", LevelFilter::DEBUG, Duration::from_mins(5))
+            .unwrap();
+        assert_eq!(set.target, "api_gateway");
+        assert_eq!(levels.overrides(), vec![set]);
+
+        assert!(levels.clear("api-gateway").unwrap());
+        assert!(!levels.clear("api-gateway").unwrap());
+        assert!(levels.overrides().is_empty());
+
+        let applied = applied.lock().unwrap();
+        assert_eq!(
+            *applied,
+            vec![
+                BTreeMap::from([("api_gateway".to_owned(), LevelFilter::DEBUG)]),
+                BTreeMap::new(),
+            ]
+        );
+    }
+
+    #[test]
+    fn invalid_input_rejected() {
+        let (levels, applied) = levels(false);
+        assert!(matches!(
+            levels.set("a=b", LevelFilter::DEBUG, Duration::from_mins(1)),
+            Err(LogLevelError::InvalidTarget(_))
+        ));
+        assert!(matches!(
+            levels.set("sqlx", LevelFilter::DEBUG, Duration::ZERO),
+            Err(LogLevelError::InvalidTtl)
+        ));
+        assert!(matches!(
+            levels.set("sqlx", LevelFilter::DEBUG, MAX_OVERRIDE_TTL * 2),
+            Err(LogLevelError::InvalidTtl)
+        ));
+        assert!(applied.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn failed_reload_keeps_previous_overrides() {
+        let (levels, _) = levels(true);
+        assert!(matches!(
+            levels.set("sqlx", LevelFilter::DEBUG, Duration::from_mins(1)),
+            Err(LogLevelError::Apply(_))
+        ));
+        assert!(levels.overrides().is_empty());
+    }
+
+    #[test]
+    fn expired_overrides_revert() {
+        let (levels, applied) = levels(false);
+        levels
+            .set("sqlx", LevelFilter::DEBUG, Duration::from_secs(1))
+            .unwrap();
+        levels.lock().get_mut("sqlx").unwrap().deadline = Instant::now();
+
+        assert!(levels.overrides().is_empty());
+        assert_eq!(applied.lock().unwrap().last(), Some(&BTreeMap::new()));
+    }
+
+    #[tokio::test]
+    async fn overrides_revert_without_reads() {
+        let (levels, applied) = levels(false);
+        levels
+            .set("sqlx", LevelFilter::TRACE, Duration::from_secs(1))
+            .unwrap();
+
+        tokio::time::sleep(Duration::from_millis(1200)).await;
+
+        assert_eq!(applied.lock().unwrap().last(), Some(&BTreeMap::new()));
+    }
+}
@@ -0,0 +1,253 @@
+//! OpenTelemetry log export
+//!
+//! [`init_log_export`] builds an OTLP logs pipeline sharing the tracing service name and
+//! resource attributes, and returns [`OtelLogsLayer`]: a `tracing_subscriber` layer that
+//! forwards events as OpenTelemetry log records. Events emitted inside a traced span carry
+//! its trace and span ids.
+//!
+//! Events of the exporter stack itself (`opentelemetry*`, `tonic`, `hyper`, `h2`, ...) are
+//! never forwarded, so exporting a batch cannot produce more records.
+
+use std::time::SystemTime;
+
+use anyhow::Context;
+use opentelemetry::Key;
+use opentelemetry::logs::{AnyValue, LogRecord as _, Logger as _, LoggerProvider as _, Severity};
+use opentelemetry_otlp::{Protocol, WithExportConfig, WithHttpConfig, WithTonicConfig};
+use opentelemetry_sdk::logs::{SdkLogRecord, SdkLogger, SdkLoggerProvider};
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::Layer;
+use tracing_subscriber::layer::Context as LayerContext;
+
+use super::config::{ExporterKind, TracingConfig};
+use super::init::{build_headers_from_cfg_and_env, build_metadata_from_cfg_and_env, resource};
+
+/// Targets of the export pipeline; forwarding their events would feed it back.
+const EXPORTER_TARGETS: &[&str] = &[
+    "opentelemetry",
+    "opentelemetry_sdk",
+    "opentelemetry_otlp",
+    "opentelemetry_http",
+    "tonic",
+    "hyper",
+    "hyper_util",
+    "h2",
+    "tower",
+    "reqwest",
+];
+
+/// Initialize the OTLP logs pipeline configured in `tracing.logs`.
+///
+/// Keep the returned provider and call `shutdown()` on it during graceful shutdown to flush
+/// pending records.
+///
+/// # Errors
+/// Returns an error if log export is disabled or the OTLP exporter fails to build.
+pub fn init_log_export(cfg: &TracingConfig) -> anyhow::Result<(OtelLogsLayer, SdkLoggerProvider)> {
+    let logs = cfg.logs.as_ref().filter(|l| l.enabled);
+    let Some(logs) = logs else {
+        return Err(anyhow::anyhow!("log export is disabled"));
+    };
+
+    let exporter = logs.exporter.as_ref().or(cfg.exporter.as_ref());
+    let provider = SdkLoggerProvider::builder()
+        .with_resource(resource(cfg.service_name.as_deref(), cfg.resource.as_ref()))
+        .with_batch_exporter(build_otlp_exporter(exporter)?)
+        .build();
+
+    tracing::info!("OpenTelemetry log export initialized");
+    Ok((OtelLogsLayer::new(&provider), provider))
+}
+
+fn build_otlp_exporter(
+    exporter: Option<&super::config::Exporter>,
+) -> anyhow::Result<opentelemetry_otlp::LogExporter> {
+    let kind = exporter.map_or(ExporterKind::OtlpGrpc, |e| e.kind);
+    let endpoint = exporter
+        .and_then(|e| e.endpoint.clone())
+        .unwrap_or_else(|| "http://127.0.0.1:4317".into());
+    let timeout = exporter
+        .and_then(|e| e.timeout_ms)
+        .map(std::time::Duration::from_millis);
+
+    if matches!(kind, ExporterKind::OtlpHttp) {
+        let mut b = opentelemetry_otlp::LogExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpBinary)
+            .with_endpoint(endpoint);
+        if let Some(t) = timeout {
+            b = b.with_timeout(t);
+        }
+        if let Some(hmap) = build_headers_from_cfg_and_env(exporter) {
+            b = b.with_headers(hmap);
+        }
+        b.build().context("build OTLP HTTP log exporter")
+    } else {
+        let mut b = opentelemetry_otlp::LogExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint);
+        if let Some(t) = timeout {
+            b = b.with_timeout(t);
+        }
+        if let Some(md) = build_metadata_from_cfg_and_env(exporter) {
+            b = b.with_metadata(md);
+        }
+        b.build().context("build OTLP gRPC log exporter")
+    }
+}
+
+/// Layer forwarding `tracing` events to an OpenTelemetry logger.
+///
+/// The event target becomes the record target, the `message` field its body and the other
+/// fields its attributes.
+#[derive(Clone)]
+pub struct OtelLogsLayer {
+    logger: SdkLogger,
+}
+
+impl OtelLogsLayer {
+    /// Forward events to a logger of `provider`.
+    #[must_use]
+    pub fn new(provider: &SdkLoggerProvider) -> Self {
+        Self {
+            logger: provider.logger("hyperspot"),
+        }
+    }
+}
+
+impl<S: Subscriber> Layer<S> for OtelLogsLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: LayerContext<'_, S>) {
+        let meta = event.metadata();
+        let target = meta.target();
+        if EXPORTER_TARGETS
+            .iter()
+            .any(|t| target == *t || target.strip_prefix(t).is_some_and(|r| r.starts_with("::")))
+        {
+            return;
+        }
+
+        let mut record = self.logger.create_log_record();
+        record.set_target(target.to_owned());
+        record.set_timestamp(SystemTime::now());
+        record.set_severity_number(severity(*meta.level()));
+        record.set_severity_text(meta.level().as_str());
+        event.record(&mut RecordVisitor(&mut record));
+        self.logger.emit(record);
+    }
+}
+
+fn severity(level: Level) -> Severity {
+    match level {
+        Level::TRACE => Severity::Trace,
+        Level::DEBUG => Severity::Debug,
+        Level::INFO => Severity::Info,
+        Level::WARN => Severity::Warn,
+        Level::ERROR => Severity::Error,
+    }
+}
+
+/// Event fields → record body (`message`) and attributes.
+struct RecordVisitor<'a>(&'a mut SdkLogRecord);
+
+impl RecordVisitor<'_> {
+    fn field(&mut self, field: &Field, value: AnyValue) {
+        if field.name() == "message" {
+            self.0.set_body(value);
+        } else {
+            self.0
+                .add_attribute(Key::from_static_str(field.name()), value);
+        }
+    }
+}
+
+impl Visit for RecordVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.field(field, AnyValue::from(format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.field(field, AnyValue::from(value.to_owned()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.field(field, AnyValue::Int(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match i64::try_from(value) {
+            Ok(v) => self.field(field, AnyValue::Int(v)),
+            Err(_) => self.field(field, AnyValue::from(value.to_string())),
+        }
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.field(field, AnyValue::Double(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.field(field, AnyValue::Boolean(value));
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use opentelemetry::InstrumentationScope;
+    use opentelemetry_sdk::error::OTelSdkResult;
+    use opentelemetry_sdk::logs::LogProcessor;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    /// `(target, severity, body, attribute keys)` of a record.
+    type Collected = (String, Severity, String, Vec<String>);
+
+    /// Keeps every emitted record.
+    #[derive(Debug, Clone, Default)]
+    struct Collect(Arc<Mutex<Vec<Collected>>>);
+
+    impl LogProcessor for Collect {
+        fn emit(&self, data: &mut SdkLogRecord, _scope: &InstrumentationScope) {
+            let body = match data.body() {
+                Some(AnyValue::String(s)) => s.to_string(),
+                other => format!("{other:?}"),
+            };
+            self.0.lock().unwrap().push((
+                data.target().map(ToString::to_string).unwrap_or_default(),
+                data.severity_number().unwrap(),
+                body,
+                data.attributes_iter().map(|(k, _)| k.to_string()).collect(),
+            ));
+        }
+
+        fn force_flush(&self) -> OTelSdkResult {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn events_forwarded_as_records() {
+        let collect = Collect::default();
+        let provider = SdkLoggerProvider::builder()
+            .with_log_processor(collect.clone())
+            .build();
+        let subscriber = tracing_subscriber::registry().with(OtelLogsLayer::new(&provider));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "orders::api", order_id = 7, "order rejected");
+            tracing::info!(target: "tonic::transport", "connection established");
+        });
+
+        let records = collect.0.lock().unwrap().clone();
+        assert_eq!(
+            records,
+            vec![(
+                "orders::api".to_owned(),
+                Severity::Warn,
+                "order rejected".to_owned(),
+                vec!["order_id".to_owned()],
+            )]
+        );
+    }
+}
@@ -0,0 +1,432 @@
+//! OpenTelemetry metrics pipeline
+//!
+//! [`init_metrics`] installs the global meter provider. Measurements are pushed to an OTLP
+//! collector and/or kept for scraping: the API gateway serves [`PrometheusExporter::render`]
+//! on `/metrics`.
+//!
+//! Modules create their own instruments from [`ModuleCtx::meter`](crate::context::ModuleCtx::meter)
+//! (scoped to the module name) or [`meter`]:
+//!
+//! ```ignore
+//! let orders = ctx.meter().u64_counter("orders.created").build();
+//! orders.add(1, &[KeyValue::new("channel", "web")]);
+//! ```
+//!
+//! Instruments created before [`init_metrics`] runs are no-ops, so create them in `init()`.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Write as _};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use anyhow::Context;
+use arc_swap::ArcSwapOption;
+use opentelemetry::global;
+use opentelemetry_otlp::{Protocol, WithExportConfig, WithHttpConfig, WithTonicConfig};
+use opentelemetry_sdk::error::OTelSdkResult;
+use opentelemetry_sdk::metrics::data::{
+    AggregatedMetrics, Gauge, Histogram, Metric, MetricData, ResourceMetrics, ScopeMetrics, Sum,
+};
+use opentelemetry_sdk::metrics::reader::MetricReader;
+use opentelemetry_sdk::metrics::{
+    InstrumentKind, ManualReader, PeriodicReader, Pipeline, SdkMeterProvider, Temporality,
+};
+
+pub use opentelemetry::KeyValue;
+pub use opentelemetry::metrics::{
+    Counter, Gauge as GaugeInstrument, Histogram as HistogramInstrument, Meter, UpDownCounter,
+};
+
+use super::config::{ExporterKind, MetricsConfig};
+use super::init::{build_headers_from_cfg_and_env, build_metadata_from_cfg_and_env, resource};
+
+/// Default OTLP push interval.
+const DEFAULT_EXPORT_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Histogram boundaries (seconds) for request durations, following the HTTP semantic conventions.
+pub const DURATION_BOUNDARIES: [f64; 14] = [
+    0.005, 0.01, 0.025, 0.05, 0.075, 0.1, 0.25, 0.5, 0.75, 1.0, 2.5, 5.0, 7.5, 10.0,
+];
+
+static PROMETHEUS: ArcSwapOption<PrometheusExporter> = ArcSwapOption::const_empty();
+
+/// Meter of a library or component, e.g. `meter("modkit-http")`.
+#[must_use]
+pub fn meter(name: &'static str) -> Meter {
+    global::meter(name)
+}
+
+/// The Prometheus exporter installed by [`init_metrics`], if enabled.
+#[must_use]
+pub fn prometheus_exporter() -> Option<PrometheusExporter> {
+    PROMETHEUS.load_full().map(|exporter| (*exporter).clone())
+}
+
+/// Initialize the OpenTelemetry meter provider from configuration and install it globally.
+///
+/// Keep the returned provider and call `shutdown()` on it during graceful shutdown to flush
+/// pending OTLP exports.
+///
+/// # Errors
+/// Returns an error if metrics are disabled or the OTLP exporter fails to build.
+pub fn init_metrics(cfg: &MetricsConfig) -> anyhow::Result<SdkMeterProvider> {
+    if !cfg.enabled {
+        return Err(anyhow::anyhow!("metrics are disabled"));
+    }
+
+    let mut builder = SdkMeterProvider::builder()
+        .with_resource(resource(cfg.service_name.as_deref(), cfg.resource.as_ref()));
+
+    if cfg.exporter.is_some() {
+        let interval = cfg
+            .export_interval_ms
+            .map_or(DEFAULT_EXPORT_INTERVAL, Duration::from_millis);
+        let reader = PeriodicReader::builder(build_otlp_exporter(cfg)?)
+            .with_interval(interval)
+            .build();
+        builder = builder.with_reader(reader);
+    }
+
+    let prometheus = cfg.prometheus.then(PrometheusExporter::new);
+    if let Some(exporter) = &prometheus {
+        builder = builder.with_reader(exporter.clone());
+    }
+
+    let provider = builder.build();
+    global::set_meter_provider(provider.clone());
+    PROMETHEUS.store(prometheus.map(Arc::new));
+
+    tracing::info!(
+        otlp = cfg.exporter.is_some(),
+        prometheus = cfg.prometheus,
+        "OpenTelemetry metrics initialized"
+    );
+    Ok(provider)
+}
+
+fn build_otlp_exporter(cfg: &MetricsConfig) -> anyhow::Result<opentelemetry_otlp::MetricExporter> {
+    let exporter = cfg.exporter.as_ref();
+    let kind = exporter.map_or(ExporterKind::OtlpGrpc, |e| e.kind);
+    let endpoint = exporter
+        .and_then(|e| e.endpoint.clone())
+        .unwrap_or_else(|| "http://127.0.0.1:4317".into());
+    let timeout = exporter
+        .and_then(|e| e.timeout_ms)
+        .map(Duration::from_millis);
+
+    if matches!(kind, ExporterKind::OtlpHttp) {
+        let mut b = opentelemetry_otlp::MetricExporter::builder()
+            .with_http()
+            .with_protocol(Protocol::HttpBinary)
+            .with_endpoint(endpoint);
+        if let Some(t) = timeout {
+            b = b.with_timeout(t);
+        }
+        if let Some(hmap) = build_headers_from_cfg_and_env(exporter) {
+            b = b.with_headers(hmap);
+        }
+        b.build().context("build OTLP HTTP metric exporter")
+    } else {
+        let mut b = opentelemetry_otlp::MetricExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint);
+        if let Some(t) = timeout {
+            b = b.with_timeout(t);
+        }
+        if let Some(md) = build_metadata_from_cfg_and_env(exporter) {
+            b = b.with_metadata(md);
+        }
+        b.build().context("build OTLP gRPC metric exporter")
+    }
+}
+
+// ===== Prometheus exposition ==================================================
+
+/// Pull-based reader rendering the current measurements in the Prometheus text format.
+#[derive(Clone, Debug)]
+pub struct PrometheusExporter {
+    reader: Arc<ManualReader>,
+}
+
+impl PrometheusExporter {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            reader: Arc::new(ManualReader::builder().build()),
+        }
+    }
+
+    /// Collect all instruments and render them in the Prometheus text exposition format (0.0.4).
+    #[must_use]
+    pub fn render(&self) -> String {
+        let mut metrics = ResourceMetrics::default();
+        if let Err(e) = self.reader.collect(&mut metrics) {
+            tracing::warn!(error = %e, "Failed to collect metrics");
+            return String::new();
+        }
+        encode(&metrics)
+    }
+}
+
+impl Default for PrometheusExporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MetricReader for PrometheusExporter {
+    fn register_pipeline(&self, pipeline: Weak<Pipeline>) {
+        self.reader.register_pipeline(pipeline);
+    }
+
+    fn collect(&self, rm: &mut ResourceMetrics) -> OTelSdkResult {
+        self.reader.collect(rm)
+    }
+
+    fn force_flush(&self) -> OTelSdkResult {
+        self.reader.force_flush()
+    }
+
+    fn shutdown_with_timeout(&self, timeout: Duration) -> OTelSdkResult {
+        self.reader.shutdown_with_timeout(timeout)
+    }
+
+    fn temporality(&self, kind: InstrumentKind) -> Temporality {
+        self.reader.temporality(kind)
+    }
+}
+
+/// One metric family: `# HELP`/`# TYPE` header plus samples (merged across scopes).
+struct Family {
+    help: String,
+    kind: &'static str,
+    samples: String,
+}
+
+fn encode(metrics: &ResourceMetrics) -> String {
+    let mut families: BTreeMap<String, Family> = BTreeMap::new();
+    for metric in metrics.scope_metrics().flat_map(ScopeMetrics::metrics) {
+        let (name, kind) = family_name_and_kind(metric);
+        let family = families.entry(name.clone()).or_insert_with(|| Family {
+            help: metric.description().to_owned(),
+            kind,
+            samples: String::new(),
+        });
+        match metric.data() {
+            AggregatedMetrics::F64(data) => write_data(&mut family.samples, &name, data),
+            AggregatedMetrics::U64(data) => write_data(&mut family.samples, &name, data),
+            AggregatedMetrics::I64(data) => write_data(&mut family.samples, &name, data),
+        }
+    }
+
+    let mut out = String::new();
+    let target: Vec<_> = metrics
+        .resource()
+        .iter()
+        .map(|(k, v)| (k.as_str().to_owned(), v.as_str().into_owned()))
+        .collect();
+    if !target.is_empty() {
+        out.push_str("# HELP target_info Target metadata\n# TYPE target_info gauge\n");
+        write_sample(&mut out, "target_info", &target, 1);
+    }
+    for (name, family) in families {
+        if !family.help.is_empty() {
+            _ = writeln!(out, "# HELP {name} {}", escape(&family.help, false));
+        }
+        _ = writeln!(out, "# TYPE {name} {}", family.kind);
+        out.push_str(&family.samples);
+    }
+    out
+}
+
+/// Prometheus family name (sanitized, with unit and `_total` suffixes) and type.
+fn family_name_and_kind(metric: &Metric) -> (String, &'static str) {
+    let mut name = sanitize(metric.name());
+    let unit = match metric.unit() {
+        "s" => "seconds",
+        "ms" => "milliseconds",
+        "By" => "bytes",
+        _ => "",
+    };
+    if !unit.is_empty() && !name.ends_with(unit) {
+        name = format!("{name}_{unit}");
+    }
+
+    let (monotonic, kind) = match metric.data() {
+        AggregatedMetrics::F64(data) => data_kind(data),
+        AggregatedMetrics::U64(data) => data_kind(data),
+        AggregatedMetrics::I64(data) => data_kind(data),
+    };
+    if monotonic && !name.ends_with("_total") {
+        name.push_str("_total");
+    }
+    (name, kind)
+}
+
+fn data_kind<T>(data: &MetricData<T>) -> (bool, &'static str) {
+    match data {
+        MetricData::Sum(sum) if sum.is_monotonic() => (true, "counter"),
+        MetricData::Sum(_) | MetricData::Gauge(_) => (false, "gauge"),
+        MetricData::Histogram(_) => (false, "histogram"),
+        MetricData::ExponentialHistogram(_) => (false, "untyped"),
+    }
+}
+
+fn write_data<T: Copy + Display>(out: &mut String, name: &str, data: &MetricData<T>) {
+    match data {
+        MetricData::Gauge(gauge) => write_gauge(out, name, gauge),
+        MetricData::Sum(sum) => write_sum(out, name, sum),
+        MetricData::Histogram(histogram) => write_histogram(out, name, histogram),
+        // Not representable in the text format
+        MetricData::ExponentialHistogram(_) => {}
+    }
+}
+
+fn write_gauge<T: Copy + Display>(out: &mut String, name: &str, gauge: &Gauge<T>) {
+    for point in gauge.data_points() {
+        write_sample(out, name, &labels(point.attributes()), point.value());
+    }
+}
+
+fn write_sum<T: Copy + Display>(out: &mut String, name: &str, sum: &Sum<T>) {
+    for point in sum.data_points() {
+        write_sample(out, name, &labels(point.attributes()), point.value());
+    }
+}
+
+fn write_histogram<T: Copy + Display>(out: &mut String, name: &str, histogram: &Histogram<T>) {
+    for point in histogram.data_points() {
+        let labels = labels(point.attributes());
+        let mut cumulative = 0;
+        let bounds = point
+            .bounds()
+            .map(|bound| bound.to_string())
+            .chain(std::iter::once("+Inf".to_owned()));
+        for (bound, count) in bounds.zip(point.bucket_counts()) {
+            cumulative += count;
+            let mut bucket_labels = labels.clone();
+            bucket_labels.push(("le".to_owned(), bound));
+            write_sample(out, &format!("{name}_bucket"), &bucket_labels, cumulative);
+        }
+        write_sample(out, &format!("{name}_sum"), &labels, point.sum());
+        write_sample(out, &format!("{name}_count"), &labels, point.count());
+    }
+}
+
+fn labels<'a>(attributes: impl Iterator<Item = &'a KeyValue>) -> Vec<(String, String)> {
+    attributes
+        .map(|kv| (sanitize(kv.key.as_str()), kv.value.as_str().into_owned()))
+        .collect()
+}
+
+fn write_sample(out: &mut String, name: &str, labels: &[(String, String)], value: impl Display) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        out.push('{');
+        for (i, (key, value)) in labels.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            _ = write!(out, "{}=\"{}\"", sanitize(key), escape(value, true));
+        }
+        out.push('}');
+    }
+    _ = writeln!(out, " {value}");
+}
+
+/// Replace characters not allowed in metric and label names with `_`.
+fn sanitize(name: &str) -> String {
+    let mut out: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn escape(value: &str, quotes: bool) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '"' if quotes => out.push_str("\\\""),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider as _;
+
+    fn provider_with(exporter: &PrometheusExporter) -> SdkMeterProvider {
+        SdkMeterProvider::builder()
+            .with_reader(exporter.clone())
+            .build()
+    }
+
+    #[test]
+    fn renders_counters_gauges_and_histograms() {
+        let exporter = PrometheusExporter::new();
+        let provider = provider_with(&exporter);
+        let meter = provider.meter("test");
+
+        let counter = meter
+            .u64_counter("orders.created")
+            .with_description("Orders")
+            .build();
+        counter.add(2, &[KeyValue::new("channel", "web")]);
+        let gauge = meter.i64_up_down_counter("jobs.active").build();
+        gauge.add(3, &[]);
+        let histogram = meter
+            .f64_histogram("request.duration")
+            .with_unit("s")
+            .with_boundaries(vec![0.1, 1.0])
+            .build();
+        histogram.record(0.5, &[]);
+
+        let text = exporter.render();
+
+        assert!(text.contains("# HELP orders_created_total Orders\n"));
+        assert!(text.contains("# TYPE orders_created_total counter\n"));
+        assert!(text.contains("orders_created_total{channel=\"web\"} 2\n"));
+        assert!(text.contains("# TYPE jobs_active gauge\njobs_active 3\n"));
+        assert!(text.contains("# TYPE request_duration_seconds histogram\n"));
+        assert!(text.contains("request_duration_seconds_bucket{le=\"0.1\"} 0\n"));
+        assert!(text.contains("request_duration_seconds_bucket{le=\"1\"} 1\n"));
+        assert!(text.contains("request_duration_seconds_bucket{le=\"+Inf\"} 1\n"));
+        assert!(text.contains("request_duration_seconds_count 1\n"));
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        let exporter = PrometheusExporter::new();
+        let provider = provider_with(&exporter);
+        let counter = provider.meter("test").u64_counter("hits").build();
+        counter.add(1, &[KeyValue::new("path", "a\"b\\c")]);
+
+        assert!(
+            exporter
+                .render()
+                .contains("hits_total{path=\"a\\\"b\\\\c\"} 1\n")
+        );
+    }
+
+    #[test]
+    fn init_metrics_disabled() {
+        let cfg = MetricsConfig::default();
+        assert!(init_metrics(&cfg).is_err());
+    }
+}
@@ -1,12 +1,26 @@
 //! Telemetry utilities for OpenTelemetry integration
 //!
 //! This module provides utilities for setting up and configuring
-//! OpenTelemetry tracing layers for distributed tracing.
+//! OpenTelemetry tracing layers for distributed tracing, OTLP log export, the metrics
+//! pipeline, and runtime log level overrides.
 
 pub mod config;
 pub mod init;
+pub mod log_levels;
+#[cfg(feature = "otel")]
+pub mod logs;
+#[cfg(feature = "otel")]
+pub mod metrics;
 pub mod throttled_log;
 
-pub use config::{Exporter, HttpOpts, LogsCorrelation, Propagation, Sampler, TracingConfig};
+pub use config::{
+    Exporter, HttpOpts, LogsCorrelation, LogsExport, MetricsConfig, Propagation, Sampler,
+    TracingConfig,
+};
 pub use init::{init_tracing, shutdown_tracing};
+pub use log_levels::{LevelOverride, LogLevelError, LogLevels, log_levels};
+#[cfg(feature = "otel")]
+pub use logs::init_log_export;
+#[cfg(feature = "otel")]
+pub use metrics::init_metrics;
 pub use throttled_log::ThrottledLog;
@@ -0,0 +1,68 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Tests for `#[modkit::instrumented_client]` wrappers and the `ClientHub` instrumented layer
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use modkit::{ClientHub, ClientLayer};
+
+#[modkit::instrumented_client]
+#[async_trait]
+pub trait GreeterApi: Send + Sync {
+    async fn greet(&self, name: &str) -> anyhow::Result<String>;
+    async fn count(&self) -> usize;
+    fn version(&self) -> &'static str;
+}
+
+struct Greeter;
+
+#[async_trait]
+impl GreeterApi for Greeter {
+    async fn greet(&self, name: &str) -> anyhow::Result<String> {
+        if name.is_empty() {
+            anyhow::bail!("empty name");
+        }
+        Ok(format!("hello, {name}"))
+    }
+
+    async fn count(&self) -> usize {
+        3
+    }
+
+    fn version(&self) -> &'static str {
+        "v1"
+    }
+}
+
+#[tokio::test]
+async fn wrapper_forwards_calls() {
+    let client = InstrumentedGreeterApi::new(Arc::new(Greeter));
+
+    assert_eq!(client.greet("bob").await.unwrap(), "hello, bob");
+    assert!(client.greet("").await.is_err());
+    assert_eq!(client.count().await, 3);
+    assert_eq!(client.version(), "v1");
+}
+
+#[tokio::test]
+async fn instrumented_layer_wraps_registered_clients() {
+    let hub = ClientHub::new();
+    let raw: Arc<dyn GreeterApi> = Arc::new(Greeter);
+    hub.register::<dyn GreeterApi>(raw.clone());
+
+    hub.add_layer(ClientLayer::instrumented());
+
+    let resolved = hub.get::<dyn GreeterApi>().unwrap();
+    assert!(!Arc::ptr_eq(&resolved, &raw));
+    assert_eq!(resolved.greet("ann").await.unwrap(), "hello, ann");
+
+    // Types without a generated wrapper pass through untouched
+    let text: Arc<str> = Arc::from("plain");
+    hub.register::<str>(text.clone());
+    assert!(Arc::ptr_eq(&hub.get::<str>().unwrap(), &text));
+
+    // Removal hands back the client as registered
+    let removed = hub.remove::<dyn GreeterApi>().unwrap();
+    assert!(Arc::ptr_eq(&removed, &raw));
+}
@@ -77,6 +77,9 @@ fn test_odata_params_with_select() {
         filter: None,
         orderby: None,
         select: Some("id, name".to_owned()),
+        count: None,
+        expand: None,
+        apply: None,
         limit: None,
         cursor: None,
     };
@@ -475,6 +478,8 @@ fn test_page_to_projected_json() {
             next_cursor: Some("abc123".to_owned()),
             prev_cursor: None,
             limit: 10,
+            total_count: None,
+            total_count_capped: false,
         },
     };
 
@@ -531,6 +536,8 @@ fn test_page_to_projected_json_without_fields() {
             next_cursor: None,
             prev_cursor: None,
             limit: 20,
+            total_count: None,
+            total_count_capped: false,
         },
     };
 
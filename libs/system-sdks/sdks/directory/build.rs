@@ -3,12 +3,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     #[cfg(feature = "grpc")]
     {
         println!("cargo:rerun-if-changed=proto/v1/directory.proto");
+        println!("cargo:rerun-if-changed=proto/v1/events.proto");
         println!("cargo:rerun-if-changed=proto");
 
+        let out_dir = std::path::PathBuf::from(std::env::var("OUT_DIR")?);
+
         tonic_prost_build::configure()
             .build_client(true)
             .build_server(true)
-            .compile_protos(&["proto/v1/directory.proto"], &["proto"])?;
+            .file_descriptor_set_path(out_dir.join("directory_descriptor.bin"))
+            .compile_protos(
+                &["proto/v1/directory.proto", "proto/v1/events.proto"],
+                &["proto"],
+            )?;
     }
 
     Ok(())
@@ -2,8 +2,11 @@
 //!
 //! This module defines the core traits and types for the directory service API.
 
+use std::pin::Pin;
+
 use anyhow::Result;
 use async_trait::async_trait;
+use futures_core::Stream;
 
 /// Represents an endpoint where a service can be reached
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -87,6 +90,39 @@ pub trait DirectoryClient: Send + Sync {
     async fn send_heartbeat(&self, module: &str, instance_id: &str) -> Result<()>;
 }
 
+/// An event as carried by the event bus, with its payload serialized to JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventRecord {
+    /// Unique event identifier
+    pub id: String,
+    /// Topic: GTS schema identifier of the event (e.g., `gts.x.core.tenants.changed.v1~`)
+    pub topic: String,
+    /// JSON-encoded event payload
+    pub payload: String,
+    /// Name of the publishing module, if known
+    pub source: Option<String>,
+    /// Events of this topic dropped for the subscriber right before this one (remote
+    /// subscriptions only; ignored on publish)
+    pub missed: u64,
+}
+
+/// Stream of events delivered to a subscriber
+pub type EventStream = Pin<Box<dyn Stream<Item = Result<EventRecord>> + Send>>;
+
+/// Event bus API trait for publish/subscribe between modules
+///
+/// It can be implemented by:
+/// - The in-process event bus of the host runtime
+/// - A gRPC client for out-of-process modules, served next to the directory
+#[async_trait]
+pub trait EventBusClient: Send + Sync {
+    /// Publish an event to all subscribers of its topic
+    async fn publish(&self, event: EventRecord) -> Result<()>;
+
+    /// Subscribe to a topic; `subscriber` names the consumer for diagnostics
+    async fn subscribe(&self, topic: &str, subscriber: &str) -> Result<EventStream>;
+}
+
 #[cfg(test)]
 #[cfg_attr(coverage_nightly, coverage(off))]
 mod tests {
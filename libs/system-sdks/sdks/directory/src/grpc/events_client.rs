@@ -0,0 +1,84 @@
+//! gRPC client implementation of the Event Bus API
+//!
+//! Out-of-process modules use this client to publish to and subscribe on the
+//! host event bus through the directory endpoint.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use tonic::transport::Channel;
+
+use crate::api::{EventBusClient, EventRecord, EventStream};
+use modkit_transport_grpc::client::{GrpcClientConfig, connect_with_retry};
+
+use crate::{EventBusServiceClient, EventMessage, SubscribeRequest};
+
+/// gRPC client for the Event Bus API
+pub struct EventBusGrpcClient {
+    inner: EventBusServiceClient<Channel>,
+}
+
+impl EventBusGrpcClient {
+    /// Connect to the event bus service using default configuration with retries.
+    ///
+    /// # Errors
+    /// It will return an error when it fails
+    pub async fn connect(uri: impl Into<String>) -> Result<Self> {
+        let cfg = GrpcClientConfig::new("event_bus");
+        Self::connect_with_retry(uri, &cfg).await
+    }
+
+    /// Connect to the event bus service with custom configuration and retry logic.
+    ///
+    /// # Errors
+    /// It will return an error when it fails
+    pub async fn connect_with_retry(
+        uri: impl Into<String>,
+        cfg: &GrpcClientConfig,
+    ) -> Result<Self> {
+        let channel: Channel = connect_with_retry(uri, cfg).await?;
+        Ok(Self::from_channel(channel))
+    }
+
+    /// Create from an existing channel (useful for testing or custom setup)
+    #[must_use]
+    pub fn from_channel(channel: Channel) -> Self {
+        Self {
+            inner: EventBusServiceClient::new(channel),
+        }
+    }
+}
+
+#[async_trait]
+impl EventBusClient for EventBusGrpcClient {
+    async fn publish(&self, event: EventRecord) -> Result<()> {
+        let mut client = self.inner.clone();
+
+        client
+            .publish(tonic::Request::new(EventMessage::from(event)))
+            .await
+            .map_err(|e| anyhow::anyhow!("gRPC publish failed: {e}"))?;
+
+        Ok(())
+    }
+
+    async fn subscribe(&self, topic: &str, subscriber: &str) -> Result<EventStream> {
+        let mut client = self.inner.clone();
+
+        let req = SubscribeRequest {
+            topic: topic.to_owned(),
+            subscriber: subscriber.to_owned(),
+        };
+
+        let stream = client
+            .subscribe(tonic::Request::new(req))
+            .await
+            .map_err(|e| anyhow::anyhow!("gRPC subscribe failed: {e}"))?
+            .into_inner();
+
+        Ok(Box::pin(stream.map(|item| {
+            item.map(EventRecord::from)
+                .map_err(|e| anyhow::anyhow!("gRPC event stream failed: {e}"))
+        })))
+    }
+}
@@ -3,6 +3,7 @@
 //! This crate provides gRPC transport for the module orchestrator.
 //! It includes generated protobuf types and client/server implementations.
 mod client;
+mod events_client;
 
 // Generated protobuf types for DirectoryService
 #[allow(clippy::all, clippy::pedantic, clippy::nursery, warnings)] // protoc problem
@@ -10,6 +11,12 @@ pub mod directory {
     tonic::include_proto!("module_orchestrator.v1.directory");
 }
 
+// Generated protobuf types for EventBusService
+#[allow(clippy::all, clippy::pedantic, clippy::nursery, warnings)] // protoc problem
+pub mod events {
+    tonic::include_proto!("module_orchestrator.v1.events");
+}
+
 // Re-export common types for DirectoryService
 pub use directory::directory_service_client::DirectoryServiceClient;
 pub use directory::directory_service_server::{DirectoryService, DirectoryServiceServer};
@@ -19,9 +26,50 @@ pub use directory::{
     ResolveGrpcServiceRequest, ResolveGrpcServiceResponse,
 };
 
-// Re-export the gRPC client implementation
+// Re-export common types for EventBusService
+pub use events::event_bus_service_client::EventBusServiceClient;
+pub use events::event_bus_service_server::{EventBusService, EventBusServiceServer};
+pub use events::{EventMessage, SubscribeRequest};
+
+// Re-export the gRPC client implementations
 pub use client::DirectoryGrpcClient;
+pub use events_client::EventBusGrpcClient;
+
+/// Encoded `FileDescriptorSet` of `DirectoryService` and `EventBusService`, for server reflection
+pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("directory_descriptor");
 
 /// Service name constant for `DirectoryService`
 pub const DIRECTORY_SERVICE_NAME: &str =
     <DirectoryServiceServer<()> as tonic::server::NamedService>::NAME;
+
+/// Service name constant for `EventBusService`
+pub const EVENT_BUS_SERVICE_NAME: &str =
+    <EventBusServiceServer<()> as tonic::server::NamedService>::NAME;
+
+impl From<crate::EventRecord> for EventMessage {
+    fn from(event: crate::EventRecord) -> Self {
+        Self {
+            id: event.id,
+            topic: event.topic,
+            payload: event.payload,
+            source: event.source.unwrap_or_default(),
+            missed: event.missed,
+        }
+    }
+}
+
+impl From<EventMessage> for crate::EventRecord {
+    fn from(msg: EventMessage) -> Self {
+        Self {
+            id: msg.id,
+            topic: msg.topic,
+            payload: msg.payload,
+            source: if msg.source.is_empty() {
+                None
+            } else {
+                Some(msg.source)
+            },
+            missed: msg.missed,
+        }
+    }
+}
@@ -2,13 +2,17 @@
 //!
 //! Domain contracts and client interfaces for module orchestration.
 //! This crate provides the `DirectoryClient` trait and related types that
-//! define the contract for service discovery and instance management.
+//! define the contract for service discovery and instance management, and the
+//! `EventBusClient` trait for publish/subscribe through the same endpoint.
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 mod api;
 #[cfg(feature = "grpc")]
 mod grpc;
 
-pub use api::{DirectoryClient, RegisterInstanceInfo, ServiceEndpoint, ServiceInstanceInfo};
+pub use api::{
+    DirectoryClient, EventBusClient, EventRecord, EventStream, RegisterInstanceInfo,
+    ServiceEndpoint, ServiceInstanceInfo,
+};
 #[cfg(feature = "grpc")]
 pub use grpc::*;
@@ -112,7 +112,7 @@ pub async fn upload_and_parse(
     let content_type_str = headers
         .get(axum::http::header::CONTENT_TYPE)
         .and_then(|v| v.to_str().ok())
-        .map(ToString::to_string);
+        .map(str::to_owned);
 
     info!(
         filename = ?filename_opt,
@@ -213,7 +213,7 @@ pub async fn upload_and_parse_markdown(
     })? {
         let field_name = field.name().unwrap_or("").to_owned();
         if field_name == "file" {
-            file_name = field.file_name().map(ToString::to_string);
+            file_name = field.file_name().map(str::to_owned);
             file_bytes = Some(field.bytes().await.map_err(|e| {
                 Problem::from(DomainError::io_error(format!("Failed to read file: {e}")))
             })?);
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use modkit_db::audit::{self, AuditEntry};
+use modkit_db::{DBProvider, DbError};
+use parking_lot::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use time::OffsetDateTime;
+
+use super::{AuditRecord, AuditSink};
+
+/// How often entries past their retention are deleted.
+const PURGE_INTERVAL: Duration = Duration::from_hours(1);
+
+/// Audit records in the gateway's database, shared by all gateway instances.
+///
+/// Requires the `modkit_audit_log` table, created by the gateway's migrations. Entries older
+/// than `retention` are deleted in the background; without a retention they are kept.
+pub struct DbAuditSink {
+    db: DBProvider<DbError>,
+    retention: Option<Duration>,
+    last_purge: Mutex<Option<Instant>>,
+}
+
+impl DbAuditSink {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>, retention: Option<Duration>) -> Self {
+        Self {
+            db,
+            retention,
+            last_purge: Mutex::new(None),
+        }
+    }
+
+    /// Deletes expired entries in the background at most once per [`PURGE_INTERVAL`].
+    fn purge_expired(&self) {
+        let Some(retention) = self.retention else {
+            return;
+        };
+        let Some(mut last) = self.last_purge.try_lock() else {
+            return;
+        };
+        if last.is_some_and(|at| at.elapsed() < PURGE_INTERVAL) {
+            return;
+        }
+        *last = Some(Instant::now());
+        drop(last);
+
+        let cutoff = OffsetDateTime::from(SystemTime::now() - retention);
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let purged = match db.conn() {
+                Ok(conn) => audit::purge_before(&conn, cutoff).await,
+                Err(e) => Err(e),
+            };
+            match purged {
+                Ok(count) => tracing::debug!(count, "Purged expired audit records"),
+                Err(e) => tracing::warn!(error = %e, "Failed to purge expired audit records"),
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl AuditSink for DbAuditSink {
+    async fn record(&self, record: &AuditRecord) -> anyhow::Result<()> {
+        self.purge_expired();
+
+        let entry = AuditEntry {
+            occurred_at: OffsetDateTime::from(SystemTime::from(record.timestamp)),
+            request_id: record.request_id.clone(),
+            subject_id: record.subject_id,
+            tenant_id: record.tenant_id,
+            operation: record.operation_id.clone(),
+            method: record.method.clone(),
+            path: record.path.clone(),
+            resource_ids: serde_json::to_string(&record.resource_ids)?,
+            status: record.status,
+            outcome: record.outcome.as_str().to_owned(),
+            latency_ms: record.latency_ms,
+            details: record
+                .request
+                .as_ref()
+                .map(|request| serde_json::json!({ "request": request }).to_string()),
+        };
+        let conn = self.db.conn()?;
+        audit::append(&conn, &entry).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use file_rotate::{ContentLimit, FileRotate, compression::Compression, suffix::AppendCount};
+use parking_lot::Mutex;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use super::{AuditRecord, AuditSink};
+
+/// Audit records appended to a file as JSON lines.
+///
+/// Once the file has grown past `max_file_bytes`, the next record starts a new file: the
+/// current one becomes `<path>.1`, older files shift to `<path>.2` and so on. At most
+/// `max_files` rotated files are kept; older ones are deleted.
+pub struct JsonlFileAuditSink {
+    file: Arc<Mutex<FileRotate<AppendCount>>>,
+}
+
+impl JsonlFileAuditSink {
+    /// Opens (or creates) `path` for appending, creating missing parent directories.
+    ///
+    /// # Errors
+    /// Returns an error if `max_file_bytes` is zero or the directory or file cannot be created.
+    pub fn new(
+        path: impl Into<PathBuf>,
+        max_file_bytes: u64,
+        max_files: usize,
+    ) -> anyhow::Result<Self> {
+        let path = path.into();
+        let max_file_bytes = usize::try_from(max_file_bytes)
+            .ok()
+            .filter(|bytes| *bytes > 0)
+            .context("audit max_file_bytes must be positive")?;
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            fs::create_dir_all(dir).with_context(|| {
+                format!("failed to create audit log directory {}", dir.display())
+            })?;
+        }
+        // `FileRotate` drops writes to a file it cannot open, so fail here instead
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("failed to open audit log {}", path.display()))?;
+        let file = FileRotate::new(
+            &path,
+            AppendCount::new(max_files),
+            ContentLimit::BytesSurpassed(max_file_bytes),
+            Compression::None,
+            None,
+        );
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+}
+
+#[async_trait]
+impl AuditSink for JsonlFileAuditSink {
+    async fn record(&self, record: &AuditRecord) -> anyhow::Result<()> {
+        let mut line = serde_json::to_vec(record)?;
+        line.push(b'\n');
+        let file = Arc::clone(&self.file);
+        tokio::task::spawn_blocking(move || {
+            let mut file = file.lock();
+            file.write_all(&line)?;
+            file.flush()
+        })
+        .await??;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::audit::AuditOutcome;
+    use std::collections::BTreeMap;
+    use std::path::Path;
+
+    fn rotated(path: &Path, n: usize) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    fn record(operation_id: &str) -> AuditRecord {
+        AuditRecord {
+            timestamp: chrono::Utc::now(),
+            request_id: None,
+            subject_id: None,
+            tenant_id: None,
+            operation_id: operation_id.to_owned(),
+            method: "POST".to_owned(),
+            path: "/tests/v1/items".to_owned(),
+            resource_ids: BTreeMap::new(),
+            status: 201,
+            outcome: AuditOutcome::Success,
+            latency_ms: 1,
+            request: None,
+        }
+    }
+
+    fn lines(path: &Path) -> Vec<AuditRecord> {
+        fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn records_appended_as_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested/audit.jsonl");
+        let sink = JsonlFileAuditSink::new(&path, 1024 * 1024, 2).unwrap();
+
+        let first = record("first");
+        sink.record(&first).await.unwrap();
+        sink.record(&record("second")).await.unwrap();
+
+        let written = lines(&path);
+        assert_eq!(written.len(), 2);
+        assert_eq!(written[0], first);
+    }
+
+    #[tokio::test]
+    async fn files_rotated_and_oldest_dropped() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        // Every record exceeds the limit, so each one starts a new file
+        let sink = JsonlFileAuditSink::new(&path, 10, 2).unwrap();
+
+        for op in ["a", "b", "c", "d"] {
+            sink.record(&record(op)).await.unwrap();
+        }
+
+        assert_eq!(lines(&path)[0].operation_id, "d");
+        assert_eq!(lines(&rotated(&path, 1))[0].operation_id, "c");
+        assert_eq!(lines(&rotated(&path, 2))[0].operation_id, "b");
+        assert!(!rotated(&path, 3).exists());
+    }
+}
@@ -0,0 +1,91 @@
+//! Audit log of auditable operations.
+//!
+//! The gateway writes an [`AuditRecord`] for every invocation of an operation declared with
+//! `auditable()`: who called it, on which resources, how it ended and how long it took.
+//! Records go to an [`AuditSink`]; the gateway ships a JSON lines file sink with size-based
+//! rotation and a database sink. A custom sink can be registered in the `ClientHub` as
+//! `dyn AuditSink`. Sensitive fields are masked by the [`Redaction`] rules before a record
+//! reaches the sink.
+
+mod db;
+mod file;
+mod redact;
+
+pub use db::DbAuditSink;
+pub use file::JsonlFileAuditSink;
+pub use redact::{REDACTED, Redaction};
+
+use async_trait::async_trait;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// How an audited invocation ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    /// `1xx`, `2xx` or `3xx` response
+    Success,
+    /// `401` or `403`: rejected by an access check
+    Denied,
+    /// Any other error response
+    Failure,
+    /// No response was produced: the request timed out or the client disconnected while
+    /// the handler ran. The operation may still have taken effect.
+    Cancelled,
+}
+
+impl AuditOutcome {
+    #[must_use]
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Denied,
+            s if s.is_client_error() || s.is_server_error() => Self::Failure,
+            _ => Self::Success,
+        }
+    }
+
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Success => "success",
+            Self::Denied => "denied",
+            Self::Failure => "failure",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// One invocation of an auditable operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub request_id: Option<String>,
+    pub subject_id: Option<Uuid>,
+    pub tenant_id: Option<Uuid>,
+    /// Operation id, or `METHOD path` for operations without one
+    pub operation_id: String,
+    pub method: String,
+    /// Route template, e.g. `/users/v1/users/{id}`
+    pub path: String,
+    /// Path parameters of the route
+    pub resource_ids: BTreeMap<String, String>,
+    /// Response status; `0` for cancelled invocations
+    pub status: u16,
+    pub outcome: AuditOutcome,
+    pub latency_ms: u64,
+    /// Redacted JSON request body, when request bodies are recorded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request: Option<serde_json::Value>,
+}
+
+/// Destination of audit records.
+///
+/// Records are written after the response is produced, or when the request is cancelled
+/// before that; a failed write is logged and does not change the response.
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, record: &AuditRecord) -> anyhow::Result<()>;
+}
@@ -0,0 +1,110 @@
+use std::collections::{BTreeMap, HashSet};
+
+use serde_json::Value;
+
+/// Replacement of masked values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Field names whose values are masked in audit records, matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct Redaction {
+    fields: HashSet<String>,
+}
+
+impl Redaction {
+    pub fn new<I, S>(fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            fields: fields
+                .into_iter()
+                .map(|f| f.as_ref().to_ascii_lowercase())
+                .collect(),
+        }
+    }
+
+    /// These rules plus `fields`.
+    #[must_use]
+    pub fn with<I, S>(&self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut redaction = self.clone();
+        redaction
+            .fields
+            .extend(fields.into_iter().map(|f| f.as_ref().to_ascii_lowercase()));
+        redaction
+    }
+
+    #[must_use]
+    pub fn is_sensitive(&self, field: &str) -> bool {
+        self.fields.contains(&field.to_ascii_lowercase())
+    }
+
+    /// Masks sensitive object members at any depth.
+    pub fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Object(members) => {
+                for (name, member) in members {
+                    if self.is_sensitive(name) {
+                        *member = Value::String(REDACTED.to_owned());
+                    } else {
+                        self.apply(member);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|item| self.apply(item)),
+            _ => {}
+        }
+    }
+
+    /// Masks sensitive entries of a flat map such as path parameters.
+    pub fn apply_map(&self, map: &mut BTreeMap<String, String>) {
+        for (name, value) in map {
+            if self.is_sensitive(name) {
+                REDACTED.clone_into(value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn nested_members_masked_case_insensitively() {
+        let redaction = Redaction::new(["password"]).with(["SSN"]);
+        let mut body = json!({
+            "name": "alice",
+            "Password": "hunter2",
+            "profile": { "ssn": "123", "tags": [{ "password": "x" }] }
+        });
+        redaction.apply(&mut body);
+        assert_eq!(
+            body,
+            json!({
+                "name": "alice",
+                "Password": REDACTED,
+                "profile": { "ssn": REDACTED, "tags": [{ "password": REDACTED }] }
+            })
+        );
+    }
+
+    #[test]
+    fn map_entries_masked() {
+        let redaction = Redaction::new(["token"]);
+        let mut params = BTreeMap::from([
+            ("id".to_owned(), "42".to_owned()),
+            ("token".to_owned(), "abc".to_owned()),
+        ]);
+        redaction.apply_map(&mut params);
+        assert_eq!(params["id"], "42");
+        assert_eq!(params["token"], REDACTED);
+    }
+}
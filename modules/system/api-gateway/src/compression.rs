@@ -0,0 +1,24 @@
+use tower_http::compression::CompressionLayer;
+use tower_http::compression::predicate::{NotForContentType, Predicate, SizeAbove};
+
+use crate::config::{CompressionAlgorithm, CompressionConfig};
+
+/// Build a response compression layer from config.
+///
+/// Responses below the size threshold, gRPC, images and server-sent event streams are
+/// sent uncompressed.
+pub fn build_compression_layer(
+    cfg: &CompressionConfig,
+) -> CompressionLayer<impl Predicate + use<>> {
+    let enabled = |algorithm| cfg.algorithms.contains(&algorithm);
+    CompressionLayer::new()
+        .gzip(enabled(CompressionAlgorithm::Gzip))
+        .br(enabled(CompressionAlgorithm::Br))
+        .zstd(enabled(CompressionAlgorithm::Zstd))
+        .compress_when(
+            SizeAbove::new(cfg.min_size_bytes)
+                .and(NotForContentType::GRPC)
+                .and(NotForContentType::IMAGES)
+                .and(NotForContentType::SSE),
+        )
+}
@@ -1,4 +1,10 @@
+use modkit_tls::ServerTlsConfig;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+pub use modkit_tls::ClientAuth;
 
 fn default_require_auth_by_default() -> bool {
     true
@@ -8,6 +14,50 @@ fn default_body_limit_bytes() -> usize {
     16 * 1024 * 1024
 }
 
+fn default_license_cache_ttl_seconds() -> u64 {
+    60
+}
+
+fn default_tls_reload_interval() -> Duration {
+    Duration::from_secs(30)
+}
+
+fn default_audit_redact() -> Vec<String> {
+    [
+        "password",
+        "secret",
+        "client_secret",
+        "token",
+        "access_token",
+        "refresh_token",
+        "api_key",
+        "authorization",
+    ]
+    .into_iter()
+    .map(str::to_owned)
+    .collect()
+}
+
+fn default_audit_max_body_bytes() -> usize {
+    64 * 1024
+}
+
+fn default_idempotency_ttl() -> Duration {
+    Duration::from_hours(24)
+}
+
+fn default_idempotency_max_response_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_max_etag_body_bytes() -> usize {
+    1024 * 1024
+}
+
+fn default_compression_min_size_bytes() -> u16 {
+    1024
+}
+
 /// API gateway configuration - reused from `api_gateway` module
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 #[serde(deny_unknown_fields)]
@@ -41,6 +91,314 @@ pub struct ApiGatewayConfig {
     /// If true, routes without explicit security requirement still require authentication (AuthN-only).
     #[serde(default = "default_require_auth_by_default")]
     pub require_auth_by_default: bool,
+
+    /// License feature enforcement
+    #[serde(default)]
+    pub license: LicenseConfig,
+
+    /// Gateway-level authorization checks
+    #[serde(default)]
+    pub auth: AuthConfig,
+
+    /// Native HTTPS termination on `bind_addr`; plain HTTP when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsConfig>,
+
+    /// `Idempotency-Key` handling for operations that accept it
+    #[serde(default)]
+    pub idempotency: IdempotencyConfig,
+
+    /// `ETag` generation for operations declared with `with_etag()`
+    #[serde(default)]
+    pub http_cache: HttpCacheConfig,
+
+    /// Negotiated response compression
+    #[serde(default)]
+    pub compression: CompressionConfig,
+
+    /// HTTP server metrics and the Prometheus scrape endpoint
+    #[serde(default)]
+    pub metrics: MetricsEndpointConfig,
+
+    /// Audit log of operations declared with `auditable()`
+    #[serde(default)]
+    pub audit: AuditConfig,
+
+    /// Lifecycle of deprecated operations
+    #[serde(default)]
+    pub deprecation: DeprecationConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct DeprecationConfig {
+    /// Refuse to start while an operation past its sunset date is still registered
+    pub fail_on_past_sunset: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct AuditConfig {
+    /// Where records are written; ignored when a sink is registered in the `ClientHub`.
+    /// Without any sink, auditable operations are not recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sink: Option<AuditSinkKind>,
+    /// Settings of the `file` sink
+    pub file: AuditFileConfig,
+    /// `database` sink: entries older than this are deleted; kept forever when unset
+    #[serde(
+        with = "modkit_utils::humantime_serde::option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retention: Option<Duration>,
+    /// Field names masked in path parameters and request bodies (case-insensitive)
+    pub redact: Vec<String>,
+    /// Include the redacted JSON request body in records
+    pub record_request_body: bool,
+    /// Larger request bodies are not recorded
+    pub max_body_bytes: usize,
+    /// Records waiting for the sink; when full, audited requests wait for room
+    pub queue_capacity: usize,
+    /// How long an audited response waits for room in a full queue before its record is
+    /// dropped (and counted in `api_gateway.audit.dropped_records`)
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub enqueue_timeout: Duration,
+}
+
+impl Default for AuditConfig {
+    fn default() -> Self {
+        Self {
+            sink: None,
+            file: AuditFileConfig::default(),
+            retention: None,
+            redact: default_audit_redact(),
+            record_request_body: false,
+            max_body_bytes: default_audit_max_body_bytes(),
+            queue_capacity: 1024,
+            enqueue_timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+/// Built-in audit sinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSinkKind {
+    /// JSON lines file with size-based rotation
+    File,
+    /// The gateway's database (requires a database configured for `api-gateway`)
+    Database,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct AuditFileConfig {
+    /// Relative paths are resolved against the working directory
+    pub path: PathBuf,
+    /// The file is rotated before it grows past this size
+    pub max_file_bytes: u64,
+    /// Rotated files kept next to the current one (`<path>.1` is the newest)
+    pub max_files: usize,
+}
+
+impl Default for AuditFileConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from("logs/audit.jsonl"),
+            max_file_bytes: 100 * 1024 * 1024,
+            max_files: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct MetricsEndpointConfig {
+    /// Record HTTP server metrics; the endpoint is served only when `metrics.prometheus`
+    /// is enabled in the app config
+    pub enabled: bool,
+    /// Scrape path
+    pub path: String,
+    /// Serve the scrape endpoint without authentication. Off by default: series carry
+    /// tenant IDs, so scrapers need a token listing `auth.admin_scope` itself
+    pub public: bool,
+}
+
+impl Default for MetricsEndpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            path: "/metrics".to_owned(),
+            public: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct HttpCacheConfig {
+    /// Larger (or streamed) bodies get no content-derived `ETag`
+    pub max_etag_body_bytes: usize,
+}
+
+impl Default for HttpCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_etag_body_bytes: default_max_etag_body_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct CompressionConfig {
+    pub enabled: bool,
+    /// Smaller responses are sent uncompressed
+    pub min_size_bytes: u16,
+    /// Encodings offered to clients via `Accept-Encoding`
+    pub algorithms: Vec<CompressionAlgorithm>,
+}
+
+impl Default for CompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_size_bytes: default_compression_min_size_bytes(),
+            algorithms: vec![
+                CompressionAlgorithm::Gzip,
+                CompressionAlgorithm::Br,
+                CompressionAlgorithm::Zstd,
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionAlgorithm {
+    Gzip,
+    Br,
+    Zstd,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct IdempotencyConfig {
+    /// How long a recorded response is replayed for its key
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub ttl: Duration,
+    /// Where records are kept; ignored when a store is registered in the `ClientHub`
+    pub store: IdempotencyStoreKind,
+    /// Larger responses are not recorded, so retries execute the operation again
+    pub max_response_bytes: usize,
+}
+
+impl Default for IdempotencyConfig {
+    fn default() -> Self {
+        Self {
+            ttl: default_idempotency_ttl(),
+            store: IdempotencyStoreKind::default(),
+            max_response_bytes: default_idempotency_max_response_bytes(),
+        }
+    }
+}
+
+/// Built-in idempotency record stores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IdempotencyStoreKind {
+    /// Process-local; records are not shared between gateway instances
+    #[default]
+    Memory,
+    /// The gateway's database (requires a database configured for `api-gateway`)
+    Database,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TlsConfig {
+    /// PEM certificate chain, leaf first
+    pub cert_path: PathBuf,
+    /// PEM private key (PKCS#8, PKCS#1 or SEC1)
+    pub key_path: PathBuf,
+    /// PEM bundle of CAs trusted for client certificates; enables client-cert auth
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ca_path: Option<PathBuf>,
+    /// Client certificate policy when `client_ca_path` is set
+    #[serde(default)]
+    pub client_auth: ClientAuth,
+    /// How often the files are checked for changes
+    #[serde(
+        default = "default_tls_reload_interval",
+        with = "modkit_utils::humantime_serde"
+    )]
+    pub reload_interval: Duration,
+    /// Optional plain HTTP listener redirecting every request to HTTPS
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_redirect_bind_addr: Option<String>,
+}
+
+impl TlsConfig {
+    /// Settings of the HTTPS listener itself.
+    #[must_use]
+    pub fn server_tls(&self) -> ServerTlsConfig {
+        ServerTlsConfig {
+            cert_path: self.cert_path.clone(),
+            key_path: self.key_path.clone(),
+            client_ca_path: self.client_ca_path.clone(),
+            client_auth: self.client_auth,
+            reload_interval: self.reload_interval,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct AuthConfig {
+    /// Early rejection of tokens lacking the scopes a route requires, before any PDP call
+    pub gateway_scope_checks: GatewayScopeChecksConfig,
+    /// Scope required for the gateway's admin operations; always enforced, and not granted by `*`
+    pub admin_scope: String,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            gateway_scope_checks: GatewayScopeChecksConfig::default(),
+            admin_scope: "api-gateway:admin".to_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct GatewayScopeChecksConfig {
+    /// Enforce operation-declared and configured route scopes
+    pub enabled: bool,
+    /// Route patterns (a trailing `*` matches any suffix) with their required scopes
+    pub routes: BTreeMap<String, RouteScopesConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RouteScopesConfig {
+    /// Scopes of which the token needs any
+    pub required_scopes: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields, default)]
+pub struct LicenseConfig {
+    /// How long enabled features resolved via the license resolver are cached
+    pub cache_ttl_seconds: u64,
+}
+
+impl Default for LicenseConfig {
+    fn default() -> Self {
+        Self {
+            cache_ttl_seconds: default_license_cache_ttl_seconds(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -67,6 +425,13 @@ pub struct RateLimitDefaults {
     pub rps: u32,
     pub burst: u32,
     pub in_flight: u32,
+    /// Caller identity that rate limit buckets are keyed by, in addition to the route
+    pub key: RateLimitKey,
+    /// Per-tenant rps/burst replacing the route limits for that tenant on every route, whether
+    /// higher or lower (requires a tenant key)
+    pub tenant_overrides: Vec<TenantRateLimit>,
+    /// How often buckets of idle callers are dropped
+    pub idle_eviction_seconds: u64,
 }
 
 impl Default for RateLimitDefaults {
@@ -75,8 +440,56 @@ impl Default for RateLimitDefaults {
             rps: 50,
             burst: 100,
             in_flight: 64,
+            key: RateLimitKey::default(),
+            tenant_overrides: Vec::new(),
+            idle_eviction_seconds: 60,
+        }
+    }
+}
+
+/// Caller part of a rate limit bucket key, taken from the request `SecurityContext`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    /// One bucket per route shared by all callers
+    #[default]
+    Route,
+    /// One bucket per route and tenant
+    Tenant,
+    /// One bucket per route and subject
+    Subject,
+    /// One bucket per route, tenant and subject
+    TenantSubject,
+}
+
+impl RateLimitKey {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Route => "route",
+            Self::Tenant => "tenant",
+            Self::Subject => "subject",
+            Self::TenantSubject => "tenant_subject",
         }
     }
+
+    #[must_use]
+    pub fn includes_tenant(self) -> bool {
+        matches!(self, Self::Tenant | Self::TenantSubject)
+    }
+
+    #[must_use]
+    pub fn includes_subject(self) -> bool {
+        matches!(self, Self::Subject | Self::TenantSubject)
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantRateLimit {
+    pub tenant_id: uuid::Uuid,
+    pub rps: u32,
+    pub burst: u32,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -0,0 +1,125 @@
+use anyhow::Context;
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use modkit_db::idempotency::{self, IdempotencyClaim};
+use modkit_db::{DBProvider, DbError};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use super::{IdempotencyBegin, IdempotencyStore, StoredResponse};
+
+/// How often expired rows are deleted.
+const PURGE_INTERVAL: Duration = Duration::from_mins(5);
+
+/// Idempotency store on the gateway's database, shared by all gateway instances.
+///
+/// Requires the `modkit_idempotency` table, created by the gateway's migrations.
+pub struct DbIdempotencyStore {
+    db: DBProvider<DbError>,
+    last_purge: Mutex<Instant>,
+}
+
+/// Serialized form of a [`StoredResponse`] in the `response` column.
+#[derive(Serialize, Deserialize)]
+struct ResponseRecord {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl DbIdempotencyStore {
+    #[must_use]
+    pub fn new(db: DBProvider<DbError>) -> Self {
+        Self {
+            db,
+            last_purge: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Deletes expired rows in the background at most once per [`PURGE_INTERVAL`].
+    fn purge_expired(&self) {
+        let Some(mut last) = self.last_purge.try_lock() else {
+            return;
+        };
+        if last.elapsed() < PURGE_INTERVAL {
+            return;
+        }
+        *last = Instant::now();
+        drop(last);
+
+        let db = self.db.clone();
+        tokio::spawn(async move {
+            let purged = match db.conn() {
+                Ok(conn) => idempotency::purge_expired(&conn).await,
+                Err(e) => Err(e),
+            };
+            match purged {
+                Ok(count) => tracing::debug!(count, "Purged expired idempotency records"),
+                Err(e) => tracing::warn!(error = %e, "Failed to purge expired idempotency records"),
+            }
+        });
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for DbIdempotencyStore {
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lease: Duration,
+    ) -> anyhow::Result<IdempotencyBegin> {
+        self.purge_expired();
+
+        let conn = self.db.conn()?;
+        Ok(
+            match idempotency::claim(&conn, key, fingerprint, lease).await? {
+                IdempotencyClaim::Claimed => IdempotencyBegin::Started,
+                IdempotencyClaim::InFlight { fingerprint } => {
+                    IdempotencyBegin::InFlight { fingerprint }
+                }
+                IdempotencyClaim::Completed {
+                    fingerprint,
+                    response,
+                } => {
+                    let record: ResponseRecord = serde_json::from_str(&response)
+                        .context("invalid stored idempotent response")?;
+                    IdempotencyBegin::Completed {
+                        fingerprint,
+                        response: StoredResponse {
+                            status: record.status,
+                            headers: record.headers,
+                            body: STANDARD
+                                .decode(record.body)
+                                .context("invalid stored idempotent response body")?,
+                        },
+                    }
+                }
+            },
+        )
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> anyhow::Result<()> {
+        let record = serde_json::to_string(&ResponseRecord {
+            status: response.status,
+            headers: response.headers.clone(),
+            body: STANDARD.encode(&response.body),
+        })?;
+        let conn = self.db.conn()?;
+        idempotency::complete(&conn, key, &record, ttl).await?;
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> anyhow::Result<()> {
+        let conn = self.db.conn()?;
+        idempotency::release(&conn, key).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,191 @@
+use async_trait::async_trait;
+use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+use super::{IdempotencyBegin, IdempotencyStore, StoredResponse};
+
+/// How often expired records are swept.
+const PURGE_INTERVAL: Duration = Duration::from_mins(1);
+
+struct Record {
+    fingerprint: String,
+    expires_at: Instant,
+    response: Option<StoredResponse>,
+}
+
+/// Process-local idempotency store.
+///
+/// Records are lost on restart and not shared between gateway instances.
+pub struct InMemoryIdempotencyStore {
+    records: DashMap<String, Record>,
+    last_purge: Mutex<Instant>,
+}
+
+impl InMemoryIdempotencyStore {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            records: DashMap::new(),
+            last_purge: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Sweeps expired records at most once per [`PURGE_INTERVAL`], without blocking callers.
+    fn purge_expired(&self, now: Instant) {
+        let Some(mut last) = self.last_purge.try_lock() else {
+            return;
+        };
+        if now.duration_since(*last) < PURGE_INTERVAL {
+            return;
+        }
+        *last = now;
+        drop(last);
+        self.records.retain(|_, record| record.expires_at > now);
+    }
+}
+
+impl Default for InMemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lease: Duration,
+    ) -> anyhow::Result<IdempotencyBegin> {
+        let now = Instant::now();
+        self.purge_expired(now);
+
+        let record = Record {
+            fingerprint: fingerprint.to_owned(),
+            expires_at: now + lease,
+            response: None,
+        };
+        match self.records.entry(key.to_owned()) {
+            Entry::Vacant(entry) => {
+                entry.insert(record);
+                Ok(IdempotencyBegin::Started)
+            }
+            Entry::Occupied(mut entry) if entry.get().expires_at <= now => {
+                entry.insert(record);
+                Ok(IdempotencyBegin::Started)
+            }
+            Entry::Occupied(entry) => {
+                let existing = entry.get();
+                let fingerprint = existing.fingerprint.clone();
+                Ok(match &existing.response {
+                    Some(response) => IdempotencyBegin::Completed {
+                        fingerprint,
+                        response: response.clone(),
+                    },
+                    None => IdempotencyBegin::InFlight { fingerprint },
+                })
+            }
+        }
+    }
+
+    async fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> anyhow::Result<()> {
+        if let Some(mut record) = self.records.get_mut(key) {
+            record.response = Some(response.clone());
+            record.expires_at = Instant::now() + ttl;
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> anyhow::Result<()> {
+        self.records
+            .remove_if(key, |_, record| record.response.is_none());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    const LEASE: Duration = Duration::from_secs(30);
+    const TTL: Duration = Duration::from_mins(1);
+
+    fn response() -> StoredResponse {
+        StoredResponse {
+            status: 201,
+            headers: vec![("content-type".to_owned(), "application/json".to_owned())],
+            body: b"{}".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn claim_complete_and_replay() {
+        let store = InMemoryIdempotencyStore::new();
+
+        assert_eq!(
+            store.begin("k", "fp", LEASE).await.unwrap(),
+            IdempotencyBegin::Started
+        );
+        assert_eq!(
+            store.begin("k", "fp", LEASE).await.unwrap(),
+            IdempotencyBegin::InFlight {
+                fingerprint: "fp".to_owned()
+            }
+        );
+
+        store.complete("k", &response(), TTL).await.unwrap();
+        assert_eq!(
+            store.begin("k", "other", LEASE).await.unwrap(),
+            IdempotencyBegin::Completed {
+                fingerprint: "fp".to_owned(),
+                response: response(),
+            }
+        );
+
+        // Completed records are not released
+        store.release("k").await.unwrap();
+        assert!(matches!(
+            store.begin("k", "fp", LEASE).await.unwrap(),
+            IdempotencyBegin::Completed { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn released_and_expired_keys_can_be_claimed_again() {
+        let store = InMemoryIdempotencyStore::new();
+
+        store.begin("released", "fp", LEASE).await.unwrap();
+        store.release("released").await.unwrap();
+        assert_eq!(
+            store.begin("released", "fp", LEASE).await.unwrap(),
+            IdempotencyBegin::Started
+        );
+
+        store.begin("expired", "fp", Duration::ZERO).await.unwrap();
+        assert_eq!(
+            store.begin("expired", "fp", LEASE).await.unwrap(),
+            IdempotencyBegin::Started
+        );
+    }
+
+    #[tokio::test]
+    async fn completed_records_outlive_the_lease() {
+        let store = InMemoryIdempotencyStore::new();
+
+        store.begin("k", "fp", Duration::ZERO).await.unwrap();
+        store.complete("k", &response(), TTL).await.unwrap();
+        assert!(matches!(
+            store.begin("k", "fp", LEASE).await.unwrap(),
+            IdempotencyBegin::Completed { .. }
+        ));
+    }
+}
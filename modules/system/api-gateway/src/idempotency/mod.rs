@@ -0,0 +1,67 @@
+//! Stores for `Idempotency-Key` handling.
+//!
+//! The gateway records the first response of an idempotency-key capable operation per
+//! caller, route and key, and replays it when the client retries. Records live in an
+//! [`IdempotencyStore`]; the gateway ships an in-memory store for single-instance
+//! deployments and a database-backed one for gateways running behind a load balancer.
+//! A custom store can be registered in the `ClientHub` as `dyn IdempotencyStore`.
+
+mod db;
+mod memory;
+
+pub use db::DbIdempotencyStore;
+pub use memory::InMemoryIdempotencyStore;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Response recorded for an idempotency key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+/// Outcome of [`IdempotencyStore::begin`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyBegin {
+    /// The key was free and is now held by the caller, who executes the request.
+    Started,
+    /// Another request holding the key is still executing.
+    InFlight { fingerprint: String },
+    /// A request with the key completed; its response is replayed.
+    Completed {
+        fingerprint: String,
+        response: StoredResponse,
+    },
+}
+
+/// Storage of idempotency records.
+///
+/// Keys are opaque and already scoped to the caller and route by the gateway.
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Claims `key` for a request with the given body `fingerprint`, or reports the
+    /// existing record. The claim expires after `lease` unless it is completed; expired
+    /// records are treated as absent.
+    async fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lease: Duration,
+    ) -> anyhow::Result<IdempotencyBegin>;
+
+    /// Records the response of a request started with [`begin`](Self::begin), to be
+    /// replayed for `ttl`.
+    async fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: Duration,
+    ) -> anyhow::Result<()>;
+
+    /// Drops an in-flight claim so that a retry executes the request again.
+    async fn release(&self, key: &str) -> anyhow::Result<()>;
+}
@@ -10,12 +10,21 @@ pub use module::ApiGateway;
 
 // === INTERNAL MODULES ===
 mod assets;
+pub mod audit;
+mod compression;
 mod config;
 mod cors;
 pub mod error;
+pub mod idempotency;
 pub mod middleware;
 mod router_cache;
+mod tls;
 mod web;
 
 // === RE-EXPORTS ===
-pub use config::{ApiGatewayConfig, CorsConfig};
+pub use audit::{AuditOutcome, AuditRecord, AuditSink, DbAuditSink, JsonlFileAuditSink, Redaction};
+pub use config::{ApiGatewayConfig, AuditSinkKind, CorsConfig, IdempotencyStoreKind, RateLimitKey};
+pub use idempotency::{
+    DbIdempotencyStore, IdempotencyBegin, IdempotencyStore, InMemoryIdempotencyStore,
+    StoredResponse,
+};
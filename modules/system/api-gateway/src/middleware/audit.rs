@@ -0,0 +1,365 @@
+use axum::body::{Body, Bytes};
+use axum::extract::{MatchedPath, Request};
+use axum::http::{Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+
+use modkit::api::{OperationSpec, Problem};
+use modkit::telemetry::metrics::{Counter, meter};
+use modkit_security::SecurityContext;
+
+use crate::audit::{AuditOutcome, AuditRecord, AuditSink, Redaction};
+use crate::config::AuditConfig;
+use crate::middleware::request_id::{XRequestId, header as request_id_header};
+
+type OperationKey = (Method, String);
+
+struct AuditedOperation {
+    operation_id: String,
+    redaction: Redaction,
+}
+
+/// Auditable operations and the queue of the task writing their records to the sink.
+#[derive(Clone)]
+pub struct AuditState {
+    operations: Arc<HashMap<OperationKey, AuditedOperation>>,
+    writer: mpsc::Sender<AuditRecord>,
+    enqueue_timeout: Duration,
+    dropped: Counter<u64>,
+    record_request_body: bool,
+    max_body_bytes: usize,
+}
+
+/// Writes queued records to `sink` until every sender is dropped.
+async fn write_records(sink: Arc<dyn AuditSink>, mut records: mpsc::Receiver<AuditRecord>) {
+    while let Some(record) = records.recv().await {
+        if let Err(e) = sink.record(&record).await {
+            tracing::error!(
+                error = %e,
+                operation_id = %record.operation_id,
+                "Failed to write audit record"
+            );
+        }
+    }
+}
+
+impl AuditState {
+    /// Returns `None` when no operation is auditable or no sink is available.
+    ///
+    /// Spawns the background task writing records to the sink, so it must be called
+    /// within a Tokio runtime.
+    #[must_use]
+    pub fn from_specs(
+        specs: &[OperationSpec],
+        sink: Option<Arc<dyn AuditSink>>,
+        cfg: &AuditConfig,
+    ) -> Option<Self> {
+        let redaction = Redaction::new(&cfg.redact);
+        let operations: HashMap<_, _> = specs
+            .iter()
+            .filter_map(|spec| {
+                let audit = spec.audit.as_ref()?;
+                let operation = AuditedOperation {
+                    operation_id: spec
+                        .operation_id
+                        .clone()
+                        .unwrap_or_else(|| format!("{} {}", spec.method, spec.path)),
+                    redaction: redaction.with(&audit.redact),
+                };
+                Some(((spec.method.clone(), spec.path.clone()), operation))
+            })
+            .collect();
+        if operations.is_empty() {
+            return None;
+        }
+        let Some(sink) = sink else {
+            tracing::warn!(
+                operations = operations.len(),
+                "Auditable operations are not recorded: no audit sink is configured"
+            );
+            return None;
+        };
+        let (writer, records) = mpsc::channel(cfg.queue_capacity.max(1));
+        tokio::spawn(write_records(sink, records));
+        let dropped = meter("api-gateway")
+            .u64_counter("api_gateway.audit.dropped_records")
+            .with_description(
+                "Audit records dropped because the queue stayed full or the writer stopped",
+            )
+            .with_unit("{record}")
+            .build();
+        Some(Self {
+            operations: Arc::new(operations),
+            writer,
+            enqueue_timeout: cfg.enqueue_timeout,
+            dropped,
+            record_request_body: cfg.record_request_body,
+            max_body_bytes: cfg.max_body_bytes,
+        })
+    }
+
+    /// Queue `record` for the writer, waiting at most `enqueue_timeout` for room.
+    async fn enqueue(&self, record: AuditRecord) {
+        match self.reserve().await {
+            Ok(permit) => permit.send(record),
+            Err(reason) => self.drop_record(&record, reason),
+        }
+    }
+
+    /// Room for one record, or why there is none within `enqueue_timeout`.
+    async fn reserve(&self) -> Result<mpsc::Permit<'_, AuditRecord>, &'static str> {
+        match tokio::time::timeout(self.enqueue_timeout, self.writer.reserve()).await {
+            Ok(Ok(permit)) => Ok(permit),
+            Ok(Err(_)) => Err("writer stopped"),
+            Err(_) => Err("queue is full"),
+        }
+    }
+
+    fn drop_record(&self, record: &AuditRecord, reason: &'static str) {
+        self.dropped.add(1, &[]);
+        tracing::error!(
+            operation_id = %record.operation_id,
+            request_id = ?record.request_id,
+            reason,
+            "Audit record dropped"
+        );
+    }
+}
+
+/// Record of an invocation that has not been queued yet.
+///
+/// If the request future is dropped before that (gateway timeout or client disconnect),
+/// the record is queued from a spawned task instead; without a response it is written
+/// as `cancelled`, since the handler may have completed its work.
+struct PendingRecord {
+    state: AuditState,
+    record: Option<AuditRecord>,
+    started: Instant,
+}
+
+impl PendingRecord {
+    async fn finish(mut self, status: StatusCode) {
+        if let Some(record) = self.record.as_mut() {
+            record.status = status.as_u16();
+            record.outcome = AuditOutcome::from_status(status);
+            record.latency_ms = latency_ms(self.started);
+        }
+        // Keep the record in the guard until there is room for it, so a request
+        // dropped while waiting still gets it written.
+        let reserved = self.state.reserve().await;
+        let Some(record) = self.record.take() else {
+            return;
+        };
+        match reserved {
+            Ok(permit) => permit.send(record),
+            Err(reason) => self.state.drop_record(&record, reason),
+        }
+    }
+}
+
+impl Drop for PendingRecord {
+    fn drop(&mut self) {
+        let Some(mut record) = self.record.take() else {
+            return;
+        };
+        if record.status == 0 {
+            record.latency_ms = latency_ms(self.started);
+        }
+        let state = self.state.clone();
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            handle.spawn(async move { state.enqueue(record).await });
+        } else {
+            state.drop_record(&record, "no runtime");
+        }
+    }
+}
+
+fn latency_ms(started: Instant) -> u64 {
+    u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Path parameters of `path` by the names used in the route `template`.
+fn path_params(template: &str, path: &str) -> BTreeMap<String, String> {
+    let mut params = BTreeMap::new();
+    let mut segments = path.trim_start_matches('/').split('/');
+    for part in template.trim_start_matches('/').split('/') {
+        let Some(name) = part.strip_prefix('{').and_then(|p| p.strip_suffix('}')) else {
+            segments.next();
+            continue;
+        };
+        if let Some(name) = name.strip_prefix('*') {
+            params.insert(
+                name.to_owned(),
+                segments.by_ref().collect::<Vec<_>>().join("/"),
+            );
+            break;
+        }
+        if let Some(value) = segments.next() {
+            params.insert(name.to_owned(), value.to_owned());
+        }
+    }
+    params
+}
+
+fn is_json(req: &Request) -> bool {
+    req.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .is_some_and(|mime| {
+            let mime = mime.trim();
+            mime == "application/json" || mime.ends_with("+json")
+        })
+}
+
+/// Response for a request body that could not be read: `413` past the gateway body limit,
+/// `400` otherwise.
+fn body_error_response(error: &axum::Error) -> Response {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(e) = source {
+        if e.is::<http_body_util::LengthLimitError>() {
+            return Problem::new(
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "Payload Too Large",
+                "Request body exceeds the configured limit",
+            )
+            .into_response();
+        }
+        source = e.source();
+    }
+    Problem::new(
+        StatusCode::BAD_REQUEST,
+        "Bad Request",
+        format!("Failed to read request body: {error}"),
+    )
+    .into_response()
+}
+
+/// Buffers a JSON request body and returns it redacted, unless it is too large or invalid.
+///
+/// A body that cannot be read yields the error response to send instead of calling the handler.
+async fn capture_body(
+    state: &AuditState,
+    redaction: &Redaction,
+    req: Request,
+) -> Result<(Request, Option<serde_json::Value>), Response> {
+    if !state.record_request_body || !is_json(&req) {
+        return Ok((req, None));
+    }
+    let (parts, body) = req.into_parts();
+    // The gateway body limit already bounds the request size
+    let body: Bytes = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::debug!(error = %e, "Failed to buffer request body for audit");
+            return Err(body_error_response(&e));
+        }
+    };
+    let captured = (body.len() <= state.max_body_bytes)
+        .then(|| serde_json::from_slice::<serde_json::Value>(&body).ok())
+        .flatten()
+        .map(|mut value| {
+            redaction.apply(&mut value);
+            value
+        });
+    Ok((Request::from_parts(parts, Body::from(body)), captured))
+}
+
+/// Writes an audit record for each invocation of an auditable operation.
+///
+/// Runs right after authentication, so the record names the caller; requests rejected
+/// by authentication itself are not recorded. A request dropped before its handler
+/// returns is recorded as `cancelled`. Records are handed to a background writer through
+/// a bounded queue: the response is not held back by the sink unless the queue is full,
+/// in which case it waits up to `enqueue_timeout` for room before the record is dropped
+/// and counted in `api_gateway.audit.dropped_records`.
+pub async fn audit_middleware(state: AuditState, req: Request, next: Next) -> Response {
+    let Some(template) = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_owned())
+    else {
+        return next.run(req).await;
+    };
+    let method = req.method().clone();
+    let Some(operation) = state.operations.get(&(method.clone(), template.clone())) else {
+        return next.run(req).await;
+    };
+
+    let started = Instant::now();
+    let timestamp = chrono::Utc::now();
+    let ctx = req.extensions().get::<SecurityContext>();
+    let subject_id = ctx.map(SecurityContext::subject_id);
+    let tenant_id = ctx.map(SecurityContext::subject_tenant_id);
+    let request_id = req
+        .extensions()
+        .get::<XRequestId>()
+        .map(|id| id.0.clone())
+        .or_else(|| {
+            req.headers()
+                .get(request_id_header())
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_owned)
+        });
+    let mut resource_ids = path_params(&template, req.uri().path());
+    operation.redaction.apply_map(&mut resource_ids);
+    let mut pending = PendingRecord {
+        state: state.clone(),
+        record: Some(AuditRecord {
+            timestamp,
+            request_id,
+            subject_id,
+            tenant_id,
+            operation_id: operation.operation_id.clone(),
+            method: method.to_string(),
+            path: template,
+            resource_ids,
+            status: 0,
+            outcome: AuditOutcome::Cancelled,
+            latency_ms: 0,
+            request: None,
+        }),
+        started,
+    };
+    let response = match capture_body(&state, &operation.redaction, req).await {
+        Ok((req, request)) => {
+            if let Some(record) = pending.record.as_mut() {
+                record.request = request;
+            }
+            next.run(req).await
+        }
+        Err(response) => response,
+    };
+    pending.finish(response.status()).await;
+    response
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_params_follow_template() {
+        let params = path_params(
+            "/users/v1/users/{id}/keys/{key_id}",
+            "/users/v1/users/42/keys/k1",
+        );
+        assert_eq!(
+            params,
+            BTreeMap::from([
+                ("id".to_owned(), "42".to_owned()),
+                ("key_id".to_owned(), "k1".to_owned()),
+            ])
+        );
+        assert!(path_params("/users/v1/users", "/users/v1/users").is_empty());
+        assert_eq!(
+            path_params("/files/v1/{*path}", "/files/v1/a/b.txt")["path"],
+            "a/b.txt"
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use anyhow::{Result, bail};
+use axum::extract::{MatchedPath, Request};
+use axum::http::{HeaderName, HeaderValue, Method, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use modkit::api::OperationSpec;
+use modkit::api::operation_builder::DeprecationSpec;
+use modkit::telemetry::metrics::{Counter, KeyValue, meter};
+use modkit_security::SecurityContext;
+
+use crate::config::DeprecationConfig;
+
+/// `Deprecation` response header (RFC 9745).
+pub const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+
+/// `Sunset` response header (RFC 8594).
+pub const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+type OperationKey = (Method, String);
+
+struct DeprecatedOperation {
+    operation_id: String,
+    headers: Vec<(HeaderName, HeaderValue)>,
+}
+
+/// Deprecated operations with the lifecycle headers their responses carry.
+#[derive(Clone)]
+pub struct DeprecationMap {
+    operations: Arc<HashMap<OperationKey, DeprecatedOperation>>,
+    usage: Counter<u64>,
+}
+
+/// IMF-fixdate, the preferred HTTP date format (RFC 9110 §5.6.7).
+fn http_date(at: DateTime<Utc>) -> String {
+    at.format("%a, %d %b %Y %H:%M:%S GMT").to_string()
+}
+
+fn lifecycle_headers(spec: &DeprecationSpec) -> Result<Vec<(HeaderName, HeaderValue)>> {
+    let mut headers = Vec::new();
+    if let Some(since) = spec.since {
+        headers.push((
+            DEPRECATION_HEADER,
+            HeaderValue::from_str(&format!("@{}", since.timestamp()))?,
+        ));
+    }
+    if let Some(sunset) = spec.sunset {
+        headers.push((SUNSET_HEADER, HeaderValue::from_str(&http_date(sunset))?));
+    }
+    for (target, rel) in [
+        (&spec.link, "deprecation"),
+        (&spec.successor, "successor-version"),
+    ] {
+        if let Some(target) = target {
+            headers.push((
+                header::LINK,
+                HeaderValue::from_str(&format!("<{target}>; rel=\"{rel}\""))?,
+            ));
+        }
+    }
+    Ok(headers)
+}
+
+impl DeprecationMap {
+    /// Returns `None` when no operation is deprecated.
+    ///
+    /// Operations whose sunset has passed are logged, or rejected when
+    /// `fail_on_past_sunset` is set.
+    ///
+    /// # Errors
+    /// Returns an error if a sunset has passed and the configuration forbids it, or if a
+    /// deprecation link cannot be sent as a header.
+    pub fn from_specs(specs: &[OperationSpec], cfg: &DeprecationConfig) -> Result<Option<Self>> {
+        let now = Utc::now();
+        let mut operations = HashMap::new();
+        let mut past_sunset = Vec::new();
+        for spec in specs {
+            let Some(deprecation) = &spec.deprecation else {
+                continue;
+            };
+            let operation_id = spec
+                .operation_id
+                .clone()
+                .unwrap_or_else(|| format!("{} {}", spec.method, spec.path));
+            if let Some(sunset) = deprecation.sunset.filter(|sunset| *sunset <= now) {
+                tracing::warn!(
+                    operation_id = %operation_id,
+                    sunset = %sunset.to_rfc3339(),
+                    "Deprecated operation is still served after its sunset"
+                );
+                past_sunset.push(operation_id.clone());
+            }
+            let headers = lifecycle_headers(deprecation).map_err(|e| {
+                anyhow::anyhow!("Invalid deprecation metadata of '{operation_id}': {e}")
+            })?;
+            operations.insert(
+                (spec.method.clone(), spec.path.clone()),
+                DeprecatedOperation {
+                    operation_id,
+                    headers,
+                },
+            );
+        }
+        if cfg.fail_on_past_sunset && !past_sunset.is_empty() {
+            past_sunset.sort();
+            bail!(
+                "Operations past their sunset date are still registered: {}",
+                past_sunset.join(", ")
+            );
+        }
+        if operations.is_empty() {
+            return Ok(None);
+        }
+
+        let usage = meter("api-gateway")
+            .u64_counter("api_gateway.deprecated_requests")
+            .with_description("Requests to deprecated operations")
+            .with_unit("{request}")
+            .build();
+        Ok(Some(Self {
+            operations: Arc::new(operations),
+            usage,
+        }))
+    }
+}
+
+/// Adds `Deprecation`, `Sunset` and `Link` headers to responses of deprecated operations
+/// and counts their use per tenant.
+pub async fn deprecation_middleware(map: DeprecationMap, req: Request, next: Next) -> Response {
+    let Some((operation, route)) = req.extensions().get::<MatchedPath>().and_then(|path| {
+        let route = path.as_str().to_owned();
+        let operation = map.operations.get(&(req.method().clone(), route.clone()))?;
+        Some((operation, route))
+    }) else {
+        return next.run(req).await;
+    };
+    let tenant_id = req
+        .extensions()
+        .get::<SecurityContext>()
+        .map(|ctx| ctx.subject_tenant_id().to_string())
+        .unwrap_or_default();
+    map.usage.add(
+        1,
+        &[
+            KeyValue::new("operation.id", operation.operation_id.clone()),
+            KeyValue::new("http.route", route),
+            KeyValue::new("tenant.id", tenant_id),
+        ],
+    );
+
+    let mut response = next.run(req).await;
+    let headers = response.headers_mut();
+    for (name, value) in &operation.headers {
+        if name == header::LINK {
+            headers.append(name, value.clone());
+        } else if !headers.contains_key(name) {
+            headers.insert(name, value.clone());
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn headers_follow_rfc_formats() {
+        let spec = DeprecationSpec {
+            since: Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()),
+            sunset: Some(Utc.with_ymd_and_hms(2027, 6, 30, 23, 59, 59).unwrap()),
+            link: Some("https://docs.example.com/v1-deprecation".to_owned()),
+            successor: Some("/users/v2/users".to_owned()),
+        };
+        let headers: Vec<_> = lifecycle_headers(&spec)
+            .unwrap()
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_str().unwrap().to_owned()))
+            .collect();
+        assert_eq!(
+            headers,
+            [
+                ("deprecation", "@1767225600"),
+                ("sunset", "Wed, 30 Jun 2027 23:59:59 GMT"),
+                (
+                    "link",
+                    "<https://docs.example.com/v1-deprecation>; rel=\"deprecation\""
+                ),
+                ("link", "</users/v2/users>; rel=\"successor-version\""),
+            ]
+            .map(|(name, value)| (name.to_owned(), value.to_owned()))
+        );
+    }
+}
@@ -0,0 +1,291 @@
+use axum::body::{Body, HttpBody};
+use axum::extract::Request;
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use modkit::api::http_cache::etag_matches;
+use modkit::api::operation_builder::HttpCacheSpec;
+use modkit::api::{OperationSpec, Problem};
+
+use crate::config::HttpCacheConfig;
+
+type OperationKey = (Method, String);
+
+/// Headers a `304 Not Modified` response carries over from the full response (RFC 9110 §15.4.5).
+const NOT_MODIFIED_HEADERS: [header::HeaderName; 6] = [
+    header::CACHE_CONTROL,
+    header::CONTENT_LOCATION,
+    header::DATE,
+    header::ETAG,
+    header::EXPIRES,
+    header::VARY,
+];
+
+/// Content codings the compression layer produces, which make strong `ETag`s encoding-specific.
+const CODINGS: [&str; 4] = ["gzip", "br", "zstd", "deflate"];
+
+/// Caching metadata of operations declaring an `ETag` or `Cache-Control`.
+#[derive(Clone)]
+pub struct HttpCacheMap {
+    operations: Arc<HashMap<OperationKey, HttpCacheSpec>>,
+    max_etag_body_bytes: usize,
+}
+
+impl HttpCacheMap {
+    /// Returns `None` when no operation declares caching metadata.
+    #[must_use]
+    pub fn from_specs(specs: &[OperationSpec], cfg: &HttpCacheConfig) -> Option<Self> {
+        let operations: HashMap<_, _> = specs
+            .iter()
+            .filter_map(|spec| {
+                let cache = spec.http_cache.clone()?;
+                Some(((spec.method.clone(), spec.path.clone()), cache))
+            })
+            .collect();
+        if operations.is_empty() {
+            return None;
+        }
+        Some(Self {
+            operations: Arc::new(operations),
+            max_etag_body_bytes: cfg.max_etag_body_bytes,
+        })
+    }
+
+    fn get(&self, method: &Method, matched_path: &str) -> Option<&HttpCacheSpec> {
+        // HEAD requests are served by the GET handler
+        let method = if method == Method::HEAD {
+            &Method::GET
+        } else {
+            method
+        };
+        self.operations
+            .get(&(method.clone(), matched_path.to_owned()))
+    }
+}
+
+/// Content-derived weak entity tag; weak because response compression changes the bytes.
+fn content_etag(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    format!("W/\"{}\"", hex::encode(&digest[..16]))
+}
+
+/// Adds the `ETag` unless the handler supplied one. Returns `None` for bodies that are
+/// streamed or too large to hash.
+async fn ensure_etag(response: Response, max_body_bytes: usize) -> (Response, Option<String>) {
+    if let Some(etag) = response
+        .headers()
+        .get(header::ETAG)
+        .and_then(|v| v.to_str().ok())
+    {
+        let etag = etag.to_owned();
+        return (response, Some(etag));
+    }
+    let hashable = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= max_body_bytes as u64);
+    if !hashable {
+        return (response, None);
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(body) = axum::body::to_bytes(body, max_body_bytes).await else {
+        return (
+            Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal Server Error",
+                "Failed to read response body",
+            )
+            .into_response(),
+            None,
+        );
+    };
+    let etag = content_etag(&body);
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        parts.headers.insert(header::ETAG, value);
+    }
+    (Response::from_parts(parts, Body::from(body)), Some(etag))
+}
+
+fn header_string(headers: &HeaderMap, name: &header::HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(ToOwned::to_owned)
+}
+
+fn not_modified(headers: &HeaderMap) -> Response {
+    let mut response = StatusCode::NOT_MODIFIED.into_response();
+    for name in NOT_MODIFIED_HEADERS {
+        if let Some(value) = headers.get(&name) {
+            response.headers_mut().insert(name, value.clone());
+        }
+    }
+    response
+}
+
+/// Adds `Cache-Control` and `ETag` to successful responses and answers conditional `GET`s.
+///
+/// `If-None-Match` (weak comparison) yields `304 Not Modified`; `If-Match` (strong comparison)
+/// yields `412 Precondition Failed`, so a body-derived weak `ETag` only satisfies `If-Match: *`.
+/// Unsafe operations check `If-Match` in the handler, which knows the current version of the
+/// resource.
+pub async fn http_cache_middleware(map: HttpCacheMap, req: Request, next: Next) -> Response {
+    let matched_path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+    let Some(spec) = map.get(req.method(), &matched_path).cloned() else {
+        return next.run(req).await;
+    };
+    let conditional = matches!(*req.method(), Method::GET | Method::HEAD);
+    let if_none_match = header_string(req.headers(), &header::IF_NONE_MATCH);
+    let if_match = header_string(req.headers(), &header::IF_MATCH);
+
+    let mut response = next.run(req).await;
+    if !response.status().is_success() {
+        return response;
+    }
+    if let Some(cache_control) = &spec.cache_control
+        && !response.headers().contains_key(header::CACHE_CONTROL)
+        && let Ok(value) = HeaderValue::from_str(cache_control)
+    {
+        response.headers_mut().insert(header::CACHE_CONTROL, value);
+    }
+    if !spec.etag || !conditional {
+        return response;
+    }
+
+    let (response, etag) = ensure_etag(response, map.max_etag_body_bytes).await;
+    let Some(etag) = etag else {
+        return response;
+    };
+    if if_match.is_some_and(|value| !etag_matches(&value, &etag, false)) {
+        return Problem::new(
+            StatusCode::PRECONDITION_FAILED,
+            "Precondition Failed",
+            "If-Match does not match the current ETag",
+        )
+        .into_response();
+    }
+    if if_none_match.is_some_and(|value| etag_matches(&value, &etag, true)) {
+        return not_modified(response.headers());
+    }
+    response
+}
+
+/// Strong `"v"` with the content coding appended (`"v-gzip"`); `None` for weak tags.
+fn with_coding(etag: &str, coding: &str) -> Option<String> {
+    let opaque = etag.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some(format!("\"{opaque}-{coding}\""))
+}
+
+/// Removes content coding suffixes from the strong tags of an `If-Match`/`If-None-Match`
+/// value; also returns the coding removed, if any.
+fn strip_codings(value: &str) -> (String, Option<&'static str>) {
+    let mut removed = None;
+    let tags: Vec<String> = value
+        .split(',')
+        .map(|tag| {
+            let tag = tag.trim();
+            for coding in CODINGS {
+                if let Some(opaque) = tag
+                    .strip_prefix('"')
+                    .and_then(|t| t.strip_suffix('"'))
+                    .and_then(|t| t.strip_suffix(coding))
+                    .and_then(|t| t.strip_suffix('-'))
+                {
+                    removed = Some(coding);
+                    return format!("\"{opaque}\"");
+                }
+            }
+            tag.to_owned()
+        })
+        .collect();
+    (tags.join(", "), removed)
+}
+
+/// Makes strong `ETag`s of compressed responses encoding-specific (RFC 9110 §8.8.3).
+///
+/// Runs outside the compression layer: a strong tag on a compressed response gets the
+/// coding appended (`"7"` becomes `"7-gzip"`), and the suffix is removed again from
+/// `If-Match`/`If-None-Match`, so handlers and [`http_cache_middleware`] only see their own
+/// tags. Weak tags are left alone; they survive compression.
+pub async fn encoding_etag_middleware(mut req: Request, next: Next) -> Response {
+    let mut requested = None;
+    for name in [header::IF_MATCH, header::IF_NONE_MATCH] {
+        let Some(value) = header_string(req.headers(), &name) else {
+            continue;
+        };
+        let (value, coding) = strip_codings(&value);
+        if coding.is_some()
+            && let Ok(value) = HeaderValue::from_str(&value)
+        {
+            requested = requested.or(coding);
+            req.headers_mut().insert(name, value);
+        }
+    }
+
+    let mut response = next.run(req).await;
+    // A `304` has no body to compress; it names the representation the client holds
+    let coding = match header_string(response.headers(), &header::CONTENT_ENCODING) {
+        Some(coding) => CODINGS.into_iter().find(|c| *c == coding),
+        None if response.status() == StatusCode::NOT_MODIFIED => requested,
+        None => None,
+    };
+    if let Some(coding) = coding
+        && let Some(etag) = header_string(response.headers(), &header::ETAG)
+            .and_then(|etag| with_coding(&etag, coding))
+        && let Ok(value) = HeaderValue::from_str(&etag)
+    {
+        response.headers_mut().insert(header::ETAG, value);
+    }
+    response
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coding_suffix_round_trips_for_strong_tags() {
+        assert_eq!(with_coding("\"7\"", "gzip").as_deref(), Some("\"7-gzip\""));
+        assert_eq!(with_coding("W/\"7\"", "gzip"), None);
+        assert_eq!(
+            strip_codings("\"7-gzip\", W/\"x\", \"8\""),
+            ("\"7\", W/\"x\", \"8\"".to_owned(), Some("gzip"))
+        );
+        assert_eq!(strip_codings("*"), ("*".to_owned(), None));
+    }
+
+    #[test]
+    fn content_etag_is_weak_and_stable() {
+        let etag = content_etag(b"{}");
+        assert!(etag.starts_with("W/\""));
+        assert_eq!(etag, content_etag(b"{}"));
+        assert_ne!(etag, content_etag(b"[]"));
+    }
+
+    #[test]
+    fn not_modified_keeps_validators_only() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ETAG, HeaderValue::from_static("\"1\""));
+        headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        headers.insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+
+        let response = not_modified(&headers);
+
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG).unwrap(), "\"1\"");
+        assert!(response.headers().get(header::CONTENT_TYPE).is_none());
+    }
+}
@@ -0,0 +1,116 @@
+use axum::extract::{MatchedPath, Request};
+use axum::http::Method;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::time::Instant;
+
+use modkit::telemetry::metrics::{
+    DURATION_BOUNDARIES, HistogramInstrument, KeyValue, UpDownCounter, meter,
+};
+
+/// HTTP server instruments following the OpenTelemetry semantic conventions.
+#[derive(Clone)]
+pub struct HttpServerMetrics {
+    duration: HistogramInstrument<f64>,
+    active: UpDownCounter<i64>,
+}
+
+impl HttpServerMetrics {
+    /// Instruments are created on the global meter provider; they are no-ops if metrics are
+    /// not enabled.
+    #[must_use]
+    pub fn new() -> Self {
+        let meter = meter("api-gateway");
+        Self {
+            duration: meter
+                .f64_histogram("http.server.request.duration")
+                .with_description("Duration of inbound HTTP requests")
+                .with_unit("s")
+                .with_boundaries(DURATION_BOUNDARIES.to_vec())
+                .build(),
+            active: meter
+                .i64_up_down_counter("http.server.active_requests")
+                .with_description("Number of in-flight inbound HTTP requests")
+                .with_unit("{request}")
+                .build(),
+        }
+    }
+}
+
+impl Default for HttpServerMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Decrements `http.server.active_requests` when dropped, so requests whose future is
+/// cancelled (client disconnect, panic) are not counted as in flight forever.
+struct ActiveRequest {
+    active: UpDownCounter<i64>,
+    method: KeyValue,
+}
+
+impl ActiveRequest {
+    fn start(active: UpDownCounter<i64>, method: KeyValue) -> Self {
+        active.add(1, std::slice::from_ref(&method));
+        Self { active, method }
+    }
+}
+
+impl Drop for ActiveRequest {
+    fn drop(&mut self) {
+        self.active.add(-1, std::slice::from_ref(&self.method));
+    }
+}
+
+/// Method label; non-standard methods are reported as `_OTHER` to bound the cardinality.
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::HEAD => "HEAD",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::DELETE => "DELETE",
+        Method::CONNECT => "CONNECT",
+        Method::OPTIONS => "OPTIONS",
+        Method::TRACE => "TRACE",
+        Method::PATCH => "PATCH",
+        _ => "_OTHER",
+    }
+}
+
+/// Records request duration and in-flight requests per method and matched route.
+///
+/// The route template (`/users/{id}`) is used rather than the request path to keep the
+/// label cardinality bounded; unmatched requests carry no `http.route`.
+pub async fn http_metrics_middleware(
+    metrics: HttpServerMetrics,
+    req: Request,
+    next: Next,
+) -> Response {
+    let method = KeyValue::new("http.request.method", method_label(req.method()));
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| KeyValue::new("http.route", p.as_str().to_owned()));
+
+    let active = ActiveRequest::start(metrics.active.clone(), method.clone());
+    let started = Instant::now();
+    let response = next.run(req).await;
+    drop(active);
+
+    let status = response.status();
+    let mut attributes = vec![
+        method,
+        KeyValue::new("http.response.status_code", i64::from(status.as_u16())),
+    ];
+    attributes.extend(route);
+    if status.is_server_error() {
+        attributes.push(KeyValue::new("error.type", status.as_str().to_owned()));
+    }
+    metrics
+        .duration
+        .record(started.elapsed().as_secs_f64(), &attributes);
+
+    response
+}
@@ -0,0 +1,325 @@
+use axum::body::{Body, Bytes, HttpBody};
+use axum::extract::Request;
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use modkit::api::{OperationSpec, Problem};
+use modkit_security::SecurityContext;
+
+use crate::config::IdempotencyConfig;
+use crate::idempotency::{IdempotencyBegin, IdempotencyStore, StoredResponse};
+
+/// Request header carrying the client-chosen idempotency key.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set on replayed responses.
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LEN: usize = 255;
+
+type OperationKey = (Method, String);
+
+/// Idempotency-key capable operations and the store their responses are recorded in.
+#[derive(Clone)]
+pub struct IdempotencyState {
+    operations: Arc<HashSet<OperationKey>>,
+    store: Arc<dyn IdempotencyStore>,
+    /// How long an unfinished request holds its key
+    lease: Duration,
+    ttl: Duration,
+    max_response_bytes: usize,
+}
+
+impl IdempotencyState {
+    /// Returns `None` when no operation accepts an idempotency key.
+    ///
+    /// In-flight claims are held for `lease`, which should cover the request timeout.
+    #[must_use]
+    pub fn from_specs(
+        specs: &[OperationSpec],
+        store: Arc<dyn IdempotencyStore>,
+        cfg: &IdempotencyConfig,
+        lease: Duration,
+    ) -> Option<Self> {
+        let operations: HashSet<_> = specs
+            .iter()
+            .filter(|spec| spec.idempotency_key)
+            .map(|spec| (spec.method.clone(), spec.path.clone()))
+            .collect();
+        if operations.is_empty() {
+            return None;
+        }
+        Some(Self {
+            operations: Arc::new(operations),
+            store,
+            lease,
+            ttl: cfg.ttl,
+            max_response_bytes: cfg.max_response_bytes,
+        })
+    }
+}
+
+/// Releases the claim on a key unless the response was recorded, including when the
+/// request future is dropped (client disconnect, timeout).
+struct ClaimGuard {
+    store: Arc<dyn IdempotencyStore>,
+    key: Option<String>,
+}
+
+impl ClaimGuard {
+    fn disarm(&mut self) -> Option<String> {
+        self.key.take()
+    }
+}
+
+impl Drop for ClaimGuard {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let store = Arc::clone(&self.store);
+            tokio::spawn(async move {
+                if let Err(e) = store.release(&key).await {
+                    tracing::warn!(error = %e, "Failed to release idempotency key");
+                }
+            });
+        }
+    }
+}
+
+fn is_valid_key(key: &str) -> bool {
+    (1..=MAX_KEY_LEN).contains(&key.len()) && key.bytes().all(|b| b.is_ascii_graphic())
+}
+
+/// Scopes the client key to the caller and the target (path and query string).
+fn store_key(ctx: Option<&SecurityContext>, method: &Method, target: &str, key: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [
+        ctx.map(|c| c.subject_tenant_id().to_string())
+            .unwrap_or_default(),
+        ctx.map(|c| c.subject_id().to_string()).unwrap_or_default(),
+        method.to_string(),
+        target.to_owned(),
+        key.to_owned(),
+    ] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hex::encode(hasher.finalize())
+}
+
+fn replay(response: StoredResponse) -> Response {
+    let mut builder = Response::builder().status(response.status);
+    for (name, value) in &response.headers {
+        builder = builder.header(name, value);
+    }
+    match builder
+        .header(IDEMPOTENT_REPLAYED_HEADER, "true")
+        .body(Body::from(response.body))
+    {
+        Ok(response) => response,
+        Err(e) => {
+            tracing::error!(error = %e, "Invalid stored idempotent response");
+            Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal Server Error",
+                "Stored response for this Idempotency-Key cannot be replayed",
+            )
+            .into_response()
+        }
+    }
+}
+
+enum RequestKey {
+    /// Not an idempotency-key capable operation, or no key sent
+    Absent,
+    Invalid,
+    /// Store key scoped to the caller and route
+    Scoped(String),
+}
+
+fn request_key(state: &IdempotencyState, req: &Request) -> RequestKey {
+    let matched_path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+    if !state
+        .operations
+        .contains(&(req.method().clone(), matched_path))
+    {
+        return RequestKey::Absent;
+    }
+    let Some(key) = req.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return RequestKey::Absent;
+    };
+    let Some(key) = key.to_str().ok().filter(|k| is_valid_key(k)) else {
+        return RequestKey::Invalid;
+    };
+    RequestKey::Scoped(store_key(
+        req.extensions().get::<SecurityContext>(),
+        req.method(),
+        req.uri()
+            .path_and_query()
+            .map_or_else(|| req.uri().path(), |target| target.as_str()),
+        key,
+    ))
+}
+
+/// Claims `key`, or returns the response for a duplicate request.
+async fn begin(state: &IdempotencyState, key: &str, fingerprint: &str) -> Option<Response> {
+    match state.store.begin(key, fingerprint, state.lease).await {
+        Ok(IdempotencyBegin::Started) => None,
+        Ok(IdempotencyBegin::InFlight {
+            fingerprint: stored,
+        }) if stored == fingerprint => Some(
+            Problem::new(
+                StatusCode::CONFLICT,
+                "Conflict",
+                "A request with this Idempotency-Key is still being processed",
+            )
+            .into_response(),
+        ),
+        Ok(IdempotencyBegin::Completed {
+            fingerprint: stored,
+            response,
+        }) if stored == fingerprint => Some(replay(response)),
+        Ok(IdempotencyBegin::InFlight { .. } | IdempotencyBegin::Completed { .. }) => Some(
+            Problem::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "Unprocessable Entity",
+                "Idempotency-Key was already used with a different request body",
+            )
+            .into_response(),
+        ),
+        Err(e) => {
+            tracing::error!(error = %e, "Idempotency store unavailable");
+            Some(
+                Problem::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Service Unavailable",
+                    "Idempotency-Key cannot be processed right now",
+                )
+                .into_response(),
+            )
+        }
+    }
+}
+
+/// Records `response` for the claimed key unless it is a server error or too large to buffer.
+async fn record(state: &IdempotencyState, mut guard: ClaimGuard, response: Response) -> Response {
+    let recordable = !response.status().is_server_error()
+        && response
+            .body()
+            .size_hint()
+            .upper()
+            .is_some_and(|len| len <= state.max_response_bytes as u64);
+    if !recordable {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body: Bytes = match axum::body::to_bytes(body, state.max_response_bytes).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to buffer response for idempotency");
+            return Problem::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Internal Server Error",
+                "Failed to read response body",
+            )
+            .into_response();
+        }
+    };
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        headers: parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((name.as_str().to_owned(), value.to_str().ok()?.to_owned()))
+            })
+            .collect(),
+        body: body.to_vec(),
+    };
+    if let Some(key) = guard.disarm()
+        && let Err(e) = state.store.complete(&key, &stored, state.ttl).await
+    {
+        tracing::warn!(error = %e, "Failed to record idempotent response");
+        guard.key = Some(key);
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+/// Records the first response per idempotency key and replays it on retries.
+///
+/// Applies to operations declared with `with_idempotency_key()` when the request carries
+/// an `Idempotency-Key` header. Server errors and responses that are streamed or larger
+/// than `max_response_bytes` are not recorded; the key is released so the client can retry.
+pub async fn idempotency_middleware(state: IdempotencyState, req: Request, next: Next) -> Response {
+    let key = match request_key(&state, &req) {
+        RequestKey::Scoped(key) => key,
+        RequestKey::Absent => return next.run(req).await,
+        RequestKey::Invalid => {
+            return Problem::new(
+                StatusCode::BAD_REQUEST,
+                "Bad Request",
+                format!("Idempotency-Key must be 1 to {MAX_KEY_LEN} visible ASCII characters"),
+            )
+            .into_response();
+        }
+    };
+
+    let (parts, body) = req.into_parts();
+    let body = match axum::body::to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            return Problem::new(
+                StatusCode::BAD_REQUEST,
+                "Bad Request",
+                format!("Failed to read request body: {e}"),
+            )
+            .into_response();
+        }
+    };
+    let fingerprint = hex::encode(Sha256::digest(&body));
+    if let Some(response) = begin(&state, &key, &fingerprint).await {
+        return response;
+    }
+
+    let guard = ClaimGuard {
+        store: Arc::clone(&state.store),
+        key: Some(key),
+    };
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    record(&state, guard, response).await
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_validation() {
+        assert!(is_valid_key("8e03978e-40d5-43e8-bc93-6894a57f9324"));
+        assert!(!is_valid_key(""));
+        assert!(!is_valid_key("with space"));
+        assert!(!is_valid_key(&"k".repeat(MAX_KEY_LEN + 1)));
+    }
+
+    #[test]
+    fn store_key_is_scoped_to_route() {
+        let a = store_key(None, &Method::POST, "/users/v1/users", "k");
+        assert_eq!(a, store_key(None, &Method::POST, "/users/v1/users", "k"));
+        assert_ne!(a, store_key(None, &Method::PUT, "/users/v1/users", "k"));
+        assert_ne!(a, store_key(None, &Method::POST, "/users/v1/groups", "k"));
+        assert_ne!(
+            a,
+            store_key(None, &Method::POST, "/users/v1/users?dry_run=true", "k")
+        );
+    }
+}
@@ -5,10 +5,14 @@ use axum::response::{IntoResponse, Response};
 use dashmap::DashMap;
 use http::Method;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
 
+use license_resolver_sdk::{
+    BASE_FEATURE, EnabledFeatures, LicenseResolverClient, LicenseResolverError,
+};
 use modkit::api::{OperationSpec, Problem};
-
-const BASE_FEATURE: &str = "gts.x.core.lic.feat.v1~x.core.global.base.v1";
+use modkit_security::SecurityContext;
 
 type LicenseKey = (Method, String);
 
@@ -43,8 +47,79 @@ impl LicenseRequirementMap {
     }
 }
 
+struct CachedFeatures {
+    features: EnabledFeatures,
+    fetched_at: Instant,
+}
+
+/// Enabled features from the license resolver, cached per scope for `ttl`.
+///
+/// The `None` key holds the global features, `Some(tenant)` the tenant-scoped ones.
+#[derive(Clone)]
+pub struct LicenseFeatureCache {
+    client: Arc<dyn LicenseResolverClient>,
+    ttl: Duration,
+    entries: Arc<DashMap<Option<Uuid>, CachedFeatures>>,
+}
+
+impl LicenseFeatureCache {
+    #[must_use]
+    pub fn new(client: Arc<dyn LicenseResolverClient>, ttl: Duration) -> Self {
+        Self {
+            client,
+            ttl,
+            entries: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Global features plus the features of the caller's tenant.
+    ///
+    /// # Errors
+    /// Returns the resolver error if a scope is not cached and cannot be fetched.
+    pub async fn enabled_for(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        let mut features = self.get_or_fetch(ctx, None).await?;
+        let tenant_id = ctx.subject_tenant_id();
+        if !tenant_id.is_nil() {
+            features.extend(self.get_or_fetch(ctx, Some(tenant_id)).await?);
+        }
+        Ok(features)
+    }
+
+    async fn get_or_fetch(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Option<Uuid>,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        if let Some(cached) = self.entries.get(&tenant_id)
+            && cached.fetched_at.elapsed() < self.ttl
+        {
+            return Ok(cached.features.clone());
+        }
+
+        let features = match tenant_id {
+            None => self.client.get_global_features(ctx).await?,
+            Some(tenant_id) => self.client.get_tenant_features(ctx, tenant_id).await?,
+        };
+        self.entries.insert(
+            tenant_id,
+            CachedFeatures {
+                features: features.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+        Ok(features)
+    }
+}
+
+/// Rejects requests to operations whose license requirements are not enabled for the caller.
+///
+/// Without a license resolver (or a plugin behind it) only [`BASE_FEATURE`] is enabled.
 pub async fn license_validation_middleware(
     map: LicenseRequirementMap,
+    features: Option<LicenseFeatureCache>,
     req: Request,
     next: Next,
 ) -> Response {
@@ -58,16 +133,39 @@ pub async fn license_validation_middleware(
         return next.run(req).await;
     };
 
-    // TODO: this is a stub implementation
-    // We need first to implement plugin and get its client from client_hub
-    // Plugin should provide an interface to get a list of global features (features that are not scoped to particular resource)
-    if required.iter().any(|r| r != BASE_FEATURE) {
+    let ctx = req
+        .extensions()
+        .get::<SecurityContext>()
+        .cloned()
+        .unwrap_or_else(SecurityContext::anonymous);
+    let enabled = match features {
+        None => Ok(EnabledFeatures::new([BASE_FEATURE])),
+        Some(features) => match features.enabled_for(&ctx).await {
+            Err(LicenseResolverError::NoPluginAvailable) => {
+                Ok(EnabledFeatures::new([BASE_FEATURE]))
+            }
+            other => other,
+        },
+    };
+    let enabled = match enabled {
+        Ok(enabled) => enabled,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to resolve license features");
+            return Problem::new(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Service Unavailable",
+                "License features could not be resolved",
+            )
+            .into_response();
+        }
+    };
+
+    let missing: Vec<&String> = required.iter().filter(|r| !enabled.contains(r)).collect();
+    if !missing.is_empty() {
         return Problem::new(
             StatusCode::FORBIDDEN,
             "Forbidden",
-            format!(
-                "Endpoint requires unsupported license features '{required:?}'; only '{BASE_FEATURE}' is allowed",
-            ),
+            format!("Endpoint requires license features that are not enabled: '{missing:?}'"),
         )
         .into_response();
     }
@@ -159,6 +159,11 @@ mod tests {
             authenticated: false,
             is_public: false,
             license_requirement: None,
+            required_scopes: Vec::new(),
+            idempotency_key: false,
+            http_cache: None,
+            audit: None,
+            deprecation: None,
             rate_limit: None,
             allowed_request_content_types: Some(vec!["multipart/form-data", "application/pdf"]),
             vendor_extensions: VendorExtensions::default(),
@@ -1,5 +1,11 @@
+pub mod audit;
 pub mod auth;
+pub mod deprecation;
+pub mod http_cache;
+pub mod http_metrics;
+pub mod idempotency;
 pub mod license_validation;
 pub mod mime_validation;
 pub mod rate_limit;
 pub mod request_id;
+pub mod scope_enforcement;
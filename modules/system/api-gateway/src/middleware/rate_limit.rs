@@ -1,90 +1,266 @@
-use crate::config::ApiGatewayConfig;
-use anyhow::{Context, Result, anyhow};
-use axum::http::{HeaderValue, Method, StatusCode, header};
+use crate::config::{ApiGatewayConfig, RateLimitKey};
+use anyhow::{Context, Result, anyhow, bail};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
 use axum::{
     extract::Request,
     middleware::Next,
     response::{IntoResponse, Response},
 };
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
 use governor::clock::Clock;
 use governor::middleware::StateInformationMiddleware;
-use governor::{DefaultDirectRateLimiter, Quota, RateLimiter};
+use governor::{DefaultKeyedRateLimiter, Quota, RateLimiter};
+use modkit_security::SecurityContext;
+use parking_lot::Mutex;
 use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Semaphore;
+use uuid::Uuid;
 
-type RateLimitKey = (Method, String);
-type BucketMap = Arc<HashMap<RateLimitKey, Arc<BucketMapEntry>>>;
-type InflightMap = Arc<HashMap<RateLimitKey, Arc<Semaphore>>>;
+type RouteKey = (Method, String);
+type RouteMap = Arc<HashMap<RouteKey, Arc<RouteLimits>>>;
+type InflightMap = Arc<HashMap<RouteKey, Arc<Semaphore>>>;
 
-#[derive(Default, Clone)]
-pub struct RateLimiterMap {
-    buckets: BucketMap,
-    inflight: InflightMap,
+const RATE_LIMIT: &str = "RateLimit";
+const RATE_LIMIT_POLICY: &str = "RateLimit-Policy";
+
+/// Peer address of the connection, attached to requests as `ConnectInfo<ClientAddr>` by
+/// both the plain and the TLS listener.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientAddr(pub SocketAddr);
+
+impl
+    axum::extract::connect_info::Connected<axum::serve::IncomingStream<'_, tokio::net::TcpListener>>
+    for ClientAddr
+{
+    fn connect_info(stream: axum::serve::IncomingStream<'_, tokio::net::TcpListener>) -> Self {
+        Self(*stream.remote_addr())
+    }
 }
 
-struct BucketMapEntry {
-    bucket: DefaultDirectRateLimiter<StateInformationMiddleware>,
-    policy: HeaderValue,
-    burst: HeaderValue,
+/// Caller part of a bucket key; fields not covered by the configured [`RateLimitKey`] stay `None`.
+///
+/// In keyed modes, anonymous callers are keyed by their client IP so that they do not
+/// share (and exhaust) a single bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct CallerKey {
+    tenant: Option<Uuid>,
+    subject: Option<Uuid>,
+    client: Option<IpAddr>,
 }
 
-impl BucketMapEntry {
-    pub fn new(rps: u32, burst: u32) -> Result<Self> {
-        let bucket = RateLimiter::direct(
+impl CallerKey {
+    fn new(kind: RateLimitKey, ctx: Option<&SecurityContext>, client: Option<IpAddr>) -> Self {
+        let non_nil = |id: Uuid| (!id.is_nil()).then_some(id);
+        let tenant = ctx
+            .filter(|_| kind.includes_tenant())
+            .and_then(|c| non_nil(c.subject_tenant_id()));
+        let subject = ctx
+            .filter(|_| kind.includes_subject())
+            .and_then(|c| non_nil(c.subject_id()));
+        let anonymous = kind != RateLimitKey::Route && tenant.is_none() && subject.is_none();
+        Self {
+            tenant,
+            subject,
+            client: client.filter(|_| anonymous),
+        }
+    }
+
+    /// Partition key reported in the `RateLimit` header, e.g. `tenant=<uuid>;subject=<uuid>`.
+    fn partition(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            ("tenant", self.tenant.map(|id| id.to_string())),
+            ("subject", self.subject.map(|id| id.to_string())),
+            ("client", self.client.map(|ip| ip.to_string())),
+        ]
+        .into_iter()
+        .filter_map(|(name, id)| id.map(|id| format!("{name}={id}")))
+        .collect();
+        (!parts.is_empty()).then(|| parts.join(";"))
+    }
+}
+
+struct Bucket {
+    limiter: DefaultKeyedRateLimiter<CallerKey, StateInformationMiddleware>,
+    rps: u32,
+    burst: u32,
+    window_secs: u32,
+}
+
+impl Bucket {
+    fn new(rps: u32, burst: u32) -> Result<Self> {
+        let quota =
             Quota::per_second(NonZeroU32::new(rps).with_context(|| anyhow!("rps is zero"))?)
-                .allow_burst(NonZeroU32::new(burst).with_context(|| anyhow!("burst is zero"))?),
-        )
-        .with_middleware::<StateInformationMiddleware>();
-        let policy = HeaderValue::from_str(&format!("\"burst\";q={burst};w={rps}"))
-            .context("Failed to create rate limit policy")?;
+                .allow_burst(NonZeroU32::new(burst).with_context(|| anyhow!("burst is zero"))?);
         Ok(Self {
-            bucket,
-            policy,
-            burst: burst.into(),
+            limiter: RateLimiter::keyed(quota).with_middleware::<StateInformationMiddleware>(),
+            rps,
+            burst,
+            window_secs: burst.div_ceil(rps),
         })
     }
+
+    /// Seconds until `used` tokens are replenished.
+    fn reset_secs(&self, used: u32) -> u32 {
+        used.div_ceil(self.rps)
+    }
+}
+
+/// Buckets of a single route: the route limits plus per-tenant overrides.
+///
+/// An override bucket replaces the route's rps and burst for its tenant, whether higher or
+/// lower; the route's in-flight limit still applies.
+struct RouteLimits {
+    default: Bucket,
+    tenants: HashMap<Uuid, Bucket>,
+}
+
+impl RouteLimits {
+    fn bucket_for(&self, caller: &CallerKey) -> &Bucket {
+        caller
+            .tenant
+            .and_then(|tenant| self.tenants.get(&tenant))
+            .unwrap_or(&self.default)
+    }
+
+    fn buckets(&self) -> impl Iterator<Item = &Bucket> {
+        std::iter::once(&self.default).chain(self.tenants.values())
+    }
+}
+
+/// Drops the state of callers whose buckets are full again at most once per `interval`.
+struct IdleEviction {
+    interval: Duration,
+    last_run: Mutex<Instant>,
+}
+
+impl IdleEviction {
+    fn run_if_due(&self, routes: &RouteMap) {
+        // Another request is already evicting
+        let Some(mut last_run) = self.last_run.try_lock() else {
+            return;
+        };
+        if last_run.elapsed() < self.interval {
+            return;
+        }
+        *last_run = Instant::now();
+        drop(last_run);
+
+        for bucket in routes.values().flat_map(|r| r.buckets()) {
+            bucket.limiter.retain_recent();
+            bucket.limiter.shrink_to_fit();
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimiterMap {
+    key: RateLimitKey,
+    routes: RouteMap,
+    inflight: InflightMap,
+    eviction: Arc<IdleEviction>,
 }
 
 impl RateLimiterMap {
     /// # Errors
-    /// Returns an error if any rate limit spec is 0.
+    /// Returns an error if any rate limit spec is 0, or if tenant overrides are configured
+    /// while the rate limit key does not include the tenant.
     pub fn from_specs(
         specs: &Vec<modkit::api::OperationSpec>,
         cfg: &ApiGatewayConfig,
     ) -> Result<Self> {
-        let mut buckets = HashMap::new();
+        let defaults = &cfg.defaults.rate_limit;
+        if !defaults.tenant_overrides.is_empty() && !defaults.key.includes_tenant() {
+            bail!(
+                "rate limit tenant_overrides require a key including the tenant, got '{}'",
+                defaults.key.as_str()
+            );
+        }
+
+        let mut routes = HashMap::new();
         let mut inflight = HashMap::new();
-        // TODO: Add support for per-route rate limiting
         for spec in specs {
-            let (rps, burst, max_in_flight) = spec.rate_limit.as_ref().map_or(
-                (
-                    cfg.defaults.rate_limit.rps,
-                    cfg.defaults.rate_limit.burst,
-                    cfg.defaults.rate_limit.in_flight,
-                ),
-                |r| (r.rps, r.burst, r.in_flight),
-            );
+            let (rps, burst, max_in_flight) = spec
+                .rate_limit
+                .as_ref()
+                .map_or((defaults.rps, defaults.burst, defaults.in_flight), |r| {
+                    (r.rps, r.burst, r.in_flight)
+                });
+            let mut tenants = HashMap::new();
+            for o in &defaults.tenant_overrides {
+                let bucket = Bucket::new(o.rps, o.burst).with_context(|| {
+                    anyhow!("RateLimit override for tenant {} invalid", o.tenant_id)
+                })?;
+                tenants.insert(o.tenant_id, bucket);
+            }
+            let limits = RouteLimits {
+                default: Bucket::new(rps, burst)
+                    .with_context(|| anyhow!("RateLimit spec invalid {spec:?} invalid"))?,
+                tenants,
+            };
             let key = (spec.method.clone(), spec.path.clone());
-            buckets.insert(
-                key.clone(),
-                Arc::new(
-                    BucketMapEntry::new(rps, burst)
-                        .with_context(|| anyhow!("RateLimit spec invalid {spec:?} invalid"))?,
-                ),
-            );
+            routes.insert(key.clone(), Arc::new(limits));
             inflight.insert(key, Arc::new(Semaphore::new(max_in_flight as usize)));
         }
         Ok(Self {
-            buckets: Arc::new(buckets),
+            key: defaults.key,
+            routes: Arc::new(routes),
             inflight: Arc::new(inflight),
+            eviction: Arc::new(IdleEviction {
+                interval: Duration::from_secs(defaults.idle_eviction_seconds),
+                last_run: Mutex::new(Instant::now()),
+            }),
         })
     }
+
+    /// Whether buckets are keyed by the caller, i.e. the limiter needs the `SecurityContext`.
+    #[must_use]
+    pub fn is_keyed(&self) -> bool {
+        self.key != RateLimitKey::Route
+    }
+}
+
+fn insert_header(headers: &mut HeaderMap, name: &'static str, value: &str) {
+    match HeaderValue::from_str(value) {
+        Ok(value) => {
+            headers.insert(name, value);
+        }
+        Err(e) => tracing::warn!(error = %e, header = name, "Invalid rate limit header value"),
+    }
+}
+
+/// Sets `RateLimit-Policy` and `RateLimit` on the response as described by the IETF
+/// `RateLimit` header fields draft, with the caller partition as the `pk` parameter.
+fn set_rate_limit_headers(
+    headers: &mut HeaderMap,
+    kind: RateLimitKey,
+    bucket: &Bucket,
+    caller: &CallerKey,
+    remaining: u32,
+    reset_secs: u32,
+) {
+    let name = kind.as_str();
+    insert_header(
+        headers,
+        RATE_LIMIT_POLICY,
+        &format!("\"{name}\";q={};w={}", bucket.burst, bucket.window_secs),
+    );
+    let limit = match caller.partition() {
+        Some(partition) => format!(
+            "\"{name}\";r={remaining};t={reset_secs};pk=:{}:",
+            BASE64.encode(partition)
+        ),
+        None => format!("\"{name}\";r={remaining};t={reset_secs}"),
+    };
+    insert_header(headers, RATE_LIMIT, &limit);
 }
 
 // TODO: Use tower-governor instead of own implementation (upd: https://github.com/benwis/tower-governor/issues/59 )
-pub async fn rate_limit_middleware(map: RateLimiterMap, mut req: Request, next: Next) -> Response {
+pub async fn rate_limit_middleware(map: RateLimiterMap, req: Request, next: Next) -> Response {
     let method = req.method().clone();
     // Use MatchedPath extension (set by Axum router) for accurate route matching
     let path = req
@@ -93,31 +269,49 @@ pub async fn rate_limit_middleware(map: RateLimiterMap, mut req: Request, next:
         .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
     let key = (method, path);
 
-    if let Some(bucker_map_entry) = map.buckets.get(&key) {
-        let headers = req.headers_mut();
-        headers.insert("RateLimit-Policy", bucker_map_entry.policy.clone());
-        match bucker_map_entry.bucket.check() {
-            Ok(state) => {
-                headers.insert("RateLimit-Limit", bucker_map_entry.burst.clone());
-                headers.insert(
-                    "RateLimit-Limit-Remaining",
-                    state.remaining_burst_capacity().into(),
-                );
-                headers.insert("X-RateLimit-Limit", bucker_map_entry.burst.clone());
-                headers.insert(
-                    "X-RateLimit-Remaining",
-                    state.remaining_burst_capacity().into(),
-                );
-            }
-            Err(not_until) => {
-                let wait = not_until.wait_time_from(bucker_map_entry.bucket.clock().now());
-                headers.insert(header::RETRY_AFTER, wait.as_secs().into());
-                return StatusCode::TOO_MANY_REQUESTS.into_response();
-            }
+    let Some(limits) = map.routes.get(&key) else {
+        return run_in_flight(&map, &key, req, next).await;
+    };
+
+    let client = req
+        .extensions()
+        .get::<axum::extract::ConnectInfo<ClientAddr>>()
+        .map(|info| info.0.0.ip());
+    let caller = CallerKey::new(map.key, req.extensions().get::<SecurityContext>(), client);
+    let bucket = limits.bucket_for(&caller);
+    let outcome = bucket.limiter.check_key(&caller);
+    map.eviction.run_if_due(&map.routes);
+
+    match outcome {
+        Ok(state) => {
+            let remaining = state.remaining_burst_capacity();
+            let mut resp = run_in_flight(&map, &key, req, next).await;
+            set_rate_limit_headers(
+                resp.headers_mut(),
+                map.key,
+                bucket,
+                &caller,
+                remaining,
+                bucket.reset_secs(bucket.burst - remaining),
+            );
+            resp
+        }
+        Err(not_until) => {
+            let wait = not_until.wait_time_from(bucket.limiter.clock().now());
+            let wait_secs = u32::try_from(wait.as_secs())
+                .unwrap_or(u32::MAX)
+                .saturating_add(u32::from(wait.subsec_nanos() > 0));
+            let mut resp = StatusCode::TOO_MANY_REQUESTS.into_response();
+            set_rate_limit_headers(resp.headers_mut(), map.key, bucket, &caller, 0, wait_secs);
+            resp.headers_mut()
+                .insert(header::RETRY_AFTER, wait_secs.into());
+            resp
         }
     }
+}
 
-    if let Some(sem) = map.inflight.get(&key) {
+async fn run_in_flight(map: &RateLimiterMap, key: &RouteKey, req: Request, next: Next) -> Response {
+    if let Some(sem) = map.inflight.get(key) {
         match sem.clone().try_acquire_owned() {
             Ok(_permit) => {
                 // Allow request; permit is dropped when response future completes
@@ -131,3 +325,107 @@ pub async fn rate_limit_middleware(map: RateLimiterMap, mut req: Request, next:
 
     next.run(req).await
 }
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+
+    fn ctx(tenant: Uuid, subject: Uuid) -> SecurityContext {
+        SecurityContext::builder()
+            .subject_tenant_id(tenant)
+            .subject_id(subject)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn caller_key_follows_configured_kind() {
+        let (tenant, subject) = (Uuid::new_v4(), Uuid::new_v4());
+        let ctx = ctx(tenant, subject);
+
+        let client: IpAddr = "192.0.2.7".parse().unwrap();
+
+        let route = CallerKey::new(RateLimitKey::Route, Some(&ctx), Some(client));
+        assert_eq!(route.partition(), None);
+
+        let both = CallerKey::new(RateLimitKey::TenantSubject, Some(&ctx), Some(client));
+        assert_eq!(
+            both.partition().as_deref(),
+            Some(format!("tenant={tenant};subject={subject}").as_str())
+        );
+
+        let anonymous = CallerKey::new(
+            RateLimitKey::TenantSubject,
+            Some(&SecurityContext::anonymous()),
+            Some(client),
+        );
+        assert_eq!(
+            anonymous,
+            CallerKey::new(RateLimitKey::TenantSubject, None, Some(client))
+        );
+        assert_eq!(anonymous.partition().as_deref(), Some("client=192.0.2.7"));
+
+        let other = CallerKey::new(
+            RateLimitKey::Tenant,
+            None,
+            Some("192.0.2.8".parse().unwrap()),
+        );
+        assert_ne!(other.client, anonymous.client);
+    }
+
+    #[test]
+    fn tenant_override_replaces_route_limits() {
+        let tenant = Uuid::new_v4();
+        let mut cfg = ApiGatewayConfig::default();
+        cfg.defaults.rate_limit.key = RateLimitKey::Tenant;
+        cfg.defaults.rate_limit.tenant_overrides = vec![crate::config::TenantRateLimit {
+            tenant_id: tenant,
+            rps: 500,
+            burst: 2,
+        }];
+        let mut builder = modkit::api::OperationBuilder::<_, _, ()>::get("/test");
+        builder.require_rate_limit(10, 20, 5);
+        let map = RateLimiterMap::from_specs(&vec![builder.spec().clone()], &cfg).unwrap();
+
+        let limits = map.routes.values().next().unwrap();
+        let caller = CallerKey {
+            tenant: Some(tenant),
+            subject: None,
+            client: None,
+        };
+        let bucket = limits.bucket_for(&caller);
+        assert_eq!((bucket.rps, bucket.burst), (500, 2));
+    }
+
+    #[test]
+    fn idle_eviction_drops_replenished_callers() {
+        let bucket = Bucket::new(1000, 1).unwrap();
+        for _ in 0..10 {
+            let caller = CallerKey {
+                tenant: Some(Uuid::new_v4()),
+                subject: None,
+                client: None,
+            };
+            bucket.limiter.check_key(&caller).unwrap();
+        }
+        assert_eq!(bucket.limiter.len(), 10);
+
+        let routes: RouteMap = Arc::new(HashMap::from([(
+            (Method::GET, "/test".to_owned()),
+            Arc::new(RouteLimits {
+                default: bucket,
+                tenants: HashMap::new(),
+            }),
+        )]));
+        let eviction = IdleEviction {
+            interval: Duration::ZERO,
+            last_run: Mutex::new(Instant::now()),
+        };
+        std::thread::sleep(Duration::from_millis(5));
+        eviction.run_if_due(&routes);
+
+        let limits = routes.values().next().unwrap();
+        assert_eq!(limits.default.limiter.len(), 0);
+    }
+}
@@ -0,0 +1,147 @@
+use anyhow::{Result, anyhow};
+use axum::extract::Request;
+use axum::http::{Method, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use modkit::api::{OperationSpec, Problem};
+use modkit_security::SecurityContext;
+
+use crate::config::GatewayScopeChecksConfig;
+
+/// Scope granting access to everything (first-party tokens).
+pub(crate) const WILDCARD_SCOPE: &str = "*";
+
+type OperationKey = (Method, String);
+
+/// Scope requirements checked by the gateway before any PDP call.
+///
+/// Each applicable requirement (declared on the operation or matched by a configured route
+/// pattern) is satisfied when the token carries any of its scopes.
+#[derive(Clone)]
+pub struct ScopeRequirementMap {
+    operations: Arc<HashMap<OperationKey, Vec<String>>>,
+    routes: Arc<matchit::Router<Vec<String>>>,
+}
+
+impl ScopeRequirementMap {
+    /// Returns `None` when gateway scope checks are disabled.
+    ///
+    /// # Errors
+    /// Returns an error if a configured route pattern is invalid or conflicts with another one.
+    pub fn from_specs(
+        specs: &[OperationSpec],
+        cfg: &GatewayScopeChecksConfig,
+    ) -> Result<Option<Self>> {
+        if !cfg.enabled {
+            return Ok(None);
+        }
+
+        let operations = specs
+            .iter()
+            .filter(|spec| !spec.required_scopes.is_empty())
+            .map(|spec| {
+                (
+                    (spec.method.clone(), spec.path.clone()),
+                    spec.required_scopes.clone(),
+                )
+            })
+            .collect();
+
+        let mut routes = matchit::Router::new();
+        for (pattern, route) in &cfg.routes {
+            routes
+                .insert(to_matchit_pattern(pattern), route.required_scopes.clone())
+                .map_err(|e| anyhow!("Invalid gateway scope route pattern '{pattern}': {e}"))?;
+        }
+
+        Ok(Some(Self {
+            operations: Arc::new(operations),
+            routes: Arc::new(routes),
+        }))
+    }
+
+    fn requirements<'a>(
+        &'a self,
+        method: &Method,
+        matched_path: &str,
+        uri_path: &str,
+    ) -> impl Iterator<Item = &'a Vec<String>> {
+        self.operations
+            .get(&(method.clone(), matched_path.to_owned()))
+            .into_iter()
+            .chain(self.routes.at(uri_path).ok().map(|m| m.value))
+    }
+}
+
+/// Convert a configured route pattern to matchit syntax: a trailing `*` matches any suffix.
+fn to_matchit_pattern(pattern: &str) -> String {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => format!("{prefix}{{*rest}}"),
+        None => pattern.to_owned(),
+    }
+}
+
+/// Whether the token scopes satisfy a requirement.
+///
+/// First-party tokens carrying `*` satisfy any requirement; tokens without scopes satisfy none.
+fn scopes_allow(token_scopes: &[String], required: &[String]) -> bool {
+    token_scopes
+        .iter()
+        .any(|s| s == WILDCARD_SCOPE || required.contains(s))
+}
+
+/// Rejects callers whose token does not list `scope` itself.
+///
+/// Guards the gateway's admin operations: unlike route requirements, it is not satisfied by
+/// the `*` wildcard that first-party tokens and the auth-disabled context carry.
+pub async fn require_exact_scope_middleware(scope: Arc<str>, req: Request, next: Next) -> Response {
+    let allowed = req
+        .extensions()
+        .get::<SecurityContext>()
+        .is_some_and(|ctx| ctx.token_scopes().iter().any(|s| **s == *scope));
+    if !allowed {
+        return Problem::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            format!(
+                "This operation requires the '{scope}' scope; '{WILDCARD_SCOPE}' does not grant it"
+            ),
+        )
+        .into_response();
+    }
+    next.run(req).await
+}
+
+/// Rejects requests whose token scopes do not cover the scopes required for the route.
+pub async fn scope_enforcement_middleware(
+    map: ScopeRequirementMap,
+    req: Request,
+    next: Next,
+) -> Response {
+    let matched_path = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path().to_owned(), |p| p.as_str().to_owned());
+    let token_scopes = req
+        .extensions()
+        .get::<SecurityContext>()
+        .map(|ctx| ctx.token_scopes().to_vec())
+        .unwrap_or_default();
+
+    let denied = map
+        .requirements(req.method(), &matched_path, req.uri().path())
+        .find(|required| !scopes_allow(&token_scopes, required));
+    if let Some(required) = denied {
+        return Problem::new(
+            StatusCode::FORBIDDEN,
+            "Forbidden",
+            format!("Token scopes do not grant any of the required scopes: {required:?}"),
+        )
+        .into_response();
+    }
+
+    next.run(req).await
+}
@@ -13,7 +13,9 @@ use axum::http::Method;
 use axum::middleware::from_fn_with_state;
 use axum::{Router, extract::DefaultBodyLimit, middleware::from_fn, routing::get};
 use modkit::api::{OpenApiRegistry, OpenApiRegistryImpl};
+use modkit::health::HealthRegistry;
 use modkit::lifecycle::ReadySignal;
+use modkit::runtime::ConfigReloader;
 use parking_lot::Mutex;
 use std::net::SocketAddr;
 use std::time::Duration;
@@ -26,21 +28,28 @@ use tower_http::{
 use tracing::debug;
 
 use authn_resolver_sdk::AuthNResolverClient;
+use license_resolver_sdk::LicenseResolverClient;
 
-use crate::config::ApiGatewayConfig;
+use crate::audit::{AuditSink, DbAuditSink, JsonlFileAuditSink};
+use crate::config::{ApiGatewayConfig, AuditSinkKind, IdempotencyStoreKind};
+use crate::idempotency::{DbIdempotencyStore, IdempotencyStore, InMemoryIdempotencyStore};
 use crate::middleware::auth;
 use modkit_security::SecurityContext;
 use modkit_security::constants::{DEFAULT_SUBJECT_ID, DEFAULT_TENANT_ID};
 
 use crate::middleware;
 use crate::router_cache::RouterCache;
+use crate::tls;
 use crate::web;
 
+/// Requests running longer than this are answered with `504 Gateway Timeout`.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Main API Gateway module — owns the HTTP server (`rest_host`) and collects
 /// typed operation specs to emit a single `OpenAPI` document.
 #[modkit::module(
 	name = "api-gateway",
-	capabilities = [rest_host, rest, stateful],
+	capabilities = [rest_host, rest, stateful, db],
     deps = ["grpc-hub", "authn-resolver"],
 	lifecycle(entry = "serve", stop_timeout = "30s", await_ready)
 )]
@@ -55,6 +64,12 @@ pub struct ApiGateway {
     pub(crate) final_router: Mutex<Option<axum::Router>>,
     // AuthN Resolver client (resolved during init, None when auth_disabled)
     pub(crate) authn_client: Mutex<Option<Arc<dyn AuthNResolverClient>>>,
+    // License Resolver client (resolved in the REST phase, None without a license resolver)
+    pub(crate) license_client: Mutex<Option<Arc<dyn LicenseResolverClient>>>,
+    // Idempotency record store (in-memory until resolved in the REST phase)
+    pub(crate) idempotency_store: Mutex<Arc<dyn IdempotencyStore>>,
+    // Audit sink (resolved in the REST phase, None when not configured)
+    pub(crate) audit_sink: Mutex<Option<Arc<dyn AuditSink>>>,
 
     // Duplicate detection (per (method, path) and per handler id)
     pub(crate) registered_routes: DashMap<(Method, String), ()>,
@@ -70,6 +85,9 @@ impl Default for ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            license_client: Mutex::new(None),
+            idempotency_store: Mutex::new(Arc::new(InMemoryIdempotencyStore::new())),
+            audit_sink: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -87,6 +105,9 @@ impl ApiGateway {
             router_cache: RouterCache::new(default_router),
             final_router: Mutex::new(None),
             authn_client: Mutex::new(None),
+            license_client: Mutex::new(None),
+            idempotency_store: Mutex::new(Arc::new(InMemoryIdempotencyStore::new())),
+            audit_sink: Mutex::new(None),
             registered_routes: DashMap::new(),
             registered_handlers: DashMap::new(),
         }
@@ -117,6 +138,64 @@ impl ApiGateway {
         Ok(())
     }
 
+    /// Pick up the optional License Resolver client; all modules are initialized by the REST phase.
+    fn resolve_license_client(&self, ctx: &modkit::context::ModuleCtx) {
+        if let Ok(license_client) = ctx.client_hub().get::<dyn LicenseResolverClient>() {
+            *self.license_client.lock() = Some(license_client);
+            tracing::info!("License Resolver client resolved from ClientHub");
+        }
+    }
+
+    /// Pick the idempotency store: one registered in the `ClientHub` wins over the configured one.
+    fn resolve_idempotency_store(
+        &self,
+        ctx: &modkit::context::ModuleCtx,
+        config: &ApiGatewayConfig,
+    ) -> Result<()> {
+        let store: Arc<dyn IdempotencyStore> =
+            if let Ok(store) = ctx.client_hub().get::<dyn IdempotencyStore>() {
+                tracing::info!("Idempotency store resolved from ClientHub");
+                store
+            } else {
+                match config.idempotency.store {
+                    IdempotencyStoreKind::Memory => Arc::new(InMemoryIdempotencyStore::new()),
+                    IdempotencyStoreKind::Database => {
+                        Arc::new(DbIdempotencyStore::new(ctx.db_required()?))
+                    }
+                }
+            };
+        *self.idempotency_store.lock() = store;
+        Ok(())
+    }
+
+    /// Pick the audit sink: one registered in the `ClientHub` wins over the configured one.
+    fn resolve_audit_sink(
+        &self,
+        ctx: &modkit::context::ModuleCtx,
+        config: &ApiGatewayConfig,
+    ) -> Result<()> {
+        let sink: Option<Arc<dyn AuditSink>> =
+            if let Ok(sink) = ctx.client_hub().get::<dyn AuditSink>() {
+                tracing::info!("Audit sink resolved from ClientHub");
+                Some(sink)
+            } else {
+                match config.audit.sink {
+                    None => None,
+                    Some(AuditSinkKind::File) => Some(Arc::new(JsonlFileAuditSink::new(
+                        &config.audit.file.path,
+                        config.audit.file.max_file_bytes,
+                        config.audit.file.max_files,
+                    )?)),
+                    Some(AuditSinkKind::Database) => Some(Arc::new(DbAuditSink::new(
+                        ctx.db_required()?,
+                        config.audit.retention,
+                    ))),
+                }
+            };
+        *self.audit_sink.lock() = sink;
+        Ok(())
+    }
+
     /// Build route policy from operation specs.
     fn build_route_policy_from_specs(&self) -> Result<auth::GatewayRoutePolicy> {
         let mut authenticated_routes = std::collections::HashSet::new();
@@ -125,8 +204,18 @@ impl ApiGateway {
         // Always mark built-in health check routes as public
         public_routes.insert((Method::GET, "/health".to_owned()));
         public_routes.insert((Method::GET, "/healthz".to_owned()));
+        public_routes.insert((Method::GET, "/readyz".to_owned()));
         public_routes.insert((Method::GET, "/docs".to_owned()));
         public_routes.insert((Method::GET, "/openapi.json".to_owned()));
+        let config = self.get_cached_config();
+        if config.metrics.enabled {
+            let route = (Method::GET, config.metrics.path.clone());
+            if config.metrics.public {
+                public_routes.insert(route);
+            } else {
+                authenticated_routes.insert(route);
+            }
+        }
 
         for spec in &self.openapi_registry.operation_specs {
             let spec = spec.value();
@@ -141,7 +230,6 @@ impl ApiGateway {
             }
         }
 
-        let config = self.get_cached_config();
         let requirements_count = authenticated_routes.len();
         let public_routes_count = public_routes.len();
 
@@ -158,6 +246,43 @@ impl ApiGateway {
         Ok(route_policy)
     }
 
+    /// Layers that record who calls an operation; applied right inside auth.
+    ///
+    /// Audit is outer to every later check that can deny; deprecation headers and
+    /// per-tenant usage of deprecated operations are inner to it.
+    fn apply_caller_layers(
+        &self,
+        mut router: Router,
+        specs: &[modkit::api::OperationSpec],
+        config: &ApiGatewayConfig,
+    ) -> Result<Router> {
+        if let Some(deprecation) =
+            middleware::deprecation::DeprecationMap::from_specs(specs, &config.deprecation)?
+        {
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let map = deprecation.clone();
+                    middleware::deprecation::deprecation_middleware(map, req, next)
+                },
+            ));
+        }
+
+        if let Some(audit) = middleware::audit::AuditState::from_specs(
+            specs,
+            self.audit_sink.lock().clone(),
+            &config.audit,
+        ) {
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let state = audit.clone();
+                    middleware::audit::audit_middleware(state, req, next)
+                },
+            ));
+        }
+
+        Ok(router)
+    }
+
     /// Apply all middleware layers to a router (request ID, tracing, timeout, body limit, CORS, rate limiting, error mapping, auth)
     pub(crate) fn apply_middleware_stack(
         &self,
@@ -171,8 +296,11 @@ impl ApiGateway {
         // becomes the **outermost** layer and therefore runs **first** on the request path.
         //
         // Desired request execution order (outermost -> innermost):
-        // SetRequestId -> PropagateRequestId -> Trace -> push_req_id_to_extensions
-        // -> Timeout -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth -> Router
+        // SetRequestId -> PropagateRequestId -> Trace -> HttpMetrics -> push_req_id_to_extensions
+        // -> Timeout -> Compression -> BodyLimit -> CORS -> MIME validation -> RateLimit -> ErrorMapping -> Auth
+        // -> Audit -> Deprecation -> ScopeChecks -> License -> Idempotency -> HttpCache -> Router
+        //
+        // When rate limits are keyed by tenant/subject, RateLimit moves between ScopeChecks and License.
         //
         // Therefore we must add layers in the reverse order (innermost -> outermost) below.
         // Due future refactoring, this order must be maintained.
@@ -187,21 +315,91 @@ impl ApiGateway {
             .map(|e| e.value().clone())
             .collect();
 
+        let rate_map = middleware::rate_limit::RateLimiterMap::from_specs(&specs, &config)?;
+        let keyed_rate_limit = rate_map.is_keyed();
+        let rate_limit_layer = from_fn(
+            move |req: axum::extract::Request, next: axum::middleware::Next| {
+                let map = rate_map.clone();
+                middleware::rate_limit::rate_limit_middleware(map, req, next)
+            },
+        );
+
+        // 13) ETag / Cache-Control / conditional GET (inner to idempotency so replays carry them)
+        if let Some(cache_map) =
+            middleware::http_cache::HttpCacheMap::from_specs(&specs, &config.http_cache)
+        {
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let map = cache_map.clone();
+                    middleware::http_cache::http_cache_middleware(map, req, next)
+                },
+            ));
+        }
+
+        // 12) Idempotency-Key replay (innermost: only requests that passed every check are recorded)
+        if let Some(idempotency) = middleware::idempotency::IdempotencyState::from_specs(
+            &specs,
+            self.idempotency_store.lock().clone(),
+            &config.idempotency,
+            REQUEST_TIMEOUT,
+        ) {
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let state = idempotency.clone();
+                    middleware::idempotency::idempotency_middleware(state, req, next)
+                },
+            ));
+        }
+
         // 11) License validation
         let license_map = middleware::license_validation::LicenseRequirementMap::from_specs(&specs);
+        let license_features = self.license_client.lock().clone().map(|client| {
+            middleware::license_validation::LicenseFeatureCache::new(
+                client,
+                Duration::from_secs(config.license.cache_ttl_seconds),
+            )
+        });
         router = router.layer(from_fn(
             move |req: axum::extract::Request, next: axum::middleware::Next| {
                 let map = license_map.clone();
-                middleware::license_validation::license_validation_middleware(map, req, next)
+                let features = license_features.clone();
+                middleware::license_validation::license_validation_middleware(
+                    map, features, req, next,
+                )
             },
         ));
 
+        // 10b) Rate limits keyed by tenant/subject need the SecurityContext set by auth
+        if keyed_rate_limit {
+            router = router.layer(rate_limit_layer.clone());
+        }
+
+        // 10a) Gateway scope checks (right after auth, before any PDP call)
+        if let Some(scope_map) = middleware::scope_enforcement::ScopeRequirementMap::from_specs(
+            &specs,
+            &config.auth.gateway_scope_checks,
+        )? {
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let map = scope_map.clone();
+                    middleware::scope_enforcement::scope_enforcement_middleware(map, req, next)
+                },
+            ));
+        }
+
+        // Audit log and deprecation headers (inner to auth: both need the caller)
+        router = self.apply_caller_layers(router, &specs, &config)?;
+
         // 10) Auth
         if config.auth_disabled {
-            // Build security contexts for compatibility during migration
+            // Build security contexts for compatibility during migration; the single
+            // trusted user holds every scope
             let default_security_context = SecurityContext::builder()
                 .subject_id(DEFAULT_SUBJECT_ID)
                 .subject_tenant_id(DEFAULT_TENANT_ID)
+                .token_scopes(vec![
+                    middleware::scope_enforcement::WILDCARD_SCOPE.to_owned(),
+                ])
                 .build()?;
 
             tracing::warn!(
@@ -234,14 +432,10 @@ impl ApiGateway {
         // 9) Error mapping (outer to auth so it can translate auth/handler errors)
         router = router.layer(from_fn(modkit::api::error_layer::error_mapping_middleware));
 
-        // 8) Per-route rate limiting & in-flight limits
-        let rate_map = middleware::rate_limit::RateLimiterMap::from_specs(&specs, &config)?;
-        router = router.layer(from_fn(
-            move |req: axum::extract::Request, next: axum::middleware::Next| {
-                let map = rate_map.clone();
-                middleware::rate_limit::rate_limit_middleware(map, req, next)
-            },
-        ));
+        // 8) Per-route rate limiting & in-flight limits (unless keyed by caller, see 10b)
+        if !keyed_rate_limit {
+            router = router.layer(rate_limit_layer);
+        }
 
         // 7) MIME type validation
         let mime_map = middleware::mime_validation::build_mime_validation_map(&specs);
@@ -261,15 +455,35 @@ impl ApiGateway {
         router = router.layer(RequestBodyLimitLayer::new(config.defaults.body_limit_bytes));
         router = router.layer(DefaultBodyLimit::max(config.defaults.body_limit_bytes));
 
+        // 4b) Response compression (negotiated via Accept-Encoding; never for SSE streams),
+        // with strong ETags made encoding-specific around it
+        if config.compression.enabled {
+            router = router.layer(crate::compression::build_compression_layer(
+                &config.compression,
+            ));
+            router = router.layer(from_fn(middleware::http_cache::encoding_etag_middleware));
+        }
+
         // 4) Timeout
         router = router.layer(TimeoutLayer::with_status_code(
             axum::http::StatusCode::GATEWAY_TIMEOUT,
-            Duration::from_secs(30),
+            REQUEST_TIMEOUT,
         ));
 
         // 3) Record request_id into span + extensions (requires span to exist first => must be inner to Trace)
         router = router.layer(from_fn(middleware::request_id::push_req_id_to_extensions));
 
+        // 2b) HTTP server metrics (outer to timeout so gateway timeouts are recorded)
+        if config.metrics.enabled {
+            let metrics = middleware::http_metrics::HttpServerMetrics::new();
+            router = router.layer(from_fn(
+                move |req: axum::extract::Request, next: axum::middleware::Next| {
+                    let metrics = metrics.clone();
+                    middleware::http_metrics::http_metrics_middleware(metrics, req, next)
+                },
+            ));
+        }
+
         // 2) Trace (outer to push_req_id_to_extensions)
         router = router.layer({
             use modkit_http::otel;
@@ -353,11 +567,11 @@ impl ApiGateway {
         }
 
         tracing::debug!("Building new router (standalone/fallback mode)");
-        // In standalone mode (no REST pipeline), register both health endpoints here.
+        // In standalone mode (no REST pipeline), register the health endpoints here.
         // In normal operation, rest_prepare() registers these instead.
         let mut router = Router::new()
             .route("/health", get(web::health_check))
-            .route("/healthz", get(|| async { "ok" }));
+            .merge(web::health_routes(Self::standalone_health()));
 
         // Apply all middleware layers including auth, above the router
         let authn_client = self.authn_client.lock().clone();
@@ -383,6 +597,13 @@ impl ApiGateway {
         self.openapi_registry.build_openapi(&info)
     }
 
+    /// Health registry used without a runtime: no modules, always serving.
+    fn standalone_health() -> Arc<HealthRegistry> {
+        let health = HealthRegistry::new();
+        health.set_serving(true);
+        Arc::new(health)
+    }
+
     /// Parse bind address from configuration string.
     fn parse_bind_address(bind_addr: &str) -> anyhow::Result<SocketAddr> {
         bind_addr
@@ -415,12 +636,27 @@ impl ApiGateway {
         let cfg = self.get_cached_config();
         let addr = Self::parse_bind_address(&cfg.bind_addr)?;
         let router = self.get_or_build_router()?;
+        let tls = cfg.tls.as_ref().map(tls::load).transpose()?;
 
-        // Bind the socket, only now consider the service "ready"
+        // Bind the sockets, only now consider the service "ready"
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        tracing::info!("HTTP server bound on {}", addr);
+        let redirect_listener = match cfg
+            .tls
+            .as_ref()
+            .and_then(|t| t.http_redirect_bind_addr.as_deref())
+        {
+            Some(redirect_addr) => {
+                Some(tokio::net::TcpListener::bind(Self::parse_bind_address(redirect_addr)?).await?)
+            }
+            None => None,
+        };
+        tracing::info!(https = tls.is_some(), "HTTP server bound on {}", addr);
         ready.notify(); // Starting -> Running
 
+        if let Some(redirect_listener) = redirect_listener {
+            Self::spawn_https_redirect(redirect_listener, listener.local_addr()?.port(), &cancel)?;
+        }
+
         // Graceful shutdown on cancel
         let shutdown = {
             let cancel = cancel.clone();
@@ -430,10 +666,48 @@ impl ApiGateway {
             }
         };
 
-        axum::serve(listener, router)
-            .with_graceful_shutdown(shutdown)
-            .await
-            .map_err(|e| anyhow::anyhow!(e))
+        // Peer addresses key the rate limit buckets of anonymous callers
+        let service = router
+            .into_make_service_with_connect_info::<crate::middleware::rate_limit::ClientAddr>();
+        match tls {
+            None => {
+                axum::serve(listener, service)
+                    .with_graceful_shutdown(shutdown)
+                    .await
+            }
+            Some(tls) => {
+                let _watcher = tls.watch(cancel.clone());
+                axum::serve(tls::TlsListener::new(&tls, listener)?, service)
+                    .with_graceful_shutdown(shutdown)
+                    .await
+            }
+        }
+        .map_err(|e| anyhow::anyhow!(e))
+    }
+
+    /// Serve permanent redirects to the HTTPS port on `listener` until cancelled.
+    fn spawn_https_redirect(
+        listener: tokio::net::TcpListener,
+        https_port: u16,
+        cancel: &CancellationToken,
+    ) -> anyhow::Result<()> {
+        tracing::info!(
+            "HTTP to HTTPS redirect listener bound on {}",
+            listener.local_addr()?
+        );
+        let router = Router::new().fallback(move |req: axum::extract::Request| async move {
+            tls::redirect_to_https(https_port, &req)
+        });
+        let cancel = cancel.clone();
+        tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, router)
+                .with_graceful_shutdown(cancel.cancelled_owned())
+                .await
+            {
+                tracing::error!(error = %e, "HTTP to HTTPS redirect listener failed");
+            }
+        });
+        Ok(())
     }
 
     /// Check if `handler_id` is already registered (returns true if duplicate)
@@ -564,15 +838,52 @@ impl modkit::Module for ApiGateway {
 impl modkit::contracts::ApiGatewayCapability for ApiGateway {
     fn rest_prepare(
         &self,
-        _ctx: &modkit::context::ModuleCtx,
+        ctx: &modkit::context::ModuleCtx,
         router: axum::Router,
     ) -> anyhow::Result<axum::Router> {
         // Add health check endpoints:
         // - /health: detailed JSON response with status and timestamp
-        // - /healthz: simple "ok" liveness probe (Kubernetes-style)
-        let router = router
+        // - /healthz, /readyz: aggregated module liveness/readiness (Kubernetes-style), status only
+        // - /api-gateway/v1/health: per-module details for authenticated callers
+        let health = ctx
+            .client_hub()
+            .get::<HealthRegistry>()
+            .unwrap_or_else(|_| Self::standalone_health());
+        let mut router = router
             .route("/health", get(web::health_check))
-            .route("/healthz", get(|| async { "ok" }));
+            .merge(web::health_routes(health.clone()))
+            .merge(web::health_details_routes(self, health));
+
+        // Prometheus scrape endpoint (only when the metrics pipeline keeps metrics for scraping)
+        let config = self.get_cached_config();
+        if config.metrics.enabled
+            && let Some(exporter) = modkit::telemetry::metrics::prometheus_exporter()
+        {
+            let admin_scope = (!config.metrics.public).then_some(config.auth.admin_scope.as_str());
+            router = router.merge(web::metrics_routes(
+                &config.metrics.path,
+                exporter,
+                admin_scope,
+            ));
+        }
+
+        // Runtime log level overrides (only when host logging installed the controller)
+        if let Some(levels) = modkit::telemetry::log_levels() {
+            router = router.merge(web::log_level_routes(
+                self,
+                levels,
+                &config.auth.admin_scope,
+            ));
+        }
+
+        // Admin trigger for live config reload (only when hosted by the runtime)
+        if let Ok(reloader) = ctx.client_hub().get::<ConfigReloader>() {
+            router = router.merge(web::config_reload_routes(
+                self,
+                reloader,
+                &config.auth.admin_scope,
+            ));
+        }
 
         // You may attach global middlewares here (trace, compression, cors), but do not start server.
         tracing::debug!("REST host prepared base router with health check endpoints");
@@ -581,10 +892,13 @@ impl modkit::contracts::ApiGatewayCapability for ApiGateway {
 
     fn rest_finalize(
         &self,
-        _ctx: &modkit::context::ModuleCtx,
+        ctx: &modkit::context::ModuleCtx,
         mut router: axum::Router,
     ) -> anyhow::Result<axum::Router> {
         let config = self.get_cached_config();
+        self.resolve_license_client(ctx);
+        self.resolve_idempotency_store(ctx, &config)?;
+        self.resolve_audit_sink(ctx, &config)?;
 
         if config.enable_docs {
             router = self.add_openapi_routes(router)?;
@@ -607,6 +921,16 @@ impl modkit::contracts::ApiGatewayCapability for ApiGateway {
     }
 }
 
+// Tables for the database-backed idempotency store and audit sink; skipped when no database is configured.
+impl modkit::contracts::DatabaseCapability for ApiGateway {
+    fn migrations(&self) -> Vec<Box<dyn sea_orm_migration::MigrationTrait>> {
+        vec![
+            modkit_db::idempotency::migration(),
+            modkit_db::audit::migration(),
+        ]
+    }
+}
+
 impl modkit::contracts::RestApiCapability for ApiGateway {
     fn register_rest(
         &self,
@@ -0,0 +1,226 @@
+//! HTTPS termination for the gateway.
+//!
+//! Certificates are loaded and hot-reloaded by [`ServerTls`] from `modkit-tls`;
+//! [`TlsListener`] adapts its accept loop to `axum::serve`. ALPN offers HTTP/2 and HTTP/1.1.
+
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use anyhow::Result;
+use axum::extract::Request;
+use axum::http::{StatusCode, Uri, header};
+use axum::response::{IntoResponse, Redirect, Response};
+use modkit_tls::ServerTls;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio_rustls::server::TlsStream;
+
+use crate::config::TlsConfig;
+use crate::middleware::rate_limit::ClientAddr;
+
+/// ALPN protocols offered on the HTTPS listener, in order of preference.
+const ALPN_PROTOCOLS: &[&[u8]] = &[b"h2", b"http/1.1"];
+
+/// Loads the gateway certificates.
+///
+/// # Errors
+/// Returns an error if a file cannot be read or does not hold valid PEM data.
+pub fn load(config: &TlsConfig) -> Result<Arc<ServerTls>> {
+    ServerTls::load(config.server_tls(), ALPN_PROTOCOLS)
+}
+
+/// Listener yielding TLS connections, for `axum::serve`.
+pub struct TlsListener {
+    local_addr: SocketAddr,
+    rx: mpsc::Receiver<(TlsStream<TcpStream>, SocketAddr)>,
+}
+
+impl TlsListener {
+    /// Completes TLS handshakes on connections accepted by `listener`. The accept loop
+    /// stops when the returned listener is dropped.
+    ///
+    /// # Errors
+    /// Returns an error if the local address of `listener` cannot be read.
+    pub fn new(tls: &Arc<ServerTls>, listener: TcpListener) -> io::Result<Self> {
+        Ok(Self {
+            local_addr: listener.local_addr()?,
+            rx: tls.accept_loop(listener),
+        })
+    }
+}
+
+impl axum::serve::Listener for TlsListener {
+    type Io = TlsStream<TcpStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.rx.recv().await {
+            Some(conn) => conn,
+            // The accept loop only ends once this listener is dropped
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+impl axum::extract::connect_info::Connected<axum::serve::IncomingStream<'_, TlsListener>>
+    for ClientAddr
+{
+    fn connect_info(stream: axum::serve::IncomingStream<'_, TlsListener>) -> Self {
+        Self(*stream.remote_addr())
+    }
+}
+
+/// Redirects plain HTTP requests to the same path on the HTTPS port.
+pub fn redirect_to_https(https_port: u16, req: &Request) -> Response {
+    let Some(host) = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.parse::<axum::http::uri::Authority>().ok())
+    else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let path = req
+        .uri()
+        .path_and_query()
+        .map_or("/", axum::http::uri::PathAndQuery::as_str);
+    let authority = if https_port == 443 {
+        host.host().to_owned()
+    } else {
+        format!("{}:{https_port}", host.host())
+    };
+    match Uri::builder()
+        .scheme("https")
+        .authority(authority)
+        .path_and_query(path)
+        .build()
+    {
+        Ok(uri) => Redirect::permanent(&uri.to_string()).into_response(),
+        Err(_) => StatusCode::BAD_REQUEST.into_response(),
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use rustls::RootCertStore;
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::TlsConnector;
+    use tokio_rustls::client::TlsStream as ClientTlsStream;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../../libs/modkit-tls/tests/fixtures/tls")
+            .join(name)
+    }
+
+    fn tls_config() -> TlsConfig {
+        TlsConfig {
+            cert_path: fixture("server.crt"),
+            key_path: fixture("server.key"),
+            client_ca_path: None,
+            client_auth: modkit_tls::ClientAuth::default(),
+            reload_interval: Duration::from_secs(30),
+            http_redirect_bind_addr: None,
+        }
+    }
+
+    async fn start(config: &TlsConfig) -> SocketAddr {
+        let listener = TlsListener::new(
+            &load(config).unwrap(),
+            TcpListener::bind("127.0.0.1:0").await.unwrap(),
+        )
+        .unwrap();
+        let addr = axum::serve::Listener::local_addr(&listener).unwrap();
+        let router = axum::Router::new().route("/", axum::routing::get(|| async { "ok" }));
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        addr
+    }
+
+    async fn connect(addr: SocketAddr, alpn: &[&[u8]]) -> io::Result<ClientTlsStream<TcpStream>> {
+        let mut roots = RootCertStore::empty();
+        roots
+            .add(
+                modkit_tls::load_certs(&fixture("ca.crt"))
+                    .unwrap()
+                    .remove(0),
+            )
+            .unwrap();
+        let mut config = rustls::ClientConfig::builder_with_provider(Arc::new(
+            rustls::crypto::aws_lc_rs::default_provider(),
+        ))
+        .with_safe_default_protocol_versions()
+        .unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+        config.alpn_protocols = alpn.iter().map(|p| p.to_vec()).collect();
+
+        let stream = TcpStream::connect(addr).await?;
+        TlsConnector::from(Arc::new(config))
+            .connect("localhost".try_into().unwrap(), stream)
+            .await
+    }
+
+    async fn http1_get(mut stream: ClientTlsStream<TcpStream>) -> io::Result<String> {
+        stream
+            .write_all(b"GET / HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await?;
+        Ok(response)
+    }
+
+    #[tokio::test]
+    async fn negotiates_http2_and_serves_http1() {
+        let addr = start(&tls_config()).await;
+
+        let h2 = connect(addr, &[b"h2", b"http/1.1"]).await.unwrap();
+        assert_eq!(h2.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+
+        let h1 = connect(addr, &[b"http/1.1"]).await.unwrap();
+        let response = http1_get(h1).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK"), "{response}");
+        assert!(response.ends_with("ok"), "{response}");
+    }
+
+    #[test]
+    fn client_ca_passed_to_server_tls() {
+        let mut config = tls_config();
+        config.client_ca_path = Some(fixture("ca.crt"));
+        assert!(load(&config).unwrap().is_mutual());
+    }
+
+    #[test]
+    fn redirects_to_https_port() {
+        let req = Request::builder()
+            .uri("/users/v1/users?limit=10")
+            .header(header::HOST, "example.com:8080")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = redirect_to_https(8443, &req);
+        assert_eq!(resp.status(), StatusCode::PERMANENT_REDIRECT);
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "https://example.com:8443/users/v1/users?limit=10"
+        );
+
+        let req = Request::builder()
+            .uri("/")
+            .header(header::HOST, "example.com")
+            .body(axum::body::Body::empty())
+            .unwrap();
+        let resp = redirect_to_https(443, &req);
+        assert_eq!(
+            resp.headers().get(header::LOCATION).unwrap(),
+            "https://example.com/"
+        );
+    }
+}
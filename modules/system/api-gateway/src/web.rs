@@ -1,10 +1,25 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
 use axum::{
-    http::StatusCode,
-    response::{Html, Json},
+    Extension, Router,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    middleware::from_fn,
+    response::{Html, IntoResponse, Json},
     routing::{MethodRouter, get},
 };
 use chrono::{SecondsFormat, Utc};
+use modkit::api::{OpenApiRegistry, OperationBuilder, Problem};
+use modkit::health::{HealthRegistry, HealthSummary};
+use modkit::runtime::{ConfigReloader, ReloadError};
+use modkit::telemetry::metrics::PrometheusExporter;
+use modkit::telemetry::{LevelOverride, LogLevelError, LogLevels};
 use serde_json::{Value, json};
+use tracing::level_filters::LevelFilter;
+
+use crate::middleware::scope_enforcement::require_exact_scope_middleware;
 
 /// Returns a 501 Not Implemented handler for operations without implementations
 #[allow(dead_code)]
@@ -27,6 +42,328 @@ pub async fn health_check() -> Json<Value> {
     }))
 }
 
+/// `/healthz` and `/readyz`: public probes for Kubernetes, without module details.
+pub fn health_routes(health: Arc<HealthRegistry>) -> Router {
+    Router::new()
+        .route("/healthz", get(liveness))
+        .route("/readyz", get(readiness))
+        .with_state(health)
+}
+
+/// Liveness probe: `ok`, or `503` when a module's liveness check is down.
+pub async fn liveness(State(health): State<Arc<HealthRegistry>>) -> (StatusCode, &'static str) {
+    let summary = health.liveness().await;
+    if summary.status.is_passing() {
+        (StatusCode::OK, "ok")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "down")
+    }
+}
+
+/// Readiness probe; `503` when any module is down or the runtime is not serving.
+pub async fn readiness(State(health): State<Arc<HealthRegistry>>) -> (StatusCode, Json<Value>) {
+    let summary = health.readiness().await;
+    (
+        probe_code(&summary),
+        Json(json!({ "status": summary.status })),
+    )
+}
+
+fn probe_code(summary: &HealthSummary) -> StatusCode {
+    if summary.status.is_passing() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+}
+
+/// `GET /api-gateway/v1/health`: per-module liveness and readiness with check details.
+pub fn health_details_routes(openapi: &dyn OpenApiRegistry, health: Arc<HealthRegistry>) -> Router {
+    OperationBuilder::get("/api-gateway/v1/health")
+        .operation_id("api_gateway.get_health")
+        .summary("Get module health")
+        .description(
+            "Liveness and readiness of every module with its checks and their details, \
+             as aggregated by the public `/healthz` and `/readyz` probes.",
+        )
+        .tag("api-gateway")
+        .authenticated()
+        .no_license_required()
+        .handler(health_details)
+        .json_response(StatusCode::OK, "Module health")
+        .standard_errors(openapi)
+        .register(Router::new(), openapi)
+        .layer(Extension(health))
+}
+
+async fn health_details(Extension(health): Extension<Arc<HealthRegistry>>) -> Json<Value> {
+    let (liveness, readiness) = tokio::join!(health.liveness(), health.readiness());
+    Json(json!({ "liveness": liveness, "readiness": readiness }))
+}
+
+/// Prometheus scrape endpoint (`/metrics` by default); restricted to tokens listing
+/// `admin_scope` when given (`*` is not enough: series carry tenant IDs).
+pub fn metrics_routes(
+    path: &str,
+    exporter: PrometheusExporter,
+    admin_scope: Option<&str>,
+) -> Router {
+    let routes = Router::new()
+        .route(path, get(scrape_metrics))
+        .with_state(exporter);
+    match admin_scope {
+        Some(scope) => exact_admin_only(routes, scope),
+        None => routes,
+    }
+}
+
+/// Current measurements in the Prometheus text exposition format.
+pub async fn scrape_metrics(State(exporter): State<PrometheusExporter>) -> impl IntoResponse {
+    (
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4; charset=utf-8",
+        )],
+        exporter.render(),
+    )
+}
+
+/// Result of a successful configuration reload
+#[modkit_macros::api_dto(response)]
+pub struct ConfigReloadDto {
+    /// Modules whose configuration changed
+    pub changed: Vec<String>,
+}
+
+/// `POST /api-gateway/v1/config/reload`: re-read the host config and apply it to running modules.
+///
+/// Requires a token listing `admin_scope` (`*` is not enough): a reload swaps the
+/// configuration of every module at once.
+pub fn config_reload_routes(
+    openapi: &dyn OpenApiRegistry,
+    reloader: Arc<ConfigReloader>,
+    admin_scope: &str,
+) -> Router {
+    let routes = OperationBuilder::post("/api-gateway/v1/config/reload")
+        .operation_id("api_gateway.reload_config")
+        .summary("Reload module configuration")
+        .description(
+            "Re-reads the host configuration and applies changed module sections. \
+             The reload is all-or-nothing: if any module rejects its change, \
+             the previous configuration stays active.",
+        )
+        .tag("api-gateway")
+        .authenticated()
+        .require_scopes([admin_scope])
+        .no_license_required()
+        .handler(reload_config)
+        .json_response_with_schema::<ConfigReloadDto>(
+            openapi,
+            StatusCode::OK,
+            "Configuration reloaded",
+        )
+        .standard_errors(openapi)
+        .register(Router::new(), openapi);
+    exact_admin_only(routes, admin_scope).layer(Extension(reloader))
+}
+
+/// Reload handler; `409` without a config source, `422` when the change is rejected.
+pub async fn reload_config(
+    Extension(reloader): Extension<Arc<ConfigReloader>>,
+) -> Result<Json<ConfigReloadDto>, Problem> {
+    match reloader.reload().await {
+        Ok(changed) => Ok(Json(ConfigReloadDto { changed })),
+        Err(e @ ReloadError::NoSource) => Err(Problem::new(
+            StatusCode::CONFLICT,
+            "Config reload unavailable",
+            e.to_string(),
+        )),
+        Err(e) => Err(Problem::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Config reload rejected",
+            e.to_string(),
+        )),
+    }
+}
+
+/// Default lifetime of a log level override.
+const DEFAULT_LOG_LEVEL_TTL: Duration = Duration::from_mins(15);
+
+/// Configured log levels and active overrides
+#[modkit_macros::api_dto(response)]
+pub struct LogLevelsDto {
+    /// Level of targets without a configured level
+    pub default: String,
+    /// Levels configured per target in the `logging` section
+    pub configured: BTreeMap<String, String>,
+    /// Temporary overrides, reverted when they expire
+    pub overrides: Vec<LogLevelOverrideDto>,
+}
+
+/// Temporary level of a target
+#[modkit_macros::api_dto(response)]
+pub struct LogLevelOverrideDto {
+    pub target: String,
+    pub level: String,
+    /// RFC 3339 time at which the configured level is restored
+    pub expires_at: String,
+}
+
+/// Level to apply to a target
+#[modkit_macros::api_dto(request)]
+pub struct SetLogLevelDto {
+    /// `off`, `error`, `warn`, `info`, `debug` or `trace`
+    pub level: String,
+    /// Seconds until the override reverts (default 900, at most one day)
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+}
+
+impl From<LevelOverride> for LogLevelOverrideDto {
+    fn from(o: LevelOverride) -> Self {
+        Self {
+            target: o.target,
+            level: o.level.to_string(),
+            expires_at: chrono::DateTime::<chrono::Utc>::from(o.expires_at).to_rfc3339(),
+        }
+    }
+}
+
+/// Restrict the routes of `router` to callers whose token lists `admin_scope` itself.
+///
+/// The `*` wildcard does not count, so first-party tokens and the auth-disabled context
+/// cannot use admin operations.
+fn exact_admin_only(router: Router, admin_scope: &str) -> Router {
+    let scope: Arc<str> = Arc::from(admin_scope);
+    router.route_layer(from_fn(move |req, next| {
+        require_exact_scope_middleware(Arc::clone(&scope), req, next)
+    }))
+}
+
+/// `/api-gateway/v1/log-levels`: read and temporarily change per-module log levels.
+///
+/// Changing a level requires a token listing `admin_scope` (`*` is not enough): a verbose
+/// level can expose query text and secrets in the logs and flood the log sinks.
+pub fn log_level_routes(
+    openapi: &dyn OpenApiRegistry,
+    levels: &'static LogLevels,
+    admin_scope: &str,
+) -> Router {
+    let list = OperationBuilder::get("/api-gateway/v1/log-levels")
+        .operation_id("api_gateway.list_log_levels")
+        .summary("List log levels")
+        .description("Returns the configured log levels and the active temporary overrides.")
+        .tag("api-gateway")
+        .authenticated()
+        .no_license_required()
+        .handler(list_log_levels)
+        .json_response_with_schema::<LogLevelsDto>(openapi, StatusCode::OK, "Log levels")
+        .standard_errors(openapi)
+        .register(Router::new(), openapi);
+
+    let change = OperationBuilder::put("/api-gateway/v1/log-levels/{target}")
+        .operation_id("api_gateway.set_log_level")
+        .summary("Override a log level")
+        .description(
+            "Logs a module (a target prefix such as `api_gateway` or `sqlx`) at the given \
+             level on every sink until the TTL expires, then restores the configured level.",
+        )
+        .tag("api-gateway")
+        .path_param("target", "Module name or log target")
+        .authenticated()
+        .require_scopes([admin_scope])
+        .no_license_required()
+        .json_request::<SetLogLevelDto>(openapi, "Level and TTL")
+        .handler(set_log_level)
+        .json_response_with_schema::<LogLevelOverrideDto>(
+            openapi,
+            StatusCode::OK,
+            "Override applied",
+        )
+        .standard_errors(openapi)
+        .register(Router::new(), openapi);
+
+    let change = OperationBuilder::delete("/api-gateway/v1/log-levels/{target}")
+        .operation_id("api_gateway.clear_log_level")
+        .summary("Revert a log level override")
+        .tag("api-gateway")
+        .path_param("target", "Module name or log target")
+        .authenticated()
+        .require_scopes([admin_scope])
+        .no_license_required()
+        .handler(clear_log_level)
+        .json_response(StatusCode::NO_CONTENT, "Configured level restored")
+        .standard_errors(openapi)
+        .register(change, openapi);
+
+    list.merge(exact_admin_only(change, admin_scope))
+        .layer(Extension(levels))
+}
+
+pub async fn list_log_levels(
+    Extension(levels): Extension<&'static LogLevels>,
+) -> Json<LogLevelsDto> {
+    Json(LogLevelsDto {
+        default: levels.default_level().to_string(),
+        configured: levels
+            .configured()
+            .iter()
+            .map(|(target, level)| (target.clone(), level.to_string()))
+            .collect(),
+        overrides: levels.overrides().into_iter().map(Into::into).collect(),
+    })
+}
+
+/// `400` for an unknown level, invalid target or TTL.
+pub async fn set_log_level(
+    Extension(levels): Extension<&'static LogLevels>,
+    Path(target): Path<String>,
+    Json(req): Json<SetLogLevelDto>,
+) -> Result<Json<LogLevelOverrideDto>, Problem> {
+    let level = req
+        .level
+        .parse::<LevelFilter>()
+        .map_err(|e| Problem::new(StatusCode::BAD_REQUEST, "Invalid log level", e.to_string()))?;
+    let ttl = req
+        .ttl_secs
+        .map_or(DEFAULT_LOG_LEVEL_TTL, Duration::from_secs);
+    levels
+        .set(&target, level, ttl)
+        .map(|o| Json(o.into()))
+        .map_err(|e| log_level_problem(&e))
+}
+
+/// `404` if the target has no override.
+pub async fn clear_log_level(
+    Extension(levels): Extension<&'static LogLevels>,
+    Path(target): Path<String>,
+) -> Result<StatusCode, Problem> {
+    match levels.clear(&target) {
+        Ok(true) => Ok(StatusCode::NO_CONTENT),
+        Ok(false) => Err(Problem::new(
+            StatusCode::NOT_FOUND,
+            "No log level override",
+            format!("'{target}' has no log level override"),
+        )),
+        Err(e) => Err(log_level_problem(&e)),
+    }
+}
+
+fn log_level_problem(e: &LogLevelError) -> Problem {
+    match e {
+        LogLevelError::Apply(_) => Problem::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Log levels not applied",
+            e.to_string(),
+        ),
+        LogLevelError::InvalidTarget(_) | LogLevelError::InvalidTtl => Problem::new(
+            StatusCode::BAD_REQUEST,
+            "Invalid log level override",
+            e.to_string(),
+        ),
+    }
+}
+
 #[cfg(not(feature = "embed_elements"))]
 pub async fn serve_docs() -> Html<&'static str> {
     // External mode: load from CDN @latest
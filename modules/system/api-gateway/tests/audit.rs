@@ -0,0 +1,356 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the audit log
+//!
+//! These tests verify that:
+//! 1. Invocations of auditable operations are recorded with caller, resource ids and outcome
+//! 2. Sensitive request body fields are redacted
+//! 3. Denied and failed invocations are recorded; other operations are not
+//! 4. A request body over the limit is rejected with `413`, not forwarded empty
+//! 5. An invocation dropped before its handler returns is recorded as cancelled
+
+use anyhow::Result;
+use api_gateway::{AuditOutcome, AuditRecord, AuditSink};
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::Path,
+    http::{Method, Request, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use modkit::{
+    ClientHub, Module,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
+};
+use modkit_security::SecurityContext;
+use serde_json::{Value, json};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const SUBJECT: Uuid = Uuid::from_u128(7);
+const TENANT: Uuid = Uuid::from_u128(1);
+
+struct TestConfigProvider {
+    config: Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&Value> {
+        self.config.get(module)
+    }
+}
+
+/// `AuthN` client accepting any bearer token as the same subject, with a read-only scope.
+struct FixedSubject;
+
+#[async_trait]
+impl AuthNResolverClient for FixedSubject {
+    async fn authenticate(
+        &self,
+        _bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(SUBJECT)
+                .subject_tenant_id(TENANT)
+                .token_scopes(vec!["items:read".to_owned()])
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct RecordingSink {
+    records: Mutex<Vec<AuditRecord>>,
+}
+
+#[async_trait]
+impl AuditSink for RecordingSink {
+    async fn record(&self, record: &AuditRecord) -> anyhow::Result<()> {
+        self.records.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+}
+
+impl RecordingSink {
+    /// Records written so far, once at least `n` reached the sink.
+    async fn wait_for(&self, n: usize) -> Vec<AuditRecord> {
+        for _ in 0..100 {
+            let records = self.records.lock().unwrap().clone();
+            if records.len() >= n {
+                return records;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("expected {n} audit records");
+    }
+}
+
+/// Request body streamed in chunks, without a known length.
+struct Chunks(Vec<&'static [u8]>);
+
+impl futures_core::Stream for Chunks {
+    type Item = Result<&'static [u8], std::io::Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready((!self.0.is_empty()).then(|| Ok(self.0.remove(0))))
+    }
+}
+
+struct ItemsModule;
+
+#[async_trait]
+impl Module for ItemsModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for ItemsModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::post("/items/v1/items")
+            .operation_id("items.create")
+            .authenticated()
+            .no_license_required()
+            .auditable()
+            .audit_redact(["pin"])
+            .handler(|Json(body): Json<Value>| async move {
+                (StatusCode::CREATED, Json(body)).into_response()
+            })
+            .json_response(StatusCode::CREATED, "Created")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/items/v1/items")
+            .operation_id("items.list")
+            .authenticated()
+            .no_license_required()
+            .handler(|| async { Json(json!([])) })
+            .json_response(StatusCode::OK, "Items")
+            .register(router, openapi);
+
+        let router = OperationBuilder::delete("/items/v1/items/{id}")
+            .operation_id("items.delete")
+            .authenticated()
+            .no_license_required()
+            .auditable()
+            .path_param("id", "Item id")
+            .handler(|Path(id): Path<String>| async move {
+                if id == "missing" {
+                    StatusCode::NOT_FOUND.into_response()
+                } else {
+                    StatusCode::NO_CONTENT.into_response()
+                }
+            })
+            .json_response(StatusCode::NO_CONTENT, "Deleted")
+            .register(router, openapi);
+
+        let router = OperationBuilder::post("/items/v1/items/{id}/purge")
+            .operation_id("items.purge")
+            .authenticated()
+            .no_license_required()
+            .require_scopes(["items:admin"])
+            .auditable()
+            .path_param("id", "Item id")
+            .handler(|| async { StatusCode::NO_CONTENT })
+            .json_response(StatusCode::NO_CONTENT, "Purged")
+            .register(router, openapi);
+
+        let router = OperationBuilder::post("/items/v1/items/{id}/export")
+            .operation_id("items.export")
+            .authenticated()
+            .no_license_required()
+            .auditable()
+            .path_param("id", "Item id")
+            .handler(|| async {
+                tokio::time::sleep(Duration::from_mins(1)).await;
+                StatusCode::NO_CONTENT
+            })
+            .json_response(StatusCode::NO_CONTENT, "Exported")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_app() -> (Router, Arc<RecordingSink>) {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "auth": { "gateway_scope_checks": { "enabled": true } },
+                "audit": { "record_request_body": true },
+                "defaults": { "body_limit_bytes": 64 }
+            }
+        }
+    });
+    let sink = Arc::new(RecordingSink::default());
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(FixedSubject));
+    hub.register::<dyn AuditSink>(sink.clone());
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare");
+    let router = ItemsModule
+        .register_rest(&ctx, router, &api_gateway)
+        .expect("Failed to register routes");
+    let app = api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize");
+    (app, sink)
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> Response {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, "Bearer token")
+        .header("x-request-id", "req-1");
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    app.clone().oneshot(request.unwrap()).await.unwrap()
+}
+
+#[tokio::test]
+async fn auditable_operation_recorded_with_redacted_body() {
+    let (app, sink) = build_app().await;
+
+    let response = send(
+        &app,
+        Method::POST,
+        "/items/v1/items",
+        Some(json!({ "name": "a", "password": "p", "pin": "1234" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let response = send(&app, Method::GET, "/items/v1/items", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let records = sink.wait_for(1).await;
+    // Give a wrongly recorded second operation the chance to show up
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    assert_eq!(
+        sink.records.lock().unwrap().len(),
+        1,
+        "only auditable operations are recorded"
+    );
+    let record = &records[0];
+    assert_eq!(record.operation_id, "items.create");
+    assert_eq!(record.method, "POST");
+    assert_eq!(record.path, "/items/v1/items");
+    assert_eq!(record.subject_id, Some(SUBJECT));
+    assert_eq!(record.tenant_id, Some(TENANT));
+    assert_eq!(record.request_id.as_deref(), Some("req-1"));
+    assert_eq!(record.status, 201);
+    assert_eq!(record.outcome, AuditOutcome::Success);
+    assert_eq!(
+        record.request,
+        Some(json!({ "name": "a", "password": "[REDACTED]", "pin": "[REDACTED]" }))
+    );
+}
+
+#[tokio::test]
+async fn failed_and_denied_invocations_recorded() {
+    let (app, sink) = build_app().await;
+
+    let response = send(&app, Method::DELETE, "/items/v1/items/42", None).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = send(&app, Method::DELETE, "/items/v1/items/missing", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = send(&app, Method::POST, "/items/v1/items/42/purge", None).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let records = sink.wait_for(3).await;
+    let summary: Vec<_> = records
+        .iter()
+        .map(|r| {
+            (
+                r.operation_id.as_str(),
+                r.resource_ids["id"].as_str(),
+                r.outcome,
+            )
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("items.delete", "42", AuditOutcome::Success),
+            ("items.delete", "missing", AuditOutcome::Failure),
+            ("items.purge", "42", AuditOutcome::Denied),
+        ]
+    );
+    assert!(records.iter().all(|r| r.request.is_none()));
+}
+
+#[tokio::test]
+async fn unreadable_body_rejected_and_recorded() {
+    let (app, sink) = build_app().await;
+
+    let body = Body::from_stream(Chunks(vec![
+        br#"{"name": ""#,
+        b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        br#""}"#,
+    ]));
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri("/items/v1/items")
+        .header(header::AUTHORIZATION, "Bearer token")
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .unwrap();
+    let response = app.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let records = sink.wait_for(1).await;
+    assert_eq!(records[0].operation_id, "items.create");
+    assert_eq!(records[0].status, 413);
+    assert_eq!(records[0].outcome, AuditOutcome::Failure);
+    assert!(records[0].request.is_none());
+}
+
+#[tokio::test]
+async fn dropped_invocation_recorded_as_cancelled() {
+    let (app, sink) = build_app().await;
+
+    let dropped = tokio::time::timeout(
+        Duration::from_millis(50),
+        send(&app, Method::POST, "/items/v1/items/42/export", None),
+    )
+    .await;
+    assert!(dropped.is_err(), "the handler must still be running");
+
+    let records = sink.wait_for(1).await;
+    assert_eq!(records[0].operation_id, "items.export");
+    assert_eq!(records[0].resource_ids["id"], "42");
+    assert_eq!(records[0].status, 0);
+    assert_eq!(records[0].outcome, AuditOutcome::Cancelled);
+}
@@ -0,0 +1,259 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the live config reload admin endpoint
+
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+};
+use modkit::{
+    ClientHub, ConfigChange, ConfigReloader, Module,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, ConfigReloadCapability},
+    registry::RegistryBuilder,
+    runtime::{ConfigSource, ModuleSections},
+};
+use modkit_security::SecurityContext;
+use parking_lot::Mutex;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const RELOAD_URI: &str = "/api-gateway/v1/config/reload";
+const ADMIN_TOKEN: &str = "api-gateway:admin";
+
+struct TestConfigProvider {
+    config: Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&Value> {
+        self.config.get(module)
+    }
+}
+
+/// Module that accepts any `config.level` except "invalid"
+#[derive(Default)]
+struct LoggingModule {
+    level: Mutex<String>,
+    applied: Mutex<usize>,
+}
+
+#[async_trait]
+impl Module for LoggingModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ConfigReloadCapability for LoggingModule {
+    async fn validate_config_change(&self, change: &ConfigChange) -> anyhow::Result<()> {
+        let level = level_of(change);
+        anyhow::ensure!(level != "invalid", "unknown log level '{level}'");
+        Ok(())
+    }
+
+    async fn on_config_change(&self, change: &ConfigChange) -> anyhow::Result<()> {
+        level_of(change).clone_into(&mut self.level.lock());
+        *self.applied.lock() += 1;
+        Ok(())
+    }
+}
+
+fn level_of(change: &ConfigChange) -> &str {
+    change.current_raw()["config"]["level"]
+        .as_str()
+        .unwrap_or("info")
+}
+
+/// `AuthN` client treating the bearer token as a comma-separated scope list
+struct ScopesFromToken;
+
+#[async_trait]
+impl AuthNResolverClient for ScopesFromToken {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::from_u128(7))
+                .subject_tenant_id(Uuid::from_u128(1))
+                .token_scopes(bearer_token.split(',').map(str::to_owned).collect())
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+/// Source returning whatever the test last wrote
+struct MemorySource(Mutex<ModuleSections>);
+
+impl ConfigSource for MemorySource {
+    fn load(&self) -> anyhow::Result<ModuleSections> {
+        Ok(self.0.lock().clone())
+    }
+}
+
+fn level_section(level: &str) -> ModuleSections {
+    ModuleSections::from([(
+        "logging".to_owned(),
+        json!({ "config": { "level": level } }),
+    )])
+}
+
+/// Router with authentication enabled; tokens are scope lists (see `ScopesFromToken`).
+async fn build_router(hub: Arc<ClientHub>) -> Router {
+    hub.register::<dyn AuthNResolverClient>(Arc::new(ScopesFromToken));
+    build_router_with_auth(hub, false).await
+}
+
+async fn build_router_with_auth(hub: Arc<ClientHub>, auth_disabled: bool) -> Router {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "enable_docs": false,
+                "cors_enabled": false,
+                "auth_disabled": auth_disabled,
+            }
+        }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn reload(router: &Router) -> (StatusCode, Value) {
+    reload_as(router, Some(ADMIN_TOKEN)).await
+}
+
+async fn reload_as(router: &Router, token: Option<&str>) -> (StatusCode, Value) {
+    let mut request = Request::builder().method(Method::POST).uri(RELOAD_URI);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+    }
+    let response = router
+        .clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, serde_json::from_slice(&body).unwrap())
+}
+
+fn reloader_for(module: &Arc<LoggingModule>) -> Arc<ConfigReloader> {
+    let mut builder = RegistryBuilder::default();
+    builder.register_core_with_meta("logging", &[], module.clone());
+    builder.register_config_reload_with_meta("logging", module.clone());
+    let registry = builder.build_topo_sorted().unwrap();
+    let initial = TestConfigProvider {
+        config: json!({ "logging": { "config": { "level": "info" } } }),
+    };
+    Arc::new(ConfigReloader::new(&registry, &initial))
+}
+
+#[tokio::test]
+async fn reload_applies_changes_and_reports_rejections() {
+    let module = Arc::new(LoggingModule::default());
+    let reloader = reloader_for(&module);
+    let source = Arc::new(MemorySource(Mutex::new(level_section("debug"))));
+    reloader.set_source(source.clone());
+
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<ConfigReloader>(reloader);
+    let router = build_router(hub).await;
+
+    let (status, body) = reload(&router).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "changed": ["logging"] }));
+    assert_eq!(*module.level.lock(), "debug");
+
+    *source.0.lock() = level_section("invalid");
+    let (status, body) = reload(&router).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(
+        body["detail"]
+            .as_str()
+            .unwrap()
+            .contains("unknown log level")
+    );
+    assert_eq!(*module.level.lock(), "debug");
+    assert_eq!(*module.applied.lock(), 1);
+}
+
+#[tokio::test]
+async fn reload_requires_admin_scope() {
+    let module = Arc::new(LoggingModule::default());
+    let reloader = reloader_for(&module);
+    reloader.set_source(Arc::new(MemorySource(Mutex::new(level_section("debug")))));
+
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<ConfigReloader>(reloader);
+    let router = build_router(hub.clone()).await;
+
+    let (status, _) = reload_as(&router, Some("users:read")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    // The first-party wildcard does not grant the admin scope
+    let (status, _) = reload_as(&router, Some("*")).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    // Neither does disabled authentication
+    let (status, _) = reload_as(&build_router_with_auth(hub, true).await, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(*module.applied.lock(), 0);
+
+    let (status, body) = reload_as(&router, Some(ADMIN_TOKEN)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "changed": ["logging"] }));
+}
+
+#[tokio::test]
+async fn reload_without_source_is_a_conflict() {
+    let module = Arc::new(LoggingModule::default());
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<ConfigReloader>(reloader_for(&module));
+    let router = build_router(hub).await;
+
+    let (status, _) = reload(&router).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn reload_endpoint_requires_runtime() {
+    let router = build_router(Arc::new(ClientHub::new())).await;
+    let response = router
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri(RELOAD_URI)
+                .header(header::AUTHORIZATION, format!("Bearer {ADMIN_TOKEN}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,145 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for deprecated operations
+//!
+//! These tests verify that:
+//! 1. Responses of deprecated operations carry `Deprecation`, `Sunset` and `Link` headers
+//! 2. Other operations are left untouched
+//! 3. A passed sunset fails startup only when `fail_on_past_sunset` is set
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use chrono::{DateTime, TimeZone, Utc};
+use modkit::{
+    ClientHub, Module,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&Value> {
+        self.config.get(module)
+    }
+}
+
+struct UsersModule {
+    sunset: DateTime<Utc>,
+}
+
+#[async_trait]
+impl Module for UsersModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for UsersModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::get("/users/v1/users")
+            .operation_id("users.list.v1")
+            .public()
+            .deprecated_since(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap())
+            .sunset(self.sunset)
+            .successor_version("/users/v2/users")
+            .handler(|| async { Json(json!([])) })
+            .json_response(StatusCode::OK, "Users")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/users/v2/users")
+            .operation_id("users.list")
+            .public()
+            .handler(|| async { Json(json!([])) })
+            .json_response(StatusCode::OK, "Users")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_app(sunset: DateTime<Utc>, fail_on_past_sunset: bool) -> Result<Router> {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "auth_disabled": true,
+                "deprecation": { "fail_on_past_sunset": fail_on_past_sunset }
+            }
+        }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = UsersModule { sunset }
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway.rest_finalize(&ctx, router)
+}
+
+async fn get(app: &Router, uri: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn deprecated_operation_carries_lifecycle_headers() {
+    let sunset = Utc.with_ymd_and_hms(2099, 6, 30, 0, 0, 0).unwrap();
+    let app = build_app(sunset, true).await.unwrap();
+
+    let response = get(&app, "/users/v1/users").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers["deprecation"], "@1767225600");
+    assert_eq!(headers["sunset"], "Tue, 30 Jun 2099 00:00:00 GMT");
+    assert_eq!(
+        headers[header::LINK],
+        "</users/v2/users>; rel=\"successor-version\""
+    );
+
+    let response = get(&app, "/users/v2/users").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get("deprecation").is_none());
+    assert!(response.headers().get(header::LINK).is_none());
+}
+
+#[tokio::test]
+async fn past_sunset_fails_startup_only_when_configured() {
+    let sunset = Utc::now() - chrono::Duration::days(1);
+
+    let err = build_app(sunset, true).await.unwrap_err();
+    assert!(err.to_string().contains("users.list.v1"), "{err}");
+
+    let app = build_app(sunset, false).await.unwrap();
+    let response = get(&app, "/users/v1/users").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key("sunset"));
+}
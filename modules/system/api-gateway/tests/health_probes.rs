@@ -0,0 +1,198 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the aggregated `/healthz` and `/readyz` probes
+//!
+//! The public probes only report the status; per-module details are served to
+//! authenticated callers at `/api-gateway/v1/health`.
+
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use modkit::{
+    ClientHub, HealthRegistry, HealthReport, Module,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, HealthCapability},
+};
+use serde_json::{Value, json};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&Value> {
+        self.config.get(module)
+    }
+}
+
+/// Module whose readiness depends on a toggleable "database"
+struct DbModule {
+    db_up: AtomicBool,
+}
+
+#[async_trait]
+impl HealthCapability for DbModule {
+    async fn readiness(&self) -> HealthReport {
+        let ping = if self.db_up.load(Ordering::Acquire) {
+            Ok(())
+        } else {
+            Err("connection refused")
+        };
+        HealthReport::up().with_result("db", &ping)
+    }
+}
+
+/// `AuthN` client rejecting every token
+struct RejectAll;
+
+#[async_trait]
+impl AuthNResolverClient for RejectAll {
+    async fn authenticate(
+        &self,
+        _bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Err(AuthNResolverError::Unauthorized("invalid token".to_owned()))
+    }
+}
+
+async fn build_router(hub: Arc<ClientHub>) -> Router {
+    build_router_with_auth(hub, false).await
+}
+
+async fn build_router_with_auth(hub: Arc<ClientHub>, auth: bool) -> Router {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "enable_docs": false,
+                "cors_enabled": false,
+                "auth_disabled": !auth,
+            }
+        }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn probe_text(router: &Router, uri: &str) -> (StatusCode, String) {
+    let response = router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed");
+    let status = response.status();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    (status, String::from_utf8(body.to_vec()).unwrap())
+}
+
+async fn probe(router: &Router, uri: &str) -> (StatusCode, Value) {
+    let (status, body) = probe_text(router, uri).await;
+    (status, serde_json::from_str(&body).unwrap())
+}
+
+#[tokio::test]
+async fn probes_report_per_module_health() {
+    let module = Arc::new(DbModule {
+        db_up: AtomicBool::new(true),
+    });
+    let health = Arc::new(HealthRegistry::new().with_module(
+        "users",
+        Some(module.clone() as Arc<dyn HealthCapability>),
+        None,
+    ));
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<HealthRegistry>(health.clone());
+    let router = build_router(hub).await;
+
+    // Not ready until the runtime has started all modules
+    let (status, body) = probe(&router, "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(body["status"], "down");
+
+    health.set_serving(true);
+    let (status, body) = probe(&router, "/readyz").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, json!({ "status": "up" }));
+    let (_, details) = probe(&router, "/api-gateway/v1/health").await;
+    assert_eq!(
+        details["readiness"],
+        json!({
+            "status": "up",
+            "modules": { "users": { "status": "up", "checks": [{ "name": "db", "status": "up" }] } }
+        })
+    );
+
+    module.db_up.store(false, Ordering::Release);
+    let (status, body) = probe(&router, "/readyz").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(
+        body,
+        json!({ "status": "down" }),
+        "no details on the public probe"
+    );
+    let (_, details) = probe(&router, "/api-gateway/v1/health").await;
+    assert_eq!(
+        details["readiness"]["modules"]["users"]["checks"][0]["details"]["error"],
+        "connection refused"
+    );
+
+    // Readiness failures do not affect liveness
+    assert_eq!(
+        probe_text(&router, "/healthz").await,
+        (StatusCode::OK, "ok".to_owned())
+    );
+}
+
+#[tokio::test]
+async fn probes_without_runtime_registry_are_up() {
+    let router = build_router(Arc::new(ClientHub::new())).await;
+
+    assert_eq!(
+        probe_text(&router, "/healthz").await,
+        (StatusCode::OK, "ok".to_owned())
+    );
+    assert_eq!(
+        probe(&router, "/readyz").await,
+        (StatusCode::OK, json!({ "status": "up" }))
+    );
+}
+
+#[tokio::test]
+async fn details_require_authentication() {
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(RejectAll));
+    let router = build_router_with_auth(hub, true).await;
+
+    assert_eq!(
+        probe_text(&router, "/healthz").await,
+        (StatusCode::OK, "ok".to_owned())
+    );
+    let (status, _) = probe_text(&router, "/api-gateway/v1/health").await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+}
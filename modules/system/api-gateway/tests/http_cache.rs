@@ -0,0 +1,340 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for HTTP caching semantics
+//!
+//! These tests verify that:
+//! 1. `ETag` and `Cache-Control` are added to successful responses of opted-in operations
+//! 2. `If-None-Match` yields 304 and `If-Match` yields 412
+//! 3. Handler-supplied `ETag`s are kept
+//! 4. Responses are compressed when negotiated and above the size threshold, but not SSE streams
+//! 5. Strong `ETag`s of compressed responses are encoding-specific
+
+use anyhow::Result;
+use async_trait::async_trait;
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use modkit::{
+    ClientHub, Module,
+    api::{OperationBuilder, http_cache::entity_tag},
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
+};
+use serde_json::json;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        self.config.get(module)
+    }
+}
+
+struct CatalogModule;
+
+#[async_trait]
+impl Module for CatalogModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+async fn list_items() -> Json<serde_json::Value> {
+    let items: Vec<_> = (0..200)
+        .map(|i| json!({ "id": i, "name": "item" }))
+        .collect();
+    Json(json!({ "items": items }))
+}
+
+async fn get_item() -> impl IntoResponse {
+    ([(header::ETAG, entity_tag(7))], Json(json!({ "id": 1 })))
+}
+
+async fn export_items() -> impl IntoResponse {
+    ([(header::ETAG, entity_tag(3))], list_items().await)
+}
+
+async fn events() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/event-stream")],
+        "data: x\n\n".repeat(500),
+    )
+}
+
+impl RestApiCapability for CatalogModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::get("/catalog/v1/items")
+            .operation_id("test.list_items")
+            .public()
+            .with_etag()
+            .cache_control("private, max-age=60")
+            .handler(list_items)
+            .json_response(http::StatusCode::OK, "Items")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/catalog/v1/items/{id}")
+            .operation_id("test.get_item")
+            .public()
+            .path_param("id", "Item id")
+            .with_etag()
+            .handler(get_item)
+            .json_response(http::StatusCode::OK, "Item")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/catalog/v1/export")
+            .operation_id("test.export_items")
+            .public()
+            .with_etag()
+            .handler(export_items)
+            .json_response(http::StatusCode::OK, "Items")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/catalog/v1/events")
+            .operation_id("test.events")
+            .public()
+            .handler(events)
+            .json_response(http::StatusCode::OK, "Events")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_app() -> Router {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "auth_disabled": true,
+                "compression": { "min_size_bytes": 256 }
+            }
+        }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = CatalogModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn get(app: &Router, uri: &str, headers: &[(header::HeaderName, &str)]) -> Response {
+    let mut request = Request::get(uri);
+    for (name, value) in headers {
+        request = request.header(name, *value);
+    }
+    app.clone()
+        .oneshot(request.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn etag_and_cache_control_added() {
+    let app = build_app().await;
+
+    let response = get(&app, "/catalog/v1/items", &[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CACHE_CONTROL).unwrap(),
+        "private, max-age=60"
+    );
+    let etag = response.headers().get(header::ETAG).unwrap();
+    assert!(etag.to_str().unwrap().starts_with("W/\""));
+}
+
+#[tokio::test]
+async fn if_none_match_yields_not_modified() {
+    let app = build_app().await;
+    let first = get(&app, "/catalog/v1/items", &[]).await;
+    let etag = first.headers().get(header::ETAG).unwrap().to_str().unwrap();
+
+    let response = get(&app, "/catalog/v1/items", &[(header::IF_NONE_MATCH, etag)]).await;
+
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers().get(header::ETAG).unwrap(), etag);
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    assert!(body.is_empty());
+
+    let changed = get(
+        &app,
+        "/catalog/v1/items",
+        &[(header::IF_NONE_MATCH, "W/\"old\"")],
+    )
+    .await;
+    assert_eq!(changed.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn handler_supplied_etag_is_kept() {
+    let app = build_app().await;
+
+    let response = get(&app, "/catalog/v1/items/1", &[]).await;
+    assert_eq!(response.headers().get(header::ETAG).unwrap(), "\"7\"");
+
+    let response = get(
+        &app,
+        "/catalog/v1/items/1",
+        &[(header::IF_NONE_MATCH, "\"7\"")],
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = get(&app, "/catalog/v1/items/1", &[(header::IF_MATCH, "\"7\"")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get(&app, "/catalog/v1/items/1", &[(header::IF_MATCH, "\"6\"")]).await;
+    assert_eq!(response.status(), StatusCode::PRECONDITION_FAILED);
+}
+
+#[tokio::test]
+async fn responses_compressed_when_negotiated() {
+    let app = build_app().await;
+
+    for encoding in ["gzip", "br", "zstd"] {
+        let response = get(
+            &app,
+            "/catalog/v1/items",
+            &[(header::ACCEPT_ENCODING, encoding)],
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get(header::CONTENT_ENCODING).unwrap(),
+            encoding
+        );
+    }
+
+    let small = get(
+        &app,
+        "/catalog/v1/items/1",
+        &[(header::ACCEPT_ENCODING, "gzip")],
+    )
+    .await;
+    assert!(small.headers().get(header::CONTENT_ENCODING).is_none());
+
+    let plain = get(&app, "/catalog/v1/items", &[]).await;
+    assert!(plain.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn strong_etags_name_the_content_coding() {
+    let app = build_app().await;
+
+    let plain = get(&app, "/catalog/v1/export", &[]).await;
+    assert_eq!(plain.headers().get(header::ETAG).unwrap(), "\"3\"");
+
+    let gzip = [(header::ACCEPT_ENCODING, "gzip")];
+    let compressed = get(&app, "/catalog/v1/export", &gzip).await;
+    assert_eq!(
+        compressed.headers().get(header::CONTENT_ENCODING).unwrap(),
+        "gzip"
+    );
+    assert_eq!(
+        compressed.headers().get(header::ETAG).unwrap(),
+        "\"3-gzip\""
+    );
+
+    let revalidated = get(
+        &app,
+        "/catalog/v1/export",
+        &[gzip[0].clone(), (header::IF_NONE_MATCH, "\"3-gzip\"")],
+    )
+    .await;
+    assert_eq!(revalidated.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(
+        revalidated.headers().get(header::ETAG).unwrap(),
+        "\"3-gzip\""
+    );
+
+    let matched = get(
+        &app,
+        "/catalog/v1/export",
+        &[(header::IF_MATCH, "\"3-gzip\"")],
+    )
+    .await;
+    assert_eq!(matched.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn event_streams_not_compressed() {
+    let app = build_app().await;
+
+    let response = get(
+        &app,
+        "/catalog/v1/events",
+        &[(header::ACCEPT_ENCODING, "gzip")],
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+}
+
+#[tokio::test]
+async fn openapi_documents_caching() {
+    let config = json!({
+        "api-gateway": { "config": { "bind_addr": "0.0.0.0:8080", "auth_disabled": true } }
+    });
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(ClientHub::new()),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.unwrap();
+    let _router = CatalogModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .unwrap();
+
+    let doc = serde_json::to_value(api_gateway.build_openapi().unwrap()).unwrap();
+    let op = &doc["paths"]["/catalog/v1/items"]["get"];
+
+    let headers = &op["responses"]["200"]["headers"];
+    assert!(headers.get("ETag").is_some());
+    assert!(
+        headers["Cache-Control"]["description"]
+            .as_str()
+            .unwrap()
+            .contains("private, max-age=60")
+    );
+    assert!(op["responses"].get("304").is_some());
+    assert!(
+        op["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["name"] == "If-None-Match")
+    );
+}
@@ -0,0 +1,249 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for `Idempotency-Key` handling
+//!
+//! These tests verify that:
+//! 1. Retries with the same key replay the first response without re-executing the handler
+//! 2. Reusing a key with a different body is rejected with 422
+//! 3. A retry while the first request is still running is rejected with 409
+//! 4. Keys are scoped to the caller, and server errors are not recorded
+
+use anyhow::Result;
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use modkit::{
+    ClientHub, Module,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
+};
+use modkit_security::SecurityContext;
+use serde_json::json;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        self.config.get(module)
+    }
+}
+
+/// `AuthN` client treating the bearer token as the subject id.
+struct SubjectFromToken;
+
+#[async_trait]
+impl AuthNResolverClient for SubjectFromToken {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::parse_str(bearer_token).unwrap())
+                .subject_tenant_id(Uuid::from_u128(1))
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+#[derive(Default)]
+struct OrdersModule {
+    calls: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl Module for OrdersModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl RestApiCapability for OrdersModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let calls = Arc::clone(&self.calls);
+        let router = OperationBuilder::post("/orders/v1/orders")
+            .operation_id("test.create_order")
+            .authenticated()
+            .no_license_required()
+            .with_idempotency_key()
+            .handler(move |body: String| {
+                let calls = Arc::clone(&calls);
+                async move {
+                    let n = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                    if body.contains("slow") {
+                        tokio::time::sleep(Duration::from_millis(300)).await;
+                    }
+                    if body.contains("fail") {
+                        return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                    }
+                    (StatusCode::CREATED, Json(json!({ "order": n }))).into_response()
+                }
+            })
+            .json_response(http::StatusCode::CREATED, "Created")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_app() -> (Router, Arc<AtomicUsize>) {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "auth_disabled": false
+            }
+        }
+    });
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(SubjectFromToken));
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let module = OrdersModule::default();
+    let router = module
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    let app = api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize");
+    (app, module.calls)
+}
+
+async fn create_order(app: &Router, subject: Uuid, key: Option<&str>, body: &str) -> Response {
+    let mut request = Request::post("/orders/v1/orders")
+        .header(header::AUTHORIZATION, format!("Bearer {subject}"))
+        .header(header::CONTENT_TYPE, "text/plain");
+    if let Some(key) = key {
+        request = request.header("Idempotency-Key", key);
+    }
+    app.clone()
+        .oneshot(request.body(Body::from(body.to_owned())).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_json(response: Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn retry_replays_first_response() {
+    let (app, calls) = build_app().await;
+    let subject = Uuid::new_v4();
+
+    let first = create_order(&app, subject, Some("order-1"), "coffee").await;
+    assert_eq!(first.status(), StatusCode::CREATED);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    assert_eq!(body_json(first).await, json!({ "order": 1 }));
+
+    let retry = create_order(&app, subject, Some("order-1"), "coffee").await;
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    assert_eq!(retry.headers().get("idempotent-replayed").unwrap(), "true");
+    assert_eq!(body_json(retry).await, json!({ "order": 1 }));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Requests without a key are executed every time
+    create_order(&app, subject, None, "coffee").await;
+    create_order(&app, subject, None, "coffee").await;
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn different_body_is_rejected() {
+    let (app, calls) = build_app().await;
+    let subject = Uuid::new_v4();
+
+    create_order(&app, subject, Some("order-1"), "coffee").await;
+    let response = create_order(&app, subject, Some("order-1"), "tea").await;
+
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn concurrent_duplicate_conflicts() {
+    let (app, calls) = build_app().await;
+    let subject = Uuid::new_v4();
+
+    let first = tokio::spawn({
+        let app = app.clone();
+        async move { create_order(&app, subject, Some("order-1"), "slow coffee").await }
+    });
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let duplicate = create_order(&app, subject, Some("order-1"), "slow coffee").await;
+    assert_eq!(duplicate.status(), StatusCode::CONFLICT);
+
+    assert_eq!(first.await.unwrap().status(), StatusCode::CREATED);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn keys_are_scoped_to_subject() {
+    let (app, calls) = build_app().await;
+
+    create_order(&app, Uuid::new_v4(), Some("order-1"), "coffee").await;
+    let other = create_order(&app, Uuid::new_v4(), Some("order-1"), "coffee").await;
+
+    assert!(other.headers().get("idempotent-replayed").is_none());
+    assert_eq!(body_json(other).await, json!({ "order": 2 }));
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn server_errors_are_not_recorded() {
+    let (app, calls) = build_app().await;
+    let subject = Uuid::new_v4();
+
+    let failed = create_order(&app, subject, Some("order-1"), "fail").await;
+    assert_eq!(failed.status(), StatusCode::INTERNAL_SERVER_ERROR);
+
+    // The claim is released asynchronously
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let retry = create_order(&app, subject, Some("order-1"), "fail").await;
+    assert_eq!(retry.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(retry.headers().get("idempotent-replayed").is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn invalid_key_is_rejected() {
+    let (app, calls) = build_app().await;
+
+    let response = create_order(&app, Uuid::new_v4(), Some("two words"), "coffee").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(calls.load(Ordering::SeqCst), 0);
+}
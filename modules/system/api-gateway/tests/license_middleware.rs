@@ -8,6 +8,7 @@ use axum::{
     http::{Request, StatusCode},
     response::IntoResponse,
 };
+use license_resolver_sdk::{EnabledFeatures, LicenseResolverClient, LicenseResolverError};
 use modkit::{
     ClientHub, Module,
     api::OperationBuilder,
@@ -16,8 +17,10 @@ use modkit::{
     context::ModuleCtx,
     contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
 };
+use modkit_security::{SecurityContext, constants::DEFAULT_TENANT_ID};
 use serde_json::json;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use tower::ServiceExt;
 use uuid::Uuid;
 
@@ -32,8 +35,10 @@ impl ConfigProvider for TestConfigProvider {
 }
 
 fn create_api_gateway_ctx(config: serde_json::Value) -> ModuleCtx {
-    let hub = Arc::new(ClientHub::new());
+    create_api_gateway_ctx_with_hub(config, Arc::new(ClientHub::new()))
+}
 
+fn create_api_gateway_ctx_with_hub(config: serde_json::Value, hub: Arc<ClientHub>) -> ModuleCtx {
     ModuleCtx::new(
         "api-gateway",
         Uuid::new_v4(),
@@ -252,3 +257,162 @@ async fn allows_no_license_requirement() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+const OTHER_FEATURE: &str = "some_other_feature";
+
+/// License resolver with fixed features that counts calls.
+struct FakeLicenses {
+    global: EnabledFeatures,
+    tenant: EnabledFeatures,
+    fail: Option<fn() -> LicenseResolverError>,
+    calls: AtomicUsize,
+}
+
+impl FakeLicenses {
+    fn new(global: &[&str], tenant: &[&str]) -> Self {
+        Self {
+            global: EnabledFeatures::new(global.iter().copied()),
+            tenant: EnabledFeatures::new(tenant.iter().copied()),
+            fail: None,
+            calls: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl LicenseResolverClient for FakeLicenses {
+    async fn get_global_features(
+        &self,
+        _ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if let Some(err) = self.fail {
+            return Err(err());
+        }
+        Ok(self.global.clone())
+    }
+
+    async fn get_tenant_features(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        if tenant_id == DEFAULT_TENANT_ID {
+            Ok(self.tenant.clone())
+        } else {
+            Ok(EnabledFeatures::default())
+        }
+    }
+}
+
+async fn router_with_licenses(licenses: Arc<FakeLicenses>) -> Router {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "enable_docs": false,
+                "cors_enabled": false,
+                "auth_disabled": true
+            }
+        }
+    });
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn LicenseResolverClient>(licenses);
+
+    let api_ctx = create_api_gateway_ctx_with_hub(config, hub);
+    let test_ctx = create_test_module_ctx();
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&api_ctx).await.expect("Failed to init");
+
+    let router = TestLicenseModule
+        .register_rest(&test_ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+
+    api_gateway
+        .rest_finalize(&api_ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn get_status(router: &Router, uri: &str) -> StatusCode {
+    router
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .expect("Request failed")
+        .status()
+}
+
+#[tokio::test]
+async fn resolver_enables_tenant_features() {
+    let licenses = Arc::new(FakeLicenses::new(
+        &["gts.x.core.lic.feat.v1~x.core.global.base.v1"],
+        &[OTHER_FEATURE],
+    ));
+    let router = router_with_licenses(licenses).await;
+
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/bad").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/good").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn resolver_rejects_features_not_enabled_and_caches_results() {
+    let licenses = Arc::new(FakeLicenses::new(&[OTHER_FEATURE], &[]));
+    let router = router_with_licenses(licenses.clone()).await;
+
+    // The base feature is only available when the resolver reports it
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/good").await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/bad").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/none").await,
+        StatusCode::OK
+    );
+
+    // Global and tenant features were fetched once and then served from the cache
+    assert_eq!(licenses.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn resolver_failure_returns_service_unavailable() {
+    let licenses = Arc::new(FakeLicenses {
+        fail: Some(|| LicenseResolverError::ServiceUnavailable("starting".to_owned())),
+        ..FakeLicenses::new(&[], &[])
+    });
+    let router = router_with_licenses(licenses).await;
+
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/good").await,
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+}
+
+#[tokio::test]
+async fn resolver_without_plugin_allows_base_feature_only() {
+    let licenses = Arc::new(FakeLicenses {
+        fail: Some(|| LicenseResolverError::NoPluginAvailable),
+        ..FakeLicenses::new(&[], &[])
+    });
+    let router = router_with_licenses(licenses).await;
+
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/good").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&router, "/tests/v1/license/bad").await,
+        StatusCode::FORBIDDEN
+    );
+}
@@ -0,0 +1,269 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for the runtime log level endpoints
+//!
+//! These tests verify that:
+//! 1. Configured levels and active overrides are listed
+//! 2. Overrides are applied by module name and reverted on `DELETE`
+//! 3. Invalid levels and TTLs are rejected with `400`
+//! 4. Changing a level requires the admin scope itself, not the `*` wildcard
+
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Router,
+    body::Body,
+    http::{Method, Request, StatusCode, header},
+    response::Response,
+};
+use modkit::{
+    ClientHub, Module,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::ApiGatewayCapability,
+    telemetry::log_levels::{LogLevels, install_log_levels},
+};
+use modkit_security::SecurityContext;
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, Once};
+use tower::ServiceExt;
+use tracing::level_filters::LevelFilter;
+use uuid::Uuid;
+
+type Applied = Arc<Mutex<Vec<BTreeMap<String, LevelFilter>>>>;
+
+struct TestConfigProvider {
+    config: Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&Value> {
+        self.config.get(module)
+    }
+}
+
+/// The controller is process-global, so it is installed once; reloads are recorded.
+fn install() -> Applied {
+    static INIT: Once = Once::new();
+    static APPLIED: Mutex<Option<Applied>> = Mutex::new(None);
+    INIT.call_once(|| {
+        let applied = Applied::default();
+        let record = applied.clone();
+        install_log_levels(LogLevels::new(
+            LevelFilter::INFO,
+            BTreeMap::from([("sqlx".to_owned(), LevelFilter::WARN)]),
+            Box::new(move |overrides| {
+                record.lock().unwrap().push(overrides.clone());
+                Ok(())
+            }),
+        ));
+        *APPLIED.lock().unwrap() = Some(applied);
+    });
+    APPLIED.lock().unwrap().clone().unwrap()
+}
+
+/// `AuthN` client treating the bearer token as a comma-separated scope list.
+struct ScopesFromToken;
+
+#[async_trait]
+impl AuthNResolverClient for ScopesFromToken {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::from_u128(7))
+                .subject_tenant_id(Uuid::from_u128(1))
+                .token_scopes(bearer_token.split(',').map(str::to_owned).collect())
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+/// Token of the `send` requests; changing levels needs the admin scope itself.
+const ADMIN_TOKEN: &str = "api-gateway:admin";
+
+async fn build_app() -> Router {
+    build_app_with(json!({ "bind_addr": "0.0.0.0:8080" })).await
+}
+
+async fn build_app_with(config: Value) -> Router {
+    let config = json!({ "api-gateway": { "config": config } });
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(ScopesFromToken));
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn send(app: &Router, method: Method, uri: &str, body: Option<Value>) -> Response {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header(header::AUTHORIZATION, format!("Bearer {ADMIN_TOKEN}"));
+    let request = match body {
+        Some(body) => request
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string())),
+        None => request.body(Body::empty()),
+    };
+    app.clone().oneshot(request.unwrap()).await.unwrap()
+}
+
+async fn json_body(response: Response) -> Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    serde_json::from_slice(&body).unwrap()
+}
+
+#[tokio::test]
+async fn override_set_listed_and_cleared() {
+    let applied = install();
+    let app = build_app().await;
+    let uri = "/api-gateway/v1/log-levels/api-gateway";
+
+    let response = send(
+        &app,
+        Method::PUT,
+        uri,
+        Some(json!({ "level": "debug", "ttl_secs": 60 })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["target"], "api_gateway");
+    assert_eq!(body["level"], "debug");
+
+    let response = send(&app, Method::GET, "/api-gateway/v1/log-levels", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = json_body(response).await;
+    assert_eq!(body["default"], "info");
+    assert_eq!(body["configured"]["sqlx"], "warn");
+    assert_eq!(body["overrides"][0]["target"], "api_gateway");
+
+    let response = send(&app, Method::DELETE, uri, None).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    let response = send(&app, Method::DELETE, uri, None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let applied = applied.lock().unwrap();
+    assert!(applied.contains(&BTreeMap::from([(
+        "api_gateway".to_owned(),
+        LevelFilter::DEBUG
+    )])));
+    assert!(applied.last().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn invalid_override_rejected() {
+    install();
+    let app = build_app().await;
+    let uri = "/api-gateway/v1/log-levels/sqlx";
+
+    let response = send(&app, Method::PUT, uri, Some(json!({ "level": "loud" }))).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = send(
+        &app,
+        Method::PUT,
+        uri,
+        Some(json!({ "level": "trace", "ttl_secs": 0 })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn changes_require_admin_scope() {
+    install();
+    let app = build_app_with(json!({ "bind_addr": "0.0.0.0:8080" })).await;
+    let uri = "/api-gateway/v1/log-levels/sqlx";
+    let request = |method: Method, uri: &str, token: &str| {
+        let builder = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::AUTHORIZATION, format!("Bearer {token}"));
+        builder.body(Body::empty()).unwrap()
+    };
+
+    let response = app
+        .clone()
+        .oneshot(request(
+            Method::GET,
+            "/api-gateway/v1/log-levels",
+            "users:read",
+        ))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method(Method::PUT)
+                .uri(uri)
+                .header(header::AUTHORIZATION, "Bearer users:read")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(json!({ "level": "trace" }).to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .clone()
+        .oneshot(request(Method::DELETE, uri, "users:read"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The first-party wildcard does not grant the admin scope
+    let response = app
+        .clone()
+        .oneshot(request(Method::DELETE, uri, "*"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // Passes the scope check; there is just nothing to revert
+    let response = app
+        .oneshot(request(Method::DELETE, uri, "users:read,api-gateway:admin"))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn changes_rejected_when_auth_is_disabled() {
+    install();
+    let app = build_app_with(json!({ "bind_addr": "0.0.0.0:8080", "auth_disabled": true })).await;
+
+    let response = send(
+        &app,
+        Method::PUT,
+        "/api-gateway/v1/log-levels/sqlx",
+        Some(json!({ "level": "trace" })),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
@@ -0,0 +1,263 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for HTTP server metrics
+//!
+//! These tests verify that:
+//! 1. Requests are recorded per method, matched route and status
+//! 2. `/metrics` serves the Prometheus text format, to admin tokens unless configured public
+//! 3. The endpoint is not mounted when metrics are disabled in the gateway config
+
+use anyhow::Result;
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+    response::Response,
+};
+use modkit::{
+    ClientHub, Module,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
+    telemetry::MetricsConfig,
+};
+use modkit_security::SecurityContext;
+use serde_json::json;
+use std::sync::{Arc, Once};
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        self.config.get(module)
+    }
+}
+
+/// `AuthN` client treating the bearer token as a comma-separated scope list.
+struct ScopesFromToken;
+
+#[async_trait]
+impl AuthNResolverClient for ScopesFromToken {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::from_u128(7))
+                .subject_tenant_id(Uuid::from_u128(1))
+                .token_scopes(bearer_token.split(',').map(str::to_owned).collect())
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+struct CatalogModule;
+
+#[async_trait]
+impl Module for CatalogModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+async fn get_item() -> Json<serde_json::Value> {
+    Json(json!({ "id": 1 }))
+}
+
+impl RestApiCapability for CatalogModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::get("/catalog/v1/items/{id}")
+            .operation_id("test.get_item")
+            .public()
+            .path_param("id", "Item id")
+            .handler(get_item)
+            .json_response(http::StatusCode::OK, "Item")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+/// The meter provider is process-global, so it is installed once for all tests.
+fn install_metrics() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let cfg = MetricsConfig {
+            enabled: true,
+            prometheus: true,
+            ..MetricsConfig::default()
+        };
+        modkit::telemetry::init_metrics(&cfg).expect("Failed to init metrics");
+    });
+}
+
+async fn build_app(gateway_config: serde_json::Value) -> Router {
+    install_metrics();
+    let mut gateway_config = gateway_config;
+    gateway_config["bind_addr"] = json!("0.0.0.0:8080");
+    let config = json!({ "api-gateway": { "config": gateway_config } });
+    let hub = ClientHub::new();
+    hub.register::<dyn AuthNResolverClient>(Arc::new(ScopesFromToken));
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        Arc::new(hub),
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = api_gateway
+        .rest_prepare(&ctx, Router::new())
+        .expect("Failed to prepare");
+    let router = CatalogModule
+        .register_rest(&ctx, router, &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn get(app: &Router, uri: &str) -> Response {
+    app.clone()
+        .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn get_as(app: &Router, uri: &str, token: &str) -> Response {
+    app.clone()
+        .oneshot(
+            Request::get(uri)
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn body_text(response: Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn requests_recorded_per_route() {
+    let app = build_app(json!({ "auth_disabled": true, "metrics": { "public": true } })).await;
+
+    assert_eq!(
+        get(&app, "/catalog/v1/items/1").await.status(),
+        StatusCode::OK
+    );
+    assert_eq!(
+        get(&app, "/catalog/v1/items/2").await.status(),
+        StatusCode::OK
+    );
+
+    let response = get(&app, "/metrics").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(
+        response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4")
+    );
+    let text = body_text(response).await;
+    assert!(text.contains("# TYPE http_server_request_duration_seconds histogram"));
+    let count = text
+        .lines()
+        .find(|line| {
+            line.starts_with("http_server_request_duration_seconds_count{")
+                && line.contains(r#"http_route="/catalog/v1/items/{id}""#)
+                && line.contains(r#"http_response_status_code="200""#)
+        })
+        .expect("route series missing");
+    let value: u64 = count.rsplit(' ').next().unwrap().parse().unwrap();
+    assert!(value >= 2, "unexpected count line: {count}");
+    assert!(!text.contains("/catalog/v1/items/1\""));
+    assert!(text.contains("http_server_active_requests"));
+}
+
+#[tokio::test]
+async fn metrics_endpoint_requires_auth_by_default() {
+    let app = build_app(json!({ "auth_disabled": false })).await;
+
+    let response = get(&app, "/metrics").await;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let response = get_as(&app, "/metrics", "users:read").await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    // Series carry tenant IDs: the first-party wildcard does not grant the admin scope
+    let response = get_as(&app, "/metrics", "*").await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    let response = get_as(&app, "/metrics", "api-gateway:admin").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Neither does disabled authentication
+    let app = build_app(json!({ "auth_disabled": true })).await;
+    let response = get(&app, "/metrics").await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[tokio::test]
+async fn metrics_endpoint_can_be_public() {
+    let app = build_app(json!({
+        "auth_disabled": false,
+        "metrics": { "path": "/internal/metrics", "public": true }
+    }))
+    .await;
+
+    let response = get(&app, "/internal/metrics").await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn non_standard_methods_recorded_as_other() {
+    let app = build_app(json!({ "auth_disabled": true, "metrics": { "public": true } })).await;
+
+    let method = http::Method::from_bytes(b"PURGE").unwrap();
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .method(method)
+                .uri("/catalog/v1/items/1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let text = body_text(get(&app, "/metrics").await).await;
+    assert!(text.contains(r#"http_request_method="_OTHER""#));
+    assert!(!text.contains("PURGE"));
+}
+
+#[tokio::test]
+async fn endpoint_absent_when_disabled() {
+    let app = build_app(json!({
+        "auth_disabled": true,
+        "metrics": { "enabled": false }
+    }))
+    .await;
+
+    let response = get(&app, "/metrics").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
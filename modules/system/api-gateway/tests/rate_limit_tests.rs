@@ -4,16 +4,21 @@
 
 use anyhow::Result;
 use async_trait::async_trait;
-use axum::{Router, extract::Json, routing::get};
+use axum::{Router, body::Body, extract::Json, routing::get};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use http::{Request, StatusCode};
 use modkit::{
     Module, ModuleCtx, RestApiCapability,
     api::OperationBuilder,
     config::ConfigProvider,
     contracts::{ApiGatewayCapability, OpenApiRegistry},
 };
+use modkit_security::constants::DEFAULT_TENANT_ID;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::time::{Duration, sleep};
+use tower::ServiceExt;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
@@ -249,3 +254,112 @@ async fn test_rate_limit_metadata_stored() {
     let test_op = json.pointer("/paths/~1tests~1v1~1test/get");
     assert!(test_op.is_some(), "Test endpoint should be in OpenAPI");
 }
+
+async fn build_app(config: &serde_json::Value) -> Result<Router> {
+    let api_gateway = api_gateway::ApiGateway::default();
+    let ctx = create_test_module_ctx_with_config(config);
+    api_gateway.init(&ctx).await?;
+    let router = RateLimitedModule.register_rest(&ctx, Router::new(), &api_gateway)?;
+    api_gateway.rest_finalize(&ctx, router)
+}
+
+async fn get_limited(app: &Router) -> axum::response::Response {
+    get_uri(app, "/tests/v1/limited").await
+}
+
+async fn get_uri(app: &Router, uri: &str) -> axum::response::Response {
+    app.clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+fn header<'a>(resp: &'a axum::response::Response, name: &str) -> Option<&'a str> {
+    resp.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+#[tokio::test]
+async fn test_rate_limit_headers_on_response() {
+    let app = build_app(&serde_json::json!({
+        "bind_addr": "127.0.0.1:0",
+        "auth_disabled": true
+    }))
+    .await
+    .unwrap();
+
+    let ok = get_limited(&app).await;
+    assert_eq!(ok.status(), StatusCode::OK);
+    assert_eq!(header(&ok, "RateLimit-Policy"), Some("\"route\";q=1;w=1"));
+    assert_eq!(header(&ok, "RateLimit"), Some("\"route\";r=0;t=1"));
+
+    let limited = get_limited(&app).await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&limited, "RateLimit"), Some("\"route\";r=0;t=1"));
+    assert_eq!(header(&limited, "Retry-After"), Some("1"));
+}
+
+#[tokio::test]
+async fn test_tenant_keyed_rate_limit_uses_tenant_override() {
+    let app = build_app(&serde_json::json!({
+        "bind_addr": "127.0.0.1:0",
+        "auth_disabled": true,
+        "defaults": {
+            "rate_limit": {
+                "key": "tenant",
+                "tenant_overrides": [
+                    { "tenant_id": DEFAULT_TENANT_ID, "rps": 1, "burst": 3 }
+                ]
+            }
+        }
+    }))
+    .await
+    .unwrap();
+
+    // The override also replaces the tighter route limits (burst 1) of /tests/v1/limited
+    for _ in 0..3 {
+        let resp = get_limited(&app).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            header(&resp, "RateLimit-Policy"),
+            Some("\"tenant\";q=3;w=3")
+        );
+    }
+    let limited = get_limited(&app).await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let pk = BASE64.encode(format!("tenant={DEFAULT_TENANT_ID}"));
+    for remaining in (0..3).rev() {
+        let resp = get_uri(&app, "/tests/v1/normal").await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(
+            header(&resp, "RateLimit-Policy"),
+            Some("\"tenant\";q=3;w=3")
+        );
+        let expected = format!("\"tenant\";r={remaining};t={};pk=:{pk}:", 3 - remaining);
+        assert_eq!(header(&resp, "RateLimit"), Some(expected.as_str()));
+    }
+
+    let limited = get_uri(&app, "/tests/v1/normal").await;
+    assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(header(&limited, "Retry-After").is_some());
+}
+
+#[tokio::test]
+async fn test_tenant_overrides_require_tenant_key() {
+    let result = build_app(&serde_json::json!({
+        "bind_addr": "127.0.0.1:0",
+        "auth_disabled": true,
+        "defaults": {
+            "rate_limit": {
+                "key": "subject",
+                "tenant_overrides": [
+                    { "tenant_id": DEFAULT_TENANT_ID, "rps": 1, "burst": 1 }
+                ]
+            }
+        }
+    }))
+    .await;
+
+    let err = result.expect_err("tenant overrides without tenant key must be rejected");
+    assert!(err.to_string().contains("tenant_overrides"), "{err:#}");
+}
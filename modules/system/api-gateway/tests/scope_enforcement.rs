@@ -0,0 +1,240 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! Integration tests for gateway-level token scope enforcement
+//!
+//! These tests verify that:
+//! 1. Operation-declared scopes are enforced when gateway scope checks are enabled
+//! 2. Configured route patterns add scope requirements
+//! 3. `*` tokens pass and scope-less tokens are rejected
+//! 4. Nothing is enforced when gateway scope checks are disabled
+
+use anyhow::Result;
+use async_trait::async_trait;
+use authn_resolver_sdk::{AuthNResolverClient, AuthNResolverError, AuthenticationResult};
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use modkit::{
+    ClientHub, Module,
+    api::OperationBuilder,
+    config::ConfigProvider,
+    context::ModuleCtx,
+    contracts::{ApiGatewayCapability, OpenApiRegistry, RestApiCapability},
+};
+use modkit_security::SecurityContext;
+use serde_json::json;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+struct TestConfigProvider {
+    config: serde_json::Value,
+}
+
+impl ConfigProvider for TestConfigProvider {
+    fn get_module_config(&self, module: &str) -> Option<&serde_json::Value> {
+        self.config.get(module)
+    }
+}
+
+/// `AuthN` client treating the bearer token as a comma-separated list of token scopes.
+struct ScopesFromToken;
+
+#[async_trait]
+impl AuthNResolverClient for ScopesFromToken {
+    async fn authenticate(
+        &self,
+        bearer_token: &str,
+    ) -> Result<AuthenticationResult, AuthNResolverError> {
+        let scopes = bearer_token
+            .split(',')
+            .filter(|s| !s.is_empty() && *s != "none")
+            .map(ToOwned::to_owned)
+            .collect();
+        Ok(AuthenticationResult {
+            security_context: SecurityContext::builder()
+                .subject_id(Uuid::new_v4())
+                .subject_tenant_id(Uuid::new_v4())
+                .token_scopes(scopes)
+                .build()
+                .unwrap(),
+        })
+    }
+}
+
+struct ScopedModule;
+
+#[async_trait]
+impl Module for ScopedModule {
+    async fn init(&self, _ctx: &ModuleCtx) -> Result<()> {
+        Ok(())
+    }
+}
+
+async fn ok_handler() -> Json<serde_json::Value> {
+    Json(json!({ "ok": true }))
+}
+
+impl RestApiCapability for ScopedModule {
+    fn register_rest(
+        &self,
+        _ctx: &ModuleCtx,
+        router: Router,
+        openapi: &dyn OpenApiRegistry,
+    ) -> Result<Router> {
+        let router = OperationBuilder::get("/events/v1/events")
+            .operation_id("test.list_events")
+            .authenticated()
+            .no_license_required()
+            .require_scopes(["read:events", "write:events"])
+            .handler(ok_handler)
+            .json_response(http::StatusCode::OK, "Events")
+            .register(router, openapi);
+
+        let router = OperationBuilder::get("/admin/v1/settings")
+            .operation_id("test.get_settings")
+            .authenticated()
+            .no_license_required()
+            .handler(ok_handler)
+            .json_response(http::StatusCode::OK, "Settings")
+            .register(router, openapi);
+
+        Ok(router)
+    }
+}
+
+async fn build_app(scope_checks: serde_json::Value) -> Router {
+    let config = json!({
+        "api-gateway": {
+            "config": {
+                "bind_addr": "0.0.0.0:8080",
+                "auth_disabled": false,
+                "auth": { "gateway_scope_checks": scope_checks }
+            }
+        }
+    });
+    let hub = Arc::new(ClientHub::new());
+    hub.register::<dyn AuthNResolverClient>(Arc::new(ScopesFromToken));
+    let ctx = ModuleCtx::new(
+        "api-gateway",
+        Uuid::new_v4(),
+        Arc::new(TestConfigProvider { config }),
+        hub,
+        tokio_util::sync::CancellationToken::new(),
+        None,
+    );
+
+    let api_gateway = api_gateway::ApiGateway::default();
+    api_gateway.init(&ctx).await.expect("Failed to init");
+    let router = ScopedModule
+        .register_rest(&ctx, Router::new(), &api_gateway)
+        .expect("Failed to register routes");
+    api_gateway
+        .rest_finalize(&ctx, router)
+        .expect("Failed to finalize")
+}
+
+async fn get_status(app: &Router, uri: &str, token: &str) -> StatusCode {
+    app.clone()
+        .oneshot(
+            Request::builder()
+                .uri(uri)
+                .header(header::AUTHORIZATION, format!("Bearer {token}"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn operation_scopes_enforced_when_enabled() {
+    let app = build_app(json!({ "enabled": true })).await;
+
+    assert_eq!(
+        get_status(&app, "/events/v1/events", "read:events").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&app, "/events/v1/events", "profile,write:events").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&app, "/events/v1/events", "*").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&app, "/events/v1/events", "none").await,
+        StatusCode::FORBIDDEN,
+        "tokens without scopes are rejected"
+    );
+    assert_eq!(
+        get_status(&app, "/events/v1/events", "read:tasks").await,
+        StatusCode::FORBIDDEN
+    );
+}
+
+#[tokio::test]
+async fn forbidden_response_is_problem() {
+    let app = build_app(json!({ "enabled": true })).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/events/v1/events")
+                .header(header::AUTHORIZATION, "Bearer read:tasks")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        "application/problem+json"
+    );
+}
+
+#[tokio::test]
+async fn configured_route_patterns_enforced() {
+    let app = build_app(json!({
+        "enabled": true,
+        "routes": {
+            "/admin/*": { "required_scopes": ["admin"] }
+        }
+    }))
+    .await;
+
+    assert_eq!(
+        get_status(&app, "/admin/v1/settings", "read:events").await,
+        StatusCode::FORBIDDEN
+    );
+    assert_eq!(
+        get_status(&app, "/admin/v1/settings", "admin").await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn scopes_not_enforced_when_disabled() {
+    let app = build_app(json!({
+        "enabled": false,
+        "routes": {
+            "/admin/*": { "required_scopes": ["admin"] }
+        }
+    }))
+    .await;
+
+    assert_eq!(
+        get_status(&app, "/events/v1/events", "read:tasks").await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        get_status(&app, "/admin/v1/settings", "read:tasks").await,
+        StatusCode::OK
+    );
+}
@@ -26,6 +26,7 @@ use crate::models::AuthenticationResult;
 ///
 /// The returned `SecurityContext` includes the original bearer token
 /// in the `bearer_token` field for downstream PDP forwarding.
+#[modkit::instrumented_client]
 #[async_trait]
 pub trait AuthNResolverClient: Send + Sync {
     /// Authenticate a bearer token and return the validated identity.
@@ -30,7 +30,7 @@ pub mod models;
 pub mod plugin_api;
 
 // Re-export main types at crate root
-pub use api::AuthNResolverClient;
+pub use api::{AuthNResolverClient, InstrumentedAuthNResolverClient};
 pub use error::AuthNResolverError;
 pub use gts::AuthNResolverPluginSpecV1;
 pub use models::AuthenticationResult;
@@ -15,6 +15,7 @@ use crate::models::{EvaluationRequest, EvaluationResponse};
 ///
 /// let response = authz.evaluate(request).await?;
 /// ```
+#[modkit::instrumented_client]
 #[async_trait]
 pub trait AuthZResolverClient: Send + Sync {
     /// Evaluate an authorization request.
@@ -51,7 +51,7 @@ pub mod pep;
 pub mod plugin_api;
 
 // Re-export main types at crate root
-pub use api::AuthZResolverClient;
+pub use api::{AuthZResolverClient, InstrumentedAuthZResolverClient};
 pub use constraints::{Constraint, EqPredicate, InPredicate, Predicate};
 pub use error::AuthZResolverError;
 pub use gts::AuthZResolverPluginSpecV1;
@@ -0,0 +1,97 @@
+//! `grpc.health.v1.Health` statuses driven by module readiness
+//!
+//! Each installed service reports `SERVING` while its module is ready according to the
+//! runtime's [`HealthRegistry`]; the empty service name reports the whole process.
+//! Everything turns `NOT_SERVING` once the hub shuts down.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use modkit::health::HealthRegistry;
+use modkit::runtime::ModuleInstallers;
+use tokio_util::sync::CancellationToken;
+use tonic_health::ServingStatus;
+use tonic_health::server::HealthReporter;
+
+/// Service name under which the overall server health is reported.
+const SERVER: &str = "";
+
+pub struct HealthSync {
+    reporter: HealthReporter,
+    /// `(module, service)` pairs
+    services: Vec<(String, &'static str)>,
+    registry: Option<Arc<HealthRegistry>>,
+    interval: Duration,
+}
+
+impl HealthSync {
+    pub fn new(
+        reporter: HealthReporter,
+        modules: &[ModuleInstallers],
+        registry: Option<Arc<HealthRegistry>>,
+        interval: Duration,
+    ) -> Self {
+        let services = modules
+            .iter()
+            .flat_map(|m| {
+                m.installers
+                    .iter()
+                    .map(|i| (m.module_name.clone(), i.service_name))
+            })
+            .collect();
+        Self {
+            reporter,
+            services,
+            registry,
+            interval,
+        }
+    }
+
+    /// Refreshes the statuses every `interval` until `cancel` fires.
+    pub async fn run(self, cancel: CancellationToken) {
+        let mut ticker = tokio::time::interval(self.interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                () = cancel.cancelled() => break,
+                _ = ticker.tick() => self.refresh().await,
+            }
+        }
+        self.set(SERVER, false).await;
+        for (_, service) in &self.services {
+            self.set(service, false).await;
+        }
+    }
+
+    async fn refresh(&self) {
+        // Without a registry (hub used standalone) every installed service is serving
+        let Some(registry) = &self.registry else {
+            self.set(SERVER, true).await;
+            for (_, service) in &self.services {
+                self.set(service, true).await;
+            }
+            return;
+        };
+
+        let summary = registry.readiness().await;
+        let serving = registry.is_serving();
+        self.set(SERVER, summary.status.is_passing()).await;
+        for (module, service) in &self.services {
+            let ready = serving
+                && summary
+                    .modules
+                    .get(module.as_str())
+                    .is_none_or(|m| m.status.is_passing());
+            self.set(service, ready).await;
+        }
+    }
+
+    async fn set(&self, service: &str, serving: bool) {
+        let status = if serving {
+            ServingStatus::Serving
+        } else {
+            ServingStatus::NotServing
+        };
+        self.reporter.set_service_status(service, status).await;
+    }
+}
@@ -4,5 +4,6 @@
 //! This module builds and hosts the single `tonic::Server` instance for the process.
 
 // === MODULE DEFINITION ===
+mod health;
 pub mod module;
 pub use module::{GrpcHub, GrpcHubConfig};
@@ -5,7 +5,7 @@
 use anyhow::Context;
 use async_trait::async_trait;
 use modkit::{
-    DirectoryClient,
+    DirectoryClient, HealthRegistry,
     context::ModuleCtx,
     contracts::{Module, SystemCapability},
     lifecycle::ReadySignal,
@@ -20,11 +20,18 @@ use std::{
     collections::HashSet,
     net::{Ipv4Addr, SocketAddr, SocketAddrV4},
     sync::{Arc, OnceLock},
+    time::Duration,
 };
 use tokio::net::TcpListener;
 use tokio_stream::wrappers::TcpListenerStream;
 use tokio_util::sync::CancellationToken;
-use tonic::{service::RoutesBuilder, transport::Server};
+use tonic::{server::NamedService, service::RoutesBuilder, transport::Server};
+use tonic_health::pb::health_server::HealthServer;
+use tonic_reflection::pb::v1::server_reflection_server::ServerReflectionServer;
+
+use crate::health::HealthSync;
+
+use modkit_transport_grpc::{GRPC_ALPN_PROTOCOLS, ServerTls, ServerTlsConfig};
 
 #[cfg(windows)]
 use modkit_transport_grpc::create_named_pipe_incoming;
@@ -32,28 +39,90 @@ use modkit_transport_grpc::create_named_pipe_incoming;
 const DEFAULT_LISTEN_ADDR: SocketAddr =
     SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, 50051));
 
+const DEFAULT_HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// `grpc.health.v1.Health`
+const HEALTH_SERVICE_NAME: &str = <HealthServer<()> as NamedService>::NAME;
+
+/// `grpc.reflection.v1.ServerReflection`
+const REFLECTION_SERVICE_NAME: &str = <ServerReflectionServer<()> as NamedService>::NAME;
+
 /// Configuration for the gRPC Hub module.
 ///
 /// Supports multiple transport types via `listen_addr`:
 /// - TCP: `"127.0.0.1:50051"` or `"0.0.0.0:0"` for ephemeral port
 /// - Unix Domain Socket (Unix only): `"uds:///path/to/socket.sock"`
 /// - Named Pipe (Windows only): `"pipe://\\.\pipe\my_pipe"` or `"npipe://\\.\pipe\my_pipe"`
+///
+/// `tls` enables TLS (and mutual TLS with `client_ca_path`) on TCP listeners.
+///
+/// Next to module services the hub serves `grpc.health.v1.Health` (on by default)
+/// and `grpc.reflection.v1.ServerReflection` (off by default).
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct GrpcHubConfig {
     /// Listen address for the gRPC server.
     /// Defaults to `0.0.0.0:50051` if not specified.
     pub listen_addr: String,
+
+    /// TLS settings for the TCP listener; plaintext if not set.
+    pub tls: Option<ServerTlsConfig>,
+
+    /// Serve `grpc.health.v1.Health`, reporting each service by its module's readiness.
+    pub enable_health: bool,
+
+    /// How often the health statuses are refreshed from module readiness; must not be zero.
+    #[serde(with = "modkit_utils::humantime_serde")]
+    pub health_check_interval: Duration,
+
+    /// Serve `grpc.reflection.v1.ServerReflection` from the modules' descriptor sets.
+    pub enable_reflection: bool,
 }
 
 impl Default for GrpcHubConfig {
     fn default() -> Self {
         Self {
             listen_addr: DEFAULT_LISTEN_ADDR.to_string(),
+            tls: None,
+            enable_health: true,
+            health_check_interval: DEFAULT_HEALTH_CHECK_INTERVAL,
+            enable_reflection: false,
+        }
+    }
+}
+
+/// Built-in services served next to the module services
+#[derive(Clone, Copy)]
+pub(crate) struct StandardServices {
+    health: bool,
+    health_check_interval: Duration,
+    reflection: bool,
+}
+
+impl Default for StandardServices {
+    fn default() -> Self {
+        Self {
+            health: true,
+            health_check_interval: DEFAULT_HEALTH_CHECK_INTERVAL,
+            reflection: false,
         }
     }
 }
 
+impl StandardServices {
+    /// Service names modules must not register themselves.
+    fn reserved_names(self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        if self.health {
+            names.push(HEALTH_SERVICE_NAME);
+        }
+        if self.reflection {
+            names.push(REFLECTION_SERVICE_NAME);
+        }
+        names
+    }
+}
+
 /// Configuration for the listen address
 #[derive(Clone)]
 pub(crate) enum ListenConfig {
@@ -77,6 +146,9 @@ pub struct GrpcHub {
     pub(crate) directory: OnceLock<Option<Arc<dyn DirectoryClient>>>,
     pub(crate) instance_id: OnceLock<String>,
     pub(crate) bound_endpoint: RwLock<Option<String>>,
+    pub(crate) tls: RwLock<Option<Arc<ServerTls>>>,
+    pub(crate) standard: RwLock<StandardServices>,
+    pub(crate) health_registry: OnceLock<Option<Arc<HealthRegistry>>>,
 }
 
 impl Default for GrpcHub {
@@ -87,6 +159,9 @@ impl Default for GrpcHub {
             directory: OnceLock::new(),
             instance_id: OnceLock::new(),
             bound_endpoint: RwLock::new(None),
+            tls: RwLock::new(None),
+            standard: RwLock::new(StandardServices::default()),
+            health_registry: OnceLock::new(),
         }
     }
 }
@@ -108,6 +183,21 @@ impl GrpcHub {
         }
     }
 
+    /// Serve TLS on the TCP listener (primarily used by tests/config).
+    pub fn set_tls(&self, tls: Arc<ServerTls>) {
+        *self.tls.write() = Some(tls);
+    }
+
+    /// Serve `grpc.health.v1.Health` (primarily used by tests/config).
+    pub fn set_health_enabled(&self, enabled: bool) {
+        self.standard.write().health = enabled;
+    }
+
+    /// Serve `grpc.reflection.v1.ServerReflection` (primarily used by tests/config).
+    pub fn set_reflection_enabled(&self, enabled: bool) {
+        self.standard.write().reflection = enabled;
+    }
+
     /// Set listen address to Windows named pipe (primarily used by tests).
     #[cfg(windows)]
     pub fn set_listen_named_pipe(&self, name: impl Into<String>) {
@@ -214,10 +304,21 @@ impl GrpcHub {
         Ok(false)
     }
 
-    /// Validate that all service names are unique across all modules.
-    fn validate_unique_services(modules: &[ModuleInstallers]) -> anyhow::Result<()> {
+    /// Validate that all service names are unique across all modules and the built-in services.
+    fn validate_unique_services(
+        modules: &[ModuleInstallers],
+        reserved: &[&'static str],
+    ) -> anyhow::Result<()> {
         let mut seen = HashSet::new();
         for module in modules {
+            for installer in &module.installers {
+                if reserved.contains(&installer.service_name) {
+                    anyhow::bail!(
+                        "gRPC service {} is served by the gRPC hub itself",
+                        installer.service_name
+                    );
+                }
+            }
             for installer in &module.installers {
                 if !seen.insert(installer.service_name) {
                     anyhow::bail!(
@@ -231,7 +332,7 @@ impl GrpcHub {
     }
 
     /// Build routes from module installers. Returns None if no services registered.
-    fn build_routes_from_modules(modules: &[ModuleInstallers]) -> Option<tonic::service::Routes> {
+    fn build_routes_from_modules(modules: &[ModuleInstallers]) -> Option<RoutesBuilder> {
         let mut routes_builder = RoutesBuilder::default();
         let mut has_services = false;
         for module in modules {
@@ -241,7 +342,7 @@ impl GrpcHub {
             }
         }
         if has_services {
-            Some(routes_builder.routes())
+            Some(routes_builder)
         } else {
             None
         }
@@ -313,15 +414,56 @@ impl GrpcHub {
         cancel: CancellationToken,
         ready: ReadySignal,
     ) -> anyhow::Result<()> {
-        Self::validate_unique_services(&data.modules)?;
+        let standard = *self.standard.read();
+        Self::validate_unique_services(&data.modules, &standard.reserved_names())?;
 
-        let Some(routes) = Self::build_routes_from_modules(&data.modules) else {
+        let Some(mut routes_builder) = Self::build_routes_from_modules(&data.modules) else {
             ready.notify();
             cancel.cancelled().await;
             return Ok(());
         };
 
+        let health_sync = standard.health.then(|| {
+            let (reporter, service) = tonic_health::server::health_reporter();
+            routes_builder.add_service(service);
+            HealthSync::new(
+                reporter,
+                &data.modules,
+                self.health_registry.get().cloned().flatten(),
+                standard.health_check_interval,
+            )
+        });
+
+        if standard.reflection {
+            let mut reflection = tonic_reflection::server::Builder::configure();
+            if standard.health {
+                reflection = reflection
+                    .register_encoded_file_descriptor_set(tonic_health::pb::FILE_DESCRIPTOR_SET);
+            }
+            for set in data.modules.iter().flat_map(|m| &m.file_descriptor_sets) {
+                reflection = reflection.register_encoded_file_descriptor_set(set);
+            }
+            routes_builder.add_service(
+                reflection
+                    .build_v1()
+                    .context("invalid gRPC file descriptor set")?,
+            );
+        }
+
+        let routes = routes_builder.routes();
+
         let listen_cfg = self.listen_cfg.read().clone();
+        if self.tls.read().is_some() && !matches!(listen_cfg, ListenConfig::Tcp(_)) {
+            anyhow::bail!("gRPC hub TLS is only supported with a TCP listen_addr");
+        }
+
+        // Health statuses are refreshed while the server runs and turn NOT_SERVING with it
+        let health_cancel = cancel.child_token();
+        let _health_guard = health_cancel.clone().drop_guard();
+        if let Some(sync) = health_sync {
+            tokio::spawn(sync.run(health_cancel));
+        }
+
         let serve_result = match listen_cfg {
             ListenConfig::Tcp(addr) => {
                 self.serve_tcp(addr, routes, &data.modules, cancel, ready)
@@ -343,7 +485,7 @@ impl GrpcHub {
         serve_result
     }
 
-    /// Serve gRPC over TCP with Directory registration.
+    /// Serve gRPC over TCP (TLS if configured) with Directory registration.
     async fn serve_tcp(
         &self,
         addr: SocketAddr,
@@ -352,22 +494,43 @@ impl GrpcHub {
         cancel: CancellationToken,
         ready: ReadySignal,
     ) -> anyhow::Result<()> {
+        let tls = self.tls.read().clone();
         let listener = TcpListener::bind(addr).await?;
         let bound_addr = listener.local_addr()?;
-        let endpoint = format!("http://{bound_addr}");
-        tracing::info!(%bound_addr, transport = "tcp", "gRPC hub listening");
+        let scheme = if tls.is_some() { "https" } else { "http" };
+        let endpoint = format!("{scheme}://{bound_addr}");
+        tracing::info!(
+            %bound_addr,
+            transport = "tcp",
+            tls = tls.is_some(),
+            mutual_tls = tls.as_ref().is_some_and(|t| t.is_mutual()),
+            "gRPC hub listening"
+        );
 
         self.set_bound_endpoint(endpoint.clone());
         self.register_modules(modules, &endpoint).await?;
         ready.notify();
 
-        let incoming = TcpListenerStream::new(listener);
-        Server::builder()
-            .add_routes(routes)
-            .serve_with_incoming_shutdown(incoming, async move {
-                cancel.cancelled().await;
-            })
-            .await?;
+        let shutdown = async move {
+            cancel.cancelled().await;
+        };
+        let server = Server::builder().add_routes(routes);
+        match tls {
+            Some(tls) => {
+                // Dropping the guard stops the certificate watcher with the server
+                let watch_cancel = CancellationToken::new();
+                let _watch_guard = watch_cancel.clone().drop_guard();
+                tls.watch(watch_cancel);
+                server
+                    .serve_with_incoming_shutdown(tls.incoming(listener), shutdown)
+                    .await?;
+            }
+            None => {
+                server
+                    .serve_with_incoming_shutdown(TcpListenerStream::new(listener), shutdown)
+                    .await?;
+            }
+        }
         Ok(())
     }
 
@@ -538,6 +701,28 @@ impl Module for GrpcHub {
         // Parse listen_addr into appropriate transport type
         self.apply_listen_config(&cfg.listen_addr)?;
 
+        if cfg.health_check_interval.is_zero() {
+            anyhow::bail!("grpc-hub health_check_interval must be greater than zero");
+        }
+        *self.standard.write() = StandardServices {
+            health: cfg.enable_health,
+            health_check_interval: cfg.health_check_interval,
+            reflection: cfg.enable_reflection,
+        };
+        self.health_registry
+            .set(ctx.client_hub().get::<HealthRegistry>().ok())
+            .map_err(|_| anyhow::anyhow!("HealthRegistry already set (init called twice?)"))?;
+
+        if let Some(tls_cfg) = cfg.tls {
+            if self.listen_addr_tcp().is_none() {
+                anyhow::bail!("gRPC hub TLS is only supported with a TCP listen_addr");
+            }
+            self.set_tls(
+                ServerTls::load(tls_cfg, GRPC_ALPN_PROTOCOLS)
+                    .context("failed to load gRPC hub TLS")?,
+            );
+        }
+
         // Fetch DirectoryClient from ClientHub if available and persist the decision exactly once.
         let dir = ctx.client_hub().get::<dyn DirectoryClient>().ok();
         self.directory
@@ -640,6 +825,7 @@ mod tests {
             modules: vec![ModuleInstallers {
                 module_name: "test".to_owned(),
                 installers: vec![installer_a(), installer_a()],
+                file_descriptor_sets: Vec::new(),
             }],
         };
         let cancel = CancellationToken::new();
@@ -659,6 +845,7 @@ mod tests {
             modules: vec![ModuleInstallers {
                 module_name: "test".to_owned(),
                 installers: vec![installer_a(), installer_b()],
+                file_descriptor_sets: Vec::new(),
             }],
         };
         let cancel = CancellationToken::new();
@@ -699,6 +886,7 @@ mod tests {
                 modules: vec![ModuleInstallers {
                     module_name: "test".to_owned(),
                     installers: vec![installer_a()],
+                    file_descriptor_sets: Vec::new(),
                 }],
             })
             .expect("store should accept installers");
@@ -745,6 +933,33 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_init_rejects_zero_health_check_interval() {
+        struct ZeroInterval(serde_json::Value);
+        impl ConfigProvider for ZeroInterval {
+            fn get_module_config(&self, module_name: &str) -> Option<&serde_json::Value> {
+                (module_name == "grpc-hub").then_some(&self.0)
+            }
+        }
+
+        let ctx = ModuleCtx::new(
+            "grpc-hub",
+            Uuid::new_v4(),
+            Arc::new(ZeroInterval(serde_json::json!({
+                "config": {
+                    "listen_addr": "127.0.0.1:10",
+                    "health_check_interval": "0s"
+                }
+            }))),
+            Arc::new(ClientHub::default()),
+            CancellationToken::new(),
+            None,
+        );
+
+        let err = GrpcHub::default().init(&ctx).await.unwrap_err();
+        assert!(err.to_string().contains("health_check_interval"), "{err}");
+    }
+
     #[tokio::test]
     async fn test_init_parses_listen_addr() {
         #[derive(Default)]
@@ -881,6 +1096,7 @@ mod tests {
             modules: vec![ModuleInstallers {
                 module_name: "test".to_owned(),
                 installers,
+                file_descriptor_sets: Vec::new(),
             }],
         };
         let cancel_clone = cancel.clone();
@@ -959,6 +1175,7 @@ mod tests {
             modules: vec![ModuleInstallers {
                 module_name: "test".to_owned(),
                 installers,
+                file_descriptor_sets: Vec::new(),
             }],
         };
         let cancel_clone = cancel.clone();
@@ -1018,4 +1235,373 @@ mod tests {
             .expect("task should join successfully")
             .expect("should exit cleanly with no services");
     }
+
+    const SERVICE_WHOAMI: &str = "grpc_hub.test.WhoAmI";
+
+    /// Answers with the common name of the client certificate in `x-peer`.
+    #[derive(Clone)]
+    struct WhoAmIImpl;
+
+    impl NamedService for WhoAmIImpl {
+        const NAME: &'static str = SERVICE_WHOAMI;
+    }
+
+    impl Service<Request<Body>> for WhoAmIImpl {
+        type Response = Response<Body>;
+        type Error = Infallible;
+        type Future = future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<Body>) -> Self::Future {
+            let peer = modkit_transport_grpc::peer_identity(&tonic::Request::from_http(req))
+                .and_then(|p| p.common_name)
+                .unwrap_or_default();
+            let mut resp = Response::new(Body::empty());
+            resp.headers_mut()
+                .insert("x-peer", peer.parse().expect("valid header value"));
+            future::ready(Ok(resp))
+        }
+    }
+
+    fn tls_fixture(name: &str) -> std::path::PathBuf {
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../../libs/modkit-tls/tests/fixtures/tls")
+            .join(name)
+    }
+
+    async fn whoami(
+        endpoint: &str,
+        tls: modkit_transport_grpc::GrpcClientTlsConfig,
+    ) -> anyhow::Result<String> {
+        use modkit_transport_grpc::client::{GrpcClientConfig, connect_with_stack};
+
+        let cfg = GrpcClientConfig::new("whoami")
+            .with_connect_timeout(Duration::from_secs(2))
+            .with_tls(tls);
+        let mut channel: tonic::transport::Channel = connect_with_stack(endpoint, &cfg).await?;
+        let req = Request::builder()
+            .uri(format!("{endpoint}/{SERVICE_WHOAMI}/Call"))
+            .header("content-type", "application/grpc")
+            .body(Body::empty())?;
+        future::poll_fn(|cx| channel.poll_ready(cx)).await?;
+        let resp = channel.call(req).await?;
+        Ok(resp
+            .headers()
+            .get("x-peer")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_owned())
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls_exposes_peer_identity() {
+        let hub = Arc::new(GrpcHub::default());
+        hub.set_listen_addr_tcp("127.0.0.1:0".parse().unwrap());
+        hub.set_tls(
+            ServerTls::load(
+                ServerTlsConfig::new(tls_fixture("server.crt"), tls_fixture("server.key"))
+                    .with_client_ca(
+                        tls_fixture("ca.crt"),
+                        modkit_transport_grpc::ClientAuth::Required,
+                    ),
+                GRPC_ALPN_PROTOCOLS,
+            )
+            .expect("TLS fixtures should load"),
+        );
+        let data = GrpcInstallerData {
+            modules: vec![ModuleInstallers {
+                module_name: "test".to_owned(),
+                installers: vec![modkit::contracts::RegisterGrpcServiceFn {
+                    service_name: SERVICE_WHOAMI,
+                    register: Box::new(|routes| {
+                        routes.add_service(WhoAmIImpl);
+                    }),
+                }],
+                file_descriptor_sets: Vec::new(),
+            }],
+        };
+        let cancel = CancellationToken::new();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let hub_task = {
+            let hub = hub.clone();
+            let cancel = cancel.clone();
+            tokio::spawn(async move {
+                hub.run_with_installers(data, cancel, ReadySignal::from_sender(tx))
+                    .await
+            })
+        };
+        rx.await.expect("ready signal should fire");
+
+        let endpoint = hub.get_bound_endpoint().expect("endpoint should be bound");
+        assert!(endpoint.starts_with("https://"), "{endpoint}");
+
+        let client_tls = modkit_transport_grpc::GrpcClientTlsConfig::new()
+            .with_ca(tls_fixture("ca.crt"))
+            .with_server_name("localhost");
+        let peer = whoami(
+            &endpoint,
+            client_tls
+                .clone()
+                .with_identity(tls_fixture("client.crt"), tls_fixture("client.key")),
+        )
+        .await
+        .expect("mTLS call should succeed");
+        assert_eq!(peer, "calculator");
+
+        // Without a client certificate the handshake is rejected
+        assert!(whoami(&endpoint, client_tls).await.is_err());
+
+        cancel.cancel();
+        hub_task
+            .await
+            .expect("task should join")
+            .expect("server should exit cleanly");
+    }
+
+    #[tokio::test]
+    #[cfg(unix)]
+    async fn test_tls_requires_tcp_listener() {
+        let hub = GrpcHub::default();
+        hub.apply_listen_config("uds:///tmp/grpc_hub_tls_test.sock")
+            .unwrap();
+        hub.set_tls(
+            ServerTls::load(
+                ServerTlsConfig::new(tls_fixture("server.crt"), tls_fixture("server.key")),
+                GRPC_ALPN_PROTOCOLS,
+            )
+            .unwrap(),
+        );
+        let data = GrpcInstallerData {
+            modules: vec![ModuleInstallers {
+                module_name: "test".to_owned(),
+                installers: vec![installer_a()],
+                file_descriptor_sets: Vec::new(),
+            }],
+        };
+        let (tx, _rx) = tokio::sync::oneshot::channel();
+        let err = hub
+            .run_with_installers(data, CancellationToken::new(), ReadySignal::from_sender(tx))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("TCP"), "{err}");
+    }
+
+    async fn start_hub(
+        hub: &Arc<GrpcHub>,
+        data: GrpcInstallerData,
+    ) -> (
+        String,
+        CancellationToken,
+        tokio::task::JoinHandle<anyhow::Result<()>>,
+    ) {
+        let cancel = CancellationToken::new();
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let hub_task = {
+            let hub = hub.clone();
+            let cancel = cancel.clone();
+            tokio::spawn(async move {
+                hub.run_with_installers(data, cancel, ReadySignal::from_sender(tx))
+                    .await
+            })
+        };
+        rx.await.expect("ready signal should fire");
+        let endpoint = hub.get_bound_endpoint().expect("endpoint should be bound");
+        (endpoint, cancel, hub_task)
+    }
+
+    async fn channel(endpoint: &str) -> tonic::transport::Channel {
+        tonic::transport::Channel::from_shared(endpoint.to_owned())
+            .unwrap()
+            .connect()
+            .await
+            .unwrap()
+    }
+
+    /// Polls until `service` reports `expected` (statuses are refreshed in the background).
+    async fn wait_for_health(
+        endpoint: &str,
+        service: &str,
+        expected: tonic_health::pb::health_check_response::ServingStatus,
+    ) {
+        use tonic_health::pb::{HealthCheckRequest, health_client::HealthClient};
+
+        let mut client = HealthClient::new(channel(endpoint).await);
+        let mut last = None;
+        for _ in 0..50 {
+            let resp = client
+                .check(HealthCheckRequest {
+                    service: service.to_owned(),
+                })
+                .await;
+            if let Ok(resp) = &resp
+                && resp.get_ref().status() == expected
+            {
+                return;
+            }
+            last = Some(resp);
+            sleep(Duration::from_millis(20)).await;
+        }
+        panic!("{service:?} never reported {expected:?}, last: {last:?}");
+    }
+
+    struct DownModule;
+
+    #[async_trait]
+    impl modkit::contracts::HealthCapability for DownModule {
+        async fn readiness(&self) -> modkit::health::HealthReport {
+            modkit::health::HealthReport::up().with_check(
+                "db",
+                modkit::health::HealthStatus::Down,
+                None,
+            )
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_serves_installed_services() {
+        use tonic_health::pb::health_check_response::ServingStatus;
+
+        let hub = Arc::new(GrpcHub::default());
+        hub.set_listen_addr_tcp("127.0.0.1:0".parse().unwrap());
+        let data = GrpcInstallerData {
+            modules: vec![ModuleInstallers {
+                module_name: "test".to_owned(),
+                installers: vec![installer_a()],
+                file_descriptor_sets: Vec::new(),
+            }],
+        };
+        let (endpoint, cancel, hub_task) = start_hub(&hub, data).await;
+
+        wait_for_health(&endpoint, "", ServingStatus::Serving).await;
+        wait_for_health(&endpoint, SERVICE_A, ServingStatus::Serving).await;
+
+        let mut client =
+            tonic_health::pb::health_client::HealthClient::new(channel(&endpoint).await);
+        let err = client
+            .check(tonic_health::pb::HealthCheckRequest {
+                service: SERVICE_B.to_owned(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        cancel.cancel();
+        hub_task.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_health_follows_module_readiness() {
+        use tonic_health::pb::health_check_response::ServingStatus;
+
+        let registry = Arc::new(
+            HealthRegistry::new()
+                .with_module("down", Some(Arc::new(DownModule)), None)
+                .with_module("up", None, None),
+        );
+        registry.set_serving(true);
+
+        let hub = Arc::new(GrpcHub::default());
+        hub.set_listen_addr_tcp("127.0.0.1:0".parse().unwrap());
+        assert!(hub.health_registry.set(Some(registry.clone())).is_ok());
+        let data = GrpcInstallerData {
+            modules: vec![
+                ModuleInstallers {
+                    module_name: "down".to_owned(),
+                    installers: vec![installer_a()],
+                    file_descriptor_sets: Vec::new(),
+                },
+                ModuleInstallers {
+                    module_name: "up".to_owned(),
+                    installers: vec![installer_b()],
+                    file_descriptor_sets: Vec::new(),
+                },
+            ],
+        };
+        {
+            let mut standard = hub.standard.write();
+            standard.health_check_interval = Duration::from_millis(10);
+        }
+        let (endpoint, cancel, hub_task) = start_hub(&hub, data).await;
+
+        wait_for_health(&endpoint, "", ServingStatus::NotServing).await;
+        wait_for_health(&endpoint, SERVICE_A, ServingStatus::NotServing).await;
+        wait_for_health(&endpoint, SERVICE_B, ServingStatus::Serving).await;
+
+        // Draining the process takes every service out of rotation
+        registry.set_serving(false);
+        wait_for_health(&endpoint, SERVICE_B, ServingStatus::NotServing).await;
+
+        cancel.cancel();
+        hub_task.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reflection_lists_services() {
+        use tonic_reflection::pb::v1::{
+            ServerReflectionRequest, server_reflection_client::ServerReflectionClient,
+            server_reflection_request::MessageRequest, server_reflection_response::MessageResponse,
+        };
+
+        let hub = Arc::new(GrpcHub::default());
+        hub.set_listen_addr_tcp("127.0.0.1:0".parse().unwrap());
+        hub.set_reflection_enabled(true);
+        let data = GrpcInstallerData {
+            modules: vec![ModuleInstallers {
+                module_name: "test".to_owned(),
+                installers: vec![installer_a()],
+                file_descriptor_sets: Vec::new(),
+            }],
+        };
+        let (endpoint, cancel, hub_task) = start_hub(&hub, data).await;
+
+        let mut client = ServerReflectionClient::new(channel(&endpoint).await);
+        let mut responses = client
+            .server_reflection_info(tokio_stream::once(ServerReflectionRequest {
+                host: String::new(),
+                message_request: Some(MessageRequest::ListServices(String::new())),
+            }))
+            .await
+            .unwrap()
+            .into_inner();
+        let resp = responses.message().await.unwrap().unwrap();
+        let Some(MessageResponse::ListServicesResponse(list)) = resp.message_response else {
+            panic!("unexpected response: {resp:?}");
+        };
+        let names: Vec<_> = list.service.into_iter().map(|s| s.name).collect();
+        assert!(names.iter().any(|n| n == HEALTH_SERVICE_NAME), "{names:?}");
+        assert!(
+            names.iter().any(|n| n == REFLECTION_SERVICE_NAME),
+            "{names:?}"
+        );
+
+        cancel.cancel();
+        hub_task.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_rejects_modules_claiming_standard_services() {
+        let hub = GrpcHub::default();
+        hub.set_listen_addr_tcp("127.0.0.1:0".parse().unwrap());
+        let data = GrpcInstallerData {
+            modules: vec![ModuleInstallers {
+                module_name: "test".to_owned(),
+                installers: vec![modkit::contracts::RegisterGrpcServiceFn {
+                    service_name: HEALTH_SERVICE_NAME,
+                    register: Box::new(|routes| {
+                        routes.add_service(ServiceAImpl);
+                    }),
+                }],
+                file_descriptor_sets: Vec::new(),
+            }],
+        };
+        let (tx, _rx) = tokio::sync::oneshot::channel();
+        let err = hub
+            .run_with_installers(data, CancellationToken::new(), ReadySignal::from_sender(tx))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(HEALTH_SERVICE_NAME), "{err}");
+    }
 }
@@ -0,0 +1,49 @@
+//! Public API trait for the license resolver.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::error::LicenseResolverError;
+use crate::models::EnabledFeatures;
+
+/// Public API trait for the license resolver gateway.
+///
+/// This trait is registered in `ClientHub` by the module and
+/// can be consumed by other modules (e.g. the API gateway):
+///
+/// ```ignore
+/// let licenses = hub.get::<dyn LicenseResolverClient>()?;
+///
+/// let features = licenses.get_global_features(&ctx).await?;
+/// ```
+#[modkit::instrumented_client]
+#[async_trait]
+pub trait LicenseResolverClient: Send + Sync {
+    /// Get the features enabled for the whole installation (not scoped to a tenant).
+    ///
+    /// # Errors
+    ///
+    /// - `NoPluginAvailable` if no license plugin is registered
+    /// - `ServiceUnavailable` if the plugin is not ready
+    /// - `Internal` for unexpected errors
+    async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, LicenseResolverError>;
+
+    /// Get the features enabled for a tenant on top of the global ones.
+    ///
+    /// Tenants without a license of their own have no tenant-scoped features.
+    ///
+    /// # Errors
+    ///
+    /// - `NoPluginAvailable` if no license plugin is registered
+    /// - `ServiceUnavailable` if the plugin is not ready
+    /// - `Internal` for unexpected errors
+    async fn get_tenant_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<EnabledFeatures, LicenseResolverError>;
+}
@@ -0,0 +1,39 @@
+//! License Resolver SDK
+//!
+//! This crate provides the public API for the `license_resolver` module:
+//!
+//! - [`LicenseResolverClient`] - Public API trait for consumers
+//! - [`LicenseResolverPluginClient`] - Plugin API trait for implementations
+//! - [`EnabledFeatures`] - Set of enabled license features
+//! - [`LicenseResolverError`] - Error types
+//! - [`LicenseResolverPluginSpecV1`] - GTS schema for plugin discovery
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! use license_resolver_sdk::{BASE_FEATURE, LicenseResolverClient};
+//!
+//! // Get the client from ClientHub
+//! let licenses = hub.get::<dyn LicenseResolverClient>()?;
+//!
+//! // Features enabled for the whole installation
+//! let global = licenses.get_global_features(&ctx).await?;
+//! assert!(global.contains(BASE_FEATURE));
+//!
+//! // Features enabled for a particular tenant
+//! let tenant = licenses.get_tenant_features(&ctx, ctx.subject_tenant_id()).await?;
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod api;
+pub mod error;
+pub mod gts;
+pub mod models;
+pub mod plugin_api;
+
+// Re-export main types at crate root
+pub use api::{InstrumentedLicenseResolverClient, LicenseResolverClient};
+pub use error::LicenseResolverError;
+pub use gts::LicenseResolverPluginSpecV1;
+pub use models::{BASE_FEATURE, EnabledFeatures};
+pub use plugin_api::LicenseResolverPluginClient;
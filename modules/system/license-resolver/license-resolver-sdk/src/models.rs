@@ -0,0 +1,57 @@
+//! Domain models for the license resolver module.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+/// Base platform feature, available to every installation.
+pub const BASE_FEATURE: &str = "gts.x.core.lic.feat.v1~x.core.global.base.v1";
+
+/// Set of enabled license features, identified by their GTS IDs.
+///
+/// # Example
+///
+/// ```
+/// use license_resolver_sdk::{BASE_FEATURE, EnabledFeatures};
+///
+/// let mut features = EnabledFeatures::new([BASE_FEATURE]);
+/// features.extend(EnabledFeatures::new(["gts.x.core.lic.feat.v1~x.core.oagw.base.v1"]));
+///
+/// assert!(features.contains(BASE_FEATURE));
+/// assert!(features.contains_all([BASE_FEATURE, "gts.x.core.lic.feat.v1~x.core.oagw.base.v1"]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnabledFeatures {
+    /// Enabled feature GTS IDs.
+    #[serde(default)]
+    pub features: BTreeSet<String>,
+}
+
+impl EnabledFeatures {
+    /// Creates a set from feature IDs.
+    pub fn new<I, S>(features: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            features: features.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns `true` if `feature` is enabled.
+    #[must_use]
+    pub fn contains(&self, feature: &str) -> bool {
+        self.features.contains(feature)
+    }
+
+    /// Returns `true` if every feature in `required` is enabled.
+    pub fn contains_all<'a>(&self, required: impl IntoIterator<Item = &'a str>) -> bool {
+        required.into_iter().all(|f| self.contains(f))
+    }
+
+    /// Adds all features of `other`.
+    pub fn extend(&mut self, other: Self) {
+        self.features.extend(other.features);
+    }
+}
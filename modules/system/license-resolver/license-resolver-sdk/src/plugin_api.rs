@@ -0,0 +1,36 @@
+//! Plugin API trait for license resolver implementations.
+
+use async_trait::async_trait;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use crate::error::LicenseResolverError;
+use crate::models::EnabledFeatures;
+
+/// Plugin API trait for license resolver implementations.
+///
+/// Each plugin registers this trait with a scoped `ClientHub` entry
+/// using its GTS instance ID as the scope.
+#[async_trait]
+pub trait LicenseResolverPluginClient: Send + Sync {
+    /// Get the features enabled for the whole installation.
+    ///
+    /// # Errors
+    ///
+    /// - `Internal` for unexpected errors
+    async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, LicenseResolverError>;
+
+    /// Get the tenant-scoped features of `tenant_id` (without the global ones).
+    ///
+    /// # Errors
+    ///
+    /// - `Internal` for unexpected errors
+    async fn get_tenant_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<EnabledFeatures, LicenseResolverError>;
+}
@@ -0,0 +1,19 @@
+//! Configuration for the license resolver.
+
+use serde::Deserialize;
+
+/// Configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LicenseResolverConfig {
+    /// Vendor selector used to pick a plugin implementation.
+    pub vendor: String,
+}
+
+impl Default for LicenseResolverConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+        }
+    }
+}
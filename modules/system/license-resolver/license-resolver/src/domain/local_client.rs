@@ -0,0 +1,53 @@
+//! Local (in-process) client for the license resolver.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use license_resolver_sdk::{EnabledFeatures, LicenseResolverClient, LicenseResolverError};
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use super::{DomainError, Service};
+
+/// Local client wrapping the service.
+#[domain_model]
+pub struct LicenseResolverLocalClient {
+    svc: Arc<Service>,
+}
+
+impl LicenseResolverLocalClient {
+    #[must_use]
+    pub fn new(svc: Arc<Service>) -> Self {
+        Self { svc }
+    }
+}
+
+fn log_and_convert(op: &str, e: DomainError) -> LicenseResolverError {
+    tracing::error!(operation = op, error = ?e, "license_resolver call failed");
+    e.into()
+}
+
+#[async_trait]
+impl LicenseResolverClient for LicenseResolverLocalClient {
+    async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        self.svc
+            .get_global_features(ctx)
+            .await
+            .map_err(|e| log_and_convert("get_global_features", e))
+    }
+
+    async fn get_tenant_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        self.svc
+            .get_tenant_features(ctx, tenant_id)
+            .await
+            .map_err(|e| log_and_convert("get_tenant_features", e))
+    }
+}
@@ -0,0 +1,131 @@
+//! Domain service for the license resolver.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use license_resolver_sdk::{
+    EnabledFeatures, LicenseResolverPluginClient, LicenseResolverPluginSpecV1,
+};
+use modkit::client_hub::{ClientHub, ClientScope};
+use modkit::plugins::{GtsPluginSelector, choose_plugin_instance};
+use modkit::telemetry::ThrottledLog;
+use modkit_macros::domain_model;
+use modkit_security::SecurityContext;
+use tracing::info;
+use types_registry_sdk::{ListQuery, TypesRegistryClient};
+use uuid::Uuid;
+
+use super::error::DomainError;
+
+/// Throttle interval for unavailable plugin warnings.
+const UNAVAILABLE_LOG_THROTTLE: Duration = Duration::from_secs(10);
+
+/// License resolver service.
+#[domain_model]
+pub struct Service {
+    hub: Arc<ClientHub>,
+    vendor: String,
+    selector: GtsPluginSelector,
+    unavailable_log_throttle: ThrottledLog,
+}
+
+impl Service {
+    #[must_use]
+    pub fn new(hub: Arc<ClientHub>, vendor: String) -> Self {
+        Self {
+            hub,
+            vendor,
+            selector: GtsPluginSelector::new(),
+            unavailable_log_throttle: ThrottledLog::new(UNAVAILABLE_LOG_THROTTLE),
+        }
+    }
+
+    async fn get_plugin(&self) -> Result<Arc<dyn LicenseResolverPluginClient>, DomainError> {
+        let instance_id = self.selector.get_or_init(|| self.resolve_plugin()).await?;
+        let scope = ClientScope::gts_id(instance_id.as_ref());
+
+        if let Some(client) = self
+            .hub
+            .try_get_scoped::<dyn LicenseResolverPluginClient>(&scope)
+        {
+            Ok(client)
+        } else {
+            if self.unavailable_log_throttle.should_log() {
+                tracing::warn!(
+                    plugin_gts_id = %instance_id,
+                    vendor = %self.vendor,
+                    "Plugin client not registered yet"
+                );
+            }
+            Err(DomainError::PluginUnavailable {
+                gts_id: instance_id.to_string(),
+                reason: "client not registered yet".into(),
+            })
+        }
+    }
+
+    #[tracing::instrument(skip_all, fields(vendor = %self.vendor))]
+    async fn resolve_plugin(&self) -> Result<String, DomainError> {
+        info!("Resolving license_resolver plugin");
+
+        let registry = self
+            .hub
+            .get::<dyn TypesRegistryClient>()
+            .map_err(|e| DomainError::TypesRegistryUnavailable(e.to_string()))?;
+
+        let plugin_type_id = LicenseResolverPluginSpecV1::gts_schema_id().clone();
+
+        let instances = registry
+            .list(
+                ListQuery::new()
+                    .with_pattern(format!("{plugin_type_id}*"))
+                    .with_is_type(false),
+            )
+            .await?;
+
+        let gts_id = choose_plugin_instance::<LicenseResolverPluginSpecV1>(
+            &self.vendor,
+            instances.iter().map(|e| (e.gts_id.as_str(), &e.content)),
+        )?;
+        info!(plugin_gts_id = %gts_id, "Selected license_resolver plugin instance");
+
+        Ok(gts_id)
+    }
+
+    /// Get the globally enabled features via the selected plugin.
+    ///
+    /// # Errors
+    ///
+    /// - Plugin resolution errors
+    /// - Plugin errors
+    #[tracing::instrument(skip_all)]
+    pub async fn get_global_features(
+        &self,
+        ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, DomainError> {
+        let plugin = self.get_plugin().await?;
+        plugin
+            .get_global_features(ctx)
+            .await
+            .map_err(DomainError::from)
+    }
+
+    /// Get the tenant-scoped features via the selected plugin.
+    ///
+    /// # Errors
+    ///
+    /// - Plugin resolution errors
+    /// - Plugin errors
+    #[tracing::instrument(skip_all, fields(tenant_id = %tenant_id))]
+    pub async fn get_tenant_features(
+        &self,
+        ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<EnabledFeatures, DomainError> {
+        let plugin = self.get_plugin().await?;
+        plugin
+            .get_tenant_features(ctx, tenant_id)
+            .await
+            .map_err(DomainError::from)
+    }
+}
@@ -0,0 +1,9 @@
+//! License Resolver Module
+//!
+//! This module discovers license resolver plugins via types-registry
+//! and routes feature queries to the selected plugin based on vendor configuration.
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod module;
@@ -0,0 +1,75 @@
+//! License resolver module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use license_resolver_sdk::{LicenseResolverClient, LicenseResolverPluginSpecV1};
+use modkit::Module;
+use modkit::context::ModuleCtx;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::LicenseResolverConfig;
+use crate::domain::{LicenseResolverLocalClient, Service};
+
+/// License Resolver module.
+///
+/// This module:
+/// 1. Registers the plugin schema in types-registry
+/// 2. Discovers plugin instances via types-registry
+/// 3. Routes requests to the selected plugin based on vendor configuration
+///
+/// Plugin discovery is lazy: happens on first API call after types-registry
+/// is ready.
+#[modkit::module(
+    name = "license-resolver",
+    deps = ["types-registry"],
+    capabilities = []
+)]
+pub(crate) struct LicenseResolver {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for LicenseResolver {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Module for LicenseResolver {
+    #[tracing::instrument(skip_all, fields(vendor))]
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        let cfg: LicenseResolverConfig = ctx.config()?;
+        tracing::Span::current().record("vendor", cfg.vendor.as_str());
+        info!(vendor = %cfg.vendor, "Initializing {} module", Self::MODULE_NAME);
+
+        // Register plugin schema in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let schema_str = LicenseResolverPluginSpecV1::gts_schema_with_refs_as_string();
+        let schema_json: serde_json::Value = serde_json::from_str(&schema_str)?;
+        let results = registry.register(vec![schema_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+        info!(
+            schema_id = %LicenseResolverPluginSpecV1::gts_schema_id(),
+            "Registered plugin schema in types-registry"
+        );
+
+        // Create service
+        let hub = ctx.client_hub();
+        let svc = Arc::new(Service::new(hub, cfg.vendor));
+        self.service
+            .set(svc.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register client in ClientHub
+        let api: Arc<dyn LicenseResolverClient> = Arc::new(LicenseResolverLocalClient::new(svc));
+        ctx.client_hub().register::<dyn LicenseResolverClient>(api);
+
+        info!("{} module initialized successfully", Self::MODULE_NAME);
+
+        Ok(())
+    }
+}
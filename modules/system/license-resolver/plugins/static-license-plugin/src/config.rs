@@ -0,0 +1,44 @@
+//! Configuration for the static license resolver plugin.
+
+use license_resolver_sdk::BASE_FEATURE;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// Plugin configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StaticLicensePluginConfig {
+    /// Vendor name for GTS instance registration.
+    pub vendor: String,
+
+    /// Plugin priority (lower = higher priority).
+    pub priority: i16,
+
+    /// Features enabled for the whole installation.
+    pub global_features: Vec<String>,
+
+    /// Tenant-scoped feature grants.
+    pub tenants: Vec<TenantFeaturesConfig>,
+}
+
+impl Default for StaticLicensePluginConfig {
+    fn default() -> Self {
+        Self {
+            vendor: "hyperspot".to_owned(),
+            priority: 100,
+            global_features: vec![BASE_FEATURE.to_owned()],
+            tenants: Vec::new(),
+        }
+    }
+}
+
+/// Features licensed to a single tenant.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TenantFeaturesConfig {
+    /// Tenant ID.
+    pub tenant_id: Uuid,
+
+    /// Features enabled for this tenant.
+    pub features: Vec<String>,
+}
@@ -0,0 +1,54 @@
+//! Client implementation for the static license resolver plugin.
+
+use async_trait::async_trait;
+use license_resolver_sdk::{EnabledFeatures, LicenseResolverError, LicenseResolverPluginClient};
+use modkit_security::SecurityContext;
+use uuid::Uuid;
+
+use super::service::Service;
+
+#[async_trait]
+impl LicenseResolverPluginClient for Service {
+    async fn get_global_features(
+        &self,
+        _ctx: &SecurityContext,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        Ok(self.global_features())
+    }
+
+    async fn get_tenant_features(
+        &self,
+        _ctx: &SecurityContext,
+        tenant_id: Uuid,
+    ) -> Result<EnabledFeatures, LicenseResolverError> {
+        Ok(self.tenant_features(tenant_id))
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::config::{StaticLicensePluginConfig, TenantFeaturesConfig};
+    use license_resolver_sdk::BASE_FEATURE;
+
+    #[tokio::test]
+    async fn plugin_trait_reports_configured_features() {
+        let tenant = Uuid::new_v4();
+        let service = Service::from_config(&StaticLicensePluginConfig {
+            tenants: vec![TenantFeaturesConfig {
+                tenant_id: tenant,
+                features: vec!["gts.x.core.lic.feat.v1~x.core.oagw.base.v1".to_owned()],
+            }],
+            ..StaticLicensePluginConfig::default()
+        });
+        let plugin: &dyn LicenseResolverPluginClient = &service;
+        let ctx = SecurityContext::anonymous();
+
+        let global = plugin.get_global_features(&ctx).await.unwrap();
+        assert!(global.contains(BASE_FEATURE));
+
+        let scoped = plugin.get_tenant_features(&ctx, tenant).await.unwrap();
+        assert!(scoped.contains("gts.x.core.lic.feat.v1~x.core.oagw.base.v1"));
+    }
+}
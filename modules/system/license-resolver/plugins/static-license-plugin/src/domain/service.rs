@@ -0,0 +1,102 @@
+//! Service implementation for the static license resolver plugin.
+
+use std::collections::HashMap;
+
+use license_resolver_sdk::EnabledFeatures;
+use modkit_macros::domain_model;
+use uuid::Uuid;
+
+use crate::config::StaticLicensePluginConfig;
+
+/// Static license resolver service.
+///
+/// Holds the configured global and per-tenant features in memory.
+/// Tenants that are not configured have no tenant-scoped features.
+#[domain_model]
+pub struct Service {
+    global: EnabledFeatures,
+    tenants: HashMap<Uuid, EnabledFeatures>,
+}
+
+impl Service {
+    /// Creates a new service from configuration.
+    ///
+    /// Repeated entries for the same tenant are merged.
+    #[must_use]
+    pub fn from_config(cfg: &StaticLicensePluginConfig) -> Self {
+        let mut tenants: HashMap<Uuid, EnabledFeatures> = HashMap::new();
+        for tenant in &cfg.tenants {
+            tenants
+                .entry(tenant.tenant_id)
+                .or_default()
+                .extend(EnabledFeatures::new(tenant.features.iter().cloned()));
+        }
+
+        Self {
+            global: EnabledFeatures::new(cfg.global_features.iter().cloned()),
+            tenants,
+        }
+    }
+
+    /// Features enabled for the whole installation.
+    #[must_use]
+    pub fn global_features(&self) -> EnabledFeatures {
+        self.global.clone()
+    }
+
+    /// Features licensed to `tenant_id`, empty for unknown tenants.
+    #[must_use]
+    pub fn tenant_features(&self, tenant_id: Uuid) -> EnabledFeatures {
+        self.tenants.get(&tenant_id).cloned().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+#[cfg_attr(coverage_nightly, coverage(off))]
+mod tests {
+    use super::*;
+    use crate::config::TenantFeaturesConfig;
+    use license_resolver_sdk::BASE_FEATURE;
+
+    const OAGW_FEATURE: &str = "gts.x.core.lic.feat.v1~x.core.oagw.base.v1";
+    const REPORTS_FEATURE: &str = "gts.x.core.lic.feat.v1~x.core.reports.base.v1";
+
+    #[test]
+    fn default_config_enables_base_feature_only() {
+        let service = Service::from_config(&StaticLicensePluginConfig::default());
+
+        assert_eq!(
+            service.global_features(),
+            EnabledFeatures::new([BASE_FEATURE])
+        );
+        assert_eq!(
+            service.tenant_features(Uuid::new_v4()),
+            EnabledFeatures::default()
+        );
+    }
+
+    #[test]
+    fn tenant_features_are_scoped_and_merged() {
+        let tenant = Uuid::new_v4();
+        let cfg = StaticLicensePluginConfig {
+            tenants: vec![
+                TenantFeaturesConfig {
+                    tenant_id: tenant,
+                    features: vec![OAGW_FEATURE.to_owned()],
+                },
+                TenantFeaturesConfig {
+                    tenant_id: tenant,
+                    features: vec![REPORTS_FEATURE.to_owned()],
+                },
+            ],
+            ..StaticLicensePluginConfig::default()
+        };
+        let service = Service::from_config(&cfg);
+
+        let features = service.tenant_features(tenant);
+        assert!(features.contains_all([OAGW_FEATURE, REPORTS_FEATURE]));
+        assert!(!features.contains(BASE_FEATURE));
+        assert!(!service.global_features().contains(OAGW_FEATURE));
+        assert!(service.tenant_features(Uuid::new_v4()).features.is_empty());
+    }
+}
@@ -0,0 +1,29 @@
+//! Static License Resolver Plugin
+//!
+//! This plugin reports license features from static configuration for development and testing.
+//!
+//! - Global features apply to the whole installation (the base feature by default)
+//! - Tenant features apply to the listed tenants only
+//!
+//! ## Configuration
+//!
+//! ```yaml
+//! modules:
+//!   static-license-plugin:
+//!     config:
+//!       vendor: "hyperspot"
+//!       priority: 100
+//!       global_features:
+//!         - "gts.x.core.lic.feat.v1~x.core.global.base.v1"
+//!       tenants:
+//!         - tenant_id: "00000000-df51-5b42-9538-d2b56b7ee953"
+//!           features:
+//!             - "gts.x.core.lic.feat.v1~x.core.oagw.base.v1"
+//! ```
+#![cfg_attr(coverage_nightly, feature(coverage_attribute))]
+
+pub mod config;
+pub mod domain;
+pub mod module;
+
+pub use module::StaticLicensePlugin;
@@ -0,0 +1,83 @@
+//! Static license resolver plugin module.
+
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use license_resolver_sdk::{LicenseResolverPluginClient, LicenseResolverPluginSpecV1};
+use modkit::Module;
+use modkit::client_hub::ClientScope;
+use modkit::context::ModuleCtx;
+use modkit::gts::BaseModkitPluginV1;
+use tracing::info;
+use types_registry_sdk::{RegisterResult, TypesRegistryClient};
+
+use crate::config::StaticLicensePluginConfig;
+use crate::domain::Service;
+
+/// Static license resolver plugin module.
+#[modkit::module(
+    name = "static-license-plugin",
+    deps = ["types-registry"]
+)]
+pub struct StaticLicensePlugin {
+    service: OnceLock<Arc<Service>>,
+}
+
+impl Default for StaticLicensePlugin {
+    fn default() -> Self {
+        Self {
+            service: OnceLock::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Module for StaticLicensePlugin {
+    async fn init(&self, ctx: &ModuleCtx) -> anyhow::Result<()> {
+        info!("Initializing {} module", Self::MODULE_NAME);
+
+        let cfg: StaticLicensePluginConfig = ctx.config()?;
+        info!(
+            vendor = %cfg.vendor,
+            priority = cfg.priority,
+            global_features = cfg.global_features.len(),
+            tenants = cfg.tenants.len(),
+            "Loaded plugin configuration"
+        );
+
+        // Generate plugin instance ID
+        let instance_id = LicenseResolverPluginSpecV1::gts_make_instance_id(
+            "hyperspot.builtin.static_license_resolver.plugin.v1",
+        );
+
+        // Register plugin instance in types-registry
+        let registry = ctx.client_hub().get::<dyn TypesRegistryClient>()?;
+        let instance = BaseModkitPluginV1::<LicenseResolverPluginSpecV1> {
+            id: instance_id.clone(),
+            vendor: cfg.vendor.clone(),
+            priority: cfg.priority,
+            properties: LicenseResolverPluginSpecV1,
+        };
+        let instance_json = serde_json::to_value(&instance)?;
+
+        let results = registry.register(vec![instance_json]).await?;
+        RegisterResult::ensure_all_ok(&results)?;
+
+        // Create service
+        let service = Arc::new(Service::from_config(&cfg));
+        self.service
+            .set(service.clone())
+            .map_err(|_| anyhow::anyhow!("{} module already initialized", Self::MODULE_NAME))?;
+
+        // Register scoped client in ClientHub
+        let api: Arc<dyn LicenseResolverPluginClient> = service;
+        ctx.client_hub()
+            .register_scoped::<dyn LicenseResolverPluginClient>(
+                ClientScope::gts_id(&instance_id),
+                api,
+            );
+
+        info!(instance_id = %instance_id, "{} module initialized successfully", Self::MODULE_NAME);
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
+use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use uuid::Uuid;
 
+use modkit::jobs::JobStatus;
 use modkit::runtime::InstanceState;
 
 use crate::domain::model::{DeploymentMode, InstanceInfo, ModuleInfo};
@@ -31,6 +33,12 @@ pub struct ModuleDto {
     pub deployment_mode: DeploymentModeDto,
     /// Running instances of this module
     pub instances: Vec<ModuleInstanceDto>,
+    /// Restart supervision of the module's process (out-of-process modules that restarted or failed)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supervision: Option<ModuleSupervisionDto>,
+    /// Scheduled jobs of the module and their last-run status
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub jobs: Vec<ScheduledJobDto>,
     /// Plugins provided by this module (reserved for follow-up implementation)
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub plugins: Vec<PluginDto>,
@@ -46,10 +54,58 @@ pub struct ModuleInstanceDto {
     pub version: Option<String>,
     /// Current instance state (e.g., "registered", "healthy", "quarantined")
     pub state: String,
+    /// Restarts of the module's process before this instance registered
+    pub restart_count: u32,
     /// gRPC services provided by this instance (service name -> endpoint URI)
     pub grpc_services: HashMap<String, String>,
 }
 
+/// Response DTO for the restart supervision of an out-of-process module
+#[modkit_macros::api_dto(response)]
+pub struct ModuleSupervisionDto {
+    /// Supervision status: "running" or "failed" (crash loop detected, no more restarts)
+    pub status: String,
+    /// Restarts performed so far
+    pub restart_count: u32,
+    /// How the last process exited (e.g., "exit status: 1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_exit: Option<String>,
+}
+
+/// Response DTO for a scheduled job of a module
+#[modkit_macros::api_dto(response)]
+pub struct ScheduledJobDto {
+    /// Job name (unique within the module)
+    pub name: String,
+    /// Schedule (e.g., "every 60s", "cron 0 3 * * *")
+    pub schedule: String,
+    /// Whether each tick runs on at most one instance in the cluster
+    pub single_runner: bool,
+    /// Whether a run is in progress
+    pub running: bool,
+    /// Next planned run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run: Option<DateTime<Utc>>,
+    /// Start of the last run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_started: Option<DateTime<Utc>>,
+    /// End of the last run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_finished: Option<DateTime<Utc>>,
+    /// Outcome of the last tick: "succeeded", "failed" or "skipped"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_outcome: Option<String>,
+    /// Error of the last failed run, or why the last tick was skipped
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_detail: Option<String>,
+    /// Runs started so far
+    pub run_count: u64,
+    /// Runs that failed
+    pub failure_count: u64,
+    /// Ticks skipped (previous run still in progress, or lock held by another instance)
+    pub skip_count: u64,
+}
+
 /// Response DTO for a plugin (reserved for follow-up implementation)
 #[modkit_macros::api_dto(response)]
 pub struct PluginDto {
@@ -82,6 +138,12 @@ impl From<&ModuleInfo> for ModuleDto {
                 .iter()
                 .map(ModuleInstanceDto::from)
                 .collect(),
+            supervision: module.supervision.as_ref().map(|s| ModuleSupervisionDto {
+                status: if s.failed { "failed" } else { "running" }.to_owned(),
+                restart_count: s.restart_count,
+                last_exit: s.last_exit.clone(),
+            }),
+            jobs: module.jobs.iter().map(ScheduledJobDto::from).collect(),
             plugins: vec![],
         }
     }
@@ -100,7 +162,27 @@ impl From<&InstanceInfo> for ModuleInstanceDto {
                 InstanceState::Draining => "draining",
             }
             .to_owned(),
+            restart_count: instance.restart_count,
             grpc_services: instance.grpc_services.clone(),
         }
     }
 }
+
+impl From<&JobStatus> for ScheduledJobDto {
+    fn from(job: &JobStatus) -> Self {
+        Self {
+            name: job.name.clone(),
+            schedule: job.schedule.clone(),
+            single_runner: job.single_runner,
+            running: job.running,
+            next_run: job.next_run,
+            last_started: job.last_started,
+            last_finished: job.last_finished,
+            last_outcome: job.last_outcome.map(|o| o.as_str().to_owned()),
+            last_detail: job.last_detail.clone(),
+            run_count: job.run_count,
+            failure_count: job.failure_count,
+            skip_count: job.skip_count,
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use modkit::runtime::InstanceState;
+use modkit::jobs::JobStatus;
+use modkit::runtime::{InstanceState, ModuleSupervision};
 use modkit_macros::domain_model;
 
 /// Deployment mode of a module.
@@ -21,6 +22,10 @@ pub struct ModuleInfo {
     pub dependencies: Vec<String>,
     pub deployment_mode: DeploymentMode,
     pub instances: Vec<InstanceInfo>,
+    /// Restart supervision of the module's process (`OoP` modules only)
+    pub supervision: Option<ModuleSupervision>,
+    /// Scheduled jobs of the module run by this host
+    pub jobs: Vec<JobStatus>,
 }
 
 /// Domain model for a running module instance.
@@ -30,5 +35,6 @@ pub struct InstanceInfo {
     pub instance_id: Uuid,
     pub version: Option<String>,
     pub state: InstanceState,
+    pub restart_count: u32,
     pub grpc_services: HashMap<String, String>,
 }
@@ -1,6 +1,7 @@
 use std::collections::HashSet;
 use std::sync::Arc;
 
+use modkit::jobs::{JobScheduler, JobStatus};
 use modkit::registry::ModuleRegistry;
 use modkit::runtime::ModuleManager;
 use modkit_macros::domain_model;
@@ -22,6 +23,8 @@ pub struct ModulesService {
     compiled: Vec<CompiledModule>,
     /// Runtime module manager for live instance queries.
     module_manager: Arc<ModuleManager>,
+    /// Runtime job scheduler for last-run status of scheduled jobs.
+    job_scheduler: Option<Arc<JobScheduler>>,
 }
 
 impl ModulesService {
@@ -49,9 +52,17 @@ impl ModulesService {
         Self {
             compiled,
             module_manager,
+            job_scheduler: None,
         }
     }
 
+    /// Report scheduled job status from the runtime's `JobScheduler`.
+    #[must_use]
+    pub fn with_job_scheduler(mut self, job_scheduler: Arc<JobScheduler>) -> Self {
+        self.job_scheduler = Some(job_scheduler);
+        self
+    }
+
     /// List all registered modules, merging compile-time catalog data with runtime instances.
     #[must_use]
     pub fn list_modules(&self) -> Vec<ModuleInfo> {
@@ -70,25 +81,37 @@ impl ModulesService {
                 dependencies: cm.deps.clone(),
                 deployment_mode: DeploymentMode::CompiledIn,
                 instances,
+                supervision: self.module_manager.supervision(&cm.name),
+                jobs: self.get_module_jobs(&cm.name),
             });
         }
 
         // 2. Add any dynamically registered modules from ModuleManager
-        //    that are not in the compiled catalog (external / out-of-process).
-        for instance in self.module_manager.all_instances() {
-            if seen_names.contains(&instance.module) {
+        //    that are not in the compiled catalog (external / out-of-process),
+        //    including supervised ones that have no live instance (e.g., crash-looping).
+        let dynamic = self
+            .module_manager
+            .all_instances()
+            .into_iter()
+            .map(|instance| instance.module.clone())
+            .chain(self.module_manager.supervised_modules());
+        for name in dynamic {
+            if !seen_names.insert(name.clone()) {
                 continue;
             }
-            seen_names.insert(instance.module.clone());
 
-            let instances = self.get_module_instances(&instance.module);
+            let instances = self.get_module_instances(&name);
+            let supervision = self.module_manager.supervision(&name);
+            let jobs = self.get_module_jobs(&name);
 
             modules.push(ModuleInfo {
-                name: instance.module.clone(),
+                name,
                 capabilities: vec![],
                 dependencies: vec![],
                 deployment_mode: DeploymentMode::OutOfProcess,
                 instances,
+                supervision,
+                jobs,
             });
         }
 
@@ -98,6 +121,13 @@ impl ModulesService {
         modules
     }
 
+    fn get_module_jobs(&self, module_name: &str) -> Vec<JobStatus> {
+        self.job_scheduler
+            .as_ref()
+            .map(|s| s.statuses_of(module_name))
+            .unwrap_or_default()
+    }
+
     fn get_module_instances(&self, module_name: &str) -> Vec<InstanceInfo> {
         self.module_manager
             .instances_of(module_name)
@@ -113,6 +143,7 @@ impl ModulesService {
                     instance_id: inst.instance_id,
                     version: inst.version.clone(),
                     state: inst.state(),
+                    restart_count: inst.restart_count(),
                     grpc_services,
                 }
             })
@@ -273,4 +304,30 @@ mod tests {
         assert_eq!(modules[0].name, "alpha");
         assert_eq!(modules[1].name, "zebra");
     }
+
+    #[test]
+    fn crash_looping_module_is_listed_as_failed() {
+        let registry = build_registry(&[]);
+        let manager = Arc::new(ModuleManager::new());
+        manager.record_restart("calculator", "exit status: 1");
+        let instance = Arc::new(ModuleInstance::new("calculator", Uuid::new_v4()));
+        manager.register_instance(instance);
+        manager.mark_failed("calculator", "exit status: 1");
+        manager.record_restart("flaky", "exit status: 2");
+
+        let svc = ModulesService::new(&registry, manager);
+        let modules = svc.list_modules();
+
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].name, "calculator");
+        assert_eq!(modules[0].deployment_mode, DeploymentMode::OutOfProcess);
+        assert!(modules[0].supervision.as_ref().unwrap().failed);
+        assert_eq!(modules[0].instances[0].restart_count, 1);
+        assert_eq!(modules[0].instances[0].state, InstanceState::Quarantined);
+
+        // A restarted module whose new process has not registered yet is still listed
+        assert_eq!(modules[1].name, "flaky");
+        assert!(modules[1].instances.is_empty());
+        assert_eq!(modules[1].supervision.as_ref().unwrap().restart_count, 1);
+    }
 }
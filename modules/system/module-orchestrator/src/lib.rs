@@ -1,7 +1,8 @@
 //! Module Orchestrator
 //!
 //! System module for service discovery.
-//! This module provides `DirectoryService` for gRPC service registration and discovery.
+//! This module provides `DirectoryService` for gRPC service registration and discovery,
+//! and `EventBusService` to relay the host event bus to out-of-process modules.
 #![cfg_attr(coverage_nightly, feature(coverage_attribute))]
 
 // === MODULE DEFINITION ===
@@ -11,7 +12,7 @@ pub use module::{ModuleOrchestrator, ModuleOrchestratorConfig};
 // === INTERNAL MODULES (pub for integration tests) ===
 pub mod api;
 pub mod domain;
-mod server;
+pub mod server;
 
 // === RE-EXPORTS ===
 pub use cf_system_sdks::directory::{
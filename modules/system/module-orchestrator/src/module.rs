@@ -5,17 +5,20 @@ use async_trait::async_trait;
 use std::sync::{Arc, OnceLock};
 use tokio::sync::RwLock;
 
-use modkit::DirectoryClient;
 use modkit::context::ModuleCtx;
 use modkit::contracts::{
     GrpcServiceCapability, OpenApiRegistry, RegisterGrpcServiceFn, RestApiCapability,
     SystemCapability,
 };
 use modkit::directory::LocalDirectoryClient;
+use modkit::events::EventBusClient;
 use modkit::registry::ModuleRegistry;
 use modkit::runtime::ModuleManager;
+use modkit::{DirectoryClient, EventBus, JobScheduler};
 
-use cf_system_sdks::directory::DIRECTORY_SERVICE_NAME;
+use cf_system_sdks::directory::{
+    DIRECTORY_SERVICE_NAME, EVENT_BUS_SERVICE_NAME, FILE_DESCRIPTOR_SET,
+};
 
 use crate::domain::service::ModulesService;
 use crate::server;
@@ -29,8 +32,9 @@ pub struct ModuleOrchestratorConfig;
 /// This module:
 /// - Provides `DirectoryClient` to the `ClientHub` for in-process modules
 /// - Exposes `DirectoryService` gRPC service via `grpc-hub`
+/// - Exposes the runtime's `EventBus` to out-of-process modules via `grpc-hub`
 /// - Tracks module instances and provides service resolution
-/// - Exposes REST API to list all registered modules
+/// - Exposes REST API to list all registered modules and their scheduled jobs
 #[modkit::module(
     name = "module-orchestrator",
     capabilities = [grpc, system, rest],
@@ -39,6 +43,7 @@ pub struct ModuleOrchestratorConfig;
 pub struct ModuleOrchestrator {
     config: RwLock<ModuleOrchestratorConfig>,
     directory_api: OnceLock<Arc<dyn DirectoryClient>>,
+    event_bus: OnceLock<Arc<dyn EventBusClient>>,
     module_manager: OnceLock<Arc<ModuleManager>>,
     modules_service: OnceLock<Arc<ModulesService>>,
 }
@@ -48,6 +53,7 @@ impl Default for ModuleOrchestrator {
         Self {
             config: RwLock::new(ModuleOrchestratorConfig),
             directory_api: OnceLock::new(),
+            event_bus: OnceLock::new(),
             module_manager: OnceLock::new(),
             modules_service: OnceLock::new(),
         }
@@ -88,10 +94,21 @@ impl modkit::Module for ModuleOrchestrator {
             .set(api_impl)
             .map_err(|_| anyhow::anyhow!("DirectoryClient already set (init called twice?)"))?;
 
+        // Relay the runtime's event bus when one is published
+        if let Ok(bus) = ctx.client_hub().get::<EventBus>() {
+            self.event_bus
+                .set(bus)
+                .map_err(|_| anyhow::anyhow!("EventBus already set (init called twice?)"))?;
+        }
+
         // Build compiled-module catalog from inventory and create the ModulesService
         let registry = ModuleRegistry::discover_and_build()
             .map_err(|e| anyhow::anyhow!("Failed to build module registry: {e}"))?;
-        let modules_service = Arc::new(ModulesService::new(&registry, manager));
+        let mut modules_service = ModulesService::new(&registry, manager);
+        if let Ok(scheduler) = ctx.client_hub().get::<JobScheduler>() {
+            modules_service = modules_service.with_job_scheduler(scheduler);
+        }
+        let modules_service = Arc::new(modules_service);
         self.modules_service
             .set(modules_service)
             .map_err(|_| anyhow::anyhow!("ModulesService already set (init called twice?)"))?;
@@ -135,11 +152,27 @@ impl GrpcServiceCapability for ModuleOrchestrator {
         // Build DirectoryService
         let directory_svc = server::make_directory_service(api);
 
-        Ok(vec![RegisterGrpcServiceFn {
+        let mut services = vec![RegisterGrpcServiceFn {
             service_name: DIRECTORY_SERVICE_NAME,
             register: Box::new(move |routes| {
                 routes.add_service(directory_svc.clone());
             }),
-        }])
+        }];
+
+        if let Some(bus) = self.event_bus.get().cloned() {
+            let event_bus_svc = server::make_event_bus_service(bus);
+            services.push(RegisterGrpcServiceFn {
+                service_name: EVENT_BUS_SERVICE_NAME,
+                register: Box::new(move |routes| {
+                    routes.add_service(event_bus_svc.clone());
+                }),
+            });
+        }
+
+        Ok(services)
+    }
+
+    fn file_descriptor_sets(&self) -> Vec<&'static [u8]> {
+        vec![FILE_DESCRIPTOR_SET]
     }
 }
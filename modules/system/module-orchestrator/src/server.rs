@@ -1,15 +1,20 @@
-//! gRPC server implementation for `DirectoryService`
+//! gRPC server implementation for `DirectoryService` and `EventBusService`
 //!
-//! This module provides the gRPC service implementation for Directory Service.
+//! This module provides the gRPC service implementations for Directory Service
+//! and for the event bus relay used by out-of-process modules.
 
+use std::pin::Pin;
 use std::sync::Arc;
+
+use futures_util::{Stream, StreamExt};
 use tonic::{Request, Response, Status};
 
 use cf_system_sdks::directory::{
     DeregisterInstanceRequest, DirectoryClient, DirectoryService, DirectoryServiceServer,
-    HeartbeatRequest, InstanceInfo, ListInstancesRequest, ListInstancesResponse,
-    RegisterInstanceInfo, RegisterInstanceRequest, ResolveGrpcServiceRequest,
-    ResolveGrpcServiceResponse, ServiceEndpoint,
+    EventBusClient, EventBusService, EventBusServiceServer, EventMessage, HeartbeatRequest,
+    InstanceInfo, ListInstancesRequest, ListInstancesResponse, RegisterInstanceInfo,
+    RegisterInstanceRequest, ResolveGrpcServiceRequest, ResolveGrpcServiceResponse,
+    ServiceEndpoint, SubscribeRequest,
 };
 
 /// gRPC service implementation of Directory Service
@@ -134,3 +139,57 @@ pub fn make_directory_service(
 ) -> DirectoryServiceServer<DirectoryServiceImpl> {
     DirectoryServiceServer::new(DirectoryServiceImpl::new(api))
 }
+
+/// gRPC service implementation of the event bus relay
+#[derive(Clone)]
+pub struct EventBusServiceImpl {
+    api: Arc<dyn EventBusClient>,
+}
+
+impl EventBusServiceImpl {
+    pub fn new(api: Arc<dyn EventBusClient>) -> Self {
+        Self { api }
+    }
+}
+
+#[tonic::async_trait]
+impl EventBusService for EventBusServiceImpl {
+    type SubscribeStream = Pin<Box<dyn Stream<Item = Result<EventMessage, Status>> + Send>>;
+
+    async fn publish(&self, request: Request<EventMessage>) -> Result<Response<()>, Status> {
+        self.api
+            .publish(request.into_inner().into())
+            .await
+            .map_err(|e| Status::invalid_argument(format!("Failed to publish event: {e}")))?;
+
+        Ok(Response::new(()))
+    }
+
+    async fn subscribe(
+        &self,
+        request: Request<SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let req = request.into_inner();
+
+        let events = self
+            .api
+            .subscribe(&req.topic, &req.subscriber)
+            .await
+            .map_err(|e| Status::invalid_argument(format!("Failed to subscribe: {e}")))?;
+
+        tracing::debug!(topic = %req.topic, subscriber = %req.subscriber, "Remote event subscription");
+        let stream = events.map(|item| {
+            item.map(EventMessage::from)
+                .map_err(|e| Status::internal(e.to_string()))
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Create an `EventBusService` server with the given API implementation
+pub fn make_event_bus_service(
+    api: Arc<dyn EventBusClient>,
+) -> EventBusServiceServer<EventBusServiceImpl> {
+    EventBusServiceServer::new(EventBusServiceImpl::new(api))
+}
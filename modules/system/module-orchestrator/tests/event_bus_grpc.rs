@@ -0,0 +1,98 @@
+#![allow(clippy::unwrap_used, clippy::expect_used)]
+
+//! End-to-end test for the event bus relay over gRPC.
+//!
+//! A host `EventBus` is served through `EventBusService` on a local port; an
+//! "out-of-process" bus uses `EventBusGrpcClient` as its upstream.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use cf_system_sdks::directory::EventBusGrpcClient;
+use gts::GtsSchema;
+use modkit::EventBus;
+use module_orchestrator::server::make_event_bus_service;
+use serde::{Deserialize, Serialize};
+use tokio::net::TcpListener;
+use tokio::time::timeout;
+use tokio_stream::wrappers::TcpListenerStream;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TenantChanged {
+    tenant_id: u32,
+}
+
+impl GtsSchema for TenantChanged {
+    const SCHEMA_ID: &'static str = "gts.x.core.tenants.tenant_changed.v1~";
+
+    fn gts_schema_with_refs() -> serde_json::Value {
+        serde_json::json!({ "type": "object" })
+    }
+}
+
+const WAIT: Duration = Duration::from_secs(5);
+
+async fn serve(host: Arc<EventBus>, cancel: CancellationToken) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(
+        tonic::transport::Server::builder()
+            .add_service(make_event_bus_service(host))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), async move {
+                cancel.cancelled().await;
+            }),
+    );
+    format!("http://{addr}")
+}
+
+#[tokio::test]
+async fn oop_bus_publishes_and_subscribes_through_host() {
+    let cancel = CancellationToken::new();
+    let host = Arc::new(EventBus::new());
+    let mut host_sub = host.subscribe::<TenantChanged>("host-cache").unwrap();
+    let endpoint = serve(host.clone(), cancel.clone()).await;
+
+    let oop = EventBus::new();
+    let mut oop_sub = oop.subscribe::<TenantChanged>("oop-cache").unwrap();
+    let client = EventBusGrpcClient::connect(endpoint).await.unwrap();
+    oop.set_upstream(Arc::new(client), cancel.clone());
+
+    timeout(WAIT, async {
+        while host.subscriber_count(TenantChanged::SCHEMA_ID) < 2 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("bridge never subscribed on the host");
+
+    // Host -> OoP
+    host.publish(&TenantChanged { tenant_id: 1 }).await.unwrap();
+    let event = timeout(WAIT, oop_sub.recv()).await.unwrap().unwrap();
+    assert_eq!(event.tenant_id, 1);
+    event.ack();
+    timeout(WAIT, host_sub.recv()).await.unwrap().unwrap().ack();
+
+    // OoP -> host (and back to the OoP subscriber through the bridge)
+    oop.publish(&TenantChanged { tenant_id: 2 }).await.unwrap();
+    let event = timeout(WAIT, host_sub.recv()).await.unwrap().unwrap();
+    assert_eq!(event.tenant_id, 2);
+    event.ack();
+    let event = timeout(WAIT, oop_sub.recv()).await.unwrap().unwrap();
+    assert_eq!(event.tenant_id, 2);
+    event.ack();
+
+    cancel.cancel();
+}
+
+#[tokio::test]
+async fn invalid_topic_is_rejected_remotely() {
+    let cancel = CancellationToken::new();
+    let endpoint = serve(Arc::new(EventBus::new()), cancel.clone()).await;
+    let client = EventBusGrpcClient::connect(endpoint).await.unwrap();
+
+    let result = modkit::events::EventBusClient::subscribe(&client, "not-a-topic", "test").await;
+    assert!(result.is_err());
+
+    cancel.cancel();
+}
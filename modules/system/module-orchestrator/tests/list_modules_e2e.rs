@@ -238,3 +238,48 @@ async fn modules_are_sorted_alphabetically() {
         .collect();
     assert_eq!(names, vec!["alpha", "middle", "zebra"]);
 }
+
+#[tokio::test]
+async fn includes_scheduled_job_status() {
+    let scheduler = Arc::new(modkit::JobScheduler::new(
+        tokio_util::sync::CancellationToken::new(),
+    ));
+    scheduler
+        .schedule(
+            "cleanup",
+            modkit::ScheduledJob::every("purge", std::time::Duration::from_millis(10), |_| async {
+                anyhow::bail!("db unavailable")
+            }),
+            None,
+        )
+        .unwrap();
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+    let mut b = RegistryBuilder::default();
+    b.register_core_with_meta("cleanup", &[], Arc::new(DummyCore));
+    b.register_core_with_meta("idle", &[], Arc::new(DummyCore));
+    let registry = b.build_topo_sorted().unwrap();
+    let svc = Arc::new(
+        ModulesService::new(&registry, Arc::new(ModuleManager::new()))
+            .with_job_scheduler(scheduler.clone()),
+    );
+    let openapi = api_gateway::ApiGateway::default();
+    let router = rest::routes::register_routes(Router::new(), &openapi, svc);
+
+    let (status, json) = get_modules(router).await;
+    scheduler.shutdown(std::time::Duration::from_secs(1)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    let modules = json.as_array().unwrap();
+    let jobs = modules[0]["jobs"].as_array().unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0]["name"], "purge");
+    assert_eq!(jobs[0]["schedule"], "every 10ms");
+    assert_eq!(jobs[0]["single_runner"], false);
+    assert_eq!(jobs[0]["last_outcome"], "failed");
+    assert_eq!(jobs[0]["last_detail"], "db unavailable");
+    assert!(jobs[0]["failure_count"].as_u64().unwrap() >= 1);
+    assert!(jobs[0]["last_started"].is_string());
+    // jobs field should be absent for modules without scheduled jobs
+    assert!(modules[1].get("jobs").is_none());
+}
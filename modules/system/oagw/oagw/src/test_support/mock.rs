@@ -581,18 +581,12 @@ async fn ws_echo(
 async fn handle_ws_echo(mut socket: WebSocket) {
     while let Some(Ok(msg)) = socket.recv().await {
         match msg {
-            Message::Text(text) => {
-                if socket.send(Message::Text(text)).await.is_err() {
-                    break;
-                }
-            }
-            Message::Binary(data) => {
-                if socket.send(Message::Binary(data)).await.is_err() {
-                    break;
-                }
-            }
+            Message::Text(_) | Message::Binary(_) => {}
             Message::Close(_) => break,
-            _ => {}
+            _ => continue,
+        }
+        if socket.send(msg).await.is_err() {
+            break;
         }
     }
 }
@@ -40,6 +40,7 @@ use crate::models::{
 /// The `SecurityContext` is passed through to the plugin, which decides
 /// how (or whether) to enforce authorization. This is intentional —
 /// different plugins may have different access-control semantics.
+#[modkit::instrumented_client]
 #[async_trait]
 pub trait TenantResolverClient: Send + Sync {
     /// Get tenant information by ID.
@@ -44,7 +44,7 @@ pub mod models;
 pub mod plugin_api;
 
 // Re-export main types at crate root
-pub use api::TenantResolverClient;
+pub use api::{InstrumentedTenantResolverClient, TenantResolverClient};
 pub use error::TenantResolverError;
 pub use gts::TenantResolverPluginSpecV1;
 pub use models::{